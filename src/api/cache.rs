@@ -0,0 +1,61 @@
+use crate::api::StockQuote;
+use crate::api::yahoo::display_symbol;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedQuote {
+    fetched_at: u64,
+    quote: StockQuote,
+}
+
+/// On-disk quote cache keyed by display symbol (see `display_symbol`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct QuoteCache {
+    entries: HashMap<String, CachedQuote>,
+}
+
+impl QuoteCache {
+    /// Load the cache, falling back to an empty one if missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Return the cached quote for a requested symbol if it is younger than `ttl_secs`.
+    pub fn get_fresh(&self, symbol: &str, ttl_secs: u64, now: u64) -> Option<&StockQuote> {
+        self.entries
+            .get(&display_symbol(&symbol.to_uppercase()))
+            .filter(|c| now.saturating_sub(c.fetched_at) < ttl_secs)
+            .map(|c| &c.quote)
+    }
+
+    /// Unix time the cached quote for a symbol was fetched.
+    pub fn fetched_at(&self, symbol: &str) -> Option<u64> {
+        self.entries
+            .get(&display_symbol(&symbol.to_uppercase()))
+            .map(|c| c.fetched_at)
+    }
+
+    pub fn insert_all(&mut self, quotes: &HashMap<String, StockQuote>, now: u64) {
+        for (key, quote) in quotes {
+            self.entries.insert(
+                key.clone(),
+                CachedQuote {
+                    fetched_at: now,
+                    quote: quote.clone(),
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use reqwest::{ClientBuilder, Proxy};
+use std::time::Duration;
+
+/// Request timeout used when the config doesn't set `http_timeout_secs`.
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Timeout and proxy shared by the quote and RSS clients.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpOptions {
+    pub timeout: Duration,
+    /// Proxy for every request, e.g. `http://proxy.corp:8080`. When unset,
+    /// reqwest's own proxy handling applies: `HTTPS_PROXY`, `HTTP_PROXY`,
+    /// `ALL_PROXY` and `NO_PROXY` from the environment.
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self::new(DEFAULT_HTTP_TIMEOUT_SECS, None)
+    }
+}
+
+impl HttpOptions {
+    /// A timeout of 0 falls back to the default; a blank proxy counts as none.
+    pub fn new(timeout_secs: u64, proxy: Option<String>) -> Self {
+        let timeout_secs = if timeout_secs == 0 {
+            DEFAULT_HTTP_TIMEOUT_SECS
+        } else {
+            timeout_secs
+        };
+        Self {
+            timeout: Duration::from_secs(timeout_secs),
+            proxy: proxy.filter(|p| !p.trim().is_empty()),
+        }
+    }
+
+    /// Apply the timeout and the configured proxy to `builder`, leaving
+    /// environment proxies to reqwest. Fails on a malformed configured URL.
+    pub fn apply(&self, builder: ClientBuilder) -> Result<ClientBuilder> {
+        let builder = builder.timeout(self.timeout);
+        match &self.proxy {
+            Some(url) => {
+                let proxy = Proxy::all(url.trim())
+                    .with_context(|| format!("Invalid proxy URL '{}'", url))?;
+                Ok(builder.proxy(proxy))
+            }
+            None => Ok(builder),
+        }
+    }
+}
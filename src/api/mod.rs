@@ -1,5 +1,13 @@
+pub mod cache;
+pub mod http;
 pub mod news;
+pub mod provider;
 pub mod yahoo;
 
+pub use cache::QuoteCache;
+pub use http::HttpOptions;
 pub use news::NewsClient;
-pub use yahoo::{ChartData, NewsItem, StockQuote, YahooClient};
+pub use provider::QuoteProvider;
+pub use yahoo::{
+    ChartData, ChartRange, NewsItem, RetryPolicy, StockQuote, YahooClient, is_offline_error,
+};
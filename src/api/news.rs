@@ -1,8 +1,13 @@
+use crate::api::http::HttpOptions;
 use crate::api::yahoo::NewsItem;
 use anyhow::Result;
 use reqwest::Client;
+use std::collections::{BTreeMap, HashSet};
 use std::time::Duration;
 
+/// Upper bound for a single feed so one dead source can't stall a refresh.
+const FEED_TIMEOUT: Duration = Duration::from_secs(8);
+
 /// Extract a short publisher name from the feed URL's domain.
 fn publisher_from_url(url: &str) -> String {
     let host = url
@@ -33,11 +38,13 @@ pub struct NewsClient {
 
 impl NewsClient {
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(15))
-            .build()
-            .expect("Failed to build RSS client");
-        Self { client }
+        Self::with_http(&HttpOptions::default()).expect("Failed to build RSS client")
+    }
+
+    /// Client using the given timeout and proxy.
+    pub fn with_http(options: &HttpOptions) -> Result<Self> {
+        let client = options.apply(Client::builder())?.build()?;
+        Ok(Self { client })
     }
 
     async fn fetch_feed(&self, url: &str) -> Result<Vec<NewsItem>> {
@@ -79,6 +86,7 @@ impl NewsClient {
                     published_at,
                     url,
                     summary,
+                    category: None,
                 }
             })
             .collect();
@@ -86,17 +94,98 @@ impl NewsClient {
         Ok(items)
     }
 
-    pub async fn fetch_all(&self, urls: &[String]) -> Result<Vec<NewsItem>> {
-        let futures: Vec<_> = urls.iter().map(|url| self.fetch_feed(url)).collect();
+    /// Fetch a single feed once (within `FEED_TIMEOUT`) to check that it
+    /// parses. Returns the number of entries.
+    pub async fn check_feed(&self, url: &str) -> Result<usize> {
+        let items = tokio::time::timeout(FEED_TIMEOUT, self.fetch_feed(url)).await??;
+        Ok(items.len())
+    }
+
+    /// Fetch `page` (1 = the feed as published, see `paged_feed_url`) of
+    /// all feeds concurrently, tagging items with their feed's entry in
+    /// `categories` (feed URL → category). Feeds that error or exceed
+    /// `FEED_TIMEOUT` are dropped; the rest are merged by `merge_feeds`.
+    /// Also returns how many items each feed that answered returned.
+    pub async fn fetch_all(
+        &self,
+        urls: &[String],
+        categories: &BTreeMap<String, String>,
+        page: u32,
+    ) -> Result<(Vec<NewsItem>, BTreeMap<String, usize>)> {
+        let futures: Vec<_> = urls
+            .iter()
+            .map(|url| {
+                let url = paged_feed_url(url, page);
+                async move { tokio::time::timeout(FEED_TIMEOUT, self.fetch_feed(&url)).await }
+            })
+            .collect();
         let results = futures::future::join_all(futures).await;
-        let mut all_items: Vec<NewsItem> = results
-            .into_iter()
-            .filter_map(|r| r.ok())
-            .flatten()
+        let mut counts = BTreeMap::new();
+        let feeds = urls
+            .iter()
+            .zip(results)
+            .filter_map(|(url, r)| {
+                let mut items = r.ok().and_then(|r| r.ok())?;
+                tag_category(&mut items, categories.get(url));
+                counts.insert(url.clone(), items.len());
+                Some(items)
+            })
             .collect();
-        all_items.sort_by(|a, b| b.published_at.cmp(&a.published_at));
-        Ok(all_items)
+        Ok((merge_feeds(feeds), counts))
+    }
+}
+
+/// URL for an older page of a feed, using the `paged=N` query parameter
+/// WordPress-style feeds understand. Page 1 is the URL unchanged. Feeds that
+/// ignore the parameter just return their latest items again.
+pub fn paged_feed_url(url: &str, page: u32) -> String {
+    if page <= 1 {
+        return url.to_string();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}paged={}", url, separator, page)
+}
+
+/// Set the category of every item in one feed's results.
+pub fn tag_category(items: &mut [NewsItem], category: Option<&String>) {
+    for item in items {
+        item.category = category.cloned();
+    }
+}
+
+/// Check that `url` is an absolute http(s) URL and return it trimmed.
+pub fn validate_feed_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url)?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        anyhow::bail!("feed URL must be http(s) with a host");
+    }
+    Ok(url.to_string())
+}
+
+/// Flatten feed results, drop items repeated across feeds (same title and
+/// URL), and sort newest first.
+pub fn merge_feeds(feeds: Vec<Vec<NewsItem>>) -> Vec<NewsItem> {
+    let mut seen: HashSet<(String, Option<String>)> = HashSet::new();
+    let mut all_items: Vec<NewsItem> = feeds
+        .into_iter()
+        .flatten()
+        .filter(|item| seen.insert((item.title.trim().to_string(), item.url.clone())))
+        .collect();
+    all_items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
+    all_items
+}
+
+/// Keep only the newest `max` items (all of them when `max` is 0), leaving
+/// `items` sorted newest first. Returns how many were dropped.
+pub fn cap_news(items: &mut Vec<NewsItem>, max: usize) -> usize {
+    items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
+    if max == 0 || items.len() <= max {
+        return 0;
     }
+    let dropped = items.len() - max;
+    items.truncate(max);
+    dropped
 }
 
 impl Default for NewsClient {
@@ -0,0 +1,107 @@
+use crate::api::yahoo::{ChartData, ChartRange, NewsItem, StockQuote, YahooClient, display_symbol};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+
+/// Source of market data used by `App`. `YahooClient` is the default;
+/// tests and alternate IDX sources plug in their own implementation.
+///
+/// Methods return boxed futures so the trait stays object-safe and `App`
+/// can hold a `Box<dyn QuoteProvider>`.
+pub trait QuoteProvider: Send {
+    /// Quotes keyed by display symbol (`BBCA`, `IHSG`). Symbols the source
+    /// doesn't know are simply absent from the map.
+    fn get_quotes<'a>(
+        &'a mut self,
+        symbols: &'a [String],
+    ) -> BoxFuture<'a, Result<HashMap<String, StockQuote>>>;
+
+    /// One quote with classification and fundamentals, for the detail view
+    /// of a source whose `get_quotes` leaves them out. Sources that always
+    /// return everything just reuse `get_quotes`.
+    fn get_full_quote<'a>(
+        &'a mut self,
+        symbol: &'a str,
+    ) -> BoxFuture<'a, Result<Option<StockQuote>>> {
+        Box::pin(async move {
+            let symbols = [symbol.to_string()];
+            let mut quotes = self.get_quotes(&symbols).await?;
+            Ok(quotes.remove(&display_symbol(&symbol.to_uppercase())))
+        })
+    }
+
+    /// Daily (or weekly) closes over `range` for the detail sparkline.
+    fn get_chart<'a>(
+        &'a self,
+        symbol: &'a str,
+        range: ChartRange,
+    ) -> BoxFuture<'a, Result<ChartData>>;
+
+    /// Today's intraday closes for the watchlist mini-trend column.
+    fn get_intraday_chart<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<ChartData>>;
+
+    /// Headlines about `symbol` from the provider itself. Sources without a
+    /// news endpoint return nothing; RSS feeds are fetched separately.
+    fn get_news<'a>(&'a self, _symbol: &'a str) -> BoxFuture<'a, Result<Vec<NewsItem>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// `(symbol, name)` matches for a partial code or company name, used to
+    /// autocomplete the add-stock prompt. Sources without search return nothing.
+    fn search_symbols<'a>(
+        &'a self,
+        _query: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<(String, String)>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// An independent handle for chart requests, so a batch of mini-charts
+    /// can run on its own task without holding the shared provider. Sources
+    /// that can't hand one out return `None` and are locked per request.
+    fn chart_handle(&self) -> Option<Box<dyn QuoteProvider>> {
+        None
+    }
+}
+
+impl QuoteProvider for YahooClient {
+    fn get_quotes<'a>(
+        &'a mut self,
+        symbols: &'a [String],
+    ) -> BoxFuture<'a, Result<HashMap<String, StockQuote>>> {
+        Box::pin(YahooClient::get_quotes(self, symbols))
+    }
+
+    fn get_full_quote<'a>(
+        &'a mut self,
+        symbol: &'a str,
+    ) -> BoxFuture<'a, Result<Option<StockQuote>>> {
+        Box::pin(async move {
+            let symbols = [symbol.to_string()];
+            let mut quotes = self.get_full_quotes(&symbols).await?;
+            Ok(quotes.remove(&display_symbol(&symbol.to_uppercase())))
+        })
+    }
+
+    fn get_chart<'a>(
+        &'a self,
+        symbol: &'a str,
+        range: ChartRange,
+    ) -> BoxFuture<'a, Result<ChartData>> {
+        Box::pin(YahooClient::get_chart(self, symbol, range))
+    }
+
+    fn get_intraday_chart<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<ChartData>> {
+        Box::pin(YahooClient::get_intraday_chart(self, symbol))
+    }
+
+    fn search_symbols<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<(String, String)>>> {
+        Box::pin(YahooClient::search_symbols(self, query))
+    }
+
+    fn chart_handle(&self) -> Option<Box<dyn QuoteProvider>> {
+        Some(Box::new(self.clone()))
+    }
+}
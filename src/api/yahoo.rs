@@ -1,14 +1,28 @@
+use crate::api::http::HttpOptions;
 use anyhow::{Result, anyhow};
-use reqwest::{Client, cookie::Jar};
-use serde::Deserialize;
+use reqwest::{Client, Response, StatusCode, cookie::Jar};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 const YAHOO_BASE_URL: &str = "https://finance.yahoo.com";
 const YAHOO_QUOTE_URL: &str = "https://query1.finance.yahoo.com/v7/finance/quote";
 const YAHOO_CHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart";
-
-#[derive(Debug, Clone)]
+const YAHOO_SEARCH_URL: &str = "https://query1.finance.yahoo.com/v1/finance/search";
+
+/// Fields requested by a lite client: only what a watchlist row shows,
+/// including `averageVolume` for the Rel Vol column and spike highlight.
+/// On the two-quote fixtures in `tests/fixtures`, laid out from the v7
+/// field list rather than captured live, the lite response is 1,849 bytes
+/// against 5,065 for the full one (about 63% smaller). The lite fixture
+/// keeps the exchange and market metadata Yahoo adds on its own.
+pub const LITE_QUOTE_FIELDS: &str = "symbol,shortName,regularMarketPrice,regularMarketChange,\
+regularMarketChangePercent,regularMarketOpen,regularMarketDayHigh,regularMarketDayLow,\
+regularMarketVolume,regularMarketPreviousClose,averageVolume";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockQuote {
     pub symbol: String,
     pub short_name: String,
@@ -35,6 +49,42 @@ pub struct StockQuote {
     pub average_volume: Option<u64>,
 }
 
+impl StockQuote {
+    /// Drop the company classification and fundamentals, keeping price data
+    /// and the average volume that relative volume needs.
+    pub fn strip_fundamentals(&mut self) {
+        self.long_name = None;
+        self.sector = None;
+        self.industry = None;
+        self.market_cap = None;
+        self.trailing_pe = None;
+        self.dividend_yield = None;
+        self.fifty_two_week_high = None;
+        self.fifty_two_week_low = None;
+        self.beta = None;
+    }
+
+    /// Fill the classification and fundamentals this quote lacks from an
+    /// earlier quote of the same symbol.
+    pub fn keep_fundamentals_from(&mut self, older: &StockQuote) {
+        fn keep<T: Clone>(field: &mut Option<T>, older: &Option<T>) {
+            if field.is_none() {
+                field.clone_from(older);
+            }
+        }
+        keep(&mut self.long_name, &older.long_name);
+        keep(&mut self.sector, &older.sector);
+        keep(&mut self.industry, &older.industry);
+        keep(&mut self.market_cap, &older.market_cap);
+        keep(&mut self.trailing_pe, &older.trailing_pe);
+        keep(&mut self.dividend_yield, &older.dividend_yield);
+        keep(&mut self.fifty_two_week_high, &older.fifty_two_week_high);
+        keep(&mut self.fifty_two_week_low, &older.fifty_two_week_low);
+        keep(&mut self.beta, &older.beta);
+        keep(&mut self.average_volume, &older.average_volume);
+    }
+}
+
 /// Historical price data for sparkline chart
 #[derive(Debug, Clone)]
 pub struct ChartData {
@@ -43,6 +93,86 @@ pub struct ChartData {
     pub low: f64,
 }
 
+impl ChartData {
+    /// Build chart data from Yahoo's close series, where holidays and halts
+    /// come through as `null`. Gaps carry the previous close forward (leading
+    /// gaps take the first real close) so the series keeps one point per
+    /// timestamp. High/low are taken from real closes only. `None` when the
+    /// series has no real closes at all.
+    pub fn from_raw_closes(raw: &[Option<f64>]) -> Option<ChartData> {
+        let real = || raw.iter().flatten().copied().filter(|c| c.is_finite());
+        let first = real().next()?;
+        let high = real().fold(f64::NEG_INFINITY, f64::max);
+        let low = real().fold(f64::INFINITY, f64::min);
+
+        let mut last = first;
+        let closes = raw
+            .iter()
+            .map(|c| {
+                if let Some(c) = c.filter(|c| c.is_finite()) {
+                    last = c;
+                }
+                last
+            })
+            .collect();
+        Some(ChartData { closes, high, low })
+    }
+}
+
+/// Time window for the detail sparkline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChartRange {
+    OneMonth,
+    #[default]
+    ThreeMonths,
+    SixMonths,
+    OneYear,
+    YearToDate,
+}
+
+impl ChartRange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChartRange::OneMonth => "1M",
+            ChartRange::ThreeMonths => "3M",
+            ChartRange::SixMonths => "6M",
+            ChartRange::OneYear => "1Y",
+            ChartRange::YearToDate => "YTD",
+        }
+    }
+
+    /// Yahoo `(range, interval)` query values for this window
+    pub fn params(&self) -> (&'static str, &'static str) {
+        match self {
+            ChartRange::OneMonth => ("1mo", "1d"),
+            ChartRange::ThreeMonths => ("3mo", "1d"),
+            ChartRange::SixMonths => ("6mo", "1d"),
+            ChartRange::OneYear => ("1y", "1wk"),
+            ChartRange::YearToDate => ("ytd", "1d"),
+        }
+    }
+
+    pub fn next(&self) -> ChartRange {
+        match self {
+            ChartRange::OneMonth => ChartRange::ThreeMonths,
+            ChartRange::ThreeMonths => ChartRange::SixMonths,
+            ChartRange::SixMonths => ChartRange::OneYear,
+            ChartRange::OneYear => ChartRange::YearToDate,
+            ChartRange::YearToDate => ChartRange::OneMonth,
+        }
+    }
+
+    pub fn prev(&self) -> ChartRange {
+        match self {
+            ChartRange::OneMonth => ChartRange::YearToDate,
+            ChartRange::ThreeMonths => ChartRange::OneMonth,
+            ChartRange::SixMonths => ChartRange::ThreeMonths,
+            ChartRange::OneYear => ChartRange::SixMonths,
+            ChartRange::YearToDate => ChartRange::OneYear,
+        }
+    }
+}
+
 // Chart API response structures
 #[derive(Debug, Deserialize)]
 struct ChartResponse {
@@ -70,6 +200,53 @@ struct ChartQuote {
     close: Option<Vec<Option<f64>>>,
 }
 
+// Search API response structures
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    quotes: Vec<SearchQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchQuote {
+    symbol: String,
+    #[serde(default)]
+    shortname: Option<String>,
+    #[serde(default)]
+    longname: Option<String>,
+}
+
+/// Parse a Yahoo search response into `(display symbol, name)` pairs,
+/// keeping only IDX (`.JK`) listings.
+pub fn parse_symbol_search(body: &str) -> Result<Vec<(String, String)>> {
+    let data: SearchResponse = serde_json::from_str(body)?;
+    Ok(data
+        .quotes
+        .into_iter()
+        .filter(|q| q.symbol.ends_with(".JK"))
+        .map(|q| {
+            let name = q.longname.or(q.shortname).unwrap_or_default();
+            (display_symbol(&q.symbol), name)
+        })
+        .collect())
+}
+
+/// Parse a Yahoo v7 quote response into quotes keyed by display symbol.
+pub fn parse_quotes(body: &str) -> Result<HashMap<String, StockQuote>> {
+    let data: YahooResponse = serde_json::from_str(body)?;
+    if let Some(err) = data.quote_response.error {
+        return Err(anyhow!("Yahoo API error: {:?}", err));
+    }
+
+    let mut quotes = HashMap::new();
+    for result in data.quote_response.result {
+        let quote: StockQuote = result.into();
+        quotes.insert(quote.symbol.clone(), quote);
+    }
+
+    Ok(quotes)
+}
+
 /// A news article from RSS feeds
 #[derive(Debug, Clone)]
 pub struct NewsItem {
@@ -78,6 +255,8 @@ pub struct NewsItem {
     pub published_at: i64, // Unix timestamp
     pub url: Option<String>,
     pub summary: Option<String>, // RSS description/summary field
+    /// Category of the feed the item came from, from `news_categories`
+    pub category: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -138,19 +317,45 @@ struct QuoteResult {
     average_volume: Option<u64>,
 }
 
+/// Prefix marking a symbol to send to Yahoo exactly as typed, for tickers
+/// such as `$AAPL` that would otherwise be taken for an IDX code.
+pub const RAW_SYMBOL_PREFIX: char = '$';
+
+/// Map a config symbol to the one Yahoo expects. Plain codes are IDX stocks
+/// and get `.JK`; anything containing `^`, `=`, `-` or `.` (`^JKSE`, `IDR=X`,
+/// `BTC-USD`, `D05.SI`) is already a Yahoo symbol, as is anything after
+/// `RAW_SYMBOL_PREFIX`.
+pub fn to_yahoo_symbol(code: &str) -> String {
+    let code = code.to_uppercase();
+    if let Some(raw) = code.strip_prefix(RAW_SYMBOL_PREFIX) {
+        raw.to_string()
+    } else if code.contains(['^', '=', '-', '.']) {
+        code
+    } else {
+        format!("{}.JK", code)
+    }
+}
+
+/// Yahoo Finance quote page for a config symbol, mapped the same way as
+/// `to_yahoo_symbol`, e.g. `https://finance.yahoo.com/quote/BBCA.JK`.
+pub fn quote_page_url(code: &str) -> String {
+    format!("https://finance.yahoo.com/quote/{}", to_yahoo_symbol(code))
+}
+
+/// Map a Yahoo (or config) symbol to the key used in the quotes map,
+/// e.g. `BBCA.JK` → `BBCA` and `^JKSE` → `IHSG`. Other Yahoo symbols
+/// (`^N225`, `IDR=X`) are kept as they are so they match the watchlist entry.
+pub fn display_symbol(symbol: &str) -> String {
+    match symbol {
+        "^JKSE" => "IHSG".to_string(),
+        other => other.trim_end_matches(".JK").to_string(),
+    }
+}
+
 impl From<QuoteResult> for StockQuote {
     fn from(q: QuoteResult) -> Self {
-        let display_symbol = if q.symbol.starts_with('^') {
-            match q.symbol.as_str() {
-                "^JKSE" => "IHSG".to_string(),
-                other => other.trim_start_matches('^').to_string(),
-            }
-        } else {
-            q.symbol.trim_end_matches(".JK").to_string()
-        };
-
         StockQuote {
-            symbol: display_symbol,
+            symbol: display_symbol(&q.symbol),
             short_name: q.short_name.unwrap_or_else(|| "N/A".to_string()),
             price: q.regular_market_price.unwrap_or(0.0),
             change: q.regular_market_change.unwrap_or(0.0),
@@ -177,25 +382,106 @@ impl From<QuoteResult> for StockQuote {
     }
 }
 
+/// Bounded exponential backoff for transient Yahoo failures.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Up to 3 retries after 500ms, 1s and 2s.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry (0-based): base, 2×base, 4×base, ...
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(retry)
+    }
+
+    /// Whether a status is worth retrying (rate limiting or server errors).
+    pub fn is_transient(status: StatusCode) -> bool {
+        matches!(status.as_u16(), 429 | 500 | 502 | 503)
+    }
+
+    /// Call `send` until it yields a non-transient response or retries run
+    /// out. Request errors (timeouts, refused connections) are returned
+    /// straight away so an unreachable Yahoo shows up as offline promptly.
+    /// Returns the last response or error.
+    pub async fn send<F, Fut>(&self, mut send: F) -> reqwest::Result<Response>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = reqwest::Result<Response>>,
+    {
+        let mut retry = 0;
+        loop {
+            let result = send().await;
+            let transient = result
+                .as_ref()
+                .is_ok_and(|response| Self::is_transient(response.status()));
+            if !transient || retry >= self.max_retries {
+                return result;
+            }
+            tokio::time::sleep(self.delay(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+/// Whether `err` came from failing to reach the network at all (DNS,
+/// refused connection, timeout) rather than from a bad response.
+pub fn is_offline_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    })
+}
+
+/// Clones share the underlying connection pool and cookie jar.
+#[derive(Clone)]
 pub struct YahooClient {
     client: Client,
     crumb: Option<String>,
+    retry: RetryPolicy,
+    lite: bool,
 }
 
 impl YahooClient {
     pub fn new() -> Self {
+        Self::with_http(&HttpOptions::default()).expect("Failed to build HTTP client")
+    }
+
+    /// Client using the given timeout and proxy.
+    pub fn with_http(options: &HttpOptions) -> Result<Self> {
         let jar = Arc::new(Jar::default());
-        let client = Client::builder()
-            .cookie_store(true)
-            .cookie_provider(jar)
-            .timeout(std::time::Duration::from_secs(15))
-            .build()
-            .expect("Failed to build HTTP client");
+        let builder = Client::builder().cookie_store(true).cookie_provider(jar);
+        let client = options.apply(builder)?.build()?;
 
-        Self {
+        Ok(Self {
             client,
             crumb: None,
-        }
+            retry: RetryPolicy::default(),
+            lite: false,
+        })
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Have `get_quotes` request only price fields, leaving classification
+    /// and fundamentals unset. `get_full_quotes` still fetches everything.
+    pub fn with_lite(mut self, lite: bool) -> Self {
+        self.lite = lite;
+        self
     }
 
     /// Fetch crumb and cookies from Yahoo Finance
@@ -247,18 +533,24 @@ impl YahooClient {
         Err(anyhow!("Could not extract crumb from Yahoo Finance"))
     }
 
-    /// Convert IDX stock code to Yahoo Finance symbol (add .JK suffix)
-    fn to_yahoo_symbol(code: &str) -> String {
-        let code = code.to_uppercase();
-        if code.starts_with('^') || code.ends_with(".JK") {
-            code
-        } else {
-            format!("{}.JK", code)
-        }
-    }
-
     /// Fetch quotes for multiple stocks
     pub async fn get_quotes(&mut self, symbols: &[String]) -> Result<HashMap<String, StockQuote>> {
+        self.fetch_quotes(symbols, self.lite).await
+    }
+
+    /// Fetch quotes with all fields, even on a lite client.
+    pub async fn get_full_quotes(
+        &mut self,
+        symbols: &[String],
+    ) -> Result<HashMap<String, StockQuote>> {
+        self.fetch_quotes(symbols, false).await
+    }
+
+    async fn fetch_quotes(
+        &mut self,
+        symbols: &[String],
+        lite: bool,
+    ) -> Result<HashMap<String, StockQuote>> {
         if symbols.is_empty() {
             return Ok(HashMap::new());
         }
@@ -269,76 +561,119 @@ impl YahooClient {
             None => self.fetch_crumb().await?,
         };
 
-        let yahoo_symbols: Vec<String> = symbols.iter().map(|s| Self::to_yahoo_symbol(s)).collect();
+        let yahoo_symbols: Vec<String> = symbols.iter().map(|s| to_yahoo_symbol(s)).collect();
         let symbols_param = yahoo_symbols.join(",");
+        let fields = lite.then_some(LITE_QUOTE_FIELDS);
 
-        let response = self
-            .client
-            .get(YAHOO_QUOTE_URL)
-            .query(&[("symbols", &symbols_param), ("crumb", &crumb)])
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .header("Accept", "application/json")
-            .header("Referer", "https://finance.yahoo.com/")
-            .send()
-            .await?;
+        let mut response = self.request_quotes(&symbols_param, &crumb, fields).await?;
 
-        // If unauthorized, try refreshing crumb
-        if response.status() == 401 {
+        // If unauthorized, refresh the crumb and go through the retry loop again
+        if response.status() == StatusCode::UNAUTHORIZED {
             self.crumb = None;
             let new_crumb = self.fetch_crumb().await?;
-
-            let response = self
-                .client
-                .get(YAHOO_QUOTE_URL)
-                .query(&[("symbols", &symbols_param), ("crumb", &new_crumb)])
-                .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-                .header("Accept", "application/json")
-                .header("Referer", "https://finance.yahoo.com/")
-                .send()
+            response = self
+                .request_quotes(&symbols_param, &new_crumb, fields)
                 .await?;
-
-            if !response.status().is_success() {
-                return Err(anyhow!("Yahoo API error: {}", response.status()));
-            }
-
-            let data: YahooResponse = response.json().await?;
-            return Self::parse_response(data);
         }
 
         if !response.status().is_success() {
             return Err(anyhow!("Yahoo API error: {}", response.status()));
         }
 
-        let data: YahooResponse = response.json().await?;
-        Self::parse_response(data)
+        let mut quotes = parse_quotes(&response.text().await?)?;
+        if lite {
+            // Yahoo may add fields on its own; keep lite quotes uniform
+            quotes.values_mut().for_each(StockQuote::strip_fundamentals);
+        }
+        // Yahoo answers `$AAPL` as `AAPL`; key it the way it was requested
+        for symbol in symbols {
+            let requested = display_symbol(&symbol.to_uppercase());
+            if requested.starts_with(RAW_SYMBOL_PREFIX)
+                && let Some(mut quote) = quotes.remove(&display_symbol(&to_yahoo_symbol(symbol)))
+            {
+                quote.symbol = requested.clone();
+                quotes.insert(requested, quote);
+            }
+        }
+        Ok(quotes)
     }
 
-    fn parse_response(data: YahooResponse) -> Result<HashMap<String, StockQuote>> {
-        if let Some(err) = data.quote_response.error {
-            return Err(anyhow!("Yahoo API error: {:?}", err));
+    async fn request_quotes(
+        &self,
+        symbols_param: &str,
+        crumb: &str,
+        fields: Option<&str>,
+    ) -> Result<Response> {
+        let mut query = vec![("symbols", symbols_param), ("crumb", crumb)];
+        if let Some(fields) = fields {
+            query.push(("fields", fields));
         }
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(YAHOO_QUOTE_URL)
+                    .query(&query)
+                    .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+                    .header("Accept", "application/json")
+                    .header("Referer", "https://finance.yahoo.com/")
+                    .send()
+            })
+            .await?;
+        Ok(response)
+    }
 
-        let mut quotes = HashMap::new();
-        for result in data.quote_response.result {
-            let quote: StockQuote = result.into();
-            quotes.insert(quote.symbol.clone(), quote);
+    /// Look up IDX symbols whose code or company name matches `query`.
+    pub async fn search_symbols(&self, query: &str) -> Result<Vec<(String, String)>> {
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(YAHOO_SEARCH_URL)
+                    .query(&[("q", query), ("quotesCount", "20"), ("newsCount", "0")])
+                    .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+                    .header("Accept", "application/json")
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Search API error: {}", response.status()));
         }
 
-        Ok(quotes)
+        parse_symbol_search(&response.text().await?)
+    }
+
+    /// Fetch historical chart data for sparkline over the given range
+    pub async fn get_chart(&self, symbol: &str, range: ChartRange) -> Result<ChartData> {
+        let (range_param, interval) = range.params();
+        self.fetch_chart(symbol, range_param, interval).await
+    }
+
+    /// Fetch today's 5-minute closes for the watchlist mini-trend column
+    pub async fn get_intraday_chart(&self, symbol: &str) -> Result<ChartData> {
+        self.fetch_chart(symbol, "1d", "5m").await
     }
 
-    /// Fetch historical chart data for sparkline (3 months daily)
-    pub async fn get_chart(&self, symbol: &str) -> Result<ChartData> {
-        let yahoo_symbol = Self::to_yahoo_symbol(symbol);
+    async fn fetch_chart(
+        &self,
+        symbol: &str,
+        range_param: &str,
+        interval: &str,
+    ) -> Result<ChartData> {
+        let yahoo_symbol = to_yahoo_symbol(symbol);
         let url = format!("{}/{}", YAHOO_CHART_URL, yahoo_symbol);
 
         let response = self
-            .client
-            .get(&url)
-            .query(&[("interval", "1d"), ("range", "3mo")])
-            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .header("Accept", "application/json")
-            .send()
+            .retry
+            .send(|| {
+                self.client
+                    .get(&url)
+                    .query(&[("interval", interval), ("range", range_param)])
+                    .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+                    .header("Accept", "application/json")
+                    .send()
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -357,25 +692,15 @@ impl YahooClient {
             .and_then(|r| r.into_iter().next())
             .ok_or_else(|| anyhow!("No chart data found"))?;
 
-        let closes: Vec<f64> = result
+        let raw = result
             .indicators
             .quote
             .into_iter()
             .next()
             .and_then(|q| q.close)
-            .unwrap_or_default()
-            .into_iter()
-            .flatten()
-            .collect();
-
-        if closes.is_empty() {
-            return Err(anyhow!("No price data in chart"));
-        }
-
-        let high = closes.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-        let low = closes.iter().cloned().fold(f64::INFINITY, f64::min);
+            .unwrap_or_default();
 
-        Ok(ChartData { closes, high, low })
+        ChartData::from_raw_closes(&raw).ok_or_else(|| anyhow!("No price data in chart"))
     }
 }
 
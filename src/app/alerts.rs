@@ -1,5 +1,16 @@
+use crate::api::yahoo::display_symbol;
 use crate::app::{App, InputMode, ViewMode};
-use crate::config::{Alert, AlertType};
+use crate::config::{Alert, AlertType, DEFAULT_ALERT_COOLDOWN_SECS, validate_alert_value};
+
+/// Announces fired alerts outside the app's own UI. `main.rs` plugs in the
+/// terminal bell and desktop notifications; without a notifier, alerts only
+/// reach the footer and the event log.
+pub trait AlertNotifier: Send {
+    /// Ring the terminal bell (`alert_sound`).
+    fn bell(&mut self);
+    /// Show a desktop notification (`alert_desktop_notifications`).
+    fn desktop(&mut self, summary: &str, body: &str);
+}
 
 impl App {
     pub fn open_alert_modal(&mut self) {
@@ -59,7 +70,7 @@ impl App {
                 .clone();
             self.config.toggle_alert(&id);
             if let Err(e) = self.config.save() {
-                self.status_message = Some(format!("Save error: {}", e));
+                self.notify(format!("Save error: {}", e));
             }
         }
     }
@@ -81,7 +92,7 @@ impl App {
             {
                 self.alert_list_selected -= 1;
             }
-            self.status_message = Some("Alert deleted".to_string());
+            self.notify("Alert deleted");
         }
         Ok(())
     }
@@ -94,27 +105,86 @@ impl App {
         self.pending_alert_type = self.pending_alert_type.next();
     }
 
+    /// Move on to the value step, or straight to the cooldown for alert
+    /// types without a target.
     pub fn alert_type_confirm(&mut self) {
-        self.input_buffer.clear();
-        self.input_mode = InputMode::AlertAddValue;
+        if self.pending_alert_type.needs_target() {
+            self.input_buffer.clear();
+            self.input_mode = InputMode::AlertAddValue;
+        } else {
+            self.pending_alert_value = 0.0;
+            self.alert_cooldown_edit = None;
+            self.input_buffer = DEFAULT_ALERT_COOLDOWN_SECS.to_string();
+            self.input_mode = InputMode::AlertCooldown;
+        }
     }
 
-    pub fn alert_value_confirm(&mut self) -> anyhow::Result<()> {
-        if let Ok(val) = self.input_buffer.trim().parse::<f64>() {
-            if val > 0.0 {
-                if let Some(ref sym) = self.alert_symbol {
-                    let alert = Alert::new(sym, self.pending_alert_type.clone(), val);
-                    self.config.add_alert(alert);
-                    self.config.save()?;
-                    self.status_message = Some(format!("Alert added for {}", sym));
-                    let count = self.config.alerts_for_symbol(sym).len();
-                    self.alert_list_selected = count.saturating_sub(1);
-                }
-            } else {
-                self.status_message = Some("Value must be > 0".to_string());
+    /// Accept the target value and move on to the cooldown step. An
+    /// implausible value for the alert type keeps the prompt open.
+    pub fn alert_value_confirm(&mut self) {
+        let Ok(val) = self.input_buffer.trim().parse::<f64>() else {
+            self.status_message = Some("Invalid number".to_string());
+            self.input_buffer.clear();
+            self.input_mode = InputMode::AlertList;
+            return;
+        };
+        if let Err(msg) = validate_alert_value(&self.pending_alert_type, val) {
+            self.status_message = Some(msg);
+            return;
+        }
+        self.pending_alert_value = val;
+        self.alert_cooldown_edit = None;
+        self.input_buffer = DEFAULT_ALERT_COOLDOWN_SECS.to_string();
+        self.input_mode = InputMode::AlertCooldown;
+    }
+
+    /// `c` in the alert list: edit the selected alert's cooldown.
+    pub fn start_alert_cooldown_edit(&mut self) {
+        let Some(sym) = &self.alert_symbol else {
+            return;
+        };
+        let Some(alert) = self
+            .config
+            .alerts_for_symbol(sym)
+            .get(self.alert_list_selected)
+            .copied()
+        else {
+            return;
+        };
+        self.alert_cooldown_edit = Some(alert.id.clone());
+        self.input_buffer = alert.cooldown_seconds.to_string();
+        self.input_mode = InputMode::AlertCooldown;
+    }
+
+    /// Finish the cooldown step: add the pending alert, or update the one
+    /// being edited. Input that isn't a whole number of seconds keeps the
+    /// prompt open.
+    pub fn alert_cooldown_confirm(&mut self) -> anyhow::Result<()> {
+        let Ok(cooldown) = self.input_buffer.trim().parse::<u32>() else {
+            self.status_message = Some("Cooldown must be a whole number of seconds".to_string());
+            return Ok(());
+        };
+        let Some(sym) = self.alert_symbol.clone() else {
+            return Ok(());
+        };
+        if let Some(id) = self.alert_cooldown_edit.take() {
+            if let Some(alert) = self.config.alerts.iter_mut().find(|a| a.id == id) {
+                alert.cooldown_seconds = cooldown;
             }
+            self.config.save()?;
+            self.status_message = Some(format!("Cooldown set to {}s", cooldown));
         } else {
-            self.status_message = Some("Invalid number".to_string());
+            let mut alert = Alert::new(
+                &sym,
+                self.pending_alert_type.clone(),
+                self.pending_alert_value,
+            );
+            alert.cooldown_seconds = cooldown;
+            self.config.add_alert(alert);
+            self.config.save()?;
+            self.notify(format!("Alert added for {}", sym));
+            let count = self.config.alerts_for_symbol(&sym).len();
+            self.alert_list_selected = count.saturating_sub(1);
         }
         self.input_buffer.clear();
         self.input_mode = InputMode::AlertList;
@@ -123,6 +193,7 @@ impl App {
 
     pub fn cancel_alert_add(&mut self) {
         self.input_buffer.clear();
+        self.alert_cooldown_edit = None;
         self.input_mode = InputMode::AlertList;
     }
 
@@ -134,8 +205,10 @@ impl App {
             .alerts
             .iter()
             .filter_map(|alert| {
-                let quote = self.quotes.get(&alert.symbol)?;
-                if alert.should_trigger(quote.price, quote.change_percent) {
+                let key = display_symbol(&alert.symbol.to_uppercase());
+                let quote = self.quotes.get(&key)?;
+                let prev_price = self.prev_quotes.get(&key).map(|q| q.price);
+                if alert.should_trigger_from(prev_price, quote) {
                     let msg = match alert.alert_type {
                         AlertType::Above => {
                             format!("{} crossed above {:.0}", alert.symbol, alert.target_value)
@@ -151,6 +224,24 @@ impl App {
                             "{} down {:.2}% (target -{:.2}%)",
                             alert.symbol, quote.change_percent, alert.target_value
                         ),
+                        AlertType::CrossAbove => format!(
+                            "{} crossed up through {:.0} (was {:.0})",
+                            alert.symbol,
+                            alert.target_value,
+                            prev_price.unwrap_or_default()
+                        ),
+                        AlertType::CrossBelow => format!(
+                            "{} crossed down through {:.0} (was {:.0})",
+                            alert.symbol,
+                            alert.target_value,
+                            prev_price.unwrap_or_default()
+                        ),
+                        AlertType::NewDayHigh => {
+                            format!("{} at day high {:.0}", alert.symbol, quote.high)
+                        }
+                        AlertType::NewDayLow => {
+                            format!("{} at day low {:.0}", alert.symbol, quote.low)
+                        }
                     };
                     Some((alert.id.clone(), alert.symbol.clone(), msg))
                 } else {
@@ -161,11 +252,21 @@ impl App {
 
         for (id, symbol, msg) in to_trigger {
             self.config.mark_triggered(&id);
+            self.log_event(format!("Alert: {}", msg));
             triggered.push((symbol, msg));
         }
 
-        if !triggered.is_empty() {
+        if let Some((_, msg)) = triggered.last() {
             let _ = self.config.save();
+            self.status_message = Some(msg.clone());
+            if let Some(notifier) = &mut self.notifier {
+                if self.config.alert_sound {
+                    notifier.bell();
+                }
+                if self.config.alert_desktop_notifications {
+                    notifier.desktop("IDX Alert", msg);
+                }
+            }
         }
 
         triggered
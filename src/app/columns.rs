@@ -0,0 +1,150 @@
+use super::{App, InputMode, ViewMode};
+use crate::ui::{portfolio_column_names, watchlist_column_names};
+use anyhow::Result;
+
+/// Resolve a configured column list against a table of `count` columns.
+/// Out-of-range and repeated indices are dropped; an absent or empty list
+/// means every column in its built-in order.
+pub fn column_layout(configured: Option<&[usize]>, count: usize) -> Vec<usize> {
+    let mut layout: Vec<usize> = Vec::new();
+    for &i in configured.unwrap_or_default() {
+        if i < count && !layout.contains(&i) {
+            layout.push(i);
+        }
+    }
+    if layout.is_empty() {
+        layout = (0..count).collect();
+    }
+    layout
+}
+
+impl App {
+    fn picker_column_names(&self) -> Vec<&'static str> {
+        match self.view_mode {
+            ViewMode::Portfolio => portfolio_column_names(),
+            _ => watchlist_column_names(),
+        }
+    }
+
+    fn picker_columns_config(&mut self) -> &mut Option<Vec<usize>> {
+        match self.view_mode {
+            ViewMode::Portfolio => &mut self.config.portfolio_columns,
+            _ => &mut self.config.watchlist_columns,
+        }
+    }
+
+    /// Enabled column indices of the current table, in display order.
+    pub fn enabled_columns(&self) -> Vec<usize> {
+        let configured = match self.view_mode {
+            ViewMode::Portfolio => &self.config.portfolio_columns,
+            _ => &self.config.watchlist_columns,
+        };
+        column_layout(configured.as_deref(), self.picker_column_names().len())
+    }
+
+    /// Rows of the column picker: enabled columns in display order, then the
+    /// disabled ones in built-in order, as `(index, name, enabled)`.
+    pub fn column_picker_rows(&self) -> Vec<(usize, &'static str, bool)> {
+        let names = self.picker_column_names();
+        let enabled = self.enabled_columns();
+        let disabled = (0..names.len()).filter(|i| !enabled.contains(i));
+        enabled
+            .iter()
+            .map(|&i| (i, names[i], true))
+            .chain(disabled.map(|i| (i, names[i], false)))
+            .collect()
+    }
+
+    pub fn open_column_picker(&mut self) {
+        if matches!(self.view_mode, ViewMode::Watchlist | ViewMode::Portfolio) {
+            self.column_picker_selected = 0;
+            self.input_mode = InputMode::ColumnPicker;
+        }
+    }
+
+    pub fn close_column_picker(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn column_picker_up(&mut self) {
+        self.column_picker_selected = self.column_picker_selected.saturating_sub(1);
+    }
+
+    pub fn column_picker_down(&mut self) {
+        let len = self.picker_column_names().len();
+        if self.column_picker_selected + 1 < len {
+            self.column_picker_selected += 1;
+        }
+    }
+
+    /// Show or hide the highlighted column. Newly shown columns go last; the
+    /// last visible column can't be hidden.
+    pub fn toggle_picker_column(&mut self) -> Result<()> {
+        let Some(&(col, name, enabled)) =
+            self.column_picker_rows().get(self.column_picker_selected)
+        else {
+            return Ok(());
+        };
+        let mut layout = self.enabled_columns();
+        if enabled {
+            if layout.len() == 1 {
+                self.status_message = Some("At least one column must stay visible".to_string());
+                return Ok(());
+            }
+            layout.retain(|&i| i != col);
+            self.status_message = Some(format!("Hid column {}", name));
+        } else {
+            layout.push(col);
+            self.status_message = Some(format!("Showing column {}", name));
+        }
+        self.set_columns(layout)?;
+        self.follow_picker_column(col);
+        Ok(())
+    }
+
+    /// Move the highlighted (enabled) column one place left (`-1`) or right (`1`).
+    pub fn move_picker_column(&mut self, delta: isize) -> Result<()> {
+        let mut layout = self.enabled_columns();
+        let pos = self.column_picker_selected;
+        if pos >= layout.len() {
+            return Ok(());
+        }
+        let Some(target) = pos.checked_add_signed(delta).filter(|&t| t < layout.len()) else {
+            return Ok(());
+        };
+        let col = layout[pos];
+        layout.swap(pos, target);
+        self.set_columns(layout)?;
+        self.follow_picker_column(col);
+        Ok(())
+    }
+
+    /// Go back to every column in the built-in order.
+    pub fn reset_columns(&mut self) -> Result<()> {
+        *self.picker_columns_config() = None;
+        self.config.save()?;
+        self.column_picker_selected = 0;
+        self.status_message = Some("Columns reset".to_string());
+        Ok(())
+    }
+
+    fn set_columns(&mut self, layout: Vec<usize>) -> Result<()> {
+        let default: Vec<usize> = (0..self.picker_column_names().len()).collect();
+        *self.picker_columns_config() = if layout == default {
+            None
+        } else {
+            Some(layout)
+        };
+        self.config.save()
+    }
+
+    fn follow_picker_column(&mut self, col: usize) {
+        if let Some(pos) = self
+            .column_picker_rows()
+            .iter()
+            .position(|&(i, _, _)| i == col)
+        {
+            self.column_picker_selected = pos;
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::{App, InputMode, ViewMode};
+use crate::api::StockQuote;
+use crate::api::yahoo::display_symbol;
+
+impl App {
+    /// `v`: compare the selected symbol with one typed at the prompt.
+    pub fn start_compare(&mut self) {
+        let selected = match self.view_mode {
+            ViewMode::Watchlist => self.selected_watchlist_symbol(),
+            ViewMode::Portfolio => self.selected_portfolio_symbol(),
+            ViewMode::News => None,
+        };
+        if let Some(symbol) = selected {
+            self.compare_symbols = vec![symbol];
+            self.compare_charts.clear();
+            self.input_buffer.clear();
+            self.input_mode = InputMode::ComparePick;
+        }
+    }
+
+    /// Open the side-by-side modal for the selected and the typed symbol,
+    /// fetching a quote for the second one if it isn't already known and a
+    /// chart for both. A missing quote or chart is shown as such in the modal.
+    pub async fn confirm_compare(&mut self) {
+        let other = self.input_buffer.trim().to_uppercase();
+        self.input_buffer.clear();
+        if other.is_empty() {
+            self.close_compare();
+            return;
+        }
+        self.compare_symbols.truncate(1);
+        self.compare_symbols.push(other);
+        self.input_mode = InputMode::Compare;
+
+        let missing: Vec<String> = self
+            .compare_symbols
+            .iter()
+            .filter(|s| !self.quotes.contains_key(&display_symbol(s)))
+            .cloned()
+            .collect();
+        self.compare_quotes.clear();
+        if !missing.is_empty() {
+            let fetched = self.client.lock().await.get_quotes(&missing).await;
+            if let Ok(quotes) = fetched {
+                self.compare_quotes = quotes;
+            }
+        }
+
+        self.chart_loading = true;
+        for symbol in self.compare_symbols.clone() {
+            if let Ok(chart) = self
+                .client
+                .lock()
+                .await
+                .get_chart(&symbol, self.chart_range)
+                .await
+            {
+                self.compare_charts.insert(symbol, chart);
+            }
+        }
+        self.chart_loading = false;
+    }
+
+    pub fn close_compare(&mut self) {
+        self.compare_symbols.clear();
+        self.compare_quotes.clear();
+        self.compare_charts.clear();
+        self.input_buffer.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Quote for a compared symbol: the live one when it's in `quotes`,
+    /// otherwise the one fetched for the modal.
+    pub fn compare_quote(&self, symbol: &str) -> Option<&StockQuote> {
+        let symbol = display_symbol(symbol);
+        self.quotes
+            .get(&symbol)
+            .or_else(|| self.compare_quotes.get(&symbol))
+    }
+}
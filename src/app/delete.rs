@@ -0,0 +1,158 @@
+use super::{App, DeleteTarget, InputMode, UndoAction, ViewMode};
+use anyhow::Result;
+
+impl App {
+    /// `d`: delete the selected stock or holding, asking first when
+    /// `confirm_deletes` is on.
+    pub fn request_delete_selected(&mut self) -> Result<()> {
+        let target = match self.view_mode {
+            ViewMode::Watchlist if !self.all_watchlists => {
+                self.selected_watchlist_symbol().map(DeleteTarget::Stock)
+            }
+            // Let remove_selected explain why the combined view can't be edited
+            ViewMode::Watchlist => return self.remove_selected(),
+            ViewMode::Portfolio => self.selected_portfolio_symbol().map(DeleteTarget::Holding),
+            ViewMode::News => None,
+        };
+        self.request_delete(target)
+    }
+
+    /// `D`: delete the active watchlist or portfolio, asking first when
+    /// `confirm_deletes` is on.
+    pub fn request_delete_list(&mut self) -> Result<()> {
+        let target = match self.view_mode {
+            ViewMode::Watchlist if self.all_watchlists => {
+                return self.remove_current_watchlist();
+            }
+            ViewMode::Watchlist if self.config.watchlists.len() > 1 => Some(
+                DeleteTarget::Watchlist(self.config.current_watchlist().name.clone()),
+            ),
+            ViewMode::Watchlist => return self.remove_current_watchlist(),
+            ViewMode::Portfolio if self.config.portfolios.len() > 1 => Some(
+                DeleteTarget::Portfolio(self.config.current_portfolio().name.clone()),
+            ),
+            ViewMode::Portfolio => return self.remove_current_portfolio(),
+            ViewMode::News => None,
+        };
+        self.request_delete(target)
+    }
+
+    fn request_delete(&mut self, target: Option<DeleteTarget>) -> Result<()> {
+        let Some(target) = target else {
+            return Ok(());
+        };
+        if self.config.confirm_deletes {
+            self.pending_delete = Some(target);
+            self.input_mode = InputMode::ConfirmDelete;
+            Ok(())
+        } else {
+            self.perform_delete(target)
+        }
+    }
+
+    /// `y` in `ConfirmDelete`: carry out the pending removal.
+    pub fn confirm_delete(&mut self) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        match self.pending_delete.take() {
+            Some(target) => self.perform_delete(target),
+            None => Ok(()),
+        }
+    }
+
+    pub fn cancel_delete(&mut self) {
+        self.pending_delete = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Remove exactly what `target` names, not whatever is selected now: a
+    /// refresh can re-sort the table while the prompt is open. Targets that
+    /// have gone away in the meantime are left alone.
+    fn perform_delete(&mut self, target: DeleteTarget) -> Result<()> {
+        match target {
+            DeleteTarget::Stock(symbol) => self.remove_watchlist_symbol(&symbol),
+            DeleteTarget::Holding(symbol) => self.remove_holding_symbol(&symbol),
+            DeleteTarget::Watchlist(name) => {
+                match self.config.watchlists.iter().position(|w| w.name == name) {
+                    Some(index) => {
+                        self.config.active_watchlist = index;
+                        self.remove_current_watchlist()
+                    }
+                    None => Ok(()),
+                }
+            }
+            DeleteTarget::Portfolio(name) => {
+                match self.config.portfolios.iter().position(|p| p.name == name) {
+                    Some(index) => {
+                        self.config.active_portfolio = index;
+                        self.remove_current_portfolio()
+                    }
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// `u`: put back whatever the last stock, holding or watchlist removal
+    /// took, at its original position, and save.
+    pub fn undo(&mut self) -> Result<()> {
+        let Some(action) = self.last_undo.take() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return Ok(());
+        };
+        let (row, label) = match action {
+            UndoAction::Stock {
+                watchlist,
+                index,
+                symbol,
+            } => {
+                let Some(list) = self.config.watchlists.get_mut(watchlist) else {
+                    return Ok(());
+                };
+                if !list.symbols.contains(&symbol) {
+                    list.symbols
+                        .insert(index.min(list.symbols.len()), symbol.clone());
+                }
+                self.config.active_watchlist = watchlist;
+                self.all_watchlists = false;
+                self.view_mode = ViewMode::Watchlist;
+                self.apply_watchlist_sort();
+                (Some(symbol.clone()), symbol)
+            }
+            UndoAction::Holding {
+                portfolio,
+                index,
+                holding,
+                transactions,
+            } => {
+                let Some(target) = self.config.portfolios.get_mut(portfolio) else {
+                    return Ok(());
+                };
+                let symbol = holding.symbol.clone();
+                if !target.holdings.iter().any(|h| h.symbol == symbol) {
+                    target
+                        .holdings
+                        .insert(index.min(target.holdings.len()), holding);
+                    target.transactions = transactions;
+                }
+                self.config.active_portfolio = portfolio;
+                self.view_mode = ViewMode::Portfolio;
+                (Some(symbol.clone()), symbol)
+            }
+            UndoAction::Watchlist { index, watchlist } => {
+                let index = index.min(self.config.watchlists.len());
+                let name = watchlist.name.clone();
+                self.config.watchlists.insert(index, watchlist);
+                self.config.active_watchlist = index;
+                self.all_watchlists = false;
+                self.view_mode = ViewMode::Watchlist;
+                self.quotes.clear();
+                self.apply_watchlist_sort();
+                (None, format!("watchlist '{}'", name))
+            }
+        };
+        self.config.save()?;
+        self.reselect_row(row);
+        self.notify(format!("Restored {}", label));
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use super::{App, InputMode};
+use chrono::{DateTime, Local};
+
+/// Most entries the event log keeps; older ones are dropped first.
+pub const EVENT_LOG_CAPACITY: usize = 100;
+
+impl App {
+    /// Show `msg` in the footer and keep it in the event log, for things
+    /// worth finding again later: errors, exports, alerts, adds and removes.
+    pub fn notify(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        self.log_event(msg.clone());
+        self.status_message = Some(msg);
+    }
+
+    /// Record `msg` in the event log without touching the footer.
+    pub fn log_event(&mut self, msg: impl Into<String>) {
+        self.log_event_at(Local::now(), msg);
+    }
+
+    pub fn log_event_at(&mut self, at: DateTime<Local>, msg: impl Into<String>) {
+        if self.event_log.len() == EVENT_LOG_CAPACITY {
+            self.event_log.pop_front();
+        }
+        self.event_log.push_back((at, msg.into()));
+    }
+
+    pub fn show_event_log(&mut self) {
+        self.event_log_scroll = 0;
+        self.input_mode = InputMode::EventLog;
+    }
+
+    pub fn close_event_log(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Scroll back towards older entries (the modal lists newest first).
+    pub fn event_log_scroll_down(&mut self) {
+        if self.event_log_scroll + 1 < self.event_log.len() {
+            self.event_log_scroll += 1;
+        }
+    }
+
+    pub fn event_log_scroll_up(&mut self) {
+        self.event_log_scroll = self.event_log_scroll.saturating_sub(1);
+    }
+}
@@ -1,6 +1,173 @@
-use super::{App, ExportFormat, ExportScope, InputMode, ViewMode};
+use super::{App, ExportFormat, ExportScope, InputMode, ViewMode, relative_volume};
+use crate::api::StockQuote;
+use crate::config::NumberLocale;
+use crate::ui::formatters::{
+    format_compact_with, format_percent_places, format_price, format_price_places, format_volume,
+};
 use anyhow::Result;
 use chrono::Local;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Header of the watchlist CSV export. Columns only ever get appended, so
+/// scripts reading them by position keep working.
+pub const WATCHLIST_CSV_HEADER: &str =
+    "Symbol,Name,Price,Change,Change%,Open,High,Low,Volume,Value,RelVol";
+
+/// How a number is shown in the UI, for human-formatted exports.
+#[derive(Debug, Clone, Copy)]
+enum NumberKind {
+    /// `9,025.50`
+    Price,
+    /// `+25`, whole units with a sign
+    Change,
+    /// `1.23B`, also used for value and cost
+    Compact,
+    /// `+1.23B`
+    ProfitLoss,
+    /// `12.34%`
+    Percent,
+    /// `+1.23%`
+    SignedPercent,
+}
+
+/// Writes export numbers either raw (`{:.2}` in CSV, plain JSON numbers)
+/// for spreadsheets and scripts, or as the strings the UI shows when
+/// `export_human_numbers` is on.
+#[derive(Debug, Clone, Copy)]
+struct ExportNumbers {
+    human: bool,
+    locale: NumberLocale,
+    price_places: u8,
+    percent_places: u8,
+}
+
+impl ExportNumbers {
+    const RAW: ExportNumbers = ExportNumbers {
+        human: false,
+        locale: NumberLocale::En,
+        price_places: 2,
+        percent_places: 2,
+    };
+
+    fn text(&self, kind: NumberKind, value: f64) -> String {
+        let sign = if value >= 0.0 { "+" } else { "-" };
+        match kind {
+            NumberKind::Price => format_price_places(value, self.locale, self.price_places),
+            NumberKind::Change => format!("{:+.0}", value),
+            NumberKind::Compact => format_compact_with(value, self.locale),
+            NumberKind::ProfitLoss => {
+                format!("{}{}", sign, format_compact_with(value, self.locale))
+            }
+            NumberKind::Percent => format_percent_places(value, self.percent_places, false),
+            NumberKind::SignedPercent => format_percent_places(value, self.percent_places, true),
+        }
+    }
+
+    fn csv(&self, kind: NumberKind, value: f64) -> String {
+        if self.human {
+            csv_quote(&self.text(kind, value))
+        } else {
+            format!("{:.2}", value)
+        }
+    }
+
+    fn json(&self, kind: NumberKind, value: f64) -> serde_json::Value {
+        if self.human {
+            self.text(kind, value).into()
+        } else {
+            value.into()
+        }
+    }
+
+    fn volume_csv(&self, volume: u64) -> String {
+        if self.human {
+            csv_quote(&format_compact_with(volume as f64, self.locale))
+        } else {
+            volume.to_string()
+        }
+    }
+
+    fn volume_json(&self, volume: u64) -> serde_json::Value {
+        if self.human {
+            format_compact_with(volume as f64, self.locale).into()
+        } else {
+            volume.into()
+        }
+    }
+}
+
+/// Quote a CSV field that contains a comma or quote, as grouped numbers do.
+fn csv_quote(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// JSON shape shared by the watchlist export and `idx-cli quote --json`.
+/// `value` is price × volume and `relative_volume` is null when Yahoo has
+/// no average volume, matching the Value and Rel Vol table columns.
+fn quote_json_value(
+    symbol: &str,
+    quote: Option<&StockQuote>,
+    numbers: ExportNumbers,
+) -> serde_json::Value {
+    use NumberKind::*;
+    if let Some(q) = quote {
+        serde_json::json!({
+            "symbol": q.symbol,
+            "name": q.short_name,
+            "price": numbers.json(Price, q.price),
+            "change": numbers.json(Change, q.change),
+            "change_percent": numbers.json(SignedPercent, q.change_percent),
+            "open": numbers.json(Price, q.open),
+            "high": numbers.json(Price, q.high),
+            "low": numbers.json(Price, q.low),
+            "volume": numbers.volume_json(q.volume),
+            "value": numbers.json(Compact, q.price * q.volume as f64),
+            "relative_volume": relative_volume(q)
+        })
+    } else {
+        serde_json::json!({
+            "symbol": symbol,
+            "name": null,
+            "price": null
+        })
+    }
+}
+
+/// Pretty JSON array of `symbols` in the watchlist export shape; symbols
+/// missing from `quotes` get a null price.
+pub fn quotes_json(symbols: &[String], quotes: &HashMap<String, StockQuote>) -> String {
+    let data: Vec<serde_json::Value> = symbols
+        .iter()
+        .map(|s| quote_json_value(s, quotes.get(s), ExportNumbers::RAW))
+        .collect();
+    serde_json::to_string_pretty(&data).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Plain-text table of symbol, price, change% and volume for terminal output.
+pub fn quotes_table(symbols: &[String], quotes: &HashMap<String, StockQuote>) -> String {
+    let mut out = format!(
+        "{:<8} {:>12} {:>9} {:>10}\n",
+        "Symbol", "Price", "Change%", "Volume"
+    );
+    for symbol in symbols {
+        match quotes.get(symbol) {
+            Some(q) => out.push_str(&format!(
+                "{:<8} {:>12} {:>+8.2}% {:>10}\n",
+                q.symbol,
+                format_price(q.price),
+                q.change_percent,
+                format_volume(q.volume)
+            )),
+            None => out.push_str(&format!("{:<8} {:>12}\n", symbol, "not found")),
+        }
+    }
+    out
+}
 
 impl App {
     pub fn start_export(&mut self) {
@@ -19,14 +186,16 @@ impl App {
     pub fn toggle_export_format(&mut self) {
         self.export_format = match self.export_format {
             ExportFormat::Csv => ExportFormat::Json,
-            ExportFormat::Json => ExportFormat::Csv,
+            ExportFormat::Json => ExportFormat::JsonLines,
+            ExportFormat::JsonLines => ExportFormat::Csv,
         };
     }
 
     pub fn toggle_export_scope(&mut self) {
         self.export_scope = match self.export_scope {
             ExportScope::Watchlist => ExportScope::Portfolio,
-            ExportScope::Portfolio => ExportScope::Watchlist,
+            ExportScope::Portfolio => ExportScope::Allocation,
+            ExportScope::Allocation => ExportScope::Watchlist,
         };
     }
 
@@ -44,50 +213,115 @@ impl App {
 
     pub fn confirm_export(&mut self) -> Result<()> {
         if self.export_menu_selection == 2 {
-            let result = self.perform_export();
+            let result = self.perform_export(None);
             self.input_mode = InputMode::Normal;
             match result {
                 Ok(path) => {
-                    self.status_message = Some(format!("Exported to {}", path));
+                    self.notify(format!("Exported to {}", path));
                 }
                 Err(e) => {
-                    self.status_message = Some(format!("Export failed: {}", e));
+                    self.notify(format!("Export failed: {}", e));
                 }
             }
         }
         Ok(())
     }
 
-    fn perform_export(&self) -> Result<String> {
+    /// Fetch one round of quotes for `scope` and write the export without
+    /// touching the terminal. Used by the `--export` CLI flag.
+    pub async fn run_headless_export(
+        &mut self,
+        format: ExportFormat,
+        scope: ExportScope,
+        output: Option<PathBuf>,
+    ) -> Result<String> {
+        self.export_format = format;
+        self.export_scope = scope;
+        self.view_mode = match scope {
+            ExportScope::Watchlist => ViewMode::Watchlist,
+            ExportScope::Portfolio | ExportScope::Allocation => ViewMode::Portfolio,
+        };
+        if let Some(symbols) = self.refresh_symbols() {
+            self.quotes = self.client.lock().await.get_quotes(&symbols).await?;
+        }
+        self.perform_export(output)
+    }
+
+    fn perform_export(&self, output: Option<PathBuf>) -> Result<String> {
         use std::fs;
         use std::io::Write;
 
-        let dir = self.get_export_dir()?;
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
         let scope_str = match self.export_scope {
             ExportScope::Watchlist => "watchlist",
             ExportScope::Portfolio => "portfolio",
+            ExportScope::Allocation => "allocation",
         };
-        let ext = match self.export_format {
-            ExportFormat::Csv => "csv",
-            ExportFormat::Json => "json",
-        };
-        let filename = format!("idx_{}_{}.{}", scope_str, timestamp, ext);
-        let filepath = dir.join(&filename);
-
-        let content = match (self.export_scope, self.export_format) {
-            (ExportScope::Watchlist, ExportFormat::Csv) => self.export_watchlist_csv(),
-            (ExportScope::Watchlist, ExportFormat::Json) => self.export_watchlist_json(),
-            (ExportScope::Portfolio, ExportFormat::Csv) => self.export_portfolio_csv(),
-            (ExportScope::Portfolio, ExportFormat::Json) => self.export_portfolio_json(),
+        let filepath = match output {
+            Some(path) => path,
+            // A fixed name so repeated runs build up one time series
+            None if self.export_format == ExportFormat::JsonLines => self
+                .get_export_dir()?
+                .join(format!("idx_{}.jsonl", scope_str)),
+            None => {
+                let dir = self.get_export_dir()?;
+                let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+                let ext = match self.export_format {
+                    ExportFormat::Csv => "csv",
+                    _ => "json",
+                };
+                dir.join(format!("idx_{}_{}.{}", scope_str, timestamp, ext))
+            }
         };
 
-        let mut file = fs::File::create(&filepath)?;
-        file.write_all(content.as_bytes())?;
+        let rows = match self.export_scope {
+            ExportScope::Watchlist => self.watchlist_json_rows(),
+            ExportScope::Portfolio => self.portfolio_json_rows(),
+            ExportScope::Allocation => self.allocation_json_rows(),
+        };
+        match self.export_format {
+            ExportFormat::Csv | ExportFormat::Json => {
+                let content = match (self.export_scope, self.export_format) {
+                    (ExportScope::Watchlist, ExportFormat::Csv) => self.export_watchlist_csv(),
+                    (ExportScope::Portfolio, ExportFormat::Csv) => self.export_portfolio_csv(),
+                    (ExportScope::Allocation, ExportFormat::Csv) => self.export_allocation_csv(),
+                    _ => serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string()),
+                };
+                let mut file = fs::File::create(&filepath)?;
+                file.write_all(content.as_bytes())?;
+            }
+            ExportFormat::JsonLines => {
+                let record = serde_json::json!({
+                    "timestamp": Local::now().to_rfc3339(),
+                    "scope": scope_str,
+                    "rows": rows,
+                });
+                // One write per line on an O_APPEND handle, so runs that
+                // overlap append whole lines instead of overwriting each other
+                let mut line = serde_json::to_string(&record)?;
+                line.push('\n');
+                let mut file = fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&filepath)?;
+                file.write_all(line.as_bytes())?;
+                file.flush()?;
+            }
+        }
 
         Ok(filepath.to_string_lossy().to_string())
     }
 
+    /// Number formatting for exports, per `export_human_numbers` and the
+    /// display settings.
+    fn export_numbers(&self) -> ExportNumbers {
+        ExportNumbers {
+            human: self.config.export_human_numbers,
+            locale: self.config.number_locale,
+            price_places: self.config.price_decimals,
+            percent_places: self.config.percent_decimals,
+        }
+    }
+
     fn get_export_dir(&self) -> Result<std::path::PathBuf> {
         if let Some(home) = dirs::home_dir() {
             let downloads = home.join("Downloads");
@@ -99,59 +333,49 @@ impl App {
         Ok(std::env::current_dir()?)
     }
 
-    fn export_watchlist_csv(&self) -> String {
-        let mut csv = String::from("Symbol,Name,Price,Change,Change%,Open,High,Low,Volume\n");
+    /// The current watchlist as CSV, one row per symbol under
+    /// `WATCHLIST_CSV_HEADER`. RelVol is left empty when unknown.
+    pub fn export_watchlist_csv(&self) -> String {
+        use NumberKind::*;
+        let numbers = self.export_numbers();
+        let mut csv = format!("{}\n", WATCHLIST_CSV_HEADER);
         for (symbol, quote) in self.get_raw_watchlist() {
             if let Some(q) = quote {
+                let rel_volume = relative_volume(q)
+                    .map(|r| format!("{:.2}", r))
+                    .unwrap_or_default();
                 csv.push_str(&format!(
-                    "{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{}\n",
+                    "{},{},{},{},{},{},{},{},{},{},{}\n",
                     q.symbol,
                     q.short_name.replace(',', ";"),
-                    q.price,
-                    q.change,
-                    q.change_percent,
-                    q.open,
-                    q.high,
-                    q.low,
-                    q.volume
+                    numbers.csv(Price, q.price),
+                    numbers.csv(Change, q.change),
+                    numbers.csv(SignedPercent, q.change_percent),
+                    numbers.csv(Price, q.open),
+                    numbers.csv(Price, q.high),
+                    numbers.csv(Price, q.low),
+                    numbers.volume_csv(q.volume),
+                    numbers.csv(Compact, q.price * q.volume as f64),
+                    rel_volume
                 ));
             } else {
-                csv.push_str(&format!("{},Loading...,,,,,,,\n", symbol));
+                csv.push_str(&format!("{},Loading...,,,,,,,,,\n", symbol));
             }
         }
         csv
     }
 
-    fn export_watchlist_json(&self) -> String {
-        let data: Vec<serde_json::Value> = self
-            .get_raw_watchlist()
+    fn watchlist_json_rows(&self) -> Vec<serde_json::Value> {
+        let numbers = self.export_numbers();
+        self.get_raw_watchlist()
             .iter()
-            .map(|(symbol, quote)| {
-                if let Some(q) = quote {
-                    serde_json::json!({
-                        "symbol": q.symbol,
-                        "name": q.short_name,
-                        "price": q.price,
-                        "change": q.change,
-                        "change_percent": q.change_percent,
-                        "open": q.open,
-                        "high": q.high,
-                        "low": q.low,
-                        "volume": q.volume
-                    })
-                } else {
-                    serde_json::json!({
-                        "symbol": symbol,
-                        "name": null,
-                        "price": null
-                    })
-                }
-            })
-            .collect();
-        serde_json::to_string_pretty(&data).unwrap_or_else(|_| "[]".to_string())
+            .map(|(symbol, quote)| quote_json_value(symbol, *quote, numbers))
+            .collect()
     }
 
     fn export_portfolio_csv(&self) -> String {
+        use NumberKind::*;
+        let numbers = self.export_numbers();
         let mut csv = String::from("Symbol,Lots,Shares,AvgPrice,CurrentPrice,Value,Cost,PL,PL%\n");
         for holding in &self.config.current_portfolio().holdings {
             let curr_price = self
@@ -159,28 +383,30 @@ impl App {
                 .get(&holding.symbol)
                 .map(|q| q.price)
                 .unwrap_or(0.0);
-            let shares = holding.shares();
-            let (value, cost, pl, pl_percent) = holding.pl_metrics(curr_price);
+            let shares = holding.shares(self.config.lot_size);
+            let (value, cost, pl, pl_percent) =
+                holding.pl_metrics(curr_price, self.config.lot_size);
 
             csv.push_str(&format!(
-                "{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2}\n",
+                "{},{},{},{},{},{},{},{},{}\n",
                 holding.symbol,
                 holding.lots,
                 shares,
-                holding.avg_price,
-                curr_price,
-                value,
-                cost,
-                pl,
-                pl_percent
+                numbers.csv(Price, holding.avg_price),
+                numbers.csv(Price, curr_price),
+                numbers.csv(Compact, value),
+                numbers.csv(Compact, cost),
+                numbers.csv(ProfitLoss, pl),
+                numbers.csv(SignedPercent, pl_percent)
             ));
         }
         csv
     }
 
-    fn export_portfolio_json(&self) -> String {
-        let data: Vec<serde_json::Value> = self
-            .config
+    fn portfolio_json_rows(&self) -> Vec<serde_json::Value> {
+        use NumberKind::*;
+        let numbers = self.export_numbers();
+        self.config
             .current_portfolio()
             .holdings
             .iter()
@@ -190,22 +416,88 @@ impl App {
                     .get(&holding.symbol)
                     .map(|q| q.price)
                     .unwrap_or(0.0);
-                let shares = holding.shares();
-                let (value, cost, pl, pl_percent) = holding.pl_metrics(curr_price);
+                let shares = holding.shares(self.config.lot_size);
+                let (value, cost, pl, pl_percent) =
+                    holding.pl_metrics(curr_price, self.config.lot_size);
 
                 serde_json::json!({
                     "symbol": holding.symbol,
                     "lots": holding.lots,
                     "shares": shares,
-                    "avg_price": holding.avg_price,
-                    "current_price": curr_price,
-                    "value": value,
-                    "cost": cost,
-                    "pl": pl,
-                    "pl_percent": pl_percent
+                    "avg_price": numbers.json(Price, holding.avg_price),
+                    "current_price": numbers.json(Price, curr_price),
+                    "value": numbers.json(Compact, value),
+                    "cost": numbers.json(Compact, cost),
+                    "pl": numbers.json(ProfitLoss, pl),
+                    "pl_percent": numbers.json(SignedPercent, pl_percent)
                 })
             })
-            .collect();
-        serde_json::to_string_pretty(&data).unwrap_or_else(|_| "[]".to_string())
+            .collect()
+    }
+
+    /// `portfolio_allocation` as CSV, largest holding first, for feeding
+    /// weights into a spreadsheet.
+    pub fn export_allocation_csv(&self) -> String {
+        let numbers = self.export_numbers();
+        let mut csv = String::from("Symbol,Value,Percent\n");
+        for (symbol, value, pct) in self.portfolio_allocation() {
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                symbol,
+                numbers.csv(NumberKind::Compact, value),
+                numbers.csv(NumberKind::Percent, pct)
+            ));
+        }
+        csv
+    }
+
+    fn allocation_json_rows(&self) -> Vec<serde_json::Value> {
+        let numbers = self.export_numbers();
+        self.portfolio_allocation()
+            .into_iter()
+            .map(|(symbol, value, pct)| {
+                serde_json::json!({
+                    "symbol": symbol,
+                    "value": numbers.json(NumberKind::Compact, value),
+                    "percent": numbers.json(NumberKind::Percent, pct)
+                })
+            })
+            .collect()
+    }
+
+    /// Text `y` (symbol) or `Y` (quote summary) copies for the selected row,
+    /// or `None` when nothing is selected.
+    pub fn clipboard_text(&self, summary: bool) -> Option<String> {
+        let symbol = match self.view_mode {
+            ViewMode::Watchlist => self.selected_watchlist_symbol(),
+            ViewMode::Portfolio => self.selected_portfolio_symbol(),
+            ViewMode::News => None,
+        }?;
+        if !summary {
+            return Some(symbol);
+        }
+        let fmt = self.config.number_format();
+        Some(match self.quotes.get(&symbol) {
+            Some(q) => format!(
+                "{} {} {:+.2} ({:+.2}%) Vol {}",
+                symbol,
+                fmt.price(q.price),
+                q.change,
+                q.change_percent,
+                fmt.volume(q.volume)
+            ),
+            None => symbol,
+        })
+    }
+
+    /// Copy the selected symbol, or its quote summary, to the system clipboard.
+    pub fn copy_selected(&mut self, summary: bool) {
+        let Some(text) = self.clipboard_text(summary) else {
+            return;
+        };
+        self.status_message = Some(match self.clipboard.copy_text(&text) {
+            Ok(()) => format!("Copied: {}", text),
+            Err(e) => format!("Copy failed: {}", e),
+        });
     }
 }
@@ -0,0 +1,137 @@
+//! Tiny arithmetic evaluator for price prompts, so `8000*1.01` or
+//! `(7800+8200)/2` can be typed instead of a worked-out number.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(f64),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn precedence(op: char) -> u8 {
+    match op {
+        '*' | '/' => 2,
+        _ => 1,
+    }
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let mut number = String::new();
+                while let Some(&d) = chars.peek() {
+                    if !(d.is_ascii_digit() || d == '.') {
+                        break;
+                    }
+                    number.push(d);
+                    chars.next();
+                }
+                tokens.push(Token::Num(number.parse().ok()?));
+            }
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Op(c));
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::Open);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                chars.next();
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+/// Shunting-yard: infix tokens to reverse Polish order. Rejects operators
+/// without an operand on each side and unbalanced parentheses.
+fn to_rpn(tokens: Vec<Token>) -> Option<Vec<Token>> {
+    let mut output = Vec::new();
+    let mut stack: Vec<Token> = Vec::new();
+    let mut expect_operand = true;
+    for token in tokens {
+        match token {
+            Token::Num(_) if expect_operand => {
+                output.push(token);
+                expect_operand = false;
+            }
+            Token::Op(op) if !expect_operand => {
+                while let Some(&Token::Op(top)) = stack.last() {
+                    if precedence(top) < precedence(op) {
+                        break;
+                    }
+                    output.push(stack.pop()?);
+                }
+                stack.push(token);
+                expect_operand = true;
+            }
+            Token::Open if expect_operand => stack.push(token),
+            Token::Close if !expect_operand => loop {
+                match stack.pop()? {
+                    Token::Open => break,
+                    op => output.push(op),
+                }
+            },
+            _ => return None,
+        }
+    }
+    if expect_operand {
+        return None;
+    }
+    while let Some(token) = stack.pop() {
+        if token == Token::Open {
+            return None;
+        }
+        output.push(token);
+    }
+    Some(output)
+}
+
+/// Evaluate `+ - * /` with the usual precedence and parentheses. `None` for
+/// anything malformed, division by zero or a non-finite result.
+pub fn eval_expression(input: &str) -> Option<f64> {
+    let rpn = to_rpn(tokenize(input)?)?;
+    let mut values: Vec<f64> = Vec::new();
+    for token in rpn {
+        match token {
+            Token::Num(n) => values.push(n),
+            Token::Op(op) => {
+                let b = values.pop()?;
+                let a = values.pop()?;
+                values.push(match op {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    _ if b == 0.0 => return None,
+                    _ => a / b,
+                });
+            }
+            Token::Open | Token::Close => return None,
+        }
+    }
+    match values.as_slice() {
+        [result] if result.is_finite() => Some(*result),
+        _ => None,
+    }
+}
+
+/// A price typed into a prompt: a plain number, or failing that an
+/// arithmetic expression. Expression results are rounded to two decimals so
+/// `8000*1.01` doesn't store float noise.
+pub fn parse_price(input: &str) -> Option<f64> {
+    let input = input.trim();
+    input
+        .parse::<f64>()
+        .ok()
+        .or_else(|| eval_expression(input).map(|v| (v * 100.0).round() / 100.0))
+}
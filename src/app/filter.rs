@@ -2,7 +2,8 @@ use super::sort::{
     compare_bookmark_column, compare_news_column, compare_portfolio_column,
     compare_watchlist_column,
 };
-use super::{App, InputMode, SortDirection};
+use super::{App, InputMode, NewsTab, SortDirection, ViewMode, title_contains_ticker};
+use crate::api::yahoo::display_symbol;
 use crate::api::{NewsItem, StockQuote};
 use crate::config::Bookmark;
 
@@ -37,6 +38,46 @@ impl App {
         self.input_buffer.clear();
     }
 
+    /// `g`: type a prefix to move the selection without filtering.
+    pub fn start_quick_jump(&mut self) {
+        self.input_mode = InputMode::QuickJump;
+        self.input_buffer.clear();
+    }
+
+    /// Select the first row of the active table whose symbol (or headline
+    /// in the News view) starts with the typed prefix. No match leaves the
+    /// selection where it is.
+    pub fn quick_jump_changed(&mut self) {
+        let prefix = self.input_buffer.trim().to_uppercase();
+        if prefix.is_empty() {
+            return;
+        }
+        let labels: Vec<String> = match self.view_mode {
+            ViewMode::Watchlist | ViewMode::Portfolio => self.current_row_keys(),
+            ViewMode::News => {
+                if self.news_tab == NewsTab::Bookmarks {
+                    self.get_filtered_bookmarks()
+                        .into_iter()
+                        .map(|b| b.headline.clone())
+                        .collect()
+                } else {
+                    self.current_row_keys()
+                }
+            }
+        };
+        if let Some(index) = labels
+            .iter()
+            .position(|l| l.to_uppercase().starts_with(&prefix))
+        {
+            self.jump_selection(|_, _| index);
+        }
+    }
+
+    pub fn end_quick_jump(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+    }
+
     pub fn clear_filter(&mut self) {
         self.search_query.clear();
         self.search_active = false;
@@ -50,19 +91,81 @@ impl App {
         *self.bookmark_table_state.offset_mut() = 0;
     }
 
+    /// Whether `symbol` is drawn in the pinned rows rather than the table body.
+    pub fn is_pinned(&self, symbol: &str) -> bool {
+        let key = display_symbol(&symbol.to_uppercase());
+        self.config
+            .pinned
+            .iter()
+            .any(|p| display_symbol(&p.to_uppercase()) == key)
+    }
+
+    /// Pinned symbols in config order with their quotes, each listed once.
+    pub fn get_pinned_rows(&self) -> Vec<(&String, Option<&StockQuote>)> {
+        let mut seen = std::collections::HashSet::new();
+        self.config
+            .pinned
+            .iter()
+            .map(|symbol| (symbol, display_symbol(&symbol.to_uppercase())))
+            .filter(|(_, key)| seen.insert(key.clone()))
+            .map(|(symbol, key)| (symbol, self.quotes.get(&key)))
+            .collect()
+    }
+
+    /// Watchlist body rows: the active list (or every list when combined)
+    /// minus pinned symbols, which are drawn separately above it.
     pub fn get_raw_watchlist(&self) -> Vec<(&String, Option<&StockQuote>)> {
+        let symbols: Vec<&String> = if self.all_watchlists {
+            self.get_all_symbols_combined()
+        } else {
+            self.config.current_watchlist().symbols.iter().collect()
+        };
+        symbols
+            .into_iter()
+            .filter(|symbol| !self.is_pinned(symbol))
+            .map(|symbol| {
+                let key = display_symbol(&symbol.to_uppercase());
+                (symbol, self.quotes.get(&key))
+            })
+            .collect()
+    }
+
+    /// Symbols from every watchlist in list order, each listed once.
+    pub fn get_all_symbols_combined(&self) -> Vec<&String> {
+        let mut seen = std::collections::HashSet::new();
         self.config
-            .current_watchlist()
-            .symbols
+            .watchlists
             .iter()
-            .map(|symbol| (symbol, self.quotes.get(symbol)))
+            .flat_map(|wl| wl.symbols.iter())
+            .filter(|s| seen.insert(s.as_str()))
             .collect()
     }
 
+    /// Case-insensitive match of the search query against the symbol and,
+    /// once a quote has been fetched, the company's short and long names.
+    fn matches_search(&self, symbol: &str, quote: Option<&StockQuote>) -> bool {
+        let query = &self.search_query;
+        symbol.to_uppercase().contains(query)
+            || quote.is_some_and(|q| {
+                q.short_name.to_uppercase().contains(query)
+                    || q.long_name
+                        .as_ref()
+                        .is_some_and(|n| n.to_uppercase().contains(query))
+            })
+    }
+
     pub fn get_filtered_watchlist(&self) -> Vec<(&String, Option<&StockQuote>)> {
         let mut items = self.get_raw_watchlist();
+        if self.config.hide_loading_rows {
+            items.retain(|(symbol, quote)| {
+                quote.is_some()
+                    || !self
+                        .pending_symbols
+                        .contains(&display_symbol(&symbol.to_uppercase()))
+            });
+        }
         if self.search_active {
-            items.retain(|(symbol, _)| symbol.to_uppercase().contains(&self.search_query));
+            items.retain(|(symbol, quote)| self.matches_search(symbol, *quote));
         }
         if let Some(col) = self.watchlist_sort_column {
             let dir = self.watchlist_sort_direction;
@@ -80,7 +183,12 @@ impl App {
             .enumerate()
             .collect();
         if self.search_active {
-            items.retain(|(_, h)| h.symbol.to_uppercase().contains(&self.search_query));
+            items.retain(|(_, h)| {
+                self.matches_search(&h.symbol, self.quotes.get(&h.symbol))
+                    || h.note
+                        .as_ref()
+                        .is_some_and(|n| n.to_uppercase().contains(&self.search_query))
+            });
         }
         if let Some(col) = self.portfolio_sort_column {
             let dir = self.portfolio_sort_direction;
@@ -108,8 +216,44 @@ impl App {
             .map(|(_, h)| h.symbol.clone())
     }
 
+    /// Identity of each row in the active table, in display order: the symbol
+    /// for watchlist/portfolio rows, the title for news and the id for bookmarks.
+    pub(crate) fn current_row_keys(&self) -> Vec<String> {
+        match self.view_mode {
+            ViewMode::Watchlist => self
+                .get_filtered_watchlist()
+                .into_iter()
+                .map(|(s, _)| s.clone())
+                .collect(),
+            ViewMode::Portfolio => self
+                .get_filtered_portfolio()
+                .into_iter()
+                .map(|(_, h)| h.symbol.clone())
+                .collect(),
+            ViewMode::News => {
+                if self.news_tab == NewsTab::Bookmarks {
+                    self.get_filtered_bookmarks()
+                        .into_iter()
+                        .map(|b| b.id.clone())
+                        .collect()
+                } else {
+                    self.get_filtered_news()
+                        .into_iter()
+                        .map(|n| n.title.clone())
+                        .collect()
+                }
+            }
+        }
+    }
+
     pub fn get_filtered_news(&self) -> Vec<&NewsItem> {
         let mut items: Vec<&NewsItem> = self.news_items.iter().collect();
+        if let Some(ticker) = &self.news_ticker_filter {
+            items.retain(|item| title_contains_ticker(&item.title, ticker));
+        }
+        if let Some(category) = &self.news_category_filter {
+            items.retain(|item| item.category.as_ref() == Some(category));
+        }
         if self.search_active {
             items.retain(|item| {
                 item.title.to_uppercase().contains(&self.search_query)
@@ -149,7 +293,7 @@ impl App {
             });
         } else {
             // No explicit sort column: default to bookmarked_at descending
-            items.sort_by(|a, b| b.bookmarked_at.cmp(&a.bookmarked_at));
+            items.sort_by_key(|b| std::cmp::Reverse(b.bookmarked_at));
         }
         items
     }
@@ -1,23 +1,58 @@
 mod alerts;
 mod bookmarks;
+mod columns;
+mod compare;
+mod delete;
+mod events;
 mod export;
+mod expr;
 mod filter;
+mod mouse;
 mod news;
 mod portfolio;
+mod rebalance;
+mod snapshots;
 pub mod sort;
 mod watchlist;
 
-use crate::api::{ChartData, NewsClient, NewsItem, StockQuote, YahooClient};
-use crate::config::{AlertType, Config};
+pub use alerts::AlertNotifier;
+pub use columns::column_layout;
+pub use events::EVENT_LOG_CAPACITY;
+pub use export::{WATCHLIST_CSV_HEADER, quotes_json, quotes_table};
+pub use expr::{eval_expression, parse_price};
+pub use mouse::TableHitArea;
+pub use rebalance::{RebalancePlan, RebalanceRow};
+pub use snapshots::{PortfolioHistory, PortfolioSnapshot, trading_date};
+
+use crate::api::yahoo::display_symbol;
+use crate::api::{
+    ChartData, ChartRange, HttpOptions, NewsClient, NewsItem, QuoteCache, QuoteProvider,
+    StockQuote, YahooClient, is_offline_error,
+};
+use crate::clipboard::Clipboard;
+use crate::config::{AlertType, Config, Holding, Transaction, Watchlist};
+use crate::keymap::KeyMap;
+use crate::ui::formatters::spinner_glyph;
 use crate::ui::{
     BOOKMARK_SORTABLE_COLUMNS, NEWS_SORTABLE_COLUMNS, PORTFOLIO_SORTABLE_COLUMNS,
     WATCHLIST_SORTABLE_COLUMNS,
 };
 use anyhow::Result;
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveTime, Utc, Weekday};
 use ratatui::widgets::TableState;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use tokio::time::Instant;
 
+const QUOTE_CACHE_FILE: &str = "quote_cache.json";
+const PORTFOLIO_HISTORY_FILE: &str = "portfolio_history.json";
+pub const MIN_REFRESH_INTERVAL_SECS: u64 = 1;
+pub const MAX_REFRESH_INTERVAL_SECS: u64 = 300;
+
 /// Check if a headline contains a ticker as a whole word, not as a substring.
 /// e.g. "DEWA" matches "Saham DEWA Naik" and "Darma (DEWA)" but not "Dewan Pengawas".
 pub fn title_contains_ticker(title: &str, ticker: &str) -> bool {
@@ -42,6 +77,127 @@ pub fn title_contains_ticker(title: &str, ticker: &str) -> bool {
     false
 }
 
+/// Today's change% of `symbol` minus the IHSG change%, i.e. how much the
+/// stock is beating (positive) or lagging the index. `None` until both the
+/// stock and IHSG have been quoted.
+pub fn vs_ihsg_pct(symbol: &str, quotes: &HashMap<String, StockQuote>) -> Option<f64> {
+    let quote = quotes.get(symbol)?;
+    let ihsg = quotes.get("IHSG")?;
+    Some(quote.change_percent - ihsg.change_percent)
+}
+
+/// Today's volume as a multiple of the average daily volume. `None` when
+/// the average is unknown or zero.
+pub fn relative_volume(quote: &StockQuote) -> Option<f64> {
+    let average = quote.average_volume.filter(|&v| v > 0)?;
+    Some(quote.volume as f64 / average as f64)
+}
+
+/// How many quotes are up, down and flat, and their average change%.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Breadth {
+    pub advancers: usize,
+    pub decliners: usize,
+    pub unchanged: usize,
+    /// Mean change% of the counted quotes; `None` when there are none
+    pub avg_change_pct: Option<f64>,
+}
+
+/// Breadth of `quotes`, leaving out the IHSG index itself.
+pub fn market_breadth<'a>(quotes: impl IntoIterator<Item = &'a StockQuote>) -> Breadth {
+    let mut breadth = Breadth::default();
+    let mut total_change = 0.0;
+    for quote in quotes {
+        if quote.symbol == "IHSG" {
+            continue;
+        }
+        if quote.change_percent > 0.0 {
+            breadth.advancers += 1;
+        } else if quote.change_percent < 0.0 {
+            breadth.decliners += 1;
+        } else {
+            breadth.unchanged += 1;
+        }
+        total_change += quote.change_percent;
+    }
+    let count = breadth.advancers + breadth.decliners + breadth.unchanged;
+    breadth.avg_change_pct = (count > 0).then(|| total_change / count as f64);
+    breadth
+}
+
+/// `now` on Jakarta time (WIB, UTC+7), which IDX trading hours are in.
+pub fn to_wib(now: DateTime<Utc>) -> DateTime<FixedOffset> {
+    let wib = FixedOffset::east_opt(7 * 3600).expect("valid offset");
+    now.with_timezone(&wib)
+}
+
+/// Whether IDX is trading at `now`: a weekday between `open` and `close`
+/// in WIB (UTC+7). Exchange holidays aren't known and count as open.
+pub fn market_is_open(now: DateTime<Utc>, open: NaiveTime, close: NaiveTime) -> bool {
+    let local = to_wib(now);
+    if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    let time = local.time();
+    time >= open && time < close
+}
+
+/// How current the quotes on screen are, for the header indicator. Ages are
+/// seconds since the last successful refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshHealth {
+    /// No refresh has succeeded since launch
+    NeverLoaded,
+    Fresh(u64),
+    /// A few refreshes in a row have been missed
+    Late(u64),
+    Stale(u64),
+}
+
+/// Classify a quote age against the refresh interval: late after three
+/// missed refreshes (at least 10s), stale after ten (at least a minute).
+/// Outside market hours quotes aren't refreshed, so they never age.
+pub fn refresh_health(
+    age_secs: Option<u64>,
+    interval_secs: u64,
+    market_open: bool,
+) -> RefreshHealth {
+    let Some(age) = age_secs else {
+        return RefreshHealth::NeverLoaded;
+    };
+    let interval = interval_secs.max(1);
+    if !market_open || age < (interval * 3).max(10) {
+        RefreshHealth::Fresh(age)
+    } else if age < (interval * 10).max(60) {
+        RefreshHealth::Late(age)
+    } else {
+        RefreshHealth::Stale(age)
+    }
+}
+
+/// Quote fetch running on its own task, from `App::spawn_quote_fetch`.
+pub type QuoteFetch = JoinHandle<Result<HashMap<String, StockQuote>>>;
+
+/// Mini-chart fetch running on its own task, from
+/// `App::spawn_mini_chart_fetch`: each symbol with its chart or error.
+pub type MiniChartFetch = JoinHandle<Vec<(String, Result<ChartData>)>>;
+
+/// A refresh between `App::start_refresh` and `App::finish_refresh`: the
+/// quotes already served from the cache and the symbols still to fetch.
+pub struct PendingRefresh {
+    cached: HashMap<String, StockQuote>,
+    to_fetch: Vec<String>,
+    anchor: Option<Option<String>>,
+    now: u64,
+}
+
+impl PendingRefresh {
+    /// Symbols that need the network, or `None` if the cache had them all.
+    pub fn to_fetch(&self) -> Option<&[String]> {
+        (!self.to_fetch.is_empty()).then_some(self.to_fetch.as_slice())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
@@ -56,16 +212,74 @@ pub enum InputMode {
     Search,
     ExportMenu,
     PortfolioChart,
+    PortfolioPL,
+    PortfolioHistory,
+    TopMovers,
     PortfolioEditLots,
     PortfolioEditPrice,
+    PortfolioSplit,
+    PortfolioNote,
     NewsDetail,
     PortfolioNew,
     PortfolioRename,
     AlertList,
     AlertAddType,
     AlertAddValue,
+    AlertCooldown,
     BookmarkDetail,
     BookmarkClearConfirm,
+    ConfirmDelete,
+    ColumnPicker,
+    NewsSources,
+    NewsSourceAdd,
+    NewsSourceCategory,
+    QuickJump,
+    ComparePick,
+    Compare,
+    EventLog,
+    Rebalance,
+    RebalanceTarget,
+}
+
+/// Something `d`/`D` is about to remove, held while `ConfirmDelete` asks y/n.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteTarget {
+    Stock(String),
+    Holding(String),
+    Watchlist(String),
+    Portfolio(String),
+}
+
+impl DeleteTarget {
+    pub fn describe(&self) -> String {
+        match self {
+            DeleteTarget::Stock(s) => format!("{} from the watchlist", s),
+            DeleteTarget::Holding(s) => format!("holding {}", s),
+            DeleteTarget::Watchlist(name) => format!("watchlist '{}'", name),
+            DeleteTarget::Portfolio(name) => format!("portfolio '{}'", name),
+        }
+    }
+}
+
+/// What the last removal took away, kept so `u` can put it back where it was.
+#[derive(Debug, Clone)]
+pub enum UndoAction {
+    Stock {
+        watchlist: usize,
+        index: usize,
+        symbol: String,
+    },
+    Holding {
+        portfolio: usize,
+        index: usize,
+        holding: Holding,
+        /// The portfolio's transaction log before the removal recorded a sell
+        transactions: Vec<Transaction>,
+    },
+    Watchlist {
+        index: usize,
+        watchlist: Watchlist,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +289,26 @@ pub enum ViewMode {
     News,
 }
 
+impl ViewMode {
+    /// Name stored in `Config::last_view_mode`.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            ViewMode::Watchlist => "watchlist",
+            ViewMode::Portfolio => "portfolio",
+            ViewMode::News => "news",
+        }
+    }
+
+    /// Parse a stored view name, falling back to Watchlist for unknown values.
+    pub fn from_config_name(name: &str) -> Self {
+        match name {
+            "portfolio" => ViewMode::Portfolio,
+            "news" => ViewMode::News,
+            _ => ViewMode::Watchlist,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum NewsTab {
     #[default]
@@ -82,22 +316,27 @@ pub enum NewsTab {
     Bookmarks,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum ExportFormat {
     #[default]
     Csv,
     Json,
+    /// One timestamped JSON object per export, appended to a fixed `.jsonl` file
+    JsonLines,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum ExportScope {
     #[default]
     Watchlist,
     Portfolio,
+    /// The portfolio allocation chart: each holding's value and weight
+    Allocation,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum SortDirection {
+    #[default]
     Ascending,
     Descending,
 }
@@ -121,14 +360,61 @@ impl SortDirection {
 pub struct App {
     pub config: Config,
     pub quotes: HashMap<String, StockQuote>,
+    /// `quotes` as they were before the latest refresh, for the tick arrows
+    pub prev_quotes: HashMap<String, StockQuote>,
     pub selected_index: usize,
     pub input_mode: InputMode,
     pub input_buffer: String,
     pub status_message: Option<String>,
     pub loading: bool,
+    /// The last quote refresh couldn't reach the network; quotes on screen
+    /// are from earlier refreshes
+    pub offline: bool,
+    /// Refreshes fetch prices only (`--lite`); fundamentals are kept from
+    /// earlier quotes and fetched for the detail view when it opens
+    pub lite: bool,
+    /// Draw without colors (`--no-color` or `NO_COLOR`)
+    pub no_color: bool,
+    /// Held open so copied text survives until another app takes it
+    pub clipboard: Clipboard,
+    /// Unix time each symbol's quote in `quotes` was fetched
+    pub quote_fetched_at: HashMap<String, u64>,
+    /// When quotes last arrived, from the network or the quote cache
+    pub last_successful_refresh: Option<Instant>,
+    /// Live filter typed while the help modal is open
+    pub help_filter: String,
+    /// Recent notable events with when they happened, oldest first
+    pub event_log: VecDeque<(DateTime<Local>, String)>,
+    /// Entries scrolled past in the event log modal, counted from the newest
+    pub event_log_scroll: usize,
+    /// Normal-mode key → action table built from `config.key_bindings`
+    pub keymap: KeyMap,
+    /// Display symbols requested by the current refresh with no answer yet
+    pub pending_symbols: HashSet<String>,
+    /// Spinner animation frame, advanced once per draw tick
+    pub spinner_frame: usize,
     pub detail_symbol: Option<String>,
     pub detail_chart: Option<ChartData>,
+    /// Intraday charts for the watchlist mini-trend column, with fetch time,
+    /// keyed by display symbol like `quotes`
+    pub mini_charts: HashMap<String, (Instant, ChartData)>,
+    pub chart_range: ChartRange,
+    /// Daily charts of portfolio holdings for the history modal, fetched
+    /// once per session
+    pub history_charts: HashMap<String, ChartData>,
+    /// Combined portfolio value series and the holdings left out of it
+    pub portfolio_history: Option<(Vec<f64>, Vec<String>)>,
+    /// Recorded once-a-day portfolio values and P/L
+    pub value_snapshots: PortfolioHistory,
+    /// Where `value_snapshots` is saved; `None` keeps them in memory only
+    pub value_snapshots_path: Option<PathBuf>,
     pub detail_news: Option<Vec<NewsItem>>,
+    /// Symbols in the compare modal: the selected one, then the typed one
+    pub compare_symbols: Vec<String>,
+    /// Quotes fetched for the compare modal, kept apart from `quotes` so a
+    /// symbol outside every list doesn't show up in watchlist views
+    pub compare_quotes: HashMap<String, StockQuote>,
+    pub compare_charts: HashMap<String, ChartData>,
     pub chart_loading: bool,
     pub news_loading: bool,
     pub view_mode: ViewMode,
@@ -141,9 +427,24 @@ pub struct App {
     pub pending_symbol: Option<String>,
     pub pending_lots: Option<u32>,
     pub pending_edit_symbol: Option<String>,
+    pub pending_delete: Option<DeleteTarget>,
+    pub last_undo: Option<UndoAction>,
+    pub column_picker_selected: usize,
+    /// `(symbol, name)` matches for the add prompt, from the provider's search
+    pub add_suggestions: Vec<(String, String)>,
+    pub add_suggestion_selected: Option<usize>,
+    /// When the debounced symbol search should run
+    pub add_suggest_due: Option<Instant>,
     pub alert_symbol: Option<String>,
     pub alert_list_selected: usize,
+    /// Highlighted row in the rebalancing modal
+    pub rebalance_selected: usize,
     pub pending_alert_type: AlertType,
+    pub pending_alert_value: f64,
+    /// Id of the alert whose cooldown is being edited; `None` while adding
+    pub alert_cooldown_edit: Option<String>,
+    /// Show the deduplicated union of every watchlist instead of the active one
+    pub all_watchlists: bool,
     pub watchlist_sort_column: Option<usize>,
     pub watchlist_sort_direction: SortDirection,
     pub portfolio_sort_column: Option<usize>,
@@ -151,39 +452,98 @@ pub struct App {
     pub news_items: Vec<NewsItem>,
     pub news_selected: usize,
     pub news_last_refresh: Option<Instant>,
+    /// Feed pages loaded since the last refresh; `load_more_news` fetches
+    /// the next one
+    pub news_page: u32,
+    /// Items each feed returned, summed over the pages loaded
+    pub news_feed_counts: BTreeMap<String, usize>,
     pub rss_loading: bool,
     pub news_sort_column: Option<usize>,
     pub news_sort_direction: SortDirection,
     pub news_tab: NewsTab,
+    /// Restrict the news feed to headlines mentioning this ticker
+    pub news_ticker_filter: Option<String>,
+    /// Show only headlines from feeds in this category
+    pub news_category_filter: Option<String>,
+    /// Symbol selected when the user last left Watchlist/Portfolio view
+    pub last_selected_symbol: Option<String>,
     pub news_detail_scroll: usize,
+    pub news_source_selected: usize,
     pub watchlist_table_state: TableState,
     pub portfolio_table_state: TableState,
     pub news_table_state: TableState,
     pub table_viewport_height: usize,
+    /// Where the active table was last drawn, for mapping mouse clicks
+    pub table_hit_area: Option<TableHitArea>,
     pub bookmark_selected: usize,
     pub bookmark_table_state: TableState,
     pub bookmark_sort_column: Option<usize>,
     pub bookmark_sort_direction: SortDirection,
     pub bookmark_detail_scroll: usize,
     pub ctrl_c_at: Option<Instant>,
+    quote_cache: QuoteCache,
     news_client: NewsClient,
-    client: YahooClient,
+    /// Shared so a quote fetch can run on its own task while the UI redraws
+    client: Arc<Mutex<Box<dyn QuoteProvider>>>,
+    /// Bell and desktop notifications for fired alerts; `None` keeps them in
+    /// the footer
+    notifier: Option<Box<dyn AlertNotifier>>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        let config = Config::load()?;
-        Ok(Self {
+    pub fn new(config_path: Option<PathBuf>) -> Result<Self> {
+        let mut config = Config::load_from(config_path)?;
+        config.apply_default_lists();
+        let quote_cache = if config.quote_cache_ttl_secs > 0 {
+            config
+                .data_file_path(QUOTE_CACHE_FILE)
+                .map(|p| QuoteCache::load(&p))
+                .unwrap_or_default()
+        } else {
+            QuoteCache::default()
+        };
+        let value_snapshots_path = config.data_file_path(PORTFOLIO_HISTORY_FILE).ok();
+        let value_snapshots = value_snapshots_path
+            .as_deref()
+            .map(PortfolioHistory::load)
+            .unwrap_or_default();
+        let http = HttpOptions::new(config.http_timeout_secs, config.http_proxy.clone());
+        let news_client = NewsClient::with_http(&http)?;
+        let client = YahooClient::with_http(&http)?;
+        let (keymap, key_warnings) = KeyMap::from_config(&config.key_bindings);
+        let mut app = Self {
             config,
             quotes: HashMap::new(),
+            prev_quotes: HashMap::new(),
             selected_index: 0,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             status_message: None,
             loading: false,
+            offline: false,
+            lite: false,
+            no_color: false,
+            clipboard: Clipboard::default(),
+            quote_fetched_at: HashMap::new(),
+            last_successful_refresh: None,
+            help_filter: String::new(),
+            event_log: VecDeque::new(),
+            event_log_scroll: 0,
+            keymap,
+            pending_symbols: HashSet::new(),
+            spinner_frame: 0,
             detail_symbol: None,
             detail_chart: None,
+            mini_charts: HashMap::new(),
+            chart_range: ChartRange::default(),
+            history_charts: HashMap::new(),
+            portfolio_history: None,
+            value_snapshots,
+            value_snapshots_path,
             detail_news: None,
+            compare_symbols: Vec::new(),
+            compare_quotes: HashMap::new(),
+            compare_charts: HashMap::new(),
             chart_loading: false,
             news_loading: false,
             view_mode: ViewMode::Watchlist,
@@ -196,9 +556,19 @@ impl App {
             pending_symbol: None,
             pending_lots: None,
             pending_edit_symbol: None,
+            pending_delete: None,
+            last_undo: None,
+            column_picker_selected: 0,
+            add_suggestions: Vec::new(),
+            add_suggestion_selected: None,
+            add_suggest_due: None,
             alert_symbol: None,
             alert_list_selected: 0,
+            rebalance_selected: 0,
             pending_alert_type: AlertType::Above,
+            pending_alert_value: 0.0,
+            alert_cooldown_edit: None,
+            all_watchlists: false,
             watchlist_sort_column: None,
             watchlist_sort_direction: SortDirection::Ascending,
             portfolio_sort_column: None,
@@ -206,38 +576,104 @@ impl App {
             news_items: Vec::new(),
             news_selected: 0,
             news_last_refresh: None,
+            news_page: 1,
+            news_feed_counts: BTreeMap::new(),
             rss_loading: false,
-            news_sort_column: None,
-            news_sort_direction: SortDirection::Ascending,
+            // Newest first until the user picks another order
+            news_sort_column: Some(0),
+            news_sort_direction: SortDirection::Descending,
             news_tab: NewsTab::default(),
+            news_ticker_filter: None,
+            news_category_filter: None,
+            last_selected_symbol: None,
             news_detail_scroll: 0,
+            news_source_selected: 0,
             watchlist_table_state: TableState::default(),
             portfolio_table_state: TableState::default(),
             news_table_state: TableState::default(),
             table_viewport_height: 0,
+            table_hit_area: None,
             bookmark_selected: 0,
             bookmark_table_state: TableState::default(),
             bookmark_sort_column: None,
             bookmark_sort_direction: SortDirection::Descending,
             bookmark_detail_scroll: 0,
             ctrl_c_at: None,
-            news_client: NewsClient::new(),
-            client: YahooClient::new(),
-        })
+            quote_cache,
+            news_client,
+            client: Arc::new(Mutex::new(Box::new(client))),
+            notifier: None,
+        };
+        app.restore_session();
+        if let Some(warning) = app.config.load_warning.take() {
+            app.notify(warning);
+        }
+        if !key_warnings.is_empty() {
+            app.notify(format!("Key bindings: {}", key_warnings.join("; ")));
+        }
+        Ok(app)
+    }
+
+    /// Switch to a lite `YahooClient` that skips fundamentals on refresh.
+    pub fn enable_lite_mode(&mut self) -> Result<()> {
+        let http = HttpOptions::new(
+            self.config.http_timeout_secs,
+            self.config.http_proxy.clone(),
+        );
+        self.client = Arc::new(Mutex::new(Box::new(
+            YahooClient::with_http(&http)?.with_lite(true),
+        )));
+        self.lite = true;
+        Ok(())
+    }
+
+    /// Announce fired alerts through `notifier` as well as the footer.
+    pub fn with_notifier(mut self, notifier: Box<dyn AlertNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Replace the market data source (defaults to `YahooClient`).
+    pub fn with_provider(mut self, provider: Box<dyn QuoteProvider>) -> Self {
+        self.client = Arc::new(Mutex::new(provider));
+        self
     }
 
     pub fn test_new(config: Config) -> Self {
+        let (keymap, _) = KeyMap::from_config(&config.key_bindings);
         Self {
             config,
             quotes: HashMap::new(),
+            prev_quotes: HashMap::new(),
             selected_index: 0,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             status_message: None,
             loading: false,
+            offline: false,
+            lite: false,
+            no_color: false,
+            clipboard: Clipboard::default(),
+            quote_fetched_at: HashMap::new(),
+            last_successful_refresh: None,
+            help_filter: String::new(),
+            event_log: VecDeque::new(),
+            event_log_scroll: 0,
+            keymap,
+            pending_symbols: HashSet::new(),
+            spinner_frame: 0,
             detail_symbol: None,
             detail_chart: None,
+            mini_charts: HashMap::new(),
+            chart_range: ChartRange::default(),
+            history_charts: HashMap::new(),
+            portfolio_history: None,
+            value_snapshots: PortfolioHistory::default(),
+            value_snapshots_path: None,
             detail_news: None,
+            compare_symbols: Vec::new(),
+            compare_quotes: HashMap::new(),
+            compare_charts: HashMap::new(),
             chart_loading: false,
             news_loading: false,
             view_mode: ViewMode::Watchlist,
@@ -250,9 +686,19 @@ impl App {
             pending_symbol: None,
             pending_lots: None,
             pending_edit_symbol: None,
+            pending_delete: None,
+            last_undo: None,
+            column_picker_selected: 0,
+            add_suggestions: Vec::new(),
+            add_suggestion_selected: None,
+            add_suggest_due: None,
             alert_symbol: None,
             alert_list_selected: 0,
+            rebalance_selected: 0,
             pending_alert_type: AlertType::Above,
+            pending_alert_value: 0.0,
+            alert_cooldown_edit: None,
+            all_watchlists: false,
             watchlist_sort_column: None,
             watchlist_sort_direction: SortDirection::Ascending,
             portfolio_sort_column: None,
@@ -260,23 +706,33 @@ impl App {
             news_items: Vec::new(),
             news_selected: 0,
             news_last_refresh: None,
+            news_page: 1,
+            news_feed_counts: BTreeMap::new(),
             rss_loading: false,
-            news_sort_column: None,
-            news_sort_direction: SortDirection::Ascending,
+            // Newest first until the user picks another order
+            news_sort_column: Some(0),
+            news_sort_direction: SortDirection::Descending,
             news_tab: NewsTab::default(),
+            news_ticker_filter: None,
+            news_category_filter: None,
+            last_selected_symbol: None,
             news_detail_scroll: 0,
+            news_source_selected: 0,
             watchlist_table_state: TableState::default(),
             portfolio_table_state: TableState::default(),
             news_table_state: TableState::default(),
             table_viewport_height: 20,
+            table_hit_area: None,
             bookmark_selected: 0,
             bookmark_table_state: TableState::default(),
             bookmark_sort_column: None,
             bookmark_sort_direction: SortDirection::Descending,
             bookmark_detail_scroll: 0,
             ctrl_c_at: None,
+            quote_cache: QuoteCache::default(),
             news_client: NewsClient::new(),
-            client: YahooClient::new(),
+            client: Arc::new(Mutex::new(Box::new(YahooClient::new()))),
+            notifier: None,
         }
     }
 
@@ -284,10 +740,28 @@ impl App {
     /// Always includes `^JKSE` so the IHSG index is available.
     pub fn refresh_symbols(&self) -> Option<Vec<String>> {
         let mut symbols: Vec<String> = match self.view_mode {
-            ViewMode::Watchlist => self.config.current_watchlist().symbols.clone(),
+            // Top movers rank every watchlist, so refresh all of them
+            ViewMode::Watchlist if self.input_mode == InputMode::TopMovers => self
+                .get_all_symbols_combined()
+                .into_iter()
+                .cloned()
+                .collect(),
+            ViewMode::Watchlist => self
+                .get_raw_watchlist()
+                .into_iter()
+                .map(|(s, _)| s.clone())
+                .collect(),
             ViewMode::Portfolio => self.config.portfolio_symbols(),
             ViewMode::News => return None,
         };
+        if self.view_mode == ViewMode::Watchlist {
+            for symbol in &self.config.pinned {
+                let symbol = symbol.to_uppercase();
+                if !symbols.contains(&symbol) {
+                    symbols.push(symbol);
+                }
+            }
+        }
         if symbols.is_empty() {
             return Some(vec!["^JKSE".to_string()]);
         }
@@ -301,28 +775,292 @@ impl App {
     /// or empty watchlists (no network call needed).
     pub fn prepare_refresh(&mut self) -> Option<Vec<String>> {
         let symbols = self.refresh_symbols();
-        if symbols.is_some() {
+        if let Some(symbols) = &symbols {
+            let anchor = self.loading_rows_anchor();
             self.loading = true;
+            self.pending_symbols = symbols.iter().map(|s| display_symbol(s)).collect();
+            self.restore_loading_rows_anchor(anchor);
         }
         symbols
     }
 
-    /// Execute the network fetch for the given symbols and clear `loading`.
+    pub fn tick_spinner(&mut self) {
+        self.spinner_frame = self.spinner_frame.wrapping_add(1);
+    }
+
+    /// Spinner glyph to draw next to `symbol`'s price while it is in flight.
+    pub fn pending_spinner(&self, symbol: &str) -> Option<char> {
+        self.pending_symbols
+            .contains(symbol)
+            .then(|| spinner_glyph(self.spinner_frame))
+    }
+
+    /// Price move for `symbol` between the previous refresh and the latest
+    /// one. `None` when it didn't move or either snapshot lacks the symbol.
+    pub fn tick_delta(&self, symbol: &str) -> Option<f64> {
+        let current = self.quotes.get(symbol)?.price;
+        let previous = self.prev_quotes.get(symbol)?.price;
+        let delta = current - previous;
+        (delta != 0.0).then_some(delta)
+    }
+
+    /// Glyph drawn after `symbol`'s price: the spinner while it is in flight,
+    /// otherwise ▲/▼ if the last refresh moved it.
+    pub fn price_marker(&self, symbol: &str) -> Option<char> {
+        self.pending_spinner(symbol).or_else(|| {
+            self.tick_delta(symbol)
+                .map(|delta| if delta > 0.0 { '▲' } else { '▼' })
+        })
+    }
+
+    /// Update quotes for the symbols present in `quotes`, keeping the rest.
+    /// A partial Yahoo response therefore never blanks rows it omitted; quotes
+    /// are only dropped when a symbol is removed or the view/list changes.
+    pub fn merge_quotes(&mut self, quotes: HashMap<String, StockQuote>) {
+        for (symbol, quote) in quotes {
+            self.pending_symbols.remove(&symbol);
+            self.merge_quote(symbol, quote);
+        }
+    }
+
+    /// With `validate_symbols`, the `symbols` the quote source has no quote
+    /// for. The quotes it does return are kept, so new rows show at once.
+    /// Empty when validation is off or the lookup fails (e.g. offline), so
+    /// the add goes ahead unchecked.
+    pub(super) async fn unknown_symbols(&mut self, symbols: &[String]) -> Vec<String> {
+        if !self.config.validate_symbols || symbols.is_empty() {
+            return Vec::new();
+        }
+        let Ok(quotes) = self.client.lock().await.get_quotes(symbols).await else {
+            return Vec::new();
+        };
+        let unknown = symbols
+            .iter()
+            .filter(|s| !quotes.contains_key(&display_symbol(s)))
+            .cloned()
+            .collect();
+        self.merge_quotes(quotes);
+        unknown
+    }
+
+    /// Store one quote. Lite quotes keep the fundamentals of the quote they
+    /// replace, so a detail view opened earlier stays filled in.
+    fn merge_quote(&mut self, symbol: String, mut quote: StockQuote) {
+        if self.lite
+            && let Some(old) = self.quotes.get(&symbol)
+        {
+            quote.keep_fundamentals_from(old);
+        }
+        self.quotes.insert(symbol, quote);
+    }
+
+    /// Execute the network fetch for the given symbols, clear `loading`, and
+    /// check alerts against the new quotes. Symbols with a fresh entry in the
+    /// quote cache are served from disk. An open detail modal whose symbol got
+    /// a new quote has its chart and headlines refreshed too.
     pub async fn execute_refresh(&mut self, symbols: &[String]) -> Result<()> {
-        match self.client.get_quotes(symbols).await {
+        let pending = self.start_refresh(symbols);
+        let fetched = match pending.to_fetch() {
+            Some(to_fetch) => Some(self.client.lock().await.get_quotes(to_fetch).await),
+            None => None,
+        };
+        self.finish_refresh(pending, fetched).await
+    }
+
+    /// First half of `execute_refresh`: serve what the quote cache can and
+    /// work out which symbols still need the network.
+    pub fn start_refresh(&mut self, symbols: &[String]) -> PendingRefresh {
+        let ttl = self.config.quote_cache_ttl_secs;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        // Arrows compare against this snapshot, so they clear on the next
+        // refresh that leaves the price unchanged
+        self.prev_quotes = self.quotes.clone();
+        let anchor = self.loading_rows_anchor();
+
+        let mut cached: HashMap<String, StockQuote> = HashMap::new();
+        let mut to_fetch: Vec<String> = Vec::new();
+        for symbol in symbols {
+            match self.quote_cache.get_fresh(symbol, ttl, now) {
+                Some(q) if ttl > 0 => {
+                    let fetched_at = self.quote_cache.fetched_at(symbol).unwrap_or(now);
+                    self.quote_fetched_at.insert(q.symbol.clone(), fetched_at);
+                    cached.insert(q.symbol.clone(), q.clone());
+                }
+                _ => to_fetch.push(symbol.clone()),
+            }
+        }
+        PendingRefresh {
+            cached,
+            to_fetch,
+            anchor,
+            now,
+        }
+    }
+
+    /// Fetch `pending`'s uncached quotes on their own task, so the caller can
+    /// keep drawing (and the spinner turning) until it finishes. `None` when
+    /// everything came from the cache.
+    pub fn spawn_quote_fetch(&self, pending: &PendingRefresh) -> Option<QuoteFetch> {
+        let to_fetch = pending.to_fetch()?.to_vec();
+        let client = Arc::clone(&self.client);
+        Some(tokio::spawn(async move {
+            client.lock().await.get_quotes(&to_fetch).await
+        }))
+    }
+
+    /// Second half of `execute_refresh`: merge the cached quotes and the
+    /// `fetched` result (`None` when nothing was fetched), then clear
+    /// `loading` and check alerts.
+    pub async fn finish_refresh(
+        &mut self,
+        pending: PendingRefresh,
+        fetched: Option<Result<HashMap<String, StockQuote>>>,
+    ) -> Result<()> {
+        let PendingRefresh {
+            mut cached,
+            anchor,
+            now,
+            ..
+        } = pending;
+        let ttl = self.config.quote_cache_ttl_secs;
+
+        let Some(fetched) = fetched else {
+            let detail_updated = self.detail_updated_by(&cached);
+            self.merge_quotes(cached);
+            self.last_successful_refresh = Some(Instant::now());
+            self.status_message = Some("(cached)".to_string());
+            self.loading = false;
+            self.pending_symbols.clear();
+            self.restore_loading_rows_anchor(anchor);
+            self.check_alerts();
+            if detail_updated {
+                self.refresh_open_detail().await;
+            }
+            return Ok(());
+        };
+
+        let mut detail_updated = false;
+
+        match fetched {
             Ok(quotes) => {
-                self.quotes = quotes;
+                for symbol in quotes.keys() {
+                    self.quote_fetched_at.insert(symbol.clone(), now);
+                }
+                if ttl > 0 {
+                    self.quote_cache.insert_all(&quotes, now);
+                    if let Ok(path) = self.config.data_file_path(QUOTE_CACHE_FILE) {
+                        let _ = self.quote_cache.save(&path);
+                    }
+                }
+                cached.extend(quotes);
+                detail_updated = self.detail_updated_by(&cached);
+                self.merge_quotes(cached);
+                self.last_successful_refresh = Some(Instant::now());
+                self.offline = false;
                 self.status_message = None;
+                self.record_portfolio_snapshot();
+            }
+            Err(e) if is_offline_error(&e) => {
+                detail_updated = self.detail_updated_by(&cached);
+                self.merge_quotes(cached);
+                // Log the drop once rather than on every failed refresh
+                if !self.offline {
+                    self.notify("Offline — showing last data");
+                }
+                self.offline = true;
             }
             Err(e) => {
-                self.status_message = Some(format!("Error: {}", e));
+                self.notify(format!("Error: {}", e));
             }
         }
         self.loading = false;
+        self.pending_symbols.clear();
+        self.restore_loading_rows_anchor(anchor);
+        self.check_alerts();
+        if detail_updated {
+            self.refresh_open_detail().await;
+        }
         Ok(())
     }
 
+    /// Whether the quote for `symbol` was fetched more than
+    /// `stale_quote_secs` before `now` (unix seconds). Symbols without a
+    /// recorded fetch time are never stale.
+    pub fn quote_is_stale_at(&self, symbol: &str, now: u64) -> bool {
+        let threshold = self.config.stale_quote_secs;
+        threshold > 0
+            && self
+                .quote_fetched_at
+                .get(symbol)
+                .is_some_and(|&fetched| now.saturating_sub(fetched) > threshold)
+    }
+
+    pub fn quote_is_stale(&self, symbol: &str) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.quote_is_stale_at(symbol, now)
+    }
+
+    /// With `hide_loading_rows`, watchlist rows come and go as quotes load.
+    /// Returns the selected row's symbol to restore afterwards, or `None`
+    /// when rows can't move.
+    fn loading_rows_anchor(&self) -> Option<Option<String>> {
+        (self.config.hide_loading_rows && self.view_mode == ViewMode::Watchlist)
+            .then(|| self.selected_row_key())
+    }
+
+    /// Put the cursor back on the row saved by `loading_rows_anchor` (the
+    /// first row if it was hidden), keeping the selection in range.
+    fn restore_loading_rows_anchor(&mut self, anchor: Option<Option<String>>) {
+        if let Some(key) = anchor {
+            self.reselect_row(key);
+        }
+    }
+
+    /// Whether `quotes` holds a new quote for the symbol of an open detail modal.
+    fn detail_updated_by(&self, quotes: &HashMap<String, StockQuote>) -> bool {
+        self.input_mode == InputMode::StockDetail
+            && self
+                .detail_symbol
+                .as_ref()
+                .is_some_and(|s| quotes.contains_key(s))
+    }
+
+    /// Re-fetch the open detail modal's chart and pick up any new RSS
+    /// headlines for it. Unlike `open_detail` this skips the feed download
+    /// and the per-symbol news request, keeping the headlines already shown.
+    async fn refresh_open_detail(&mut self) {
+        let Some(symbol) = self.detail_symbol.clone() else {
+            return;
+        };
+        let mut news = self.get_detail_news(&symbol);
+        for item in self.detail_news.take().unwrap_or_default() {
+            if !news.iter().any(|n| n.title == item.title) {
+                news.push(item);
+            }
+        }
+        self.detail_news = Some(news);
+        if let Ok(chart) = self
+            .client
+            .lock()
+            .await
+            .get_chart(&symbol, self.chart_range)
+            .await
+        {
+            self.detail_chart = Some(chart);
+        }
+    }
+
     pub fn move_up(&mut self) {
+        if self.config.wrap_navigation {
+            self.jump_selection(|sel, len| if sel == 0 { len - 1 } else { sel - 1 });
+            return;
+        }
         let vh = self.table_viewport_height;
         match self.view_mode {
             ViewMode::Watchlist => {
@@ -386,6 +1124,10 @@ impl App {
     }
 
     pub fn move_down(&mut self) {
+        if self.config.wrap_navigation {
+            self.jump_selection(|sel, len| (sel + 1) % len);
+            return;
+        }
         let vh = self.table_viewport_height;
         match self.view_mode {
             ViewMode::Watchlist => {
@@ -452,8 +1194,76 @@ impl App {
         }
     }
 
-    pub fn cycle_sort_column(&mut self) {
-        let num_columns = match self.view_mode {
+    pub fn page_up(&mut self) {
+        let page = self.table_viewport_height.max(1);
+        self.jump_selection(|sel, _| sel.saturating_sub(page));
+    }
+
+    pub fn page_down(&mut self) {
+        let page = self.table_viewport_height.max(1);
+        self.jump_selection(|sel, len| (sel + page).min(len - 1));
+    }
+
+    pub fn go_top(&mut self) {
+        self.jump_selection(|_, _| 0);
+    }
+
+    pub fn go_bottom(&mut self) {
+        self.jump_selection(|_, len| len - 1);
+    }
+
+    /// Move the selection of the active table to `target(selected, len)` and
+    /// scroll its `TableState` so the new row stays visible. `target` is only
+    /// called for non-empty lists.
+    fn jump_selection(&mut self, target: impl Fn(usize, usize) -> usize) {
+        let vh = self.table_viewport_height;
+        let len = self.active_table_len();
+        if len == 0 {
+            return;
+        }
+        let (selected, state) = match self.view_mode {
+            ViewMode::Watchlist => (&mut self.selected_index, &mut self.watchlist_table_state),
+            ViewMode::Portfolio => (
+                &mut self.portfolio_selected,
+                &mut self.portfolio_table_state,
+            ),
+            ViewMode::News => {
+                if self.news_tab == NewsTab::Bookmarks {
+                    (&mut self.bookmark_selected, &mut self.bookmark_table_state)
+                } else {
+                    (&mut self.news_selected, &mut self.news_table_state)
+                }
+            }
+        };
+        let sel = target((*selected).min(len - 1), len).min(len - 1);
+        *selected = sel;
+        state.select(Some(sel));
+        let off = state.offset();
+        if sel < off {
+            *state.offset_mut() = sel;
+        } else if vh > 0 && sel >= off + vh {
+            *state.offset_mut() = sel + 1 - vh;
+        }
+    }
+
+    /// Rows in the table of the current view, after filtering.
+    fn active_table_len(&self) -> usize {
+        match self.view_mode {
+            ViewMode::Watchlist => self.get_filtered_watchlist().len(),
+            ViewMode::Portfolio => self.get_filtered_portfolio().len(),
+            ViewMode::News => {
+                if self.news_tab == NewsTab::Bookmarks {
+                    self.get_filtered_bookmarks().len()
+                } else {
+                    self.get_filtered_news().len()
+                }
+            }
+        }
+    }
+
+    /// Number of leading columns the current view's table can sort on.
+    fn sortable_columns(&self) -> usize {
+        match self.view_mode {
             ViewMode::Watchlist => WATCHLIST_SORTABLE_COLUMNS,
             ViewMode::Portfolio => PORTFOLIO_SORTABLE_COLUMNS,
             ViewMode::News => {
@@ -463,74 +1273,145 @@ impl App {
                     NEWS_SORTABLE_COLUMNS
                 }
             }
-        };
-        let (col, selected) = match self.view_mode {
-            ViewMode::Watchlist => (&mut self.watchlist_sort_column, &mut self.selected_index),
-            ViewMode::Portfolio => (
-                &mut self.portfolio_sort_column,
-                &mut self.portfolio_selected,
-            ),
+        }
+    }
+
+    fn sort_column_mut(&mut self) -> &mut Option<usize> {
+        match self.view_mode {
+            ViewMode::Watchlist => &mut self.watchlist_sort_column,
+            ViewMode::Portfolio => &mut self.portfolio_sort_column,
             ViewMode::News => {
                 if self.news_tab == NewsTab::Bookmarks {
-                    (&mut self.bookmark_sort_column, &mut self.bookmark_selected)
+                    &mut self.bookmark_sort_column
                 } else {
-                    (&mut self.news_sort_column, &mut self.news_selected)
+                    &mut self.news_sort_column
                 }
             }
-        };
+        }
+    }
+
+    pub fn cycle_sort_column(&mut self) {
+        let num_columns = self.sortable_columns();
+        let key = self.selected_row_key();
+        let col = self.sort_column_mut();
         *col = match *col {
             None => Some(0),
             Some(i) if i + 1 >= num_columns => None,
             Some(i) => Some(i + 1),
         };
-        *selected = 0;
-        self.reset_current_table_offset();
+        self.reselect_row(key);
+        if self.view_mode == ViewMode::Watchlist && !self.all_watchlists {
+            self.persist_watchlist_sort();
+        }
+    }
+
+    /// Sort the current table on `column`, or flip the direction when it is
+    /// already the sort column. Columns that can't be sorted are ignored.
+    pub fn sort_by_column(&mut self, column: usize) {
+        if column >= self.sortable_columns() {
+            return;
+        }
+        if *self.sort_column_mut() == Some(column) {
+            self.toggle_sort_direction();
+            return;
+        }
+        let key = self.selected_row_key();
+        *self.sort_column_mut() = Some(column);
+        self.reselect_row(key);
+        if self.view_mode == ViewMode::Watchlist && !self.all_watchlists {
+            self.persist_watchlist_sort();
+        }
     }
 
     pub fn toggle_sort_direction(&mut self) {
-        let (dir, selected) = match self.view_mode {
-            ViewMode::Watchlist => (&mut self.watchlist_sort_direction, &mut self.selected_index),
-            ViewMode::Portfolio => (
-                &mut self.portfolio_sort_direction,
-                &mut self.portfolio_selected,
-            ),
+        let key = self.selected_row_key();
+        let dir = match self.view_mode {
+            ViewMode::Watchlist => &mut self.watchlist_sort_direction,
+            ViewMode::Portfolio => &mut self.portfolio_sort_direction,
             ViewMode::News => {
                 if self.news_tab == NewsTab::Bookmarks {
-                    (
-                        &mut self.bookmark_sort_direction,
-                        &mut self.bookmark_selected,
-                    )
+                    &mut self.bookmark_sort_direction
                 } else {
-                    (&mut self.news_sort_direction, &mut self.news_selected)
+                    &mut self.news_sort_direction
                 }
             }
         };
         dir.toggle();
-        *selected = 0;
-        self.reset_current_table_offset();
+        self.reselect_row(key);
+        if self.view_mode == ViewMode::Watchlist && !self.all_watchlists {
+            self.persist_watchlist_sort();
+        }
     }
 
-    fn reset_current_table_offset(&mut self) {
-        let state = match self.view_mode {
-            ViewMode::Watchlist => &mut self.watchlist_table_state,
-            ViewMode::Portfolio => &mut self.portfolio_table_state,
+    fn selected_row_key(&self) -> Option<String> {
+        let selected = match self.view_mode {
+            ViewMode::Watchlist => self.selected_index,
+            ViewMode::Portfolio => self.portfolio_selected,
             ViewMode::News => {
                 if self.news_tab == NewsTab::Bookmarks {
-                    &mut self.bookmark_table_state
+                    self.bookmark_selected
                 } else {
-                    &mut self.news_table_state
+                    self.news_selected
                 }
             }
         };
-        *state.offset_mut() = 0;
+        self.current_row_keys().into_iter().nth(selected)
+    }
+
+    /// After a reorder, move the cursor to the row identified by `key`
+    /// (or the first row if it is gone), scrolling only as far as needed.
+    fn reselect_row(&mut self, key: Option<String>) {
+        let index = key
+            .and_then(|k| self.current_row_keys().iter().position(|r| *r == k))
+            .unwrap_or(0);
+        self.jump_selection(|_, _| index);
     }
 
     pub fn cancel_input(&mut self) {
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
+        self.clear_add_suggestions();
+    }
+
+    /// Restore the view and row selections saved by `save_session`, clamped
+    /// to the current list lengths.
+    pub fn restore_session(&mut self) {
+        self.view_mode = ViewMode::from_config_name(&self.config.last_view_mode);
+        self.apply_watchlist_sort();
+        let wl_len = self.config.current_watchlist().symbols.len();
+        self.selected_index = self
+            .config
+            .last_watchlist_selected
+            .min(wl_len.saturating_sub(1));
+        let pf_len = self.config.current_portfolio().holdings.len();
+        self.portfolio_selected = self
+            .config
+            .last_portfolio_selected
+            .min(pf_len.saturating_sub(1));
+        self.watchlist_table_state.select(Some(self.selected_index));
+        self.portfolio_table_state
+            .select(Some(self.portfolio_selected));
+    }
+
+    /// Record the current view and selections in the config and save it.
+    /// Called when the user quits.
+    pub fn save_session(&mut self) -> Result<()> {
+        self.remember_session();
+        self.config.save()
+    }
+
+    pub fn remember_session(&mut self) {
+        self.config.last_view_mode = self.view_mode.config_name().to_string();
+        self.config.last_watchlist_selected = self.selected_index;
+        self.config.last_portfolio_selected = self.portfolio_selected;
     }
 
     pub fn toggle_view(&mut self) {
+        match self.view_mode {
+            ViewMode::Watchlist => self.last_selected_symbol = self.selected_watchlist_symbol(),
+            ViewMode::Portfolio => self.last_selected_symbol = self.selected_portfolio_symbol(),
+            ViewMode::News => {}
+        }
         self.view_mode = match self.view_mode {
             ViewMode::Watchlist => ViewMode::Portfolio,
             ViewMode::Portfolio => ViewMode::News,
@@ -545,6 +1426,61 @@ impl App {
         self.clear_filter();
     }
 
+    /// `W`: copy the current watchlist into a new portfolio of zero-lot
+    /// holdings, or the current portfolio's symbols into a new watchlist,
+    /// named after the source, and switch to it.
+    pub fn convert_list(&mut self) -> Result<()> {
+        match self.view_mode {
+            ViewMode::Watchlist => {
+                if self.blocked_by_all_watchlists() {
+                    return Ok(());
+                }
+                let name = self.config.current_watchlist().name.clone();
+                let count = self.config.portfolio_from_watchlist(&name);
+                if count == 0 {
+                    self.status_message = Some("Watchlist is empty".to_string());
+                    return Ok(());
+                }
+                self.last_undo = None;
+                self.config.save()?;
+                self.last_selected_symbol = self.selected_watchlist_symbol();
+                self.view_mode = ViewMode::Portfolio;
+                self.quotes.clear();
+                self.clear_filter();
+                self.portfolio_selected = 0;
+                *self.portfolio_table_state.offset_mut() = 0;
+                self.notify(format!(
+                    "Created portfolio '{}' with {} holdings at 0 lots (e to fill in)",
+                    name, count
+                ));
+            }
+            ViewMode::Portfolio => {
+                let name = self.config.current_portfolio().name.clone();
+                let count = self.config.watchlist_from_portfolio(&name);
+                if count == 0 {
+                    self.status_message = Some("Portfolio is empty".to_string());
+                    return Ok(());
+                }
+                self.last_undo = None;
+                self.config.save()?;
+                self.last_selected_symbol = self.selected_portfolio_symbol();
+                self.view_mode = ViewMode::Watchlist;
+                self.all_watchlists = false;
+                self.quotes.clear();
+                self.clear_filter();
+                self.apply_watchlist_sort();
+                self.selected_index = 0;
+                *self.watchlist_table_state.offset_mut() = 0;
+                self.notify(format!(
+                    "Created watchlist '{}' with {} symbols",
+                    name, count
+                ));
+            }
+            ViewMode::News => {}
+        }
+        Ok(())
+    }
+
     pub fn toggle_news_tab(&mut self) {
         self.news_tab = match self.news_tab {
             NewsTab::Feed => NewsTab::Bookmarks,
@@ -560,8 +1496,45 @@ impl App {
         self.input_mode = InputMode::Normal;
     }
 
+    /// Symbol `o` opens: the detail popup's, else the selected row's.
+    pub fn stock_page_symbol(&self) -> Option<String> {
+        if self.input_mode == InputMode::StockDetail {
+            return self.detail_symbol.clone();
+        }
+        match self.view_mode {
+            ViewMode::Watchlist => self.selected_watchlist_symbol(),
+            ViewMode::Portfolio => self.selected_portfolio_symbol(),
+            ViewMode::News => None,
+        }
+    }
+
+    /// Open the Yahoo Finance page of `stock_page_symbol` in the browser.
+    pub fn open_stock_page(&mut self) {
+        let Some(symbol) = self.stock_page_symbol() else {
+            return;
+        };
+        let url = crate::api::yahoo::quote_page_url(&symbol);
+        match crate::browser::open_url(&url) {
+            Ok(()) => self.status_message = Some(format!("Opened {}", url)),
+            Err(e) => self.notify(format!("Couldn't open browser: {:#}", e)),
+        }
+    }
+
     pub fn get_detail_quote(&self) -> Option<&StockQuote> {
-        self.detail_symbol.as_ref().and_then(|s| self.quotes.get(s))
+        let symbol = self.detail_symbol.as_ref()?;
+        self.quotes.get(&display_symbol(&symbol.to_uppercase()))
+    }
+
+    /// Note on the detail symbol's holding in the current portfolio, if any.
+    pub fn get_detail_note(&self) -> Option<&str> {
+        let symbol = self.detail_symbol.as_ref()?;
+        self.config
+            .current_portfolio()
+            .holdings
+            .iter()
+            .find(|h| &h.symbol == symbol)?
+            .note
+            .as_deref()
     }
 
     pub fn get_ihsg_quote(&self) -> Option<&StockQuote> {
@@ -583,15 +1556,49 @@ impl App {
         }
 
         // Filter RSS headlines matching this stock's ticker or company name
-        self.detail_news = Some(self.get_detail_news(symbol));
+        let mut news = self.get_detail_news(symbol);
+        if let Ok(extra) = self.client.lock().await.get_news(symbol).await {
+            news.extend(extra);
+        }
+        self.detail_news = Some(news);
         self.news_loading = false;
 
-        if let Ok(chart) = self.client.get_chart(symbol).await {
+        if self.lite {
+            let full = self.client.lock().await.get_full_quote(symbol).await;
+            if let Ok(Some(quote)) = full {
+                self.merge_quote(symbol.to_string(), quote);
+            }
+        }
+        self.load_detail_chart(symbol).await;
+    }
+
+    async fn load_detail_chart(&mut self, symbol: &str) {
+        self.chart_loading = true;
+        self.detail_chart = None;
+        if let Ok(chart) = self
+            .client
+            .lock()
+            .await
+            .get_chart(symbol, self.chart_range)
+            .await
+        {
             self.detail_chart = Some(chart);
         }
         self.chart_loading = false;
     }
 
+    /// Switch the detail sparkline to the next (or previous) range and re-fetch it.
+    pub async fn cycle_chart_range(&mut self, forward: bool) {
+        self.chart_range = if forward {
+            self.chart_range.next()
+        } else {
+            self.chart_range.prev()
+        };
+        if let Some(symbol) = self.detail_symbol.clone() {
+            self.load_detail_chart(&symbol).await;
+        }
+    }
+
     /// Filter RSS news items relevant to a specific stock by ticker match
     fn get_detail_news(&self, symbol: &str) -> Vec<NewsItem> {
         let sym_upper = symbol.to_uppercase();
@@ -603,11 +1610,86 @@ impl App {
             .collect()
     }
 
+    /// Whether the configured IDX session is trading right now.
+    pub fn market_open_now(&self) -> bool {
+        let (open, close) = self.config.trading_hours();
+        market_is_open(Utc::now(), open, close)
+    }
+
+    /// Breadth of the quoted rows currently shown in the watchlist.
+    pub fn watchlist_breadth(&self) -> Breadth {
+        market_breadth(
+            self.get_filtered_watchlist()
+                .into_iter()
+                .filter_map(|(_, quote)| quote),
+        )
+    }
+
+    pub fn refresh_health(&self) -> RefreshHealth {
+        refresh_health(
+            self.last_successful_refresh
+                .map(|at| at.elapsed().as_secs()),
+            self.config.refresh_interval_secs,
+            self.market_open_now(),
+        )
+    }
+
+    /// Switch between normal and compact table chrome and persist it.
+    pub fn toggle_density(&mut self) -> Result<()> {
+        self.config.density = self.config.density.toggled();
+        self.config.save()?;
+        self.status_message = Some(format!("Density: {:?}", self.config.density));
+        Ok(())
+    }
+
+    /// Step the quote refresh interval up or down within 1–300s and persist it.
+    /// Steps grow with the interval: 1s below 10s, 5s below 60s, then 30s.
+    pub fn adjust_refresh_interval(&mut self, increase: bool) -> Result<()> {
+        let current = self.config.refresh_interval_secs;
+        let step = |secs: u64| match secs {
+            0..=9 => 1,
+            10..=59 => 5,
+            _ => 30,
+        };
+        let next = if increase {
+            current + step(current)
+        } else {
+            current.saturating_sub(step(current.saturating_sub(1)))
+        };
+        self.config.refresh_interval_secs =
+            next.clamp(MIN_REFRESH_INTERVAL_SECS, MAX_REFRESH_INTERVAL_SECS);
+        self.config.save()?;
+        self.status_message = Some(format!(
+            "Refresh interval: {}s",
+            self.config.refresh_interval_secs
+        ));
+        Ok(())
+    }
+
     pub fn show_help(&mut self) {
+        self.help_filter.clear();
         self.input_mode = InputMode::Help;
     }
 
     pub fn close_help(&mut self) {
+        self.help_filter.clear();
         self.input_mode = InputMode::Normal;
     }
+
+    pub fn help_filter_push(&mut self, c: char) {
+        self.help_filter.push(c);
+    }
+
+    pub fn help_filter_pop(&mut self) {
+        self.help_filter.pop();
+    }
+
+    /// Esc in help clears a typed filter first, and closes on a second press.
+    pub fn help_escape(&mut self) {
+        if self.help_filter.is_empty() {
+            self.close_help();
+        } else {
+            self.help_filter.clear();
+        }
+    }
 }
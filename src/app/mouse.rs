@@ -0,0 +1,103 @@
+use super::{App, InputMode, NewsTab, ViewMode};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Position, Rect};
+
+/// Screen geometry of the last drawn main table: its header row, the body
+/// rows below it and the x-span of each visible column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableHitArea {
+    pub header: Rect,
+    pub body: Rect,
+    /// `(x, width, column index)` for each visible column
+    pub columns: Vec<(u16, u16, usize)>,
+}
+
+impl TableHitArea {
+    /// Geometry for a table drawn with `constraints` for the `vis` columns
+    /// inside `inner` (the block's inner area). Mirrors how `Table` lays out
+    /// its columns: left-aligned with one cell of spacing.
+    pub fn new(inner: Rect, vis: &[usize], constraints: &[Constraint]) -> Self {
+        let header = Rect { height: 1, ..inner }.intersection(inner);
+        let body = Rect {
+            y: inner.y.saturating_add(1),
+            height: inner.height.saturating_sub(1),
+            ..inner
+        };
+        let columns = Layout::horizontal(constraints.iter().copied())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(header)
+            .iter()
+            .zip(vis)
+            .map(|(rect, &col)| (rect.x, rect.width, col))
+            .collect();
+        Self {
+            header,
+            body,
+            columns,
+        }
+    }
+
+    /// Column index under a click on the header row.
+    pub fn header_column(&self, x: u16, y: u16) -> Option<usize> {
+        if !self.header.contains(Position::new(x, y)) {
+            return None;
+        }
+        self.columns
+            .iter()
+            .find(|(start, width, _)| (*start..start + width).contains(&x))
+            .map(|&(_, _, col)| col)
+    }
+
+    /// Visible row (0 = first row on screen) under a click in the body.
+    pub fn body_row(&self, x: u16, y: u16) -> Option<usize> {
+        self.body
+            .contains(Position::new(x, y))
+            .then(|| (y - self.body.y) as usize)
+    }
+}
+
+impl App {
+    /// Mouse input in Normal mode: the wheel moves the selection (stopping
+    /// at the ends even with `wrap_navigation`), a left click selects a row
+    /// or sorts on a header column. Modals ignore the mouse.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.input_mode != InputMode::Normal {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.jump_selection(|sel, _| sel.saturating_sub(1)),
+            MouseEventKind::ScrollDown => self.jump_selection(|sel, len| (sel + 1).min(len - 1)),
+            MouseEventKind::Down(MouseButton::Left) => self.click_at(mouse.column, mouse.row),
+            _ => {}
+        }
+    }
+
+    fn click_at(&mut self, x: u16, y: u16) {
+        let Some(hit) = &self.table_hit_area else {
+            return;
+        };
+        if let Some(column) = hit.header_column(x, y) {
+            self.sort_by_column(column);
+        } else if let Some(row) = hit.body_row(x, y) {
+            let row = row + self.active_table_offset();
+            if row < self.active_table_len() {
+                self.jump_selection(|_, _| row);
+            }
+        }
+    }
+
+    fn active_table_offset(&self) -> usize {
+        match self.view_mode {
+            ViewMode::Watchlist => self.watchlist_table_state.offset(),
+            ViewMode::Portfolio => self.portfolio_table_state.offset(),
+            ViewMode::News => {
+                if self.news_tab == NewsTab::Bookmarks {
+                    self.bookmark_table_state.offset()
+                } else {
+                    self.news_table_state.offset()
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::Utc;
 
 use super::{App, InputMode, title_contains_ticker};
+use crate::api::news::{cap_news, merge_feeds, validate_feed_url};
+use anyhow::Result;
 
 impl App {
     pub fn has_recent_news(&self, symbol: &str) -> bool {
@@ -11,6 +13,55 @@ impl App {
             .any(|item| item.published_at >= cutoff && title_contains_ticker(&item.title, &sym))
     }
 
+    /// Toggle filtering the news feed to the last symbol selected in the
+    /// Watchlist or Portfolio view.
+    pub fn toggle_news_ticker_filter(&mut self) {
+        if self.news_ticker_filter.take().is_none() {
+            match &self.last_selected_symbol {
+                Some(symbol) => self.news_ticker_filter = Some(symbol.clone()),
+                None => {
+                    self.status_message =
+                        Some("Select a stock in Watchlist or Portfolio first".to_string());
+                }
+            }
+        }
+        self.news_selected = 0;
+        *self.news_table_state.offset_mut() = 0;
+    }
+
+    /// Distinct categories of the configured feeds, sorted.
+    pub fn news_categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .config
+            .news_sources
+            .iter()
+            .filter_map(|url| self.config.news_categories.get(url).cloned())
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    /// Step the category filter through each feed category, then back to
+    /// all headlines.
+    pub fn cycle_news_category_filter(&mut self) {
+        let categories = self.news_categories();
+        if categories.is_empty() {
+            self.status_message = Some("No feed categories set (c in the source list)".to_string());
+            return;
+        }
+        self.news_category_filter = match &self.news_category_filter {
+            None => Some(categories[0].clone()),
+            Some(current) => categories
+                .iter()
+                .position(|c| c == current)
+                .and_then(|i| categories.get(i + 1))
+                .cloned(),
+        };
+        self.news_selected = 0;
+        *self.news_table_state.offset_mut() = 0;
+    }
+
     /// Set `rss_loading = true` and return the feed URLs.
     pub fn prepare_news_refresh(&mut self) -> Vec<String> {
         self.rss_loading = true;
@@ -19,19 +70,71 @@ impl App {
 
     /// Execute the network fetch for news feeds and clear `rss_loading`.
     pub async fn execute_news_refresh(&mut self, urls: &[String]) {
-        match self.news_client.fetch_all(urls).await {
-            Ok(items) => {
+        match self
+            .news_client
+            .fetch_all(urls, &self.config.news_categories, 1)
+            .await
+        {
+            Ok((items, counts)) => {
                 self.news_items = items;
+                cap_news(&mut self.news_items, self.config.news_max_items);
+                self.news_page = 1;
+                self.news_feed_counts = counts;
                 self.news_last_refresh = Some(tokio::time::Instant::now());
                 self.status_message = None;
             }
             Err(e) => {
-                self.status_message = Some(format!("News error: {}", e));
+                self.notify(format!("News error: {}", e));
             }
         }
         self.rss_loading = false;
     }
 
+    /// Fetch the next page of every feed and merge in the headlines not
+    /// already shown. Feeds without paging only repeat their latest items,
+    /// so a page that adds nothing is not counted and can be retried later.
+    pub async fn load_more_news(&mut self) {
+        if self.rss_loading {
+            return;
+        }
+        let page = self.news_page + 1;
+        self.rss_loading = true;
+        let urls = self.config.news_sources.clone();
+        let result = self
+            .news_client
+            .fetch_all(&urls, &self.config.news_categories, page)
+            .await;
+        self.rss_loading = false;
+        let (items, counts) = match result {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                self.notify(format!("News error: {}", e));
+                return;
+            }
+        };
+        let before = self.news_items.len();
+        let existing = std::mem::take(&mut self.news_items);
+        self.news_items = merge_feeds(vec![existing, items]);
+        let added = self.news_items.len() - before;
+        if added == 0 {
+            self.status_message = Some("No older headlines from these feeds".to_string());
+            return;
+        }
+        self.news_page = page;
+        for (url, count) in counts {
+            *self.news_feed_counts.entry(url).or_default() += count;
+        }
+        let max = self.config.news_max_items;
+        self.status_message = Some(if cap_news(&mut self.news_items, max) > 0 {
+            format!(
+                "Loaded {} older headlines; keeping the newest {} (news_max_items)",
+                added, max
+            )
+        } else {
+            format!("Loaded {} older headlines", added)
+        });
+    }
+
     /// Open the news detail modal for the currently selected news item.
     pub fn open_news_detail(&mut self) {
         let items = self.get_filtered_news();
@@ -41,4 +144,136 @@ impl App {
         self.input_mode = InputMode::NewsDetail;
         self.news_detail_scroll = 0;
     }
+
+    /// `N` in the news feed: list the configured RSS sources.
+    pub fn open_news_sources(&mut self) {
+        self.news_source_selected = 0;
+        self.input_mode = InputMode::NewsSources;
+    }
+
+    pub fn close_news_sources(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn news_source_up(&mut self) {
+        self.news_source_selected = self.news_source_selected.saturating_sub(1);
+    }
+
+    pub fn news_source_down(&mut self) {
+        if self.news_source_selected + 1 < self.config.news_sources.len() {
+            self.news_source_selected += 1;
+        }
+    }
+
+    pub fn start_news_source_add(&mut self) {
+        self.input_buffer.clear();
+        self.input_mode = InputMode::NewsSourceAdd;
+    }
+
+    pub fn cancel_news_source_add(&mut self) {
+        self.input_buffer.clear();
+        self.input_mode = InputMode::NewsSources;
+    }
+
+    /// Validate the typed URL, fetch it once to make sure it is a readable
+    /// feed, then add it. Failures keep the prompt open with the reason.
+    pub async fn confirm_news_source_add(&mut self) -> Result<()> {
+        let url = match self.checked_news_source(&self.input_buffer) {
+            Ok(url) => url,
+            Err(e) => {
+                self.notify(format!("Invalid feed: {}", e));
+                return Ok(());
+            }
+        };
+        match self.news_client.check_feed(&url).await {
+            Ok(count) => {
+                self.add_news_source(&url)?;
+                self.notify(format!("Added feed ({} articles)", count));
+            }
+            Err(e) => self.notify(format!("Feed check failed: {}", e)),
+        }
+        Ok(())
+    }
+
+    /// Parsed, not-yet-configured feed URL.
+    fn checked_news_source(&self, input: &str) -> Result<String> {
+        let url = validate_feed_url(input)?;
+        if self.config.news_sources.contains(&url) {
+            anyhow::bail!("already in the source list");
+        }
+        Ok(url)
+    }
+
+    /// Append a feed URL, save, and schedule a news refresh.
+    pub fn add_news_source(&mut self, url: &str) -> Result<()> {
+        self.config.news_sources.push(url.to_string());
+        self.config.save()?;
+        self.news_source_selected = self.config.news_sources.len() - 1;
+        self.news_last_refresh = None;
+        self.input_buffer.clear();
+        self.input_mode = InputMode::NewsSources;
+        Ok(())
+    }
+
+    /// `c` in the source list: edit the highlighted feed's category.
+    pub fn start_news_source_category(&mut self) {
+        let Some(url) = self.config.news_sources.get(self.news_source_selected) else {
+            return;
+        };
+        self.input_buffer = self
+            .config
+            .news_categories
+            .get(url)
+            .cloned()
+            .unwrap_or_default();
+        self.input_mode = InputMode::NewsSourceCategory;
+    }
+
+    /// Save the typed category for the highlighted feed; empty clears it.
+    /// The feed is refetched so loaded headlines pick up the new category.
+    pub fn confirm_news_source_category(&mut self) -> Result<()> {
+        let category = self.input_buffer.trim().to_string();
+        if let Some(url) = self
+            .config
+            .news_sources
+            .get(self.news_source_selected)
+            .cloned()
+        {
+            if category.is_empty() {
+                self.config.news_categories.remove(&url);
+            } else {
+                self.config
+                    .news_categories
+                    .insert(url.clone(), category.clone());
+            }
+            self.config.save()?;
+            self.news_last_refresh = None;
+            if self
+                .news_category_filter
+                .as_ref()
+                .is_some_and(|c| !self.news_categories().contains(c))
+            {
+                self.news_category_filter = None;
+            }
+        }
+        self.input_buffer.clear();
+        self.input_mode = InputMode::NewsSources;
+        Ok(())
+    }
+
+    /// `d` in the source list: drop the highlighted feed and save.
+    pub fn remove_selected_news_source(&mut self) -> Result<()> {
+        if self.news_source_selected >= self.config.news_sources.len() {
+            return Ok(());
+        }
+        let url = self.config.news_sources.remove(self.news_source_selected);
+        self.config.news_categories.remove(&url);
+        self.config.save()?;
+        if self.news_source_selected >= self.config.news_sources.len() {
+            self.news_source_selected = self.config.news_sources.len().saturating_sub(1);
+        }
+        self.news_last_refresh = None;
+        self.notify(format!("Removed feed {}", url));
+        Ok(())
+    }
 }
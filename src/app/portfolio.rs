@@ -1,4 +1,7 @@
-use super::{App, InputMode};
+use super::expr::parse_price;
+use super::{App, InputMode, UndoAction};
+use crate::api::ChartRange;
+use crate::config::Holding;
 use anyhow::Result;
 use std::cmp::Ordering;
 
@@ -10,8 +13,19 @@ impl App {
         self.pending_lots = None;
     }
 
-    pub fn confirm_portfolio_symbol(&mut self) {
+    /// Take the typed symbol and ask for lots. With `validate_symbols`, a
+    /// symbol that resolves to nothing keeps the prompt open.
+    pub async fn confirm_portfolio_symbol(&mut self) {
         let symbol = self.input_buffer.trim().to_uppercase();
+        if !symbol.is_empty()
+            && !self
+                .unknown_symbols(std::slice::from_ref(&symbol))
+                .await
+                .is_empty()
+        {
+            self.status_message = Some(format!("Not found: {}", symbol));
+            return;
+        }
         if symbol.is_empty() {
             self.status_message = Some("Symbol cannot be empty".to_string());
             self.input_mode = InputMode::Normal;
@@ -39,14 +53,17 @@ impl App {
     }
 
     pub fn confirm_portfolio_price(&mut self) -> Result<()> {
-        if let Ok(avg_price) = self.input_buffer.trim().parse::<f64>() {
+        if let Some(avg_price) = parse_price(&self.input_buffer) {
             if avg_price > 0.0 {
                 match (&self.pending_symbol, self.pending_lots) {
                     (Some(symbol), Some(lots)) => {
                         if self.config.add_holding(symbol, lots, avg_price) {
+                            self.last_undo = None;
                             self.config.save()?;
-                            self.status_message =
-                                Some(format!("Added {} lots of {} @ {}", lots, symbol, avg_price));
+                            self.notify(format!(
+                                "Added {} lots of {} @ {}",
+                                lots, symbol, avg_price
+                            ));
                         } else {
                             self.status_message =
                                 Some("Total lots would exceed maximum (4,294,967,295)".to_string());
@@ -121,13 +138,16 @@ impl App {
     }
 
     pub fn confirm_portfolio_edit_price(&mut self) -> Result<()> {
-        if let Ok(avg_price) = self.input_buffer.trim().parse::<f64>() {
+        if let Some(avg_price) = parse_price(&self.input_buffer) {
             if avg_price > 0.0 {
                 match (&self.pending_edit_symbol, self.pending_lots) {
                     (Some(symbol), Some(lots)) => {
-                        self.config.update_holding(symbol, lots, avg_price);
+                        let sell_price = self.quotes.get(symbol).map(|q| q.price);
+                        self.config
+                            .update_holding(symbol, lots, avg_price, sell_price);
+                        self.last_undo = None;
                         self.config.save()?;
-                        self.status_message = Some(format!(
+                        self.notify(format!(
                             "Updated {} → {} lots @ {}",
                             symbol, lots, avg_price
                         ));
@@ -149,6 +169,39 @@ impl App {
         Ok(())
     }
 
+    /// Cost basis and unrealized P/L the holding being edited would have if
+    /// the typed value were saved. `None` while the input isn't a positive
+    /// number; P/L is `None` without a quote.
+    pub fn portfolio_edit_preview(&self) -> Option<(f64, Option<f64>)> {
+        let symbol = self.pending_edit_symbol.as_ref()?;
+        let current = self
+            .config
+            .current_portfolio()
+            .holdings
+            .iter()
+            .find(|h| &h.symbol == symbol)?;
+        let input = self.input_buffer.trim();
+        let (lots, avg_price) = match self.input_mode {
+            InputMode::PortfolioEditLots => (input.parse::<u32>().ok()?, current.avg_price),
+            InputMode::PortfolioEditPrice => (self.pending_lots?, parse_price(input)?),
+            _ => return None,
+        };
+        if lots == 0 || avg_price <= 0.0 {
+            return None;
+        }
+        let preview = Holding {
+            symbol: symbol.clone(),
+            lots,
+            avg_price,
+            note: None,
+        };
+        let pl = self
+            .quotes
+            .get(symbol)
+            .map(|q| preview.pl_metrics(q.price, self.config.lot_size).2);
+        Some((preview.cost_basis(self.config.lot_size), pl))
+    }
+
     pub fn cancel_portfolio_edit(&mut self) {
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
@@ -156,17 +209,115 @@ impl App {
         self.pending_lots = None;
     }
 
-    pub fn remove_selected_holding(&mut self) -> Result<()> {
+    /// `t`: edit the selected holding's note, pre-filled with the current one.
+    pub fn start_portfolio_note(&mut self) {
         if let Some(symbol) = self.selected_portfolio_symbol() {
-            self.config.remove_holding(&symbol);
+            self.input_buffer = self
+                .config
+                .current_portfolio()
+                .holdings
+                .iter()
+                .find(|h| h.symbol == symbol)
+                .and_then(|h| h.note.clone())
+                .unwrap_or_default();
+            self.pending_edit_symbol = Some(symbol);
+            self.input_mode = InputMode::PortfolioNote;
+        }
+    }
+
+    /// Save the typed note; an empty one removes it.
+    pub fn confirm_portfolio_note(&mut self) -> Result<()> {
+        if let Some(symbol) = self.pending_edit_symbol.take()
+            && self.config.set_holding_note(&symbol, &self.input_buffer)
+        {
             self.config.save()?;
-            self.quotes.remove(&symbol);
-            self.status_message = Some(format!("Removed {}", symbol));
-            let len = self.get_filtered_portfolio().len();
-            if self.portfolio_selected >= len && self.portfolio_selected > 0 {
-                self.portfolio_selected -= 1;
+            self.status_message = Some(if self.input_buffer.trim().is_empty() {
+                format!("Cleared note on {}", symbol)
+            } else {
+                format!("Saved note on {}", symbol)
+            });
+        }
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        Ok(())
+    }
+
+    pub fn start_portfolio_split(&mut self) {
+        if let Some(symbol) = self.selected_portfolio_symbol() {
+            self.pending_edit_symbol = Some(symbol);
+            self.input_buffer.clear();
+            self.input_mode = InputMode::PortfolioSplit;
+        }
+    }
+
+    /// Parse a "new:old" ratio such as "2:1" and apply it to the pending holding.
+    pub fn confirm_portfolio_split(&mut self) -> Result<()> {
+        let ratio = self.input_buffer.trim().split_once(':').and_then(|(n, d)| {
+            Some((n.trim().parse::<u32>().ok()?, d.trim().parse::<u32>().ok()?))
+        });
+        match (&self.pending_edit_symbol, ratio) {
+            (Some(symbol), Some((num, den))) => match self.config.apply_split(symbol, num, den) {
+                Ok(rounded) => {
+                    self.last_undo = None;
+                    self.config.save()?;
+                    let mut msg = format!("Applied {}:{} split to {}", num, den, symbol);
+                    if rounded > 0 {
+                        msg.push_str(&format!(
+                            " ({} logged trade(s) rounded to whole lots)",
+                            rounded
+                        ));
+                    }
+                    self.status_message = Some(msg);
+                }
+                Err(reason) => {
+                    self.status_message = Some(format!(
+                        "Cannot apply {}:{} split to {}: {}",
+                        num, den, symbol, reason
+                    ));
+                }
+            },
+            (Some(_), None) => {
+                self.status_message = Some("Invalid ratio, expected e.g. 2:1".to_string());
+            }
+            _ => {
+                self.status_message = Some("Missing split data".to_string());
             }
         }
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+        self.pending_edit_symbol = None;
+        Ok(())
+    }
+
+    pub fn remove_selected_holding(&mut self) -> Result<()> {
+        match self.selected_portfolio_symbol() {
+            Some(symbol) => self.remove_holding_symbol(&symbol),
+            None => Ok(()),
+        }
+    }
+
+    /// Sell off the holding of `symbol` in the active portfolio and save. Does
+    /// nothing if it is no longer held.
+    pub fn remove_holding_symbol(&mut self, symbol: &str) -> Result<()> {
+        let sell_price = self.quotes.get(symbol).map(|q| q.price);
+        let portfolio = self.config.current_portfolio();
+        let Some(index) = portfolio.holdings.iter().position(|h| h.symbol == symbol) else {
+            return Ok(());
+        };
+        self.last_undo = Some(UndoAction::Holding {
+            portfolio: self.config.active_portfolio,
+            index,
+            holding: portfolio.holdings[index].clone(),
+            transactions: portfolio.transactions.clone(),
+        });
+        self.config.remove_holding(symbol, sell_price);
+        self.config.save()?;
+        self.quotes.remove(symbol);
+        self.notify(format!("Removed {}", symbol));
+        let len = self.get_filtered_portfolio().len();
+        if self.portfolio_selected >= len && self.portfolio_selected > 0 {
+            self.portfolio_selected -= 1;
+        }
         Ok(())
     }
 
@@ -211,15 +362,30 @@ impl App {
         self.input_buffer = self.config.current_portfolio().name.clone();
     }
 
+    /// Create the typed portfolio, or switch to it if one with that name
+    /// (ignoring case) already exists.
     pub fn confirm_portfolio_new(&mut self) -> Result<()> {
         if !self.input_buffer.is_empty() {
             let name = self.input_buffer.trim().to_string();
-            self.config.add_portfolio(&name);
-            self.config.save()?;
+            let existing = self.config.portfolio_index(&name);
+            match existing {
+                Some(index) => self.config.active_portfolio = index,
+                None => {
+                    self.config.add_portfolio(&name);
+                    self.last_undo = None;
+                    self.config.save()?;
+                }
+            }
             self.quotes.clear();
             self.portfolio_selected = 0;
             *self.portfolio_table_state.offset_mut() = 0;
-            self.status_message = Some(format!("Created portfolio '{}'", name));
+            self.notify(match existing {
+                Some(_) => format!(
+                    "Portfolio '{}' already exists, switched to it",
+                    self.config.current_portfolio().name
+                ),
+                None => format!("Created portfolio '{}'", name),
+            });
         }
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
@@ -231,6 +397,7 @@ impl App {
             let new_name = self.input_buffer.trim().to_string();
             let old_name = self.config.current_portfolio().name.clone();
             self.config.rename_portfolio(&new_name);
+            self.last_undo = None;
             self.config.save()?;
             self.status_message = Some(format!("Renamed '{}' to '{}'", old_name, new_name));
         }
@@ -243,11 +410,12 @@ impl App {
         if self.config.portfolios.len() > 1 {
             let name = self.config.current_portfolio().name.clone();
             self.config.remove_portfolio();
+            self.last_undo = None;
             self.config.save()?;
             self.quotes.clear();
             self.portfolio_selected = 0;
             *self.portfolio_table_state.offset_mut() = 0;
-            self.status_message = Some(format!("Removed portfolio '{}'", name));
+            self.notify(format!("Removed portfolio '{}'", name));
         } else {
             self.status_message = Some("Cannot remove the last portfolio".to_string());
         }
@@ -264,6 +432,167 @@ impl App {
         self.input_mode = InputMode::Normal;
     }
 
+    pub async fn show_portfolio_history(&mut self) {
+        if self.config.current_portfolio().holdings.is_empty() {
+            return;
+        }
+        self.input_mode = InputMode::PortfolioHistory;
+        self.portfolio_history = Some(self.portfolio_value_series().await);
+    }
+
+    pub fn close_portfolio_history(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.portfolio_history = None;
+    }
+
+    /// Approximate daily value of the current holdings over the default
+    /// chart range: `shares * close` summed per day. Series are aligned on
+    /// their latest close and shorter ones padded with their first close.
+    /// Returns the values and the symbols left out for lack of chart data.
+    pub async fn portfolio_value_series(&mut self) -> (Vec<f64>, Vec<String>) {
+        let holdings = self.config.current_portfolio().holdings.clone();
+        let missing: Vec<String> = holdings
+            .iter()
+            .map(|h| h.symbol.clone())
+            .filter(|s| !self.history_charts.contains_key(s))
+            .collect();
+        if !missing.is_empty() {
+            let client = self.client.lock().await;
+            let results = futures::future::join_all(
+                missing
+                    .iter()
+                    .map(|s| client.get_chart(s, ChartRange::default())),
+            )
+            .await;
+            for (symbol, result) in missing.into_iter().zip(results) {
+                if let Ok(chart) = result
+                    && !chart.closes.is_empty()
+                {
+                    self.history_charts.insert(symbol, chart);
+                }
+            }
+        }
+
+        let mut excluded = Vec::new();
+        let mut series: Vec<(f64, &[f64])> = Vec::new();
+        for h in &holdings {
+            match self.history_charts.get(&h.symbol) {
+                Some(chart) => series.push((h.shares(self.config.lot_size) as f64, &chart.closes)),
+                None => excluded.push(h.symbol.clone()),
+            }
+        }
+        let len = series.iter().map(|(_, c)| c.len()).max().unwrap_or(0);
+        let values = (0..len)
+            .map(|i| {
+                series
+                    .iter()
+                    .map(|(shares, closes)| {
+                        let pad = len - closes.len();
+                        shares * closes[i.saturating_sub(pad)]
+                    })
+                    .sum()
+            })
+            .collect();
+        (values, excluded)
+    }
+
+    pub fn show_portfolio_pl(&mut self) {
+        let portfolio = self.config.current_portfolio();
+        if !portfolio.holdings.is_empty() || !portfolio.transactions.is_empty() {
+            self.input_mode = InputMode::PortfolioPL;
+        }
+    }
+
+    pub fn close_portfolio_pl(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Returns (symbol, realized P/L, unrealized P/L) for every symbol held or
+    /// traded in the current portfolio. Unrealized is `None` without a quote.
+    pub fn portfolio_pl_breakdown(&self) -> Vec<(String, f64, Option<f64>)> {
+        let portfolio = self.config.current_portfolio();
+        let mut symbols: Vec<String> = portfolio
+            .holdings
+            .iter()
+            .map(|h| h.symbol.clone())
+            .collect();
+        for tx in &portfolio.transactions {
+            if !symbols.contains(&tx.symbol) {
+                symbols.push(tx.symbol.clone());
+            }
+        }
+
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                let realized = self.config.realized_pl(&symbol);
+                let unrealized = match portfolio.holdings.iter().find(|h| h.symbol == symbol) {
+                    Some(h) => self
+                        .quotes
+                        .get(&symbol)
+                        .map(|q| h.pl_metrics(q.price, self.config.lot_size).2),
+                    None => Some(0.0),
+                };
+                (symbol, realized, unrealized)
+            })
+            .collect()
+    }
+
+    /// Aggregate day P/L (sum of `change * shares`) and its percent of the
+    /// previous close value. Holdings without a quote are skipped; `None` when
+    /// nothing in the portfolio has been quoted yet.
+    pub fn portfolio_day_change(&self) -> Option<(f64, f64)> {
+        let mut day_pl = 0.0;
+        let mut prev_value = 0.0;
+        let mut quoted = false;
+        for h in &self.config.current_portfolio().holdings {
+            if let Some(q) = self.quotes.get(&h.symbol) {
+                let shares = h.shares(self.config.lot_size) as f64;
+                day_pl += q.change * shares;
+                prev_value += (q.price - q.change) * shares;
+                quoted = true;
+            }
+        }
+        if !quoted {
+            return None;
+        }
+        let pct = if prev_value > 0.0 {
+            (day_pl / prev_value) * 100.0
+        } else {
+            0.0
+        };
+        Some((day_pl, pct))
+    }
+
+    /// Projected yearly dividend income for the active portfolio as
+    /// `(income, blended_yield_pct, covered, total)`. Holdings without a
+    /// quoted yield are left out; the blended yield is income over the value
+    /// of the covered holdings. `None` when no holding has a yield.
+    pub fn projected_annual_income(&self) -> Option<(f64, f64, usize, usize)> {
+        let holdings = &self.config.current_portfolio().holdings;
+        let mut income = 0.0;
+        let mut covered_value = 0.0;
+        let mut covered = 0;
+        for h in holdings {
+            if let Some(q) = self.quotes.get(&h.symbol)
+                && let Some(dividend) = h.projected_annual_dividend(q, self.config.lot_size)
+            {
+                income += dividend;
+                covered_value += q.price * h.shares(self.config.lot_size) as f64;
+                covered += 1;
+            }
+        }
+        if covered == 0 {
+            return None;
+        }
+        let yield_pct = if covered_value > 0.0 {
+            income / covered_value * 100.0
+        } else {
+            0.0
+        };
+        Some((income, yield_pct, covered, holdings.len()))
+    }
+
     /// Returns (symbol, value, percentage) sorted by value descending.
     pub fn portfolio_allocation(&self) -> Vec<(String, f64, f64)> {
         let mut items: Vec<(String, f64)> = self
@@ -273,7 +602,7 @@ impl App {
             .iter()
             .map(|h| {
                 let price = self.quotes.get(&h.symbol).map(|q| q.price).unwrap_or(0.0);
-                let value = price * h.shares() as f64;
+                let value = price * h.shares(self.config.lot_size) as f64;
                 (h.symbol.clone(), value)
             })
             .collect();
@@ -293,4 +622,29 @@ impl App {
             })
             .collect()
     }
+
+    /// `portfolio_allocation` grouped by each quote's sector as
+    /// (sector, value, percentage), sorted by value descending. Holdings
+    /// without a quoted sector go under "Unknown".
+    pub fn sector_allocation(&self) -> Vec<(String, f64, f64)> {
+        let mut sectors: Vec<(String, f64, f64)> = Vec::new();
+        for (symbol, value, pct) in self.portfolio_allocation() {
+            let sector = self
+                .quotes
+                .get(&symbol)
+                .and_then(|q| q.sector.as_deref())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .unwrap_or("Unknown");
+            match sectors.iter_mut().find(|(name, _, _)| name == sector) {
+                Some(entry) => {
+                    entry.1 += value;
+                    entry.2 += pct;
+                }
+                None => sectors.push((sector.to_string(), value, pct)),
+            }
+        }
+        sectors.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        sectors
+    }
 }
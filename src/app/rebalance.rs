@@ -0,0 +1,175 @@
+use super::{App, InputMode};
+use anyhow::Result;
+
+/// One holding in the rebalancing modal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalanceRow {
+    pub symbol: String,
+    /// Share of portfolio value now, in percent
+    pub current_pct: f64,
+    /// Target share after normalizing, in percent; `None` when unset
+    pub target_pct: Option<f64>,
+    /// Whole lots to buy (positive) or sell (negative) to reach the target;
+    /// `None` without a target or a quote
+    pub lots: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalancePlan {
+    pub rows: Vec<RebalanceRow>,
+    /// Targets as entered, summed over held symbols
+    pub target_sum: f64,
+    /// Share the targets have to fill: 100% minus the holdings without one
+    pub available_pct: f64,
+}
+
+impl RebalancePlan {
+    /// Targets were scaled because they didn't add up to the share they
+    /// cover.
+    pub fn normalized(&self) -> bool {
+        self.target_sum > 0.0 && (self.target_sum - self.available_pct).abs() > 0.05
+    }
+
+    pub fn warning(&self) -> Option<String> {
+        self.normalized().then(|| {
+            format!(
+                "Targets add up to {:.1}%, scaled to {:.1}%",
+                self.target_sum, self.available_pct
+            )
+        })
+    }
+}
+
+impl App {
+    /// Lots to trade so each targeted holding reaches its target share at
+    /// current prices. Holdings without a target keep their current weight;
+    /// the targets are scaled proportionally to fill the rest.
+    pub fn rebalance_plan(&self) -> RebalancePlan {
+        let allocation = self.portfolio_allocation();
+        let targets = &self.config.current_portfolio().targets;
+        let lot_size = self.config.lot_size.max(1) as f64;
+        let total: f64 = allocation.iter().map(|(_, value, _)| value).sum();
+        let untargeted_pct: f64 = allocation
+            .iter()
+            .filter(|(symbol, _, _)| !targets.contains_key(symbol))
+            .map(|(_, _, pct)| pct)
+            .sum();
+        let target_sum: f64 = allocation
+            .iter()
+            .filter_map(|(symbol, _, _)| targets.get(symbol))
+            .sum();
+        let available_pct = if total > 0.0 {
+            100.0 - untargeted_pct
+        } else {
+            100.0
+        };
+        let scale = if target_sum > 0.0 {
+            available_pct / target_sum
+        } else {
+            0.0
+        };
+
+        let rows = allocation
+            .into_iter()
+            .map(|(symbol, value, current_pct)| {
+                let target_pct = targets.get(&symbol).map(|t| t * scale);
+                let price = self
+                    .quotes
+                    .get(&symbol)
+                    .map(|q| q.price)
+                    .filter(|&p| p > 0.0);
+                let lots = match (target_pct, price) {
+                    (Some(target), Some(price)) => {
+                        let delta = total * target / 100.0 - value;
+                        Some((delta / (price * lot_size)).round() as i64)
+                    }
+                    _ => None,
+                };
+                RebalanceRow {
+                    symbol,
+                    current_pct,
+                    target_pct,
+                    lots,
+                }
+            })
+            .collect();
+        RebalancePlan {
+            rows,
+            target_sum,
+            available_pct,
+        }
+    }
+
+    pub fn show_rebalance(&mut self) {
+        if !self.config.current_portfolio().holdings.is_empty() {
+            self.rebalance_selected = 0;
+            self.input_mode = InputMode::Rebalance;
+        }
+    }
+
+    pub fn close_rebalance(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn rebalance_up(&mut self) {
+        self.rebalance_selected = self.rebalance_selected.saturating_sub(1);
+    }
+
+    pub fn rebalance_down(&mut self) {
+        let len = self.config.current_portfolio().holdings.len();
+        if self.rebalance_selected + 1 < len {
+            self.rebalance_selected += 1;
+        }
+    }
+
+    /// Prompt for the target of the selected row, prefilled with the current
+    /// one.
+    pub fn start_rebalance_target(&mut self) {
+        let plan = self.rebalance_plan();
+        let Some(row) = plan.rows.get(self.rebalance_selected) else {
+            return;
+        };
+        self.input_buffer = self
+            .config
+            .current_portfolio()
+            .targets
+            .get(&row.symbol)
+            .map(|t| t.to_string())
+            .unwrap_or_default();
+        self.pending_edit_symbol = Some(row.symbol.clone());
+        self.input_mode = InputMode::RebalanceTarget;
+    }
+
+    pub fn cancel_rebalance_target(&mut self) {
+        self.pending_edit_symbol = None;
+        self.input_buffer.clear();
+        self.input_mode = InputMode::Rebalance;
+    }
+
+    /// Save the typed target (0–100%); an empty one clears it.
+    pub fn confirm_rebalance_target(&mut self) -> Result<()> {
+        let input = self.input_buffer.trim().trim_end_matches('%').to_string();
+        let target = if input.is_empty() {
+            None
+        } else {
+            match input.parse::<f64>() {
+                Ok(pct) if (0.0..=100.0).contains(&pct) => Some(pct),
+                _ => {
+                    self.status_message = Some("Target must be between 0 and 100%".to_string());
+                    return Ok(());
+                }
+            }
+        };
+        if let Some(symbol) = self.pending_edit_symbol.take() {
+            self.config.set_target(&symbol, target);
+            self.config.save()?;
+            self.status_message = Some(match target {
+                Some(pct) => format!("Target for {}: {}%", symbol, pct),
+                None => format!("Cleared target for {}", symbol),
+            });
+        }
+        self.input_buffer.clear();
+        self.input_mode = InputMode::Rebalance;
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use super::{App, to_wib};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// A portfolio's total value and unrealized P/L on one trading day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub date: NaiveDate,
+    pub value: f64,
+    pub pl: f64,
+}
+
+/// Daily snapshots keyed by portfolio name, oldest first, stored in
+/// `portfolio_history.json` next to the config.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PortfolioHistory {
+    portfolios: BTreeMap<String, Vec<PortfolioSnapshot>>,
+}
+
+impl PortfolioHistory {
+    /// Load the history, falling back to an empty one if missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn snapshots(&self, portfolio: &str) -> &[PortfolioSnapshot] {
+        self.portfolios
+            .get(portfolio)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Add `snapshot`, replacing one already recorded for its date so the
+    /// day ends up with its last (closing) value. Returns whether anything
+    /// changed.
+    pub fn record(&mut self, portfolio: &str, snapshot: PortfolioSnapshot) -> bool {
+        let snapshots = self.portfolios.entry(portfolio.to_string()).or_default();
+        match snapshots.binary_search_by_key(&snapshot.date, |s| s.date) {
+            Ok(pos) if snapshots[pos] == snapshot => false,
+            Ok(pos) => {
+                snapshots[pos] = snapshot;
+                true
+            }
+            Err(pos) => {
+                snapshots.insert(pos, snapshot);
+                true
+            }
+        }
+    }
+}
+
+/// The WIB date whose prices quotes show at `now`, once the session has
+/// opened at `open`. `None` on weekends and before the open, when quotes
+/// still show the previous trading day's close.
+pub fn trading_date(now: DateTime<Utc>, open: NaiveTime) -> Option<NaiveDate> {
+    let local = to_wib(now);
+    let today = local.date_naive();
+    let trading_day = !matches!(today.weekday(), Weekday::Sat | Weekday::Sun);
+    (trading_day && local.time() >= open).then_some(today)
+}
+
+impl App {
+    /// Value and unrealized P/L of the current portfolio on `date`. `None`
+    /// when it is empty or a holding has no quote yet, so a partial total is
+    /// never recorded.
+    pub fn portfolio_snapshot(&self, date: NaiveDate) -> Option<PortfolioSnapshot> {
+        let holdings = &self.config.current_portfolio().holdings;
+        if holdings.is_empty() {
+            return None;
+        }
+        let lot_size = self.config.lot_size;
+        let mut value = 0.0;
+        let mut cost = 0.0;
+        for holding in holdings {
+            let price = self
+                .quotes
+                .get(&holding.symbol)
+                .map(|q| q.price)
+                .filter(|&p| p > 0.0)?;
+            value += price * holding.shares(lot_size) as f64;
+            cost += holding.cost_basis(lot_size);
+        }
+        Some(PortfolioSnapshot {
+            date,
+            value,
+            pl: value - cost,
+        })
+    }
+
+    /// Record today's snapshot of the current portfolio once the session
+    /// has opened and all its holdings are quoted, saving the history file.
+    /// Later refreshes update it, so the day keeps its closing value.
+    pub fn record_portfolio_snapshot(&mut self) {
+        let (open, _) = self.config.trading_hours();
+        let Some(snapshot) =
+            trading_date(Utc::now(), open).and_then(|d| self.portfolio_snapshot(d))
+        else {
+            return;
+        };
+        let name = self.config.current_portfolio().name.clone();
+        if !self.value_snapshots.record(&name, snapshot) {
+            return;
+        }
+        let saved = match &self.value_snapshots_path {
+            Some(path) => self.value_snapshots.save(path),
+            None => Ok(()),
+        };
+        if let Err(e) = saved {
+            self.notify(format!("Couldn't save portfolio history: {:#}", e));
+        }
+    }
+
+    /// Recorded daily snapshots of the current portfolio, oldest first.
+    pub fn current_portfolio_snapshots(&self) -> &[PortfolioSnapshot] {
+        self.value_snapshots
+            .snapshots(&self.config.current_portfolio().name)
+    }
+}
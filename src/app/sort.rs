@@ -1,4 +1,4 @@
-use super::SortDirection;
+use super::{SortDirection, relative_volume, vs_ihsg_pct};
 use crate::api::{NewsItem, StockQuote};
 use crate::config::{Bookmark, Holding};
 use std::cmp::Ordering;
@@ -30,6 +30,10 @@ pub fn compare_watchlist_column(
                 7 => cmp_f64(qa.low, qb.low),
                 8 => qa.volume.cmp(&qb.volume),
                 9 => cmp_f64(qa.price * qa.volume as f64, qb.price * qb.volume as f64),
+                10 => cmp_f64(
+                    relative_volume(qa).unwrap_or(f64::NEG_INFINITY),
+                    relative_volume(qb).unwrap_or(f64::NEG_INFINITY),
+                ),
                 _ => Ordering::Equal,
             };
             match direction {
@@ -56,20 +60,27 @@ pub fn compare_portfolio_column(
         .get(&b.symbol)
         .map(|q| q.short_name.as_str())
         .unwrap_or("");
+    // Value, cost and P/L all scale with the lot size, so comparing per
+    // share orders them the same.
     match col {
         0 => a.symbol.cmp(&b.symbol),
         1 => name_a.cmp(name_b),
         2 => a.lots.cmp(&b.lots),
         3 => cmp_f64(a.avg_price, b.avg_price),
         4 => cmp_f64(price_a, price_b),
-        5 => cmp_f64(a.pl_metrics(price_a).0, b.pl_metrics(price_b).0),
-        6 => cmp_f64(a.cost_basis(), b.cost_basis()),
-        7 => cmp_f64(a.pl_metrics(price_a).2, b.pl_metrics(price_b).2),
-        8 => cmp_f64(a.pl_metrics(price_a).3, b.pl_metrics(price_b).3),
+        5 => cmp_f64(a.pl_metrics(price_a, 1).0, b.pl_metrics(price_b, 1).0),
+        6 => cmp_f64(a.cost_basis(1), b.cost_basis(1)),
+        7 => cmp_f64(a.pl_metrics(price_a, 1).2, b.pl_metrics(price_b, 1).2),
+        8 => cmp_f64(a.pl_metrics(price_a, 1).3, b.pl_metrics(price_b, 1).3),
+        9 => cmp_f64(
+            vs_ihsg_pct(&a.symbol, quotes).unwrap_or(f64::NEG_INFINITY),
+            vs_ihsg_pct(&b.symbol, quotes).unwrap_or(f64::NEG_INFINITY),
+        ),
         _ => Ordering::Equal,
     }
 }
 
+/// Columns: 0=Age (published time), 1=Source, 2=Headline
 pub fn compare_news_column(col: usize, a: &NewsItem, b: &NewsItem) -> Ordering {
     match col {
         0 => a.published_at.cmp(&b.published_at),
@@ -1,39 +1,306 @@
-use super::{App, InputMode};
+use super::sort::compare_watchlist_column;
+use super::{App, InputMode, MiniChartFetch, SortDirection, UndoAction};
+use crate::api::yahoo::display_symbol;
+use crate::api::{ChartData, StockQuote};
 use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long a watchlist mini-trend chart stays fresh before refetching.
+const MINI_CHART_TTL: Duration = Duration::from_secs(300);
+/// Pause after the last keystroke in the add prompt before searching.
+const SUGGEST_DEBOUNCE: Duration = Duration::from_millis(350);
+/// Shortest add-prompt input that triggers a symbol search.
+const SUGGEST_MIN_CHARS: usize = 2;
+/// Most matches kept for the add-prompt dropdown.
+const MAX_SUGGESTIONS: usize = 8;
 
 impl App {
     pub fn start_adding(&mut self) {
+        if self.blocked_by_all_watchlists() {
+            return;
+        }
         self.input_mode = InputMode::Adding;
         self.input_buffer.clear();
     }
 
-    pub fn confirm_add(&mut self) -> Result<()> {
-        if !self.input_buffer.is_empty() {
-            let symbol = self.input_buffer.trim().to_uppercase();
-            self.config.add_stock(&symbol);
-            self.config.save()?;
-            self.status_message = Some(format!("Added {}", symbol));
+    pub fn show_top_movers(&mut self) {
+        self.input_mode = InputMode::TopMovers;
+    }
+
+    pub fn close_top_movers(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Biggest gainers (best first) and losers (worst first) by change %
+    /// across every watchlist, at most `top_movers_count` of each. Symbols
+    /// without a quote or with no change are left out, so either side may
+    /// be shorter.
+    pub fn top_movers(&self) -> (Vec<&StockQuote>, Vec<&StockQuote>) {
+        let count = self.config.top_movers_count;
+        let mut rows: Vec<_> = self
+            .get_all_symbols_combined()
+            .into_iter()
+            .filter_map(|s| self.quotes.get(s).map(|q| (s, Some(q))))
+            .collect();
+        // Column 4 is Chg%
+        rows.sort_by(|a, b| compare_watchlist_column(4, a, b, SortDirection::Descending));
+        let quotes: Vec<&StockQuote> = rows.into_iter().filter_map(|(_, q)| q).collect();
+        let gainers = quotes
+            .iter()
+            .filter(|q| q.change_percent > 0.0)
+            .take(count)
+            .copied()
+            .collect();
+        let losers = quotes
+            .iter()
+            .rev()
+            .filter(|q| q.change_percent < 0.0)
+            .take(count)
+            .copied()
+            .collect();
+        (gainers, losers)
+    }
+
+    /// Switch between the active watchlist and the combined all-watchlists
+    /// view. Editing keys are disabled while combined.
+    pub fn toggle_all_watchlists(&mut self) {
+        self.all_watchlists = !self.all_watchlists;
+        self.selected_index = 0;
+        self.watchlist_table_state.select(Some(0));
+        *self.watchlist_table_state.offset_mut() = 0;
+        if !self.all_watchlists {
+            self.apply_watchlist_sort();
+        }
+    }
+
+    /// True (and a status hint is shown) when an edit would be ambiguous
+    /// because the combined view is active.
+    pub(super) fn blocked_by_all_watchlists(&mut self) -> bool {
+        if self.all_watchlists {
+            self.status_message = Some("Switch to a single watchlist (w) to edit".to_string());
+        }
+        self.all_watchlists
+    }
+
+    /// Record an edit to the add prompt; the symbol search runs once typing
+    /// pauses for `SUGGEST_DEBOUNCE`.
+    pub fn add_input_changed(&mut self) {
+        self.add_suggestion_selected = None;
+        if last_symbol_token(&self.input_buffer).len() < SUGGEST_MIN_CHARS {
+            self.add_suggestions.clear();
+            self.add_suggest_due = None;
+        } else {
+            self.add_suggest_due = Some(Instant::now() + SUGGEST_DEBOUNCE);
+        }
+    }
+
+    /// Run the pending symbol search if its debounce delay has passed.
+    pub async fn update_add_suggestions(&mut self) {
+        let due = self
+            .add_suggest_due
+            .is_some_and(|due| Instant::now() >= due);
+        if self.input_mode != InputMode::Adding || !due {
+            return;
+        }
+        self.add_suggest_due = None;
+        let query = last_symbol_token(&self.input_buffer).to_string();
+        if let Ok(mut matches) = self.client.lock().await.search_symbols(&query).await {
+            matches.truncate(MAX_SUGGESTIONS);
+            self.add_suggestions = matches;
+            self.add_suggestion_selected = None;
+        }
+    }
+
+    pub fn add_suggestion_down(&mut self) {
+        if self.add_suggestions.is_empty() {
+            return;
+        }
+        let last = self.add_suggestions.len() - 1;
+        self.add_suggestion_selected = Some(match self.add_suggestion_selected {
+            Some(i) => (i + 1).min(last),
+            None => 0,
+        });
+    }
+
+    /// Move up the dropdown; moving above the first match returns to the
+    /// typed text.
+    pub fn add_suggestion_up(&mut self) {
+        self.add_suggestion_selected = match self.add_suggestion_selected {
+            Some(0) | None => None,
+            Some(i) => Some(i - 1),
+        };
+    }
+
+    pub(crate) fn clear_add_suggestions(&mut self) {
+        self.add_suggestions.clear();
+        self.add_suggestion_selected = None;
+        self.add_suggest_due = None;
+    }
+
+    /// Add every symbol in the prompt; several can be separated by commas
+    /// or spaces ("BBCA, BBRI TLKM"). A highlighted suggestion replaces the
+    /// symbol being typed. With `validate_symbols`, symbols that resolve to
+    /// nothing are left out, and the prompt stays open if none are left.
+    pub async fn confirm_add(&mut self) -> Result<()> {
+        if let Some((symbol, _)) = self
+            .add_suggestion_selected
+            .and_then(|i| self.add_suggestions.get(i))
+        {
+            let keep = self.input_buffer.len() - last_symbol_token(&self.input_buffer).len();
+            self.input_buffer.truncate(keep);
+            self.input_buffer.push_str(symbol);
+        }
+        self.clear_add_suggestions();
+        let symbols = parse_symbol_list(&self.input_buffer);
+        if !symbols.is_empty() {
+            let new: Vec<String> = symbols
+                .iter()
+                .filter(|s| !self.config.current_watchlist().symbols.contains(s))
+                .cloned()
+                .collect();
+            let unknown = self.unknown_symbols(&new).await;
+            if !unknown.is_empty() && unknown.len() == symbols.len() {
+                self.status_message = Some(format!("Not found: {}", unknown.join(", ")));
+                return Ok(());
+            }
+            let mut added = Vec::new();
+            let mut present = 0;
+            for symbol in &symbols {
+                if unknown.contains(symbol) {
+                    continue;
+                }
+                if self.config.current_watchlist().symbols.contains(symbol) {
+                    present += 1;
+                } else {
+                    self.config.add_stock(symbol);
+                    added.push(symbol.as_str());
+                }
+            }
+            if !added.is_empty() {
+                self.last_undo = None;
+                self.config.save()?;
+            }
+            let mut msg = match (added.as_slice(), present) {
+                ([symbol], 0) => format!("Added {}", symbol),
+                ([], 1) => format!("{} is already in the watchlist", symbols[0]),
+                _ => format!(
+                    "Added {} of {} symbols ({} already present)",
+                    added.len(),
+                    symbols.len(),
+                    present
+                ),
+            };
+            if !unknown.is_empty() {
+                msg.push_str(&format!("; not found: {}", unknown.join(", ")));
+            }
+            self.notify(msg);
         }
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
         Ok(())
     }
 
-    pub fn remove_selected(&mut self) -> Result<()> {
-        if let Some(symbol) = self.selected_watchlist_symbol() {
-            self.config.remove_stock(&symbol);
-            self.config.save()?;
-            self.quotes.remove(&symbol);
-            self.status_message = Some(format!("Removed {}", symbol));
-            let len = self.get_filtered_watchlist().len();
-            if self.selected_index >= len && self.selected_index > 0 {
-                self.selected_index -= 1;
+    /// `K`/`J`: move the selected symbol one row up or down in the
+    /// watchlist's saved order, keeping it selected. Only the unsorted,
+    /// unfiltered view shows that order, so it's refused otherwise.
+    pub fn move_selected_symbol(&mut self, up: bool) -> Result<()> {
+        if self.blocked_by_all_watchlists() {
+            return Ok(());
+        }
+        if self.watchlist_sort_column.is_some() {
+            self.status_message = Some("Clear the sort (s) to reorder".to_string());
+            return Ok(());
+        }
+        if self.search_active {
+            self.status_message = Some("Clear the filter (/ then Esc) to reorder".to_string());
+            return Ok(());
+        }
+        let index = self.selected_index;
+        let Some(target) = (if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        }) else {
+            return Ok(());
+        };
+        let rows: Vec<String> = self
+            .get_raw_watchlist()
+            .into_iter()
+            .map(|(s, _)| s.clone())
+            .collect();
+        let (Some(selected), Some(neighbour)) = (rows.get(index), rows.get(target)) else {
+            return Ok(());
+        };
+        // Step past any pinned symbols hidden between the two rows
+        let symbols = &self.config.current_watchlist().symbols;
+        let (Some(mut pos), Some(to)) = (
+            symbols.iter().position(|s| s == selected),
+            symbols.iter().position(|s| s == neighbour),
+        ) else {
+            return Ok(());
+        };
+        while pos != to {
+            if up {
+                self.config.move_symbol_up(pos);
+                pos -= 1;
+            } else {
+                self.config.move_symbol_down(pos);
+                pos += 1;
             }
         }
+        self.jump_selection(|_, _| target);
+        self.last_undo = None;
+        self.config.save()?;
+        Ok(())
+    }
+
+    pub fn remove_selected(&mut self) -> Result<()> {
+        if self.blocked_by_all_watchlists() {
+            return Ok(());
+        }
+        match self.selected_watchlist_symbol() {
+            Some(symbol) => self.remove_watchlist_symbol(&symbol),
+            None => Ok(()),
+        }
+    }
+
+    /// Remove `symbol` from the active watchlist and save. Does nothing if it
+    /// is no longer there.
+    pub fn remove_watchlist_symbol(&mut self, symbol: &str) -> Result<()> {
+        let Some(index) = self
+            .config
+            .current_watchlist()
+            .symbols
+            .iter()
+            .position(|s| s == symbol)
+        else {
+            return Ok(());
+        };
+        self.last_undo = Some(UndoAction::Stock {
+            watchlist: self.config.active_watchlist,
+            index,
+            symbol: symbol.to_string(),
+        });
+        self.config.remove_stock(symbol);
+        self.config.save()?;
+        self.quotes.remove(symbol);
+        self.notify(format!("Removed {}", symbol));
+        let len = self.get_filtered_watchlist().len();
+        if self.selected_index >= len && self.selected_index > 0 {
+            self.selected_index -= 1;
+        }
         Ok(())
     }
 
     pub fn watchlist_indicator(&self) -> String {
+        if self.all_watchlists {
+            return format!(
+                "All watchlists ({} symbols)",
+                self.get_all_symbols_combined().len()
+            );
+        }
         format!(
             "{} ({}/{})",
             self.config.current_watchlist().name,
@@ -43,19 +310,37 @@ impl App {
     }
 
     pub fn next_watchlist(&mut self) {
+        self.all_watchlists = false;
         self.config.next_watchlist();
         self.selected_index = 0;
         *self.watchlist_table_state.offset_mut() = 0;
         self.quotes.clear();
-        self.watchlist_sort_column = None;
+        self.apply_watchlist_sort();
     }
 
     pub fn prev_watchlist(&mut self) {
+        self.all_watchlists = false;
         self.config.prev_watchlist();
         self.selected_index = 0;
         *self.watchlist_table_state.offset_mut() = 0;
         self.quotes.clear();
-        self.watchlist_sort_column = None;
+        self.apply_watchlist_sort();
+    }
+
+    /// Load the active watchlist's saved sort into the table state.
+    pub fn apply_watchlist_sort(&mut self) {
+        let wl = self.config.current_watchlist();
+        self.watchlist_sort_column = wl.sort_column;
+        self.watchlist_sort_direction = wl.sort_direction;
+    }
+
+    /// Store the current table sort on the active watchlist and save it.
+    pub(super) fn persist_watchlist_sort(&mut self) {
+        let (column, direction) = (self.watchlist_sort_column, self.watchlist_sort_direction);
+        let wl = self.config.current_watchlist_mut();
+        wl.sort_column = column;
+        wl.sort_direction = direction;
+        let _ = self.config.save();
     }
 
     pub fn start_watchlist_add(&mut self) {
@@ -64,19 +349,39 @@ impl App {
     }
 
     pub fn start_watchlist_rename(&mut self) {
+        if self.blocked_by_all_watchlists() {
+            return;
+        }
         self.input_mode = InputMode::WatchlistRename;
         self.input_buffer = self.config.current_watchlist().name.clone();
     }
 
+    /// Create the typed watchlist, or switch to it if one with that name
+    /// (ignoring case) already exists.
     pub fn confirm_watchlist_add(&mut self) -> Result<()> {
         if !self.input_buffer.is_empty() {
             let name = self.input_buffer.trim().to_string();
-            self.config.add_watchlist(&name);
-            self.config.save()?;
+            let existing = self.config.watchlist_index(&name);
+            match existing {
+                Some(index) => self.config.active_watchlist = index,
+                None => {
+                    self.config.add_watchlist(&name);
+                    self.last_undo = None;
+                    self.config.save()?;
+                }
+            }
+            self.all_watchlists = false;
             self.quotes.clear();
+            self.apply_watchlist_sort();
             self.selected_index = 0;
             *self.watchlist_table_state.offset_mut() = 0;
-            self.status_message = Some(format!("Created watchlist '{}'", name));
+            self.notify(match existing {
+                Some(_) => format!(
+                    "Watchlist '{}' already exists, switched to it",
+                    self.config.current_watchlist().name
+                ),
+                None => format!("Created watchlist '{}'", name),
+            });
         }
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
@@ -88,6 +393,7 @@ impl App {
             let new_name = self.input_buffer.trim().to_string();
             let old_name = self.config.current_watchlist().name.clone();
             self.config.rename_watchlist(&new_name);
+            self.last_undo = None;
             self.config.save()?;
             self.status_message = Some(format!("Renamed '{}' to '{}'", old_name, new_name));
         }
@@ -97,14 +403,22 @@ impl App {
     }
 
     pub fn remove_current_watchlist(&mut self) -> Result<()> {
+        if self.blocked_by_all_watchlists() {
+            return Ok(());
+        }
         if self.config.watchlists.len() > 1 {
             let name = self.config.current_watchlist().name.clone();
+            self.last_undo = Some(UndoAction::Watchlist {
+                index: self.config.active_watchlist,
+                watchlist: self.config.current_watchlist().clone(),
+            });
             self.config.remove_watchlist();
             self.config.save()?;
             self.quotes.clear();
+            self.apply_watchlist_sort();
             self.selected_index = 0;
             *self.watchlist_table_state.offset_mut() = 0;
-            self.status_message = Some(format!("Removed watchlist '{}'", name));
+            self.notify(format!("Removed watchlist '{}'", name));
         } else {
             self.status_message = Some("Cannot remove the last watchlist".to_string());
         }
@@ -116,4 +430,93 @@ impl App {
             self.open_detail(&symbol).await;
         }
     }
+
+    /// Concurrently fetch intraday charts for current watchlist symbols whose
+    /// cached chart is missing or older than `MINI_CHART_TTL`. No-op unless
+    /// `watchlist_sparklines` is enabled.
+    pub async fn refresh_mini_charts(&mut self) {
+        if let Some(fetch) = self.spawn_mini_chart_fetch()
+            && let Ok(results) = fetch.await
+        {
+            self.finish_mini_chart_fetch(results);
+        }
+    }
+
+    /// `refresh_mini_charts` on its own task, so the input loop keeps
+    /// drawing and reading keys while the charts load. `None` when there is
+    /// nothing to fetch; hand the results to `finish_mini_chart_fetch`.
+    pub fn spawn_mini_chart_fetch(&self) -> Option<MiniChartFetch> {
+        if !self.config.watchlist_sparklines {
+            return None;
+        }
+        // (display key, config symbol): charts are stored like quotes but
+        // requested with the symbol as written, so `^JKSE` stays the index.
+        let stale: Vec<(String, String)> = self
+            .get_raw_watchlist()
+            .into_iter()
+            .map(|(s, _)| (display_symbol(&s.to_uppercase()), s.clone()))
+            .filter(|(key, _)| {
+                self.mini_charts
+                    .get(key)
+                    .is_none_or(|(fetched, _)| fetched.elapsed() >= MINI_CHART_TTL)
+            })
+            .collect();
+        if stale.is_empty() {
+            return None;
+        }
+
+        let client = Arc::clone(&self.client);
+        Some(tokio::spawn(async move {
+            // Only hold the shared provider long enough to get a chart
+            // handle; refreshes and searches need it while charts load.
+            let handle = client.lock().await.chart_handle();
+            let results = match &handle {
+                Some(handle) => {
+                    futures::future::join_all(
+                        stale.iter().map(|(_, s)| handle.get_intraday_chart(s)),
+                    )
+                    .await
+                }
+                None => {
+                    futures::future::join_all(stale.iter().map(|(_, s)| {
+                        let client = Arc::clone(&client);
+                        async move { client.lock().await.get_intraday_chart(s).await }
+                    }))
+                    .await
+                }
+            };
+            stale.into_iter().map(|(key, _)| key).zip(results).collect()
+        }))
+    }
+
+    /// Store the charts a `spawn_mini_chart_fetch` task returned; failed
+    /// symbols keep their previous chart and are retried next time.
+    pub fn finish_mini_chart_fetch(&mut self, results: Vec<(String, Result<ChartData>)>) {
+        let now = Instant::now();
+        for (symbol, result) in results {
+            if let Ok(chart) = result {
+                self.mini_charts.insert(symbol, (now, chart));
+            }
+        }
+    }
+}
+
+/// Uppercased, de-duplicated symbols from a comma- or space-separated list.
+fn parse_symbol_list(input: &str) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for token in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let symbol = token.to_uppercase();
+        if !symbol.is_empty() && !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    symbols
+}
+
+/// The symbol currently being typed: everything after the last separator.
+fn last_symbol_token(input: &str) -> &str {
+    input
+        .rsplit(|c: char| c == ',' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
 }
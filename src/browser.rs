@@ -0,0 +1,19 @@
+//! Opening links in the desktop browser through the platform's opener
+//! command (`xdg-open`, or `open` on macOS).
+
+use anyhow::{Context, Result};
+
+#[cfg(target_os = "macos")]
+const OPENER: &str = "open";
+#[cfg(not(target_os = "macos"))]
+const OPENER: &str = "xdg-open";
+
+/// Hand `url` to the opener without waiting for it. Fails when the opener
+/// can't be started, e.g. on a headless machine.
+pub fn open_url(url: &str) -> Result<()> {
+    std::process::Command::new(OPENER)
+        .arg(url)
+        .spawn()
+        .with_context(|| format!("couldn't run {}", OPENER))?;
+    Ok(())
+}
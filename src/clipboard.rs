@@ -0,0 +1,31 @@
+//! System clipboard access. Only available when built with the `clipboard`
+//! feature, so headless or minimal builds don't pull in a display backend.
+
+use anyhow::Result;
+
+/// Handle to the system clipboard, opened on first copy and kept for the
+/// life of the app. On X11 and Wayland the process that owns the clipboard
+/// has to keep serving it, so dropping the handle right after a copy would
+/// lose the text before anything could paste it.
+#[derive(Default)]
+pub struct Clipboard {
+    #[cfg(feature = "clipboard")]
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    #[cfg(feature = "clipboard")]
+    pub fn copy_text(&mut self, text: &str) -> Result<()> {
+        let clipboard = match &mut self.inner {
+            Some(clipboard) => clipboard,
+            None => self.inner.insert(arboard::Clipboard::new()?),
+        };
+        clipboard.set_text(text)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    pub fn copy_text(&mut self, _text: &str) -> Result<()> {
+        anyhow::bail!("built without the `clipboard` feature")
+    }
+}
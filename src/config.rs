@@ -1,12 +1,27 @@
-use anyhow::Result;
+use crate::api::StockQuote;
+use crate::api::http::DEFAULT_HTTP_TIMEOUT_SECS;
+use crate::api::yahoo::display_symbol;
+use crate::app::{SortDirection, parse_price};
+use crate::ui::formatters::NumberFormat;
+use anyhow::{Result, bail};
+use chrono::NaiveTime;
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Watchlist {
     pub name: String,
     pub symbols: Vec<String>,
+    /// Sort applied whenever this watchlist is opened; updated when the user sorts
+    #[serde(default)]
+    pub sort_column: Option<usize>,
+    #[serde(default)]
+    pub sort_direction: SortDirection,
 }
 
 impl Default for Watchlist {
@@ -19,36 +34,55 @@ impl Default for Watchlist {
                 "TLKM".to_string(),
                 "ASII".to_string(),
             ],
+            sort_column: None,
+            sort_direction: SortDirection::default(),
         }
     }
 }
 
-/// A holding in the portfolio (1 lot = 100 shares for IDX)
+/// Shares in `lots` lots of `lot_size` shares (100 on IDX). A lot size of
+/// 0 counts as 1.
+pub fn lots_to_shares(lots: u32, lot_size: u32) -> u64 {
+    lots as u64 * lot_size.max(1) as u64
+}
+
+/// A holding in the portfolio, counted in lots of `Config::lot_size` shares
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Holding {
     pub symbol: String,
     pub lots: u32,
     pub avg_price: f64,
+    /// Free-text note or tags, e.g. "core, long term"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl Holding {
-    pub fn shares(&self) -> u64 {
-        self.lots as u64 * 100
+    pub fn shares(&self, lot_size: u32) -> u64 {
+        lots_to_shares(self.lots, lot_size)
     }
 
-    pub fn cost_basis(&self) -> f64 {
-        self.shares() as f64 * self.avg_price
+    pub fn cost_basis(&self, lot_size: u32) -> f64 {
+        self.shares(lot_size) as f64 * self.avg_price
     }
 
     /// Calculate P/L metrics given the current market price
-    pub fn pl_metrics(&self, current_price: f64) -> (f64, f64, f64, f64) {
-        let shares = self.shares();
+    pub fn pl_metrics(&self, current_price: f64, lot_size: u32) -> (f64, f64, f64, f64) {
+        let shares = self.shares(lot_size);
         let value = current_price * shares as f64;
-        let cost = self.cost_basis();
+        let cost = self.cost_basis(lot_size);
         let pl = value - cost;
         let pl_pct = if cost > 0.0 { (pl / cost) * 100.0 } else { 0.0 };
         (value, cost, pl, pl_pct)
     }
+
+    /// Yearly dividend at the quote's trailing yield (a fraction, 0.05 = 5%)
+    /// and current price. `None` when the quote has no yield.
+    pub fn projected_annual_dividend(&self, quote: &StockQuote, lot_size: u32) -> Option<f64> {
+        quote
+            .dividend_yield
+            .map(|y| y * quote.price * self.shares(lot_size) as f64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -57,8 +91,19 @@ pub enum AlertType {
     Below,
     PercentGain,
     PercentLoss,
+    /// Fires only when the price moves from below the target to at or above it
+    CrossAbove,
+    /// Fires only when the price moves from above the target to at or below it
+    CrossBelow,
+    /// Fires while the price sits at the session high; has no target value
+    NewDayHigh,
+    /// Fires while the price sits at the session low; has no target value
+    NewDayLow,
 }
 
+/// How close the price must be to the session high/low to count as at it.
+const DAY_EXTREME_EPSILON: f64 = 1e-6;
+
 impl AlertType {
     pub fn label(&self) -> &'static str {
         match self {
@@ -66,28 +111,69 @@ impl AlertType {
             AlertType::Below => "Below",
             AlertType::PercentGain => "% Gain",
             AlertType::PercentLoss => "% Loss",
+            AlertType::CrossAbove => "Cross above",
+            AlertType::CrossBelow => "Cross below",
+            AlertType::NewDayHigh => "New day high",
+            AlertType::NewDayLow => "New day low",
         }
     }
 
+    /// Whether the alert compares against a user-entered target. Day high/low
+    /// alerts compare against the quote itself.
+    pub fn needs_target(&self) -> bool {
+        !matches!(self, AlertType::NewDayHigh | AlertType::NewDayLow)
+    }
+
     pub fn next(&self) -> AlertType {
         match self {
             AlertType::Above => AlertType::Below,
             AlertType::Below => AlertType::PercentGain,
             AlertType::PercentGain => AlertType::PercentLoss,
-            AlertType::PercentLoss => AlertType::Above,
+            AlertType::PercentLoss => AlertType::CrossAbove,
+            AlertType::CrossAbove => AlertType::CrossBelow,
+            AlertType::CrossBelow => AlertType::NewDayHigh,
+            AlertType::NewDayHigh => AlertType::NewDayLow,
+            AlertType::NewDayLow => AlertType::Above,
         }
     }
 
     pub fn prev(&self) -> AlertType {
         match self {
-            AlertType::Above => AlertType::PercentLoss,
+            AlertType::Above => AlertType::NewDayLow,
             AlertType::Below => AlertType::Above,
             AlertType::PercentGain => AlertType::Below,
             AlertType::PercentLoss => AlertType::PercentGain,
+            AlertType::CrossAbove => AlertType::PercentLoss,
+            AlertType::CrossBelow => AlertType::CrossAbove,
+            AlertType::NewDayHigh => AlertType::CrossBelow,
+            AlertType::NewDayLow => AlertType::NewDayHigh,
         }
     }
 }
 
+/// Reject alert targets that can't be what the user meant: prices must be
+/// above 0, percentages (gain or loss, both entered as positive numbers)
+/// above 0 and at most 100. Day high/low alerts ignore their value.
+pub fn validate_alert_value(alert_type: &AlertType, value: f64) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err("Value must be a number".to_string());
+    }
+    match alert_type {
+        AlertType::PercentGain | AlertType::PercentLoss => {
+            if value <= 0.0 || value > 100.0 {
+                return Err("% must be between 0 and 100".to_string());
+            }
+        }
+        AlertType::Above | AlertType::Below | AlertType::CrossAbove | AlertType::CrossBelow => {
+            if value <= 0.0 {
+                return Err("Price must be > 0".to_string());
+            }
+        }
+        AlertType::NewDayHigh | AlertType::NewDayLow => {}
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub id: String,
@@ -99,6 +185,9 @@ pub struct Alert {
     pub cooldown_seconds: u32,
 }
 
+/// Cooldown given to new alerts until the user picks another.
+pub const DEFAULT_ALERT_COOLDOWN_SECS: u32 = 300;
+
 impl Alert {
     pub fn new(symbol: &str, alert_type: AlertType, target_value: f64) -> Self {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -113,11 +202,19 @@ impl Alert {
             target_value,
             enabled: true,
             last_triggered: None,
-            cooldown_seconds: 300,
+            cooldown_seconds: DEFAULT_ALERT_COOLDOWN_SECS,
         }
     }
 
-    pub fn should_trigger(&self, price: f64, change_pct: f64) -> bool {
+    pub fn should_trigger(&self, quote: &StockQuote) -> bool {
+        self.should_trigger_from(None, quote)
+    }
+
+    /// Like `should_trigger`, also given the price seen on the previous
+    /// refresh. Cross alerts need it: they fire on the move from one side of
+    /// the target to the other, never while the price just stays there, so
+    /// without a previous price they don't fire.
+    pub fn should_trigger_from(&self, prev_price: Option<f64>, quote: &StockQuote) -> bool {
         if !self.enabled {
             return false;
         }
@@ -131,11 +228,23 @@ impl Alert {
                 return false;
             }
         }
+        let price = quote.price;
         match self.alert_type {
             AlertType::Above => price >= self.target_value,
             AlertType::Below => price <= self.target_value,
-            AlertType::PercentGain => change_pct >= self.target_value,
-            AlertType::PercentLoss => change_pct <= -self.target_value,
+            AlertType::PercentGain => quote.change_percent >= self.target_value,
+            AlertType::PercentLoss => quote.change_percent <= -self.target_value,
+            AlertType::CrossAbove => {
+                prev_price.is_some_and(|p| p < self.target_value) && price >= self.target_value
+            }
+            AlertType::CrossBelow => {
+                prev_price.is_some_and(|p| p > self.target_value) && price <= self.target_value
+            }
+            // A quote without a session range yet reports zeros; don't fire on it
+            AlertType::NewDayHigh => {
+                price > 0.0 && (price - quote.high).abs() <= DAY_EXTREME_EPSILON
+            }
+            AlertType::NewDayLow => price > 0.0 && (price - quote.low).abs() <= DAY_EXTREME_EPSILON,
         }
     }
 }
@@ -159,19 +268,452 @@ fn default_bookmarks() -> Vec<Bookmark> {
     Vec::new()
 }
 
+/// How a percentage move should be emphasized in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeClass {
+    StrongLoss,
+    Loss,
+    Neutral,
+    Gain,
+    StrongGain,
+}
+
+/// Moves with `|pct| < neutral_band_pct` are neutral and moves with
+/// `|pct| >= strong_pct` are strong. A threshold of 0 disables that class.
+pub fn classify_change(pct: f64, neutral_band_pct: f64, strong_pct: f64) -> ChangeClass {
+    let abs = pct.abs();
+    if abs < neutral_band_pct {
+        ChangeClass::Neutral
+    } else if strong_pct > 0.0 && abs >= strong_pct {
+        if pct >= 0.0 {
+            ChangeClass::StrongGain
+        } else {
+            ChangeClass::StrongLoss
+        }
+    } else if pct >= 0.0 {
+        ChangeClass::Gain
+    } else {
+        ChangeClass::Loss
+    }
+}
+
+/// Relative volume (today's volume over the average) at or above
+/// `threshold` is a spike. A threshold of 0 disables the highlight.
+pub fn is_volume_spike(relative_volume: f64, threshold: f64) -> bool {
+    threshold > 0.0 && relative_volume >= threshold
+}
+
+/// Background for a change% cell: dark at 0%, brightening linearly to full
+/// green (gains) or red (losses) at `max_pct` and clamped beyond it.
+pub fn heatmap_color(pct: f64, max_pct: f64) -> Color {
+    let t = if max_pct > 0.0 {
+        (pct.abs() / max_pct).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let level = (40.0 + 180.0 * t).round() as u8;
+    if pct > 0.0 {
+        Color::Rgb(0, level, 0)
+    } else if pct < 0.0 {
+        Color::Rgb(level, 0, 0)
+    } else {
+        Color::Rgb(40, 40, 40)
+    }
+}
+
+/// Named UI colors. In config.json each value is a color name ("green",
+/// "darkgray") or a hex string ("#285078").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Theme {
+    pub selection_bg: Color,
+    pub portfolio_selection_bg: Color,
+    pub news_selection_bg: Color,
+    pub bookmark_selection_bg: Color,
+    /// Selected item in modal lists and menus
+    pub menu_selection_bg: Color,
+    /// Selected action in a menu, e.g. "+ Add Alert"
+    pub action_selection_bg: Color,
+    pub gain: Color,
+    pub loss: Color,
+    pub gain_selected: Color,
+    pub loss_selected: Color,
+    pub header: Color,
+    pub portfolio_header: Color,
+    pub accent: Color,
+    pub neutral: Color,
+    pub news_header: Color,
+    /// Values and headings that stand out from the surrounding text
+    pub text: Color,
+    /// Labels, hints and separators
+    pub muted: Color,
+    /// Background of modals, covering the view behind them
+    pub backdrop: Color,
+    /// Handed out in turn to allocation bars and feed categories
+    pub series: Vec<Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            selection_bg: Color::Rgb(40, 80, 120),
+            portfolio_selection_bg: Color::Rgb(80, 40, 80),
+            news_selection_bg: Color::Rgb(40, 60, 100),
+            bookmark_selection_bg: Color::Rgb(40, 80, 40),
+            menu_selection_bg: Color::Rgb(40, 40, 80),
+            action_selection_bg: Color::Rgb(40, 80, 40),
+            gain: Color::Green,
+            loss: Color::Red,
+            gain_selected: Color::LightGreen,
+            loss_selected: Color::LightRed,
+            header: Color::Yellow,
+            portfolio_header: Color::Magenta,
+            accent: Color::Cyan,
+            neutral: Color::Gray,
+            news_header: Color::Blue,
+            text: Color::White,
+            muted: Color::DarkGray,
+            backdrop: Color::Black,
+            series: vec![
+                Color::Cyan,
+                Color::Green,
+                Color::Yellow,
+                Color::Magenta,
+                Color::Blue,
+                Color::Red,
+                Color::LightCyan,
+                Color::LightGreen,
+            ],
+        }
+    }
+}
+
+impl Theme {
+    /// Grayscale palette; gains and losses differ by brightness and sign only.
+    pub fn mono() -> Self {
+        Self {
+            selection_bg: Color::Rgb(70, 70, 70),
+            portfolio_selection_bg: Color::Rgb(70, 70, 70),
+            news_selection_bg: Color::Rgb(70, 70, 70),
+            bookmark_selection_bg: Color::Rgb(70, 70, 70),
+            menu_selection_bg: Color::Rgb(70, 70, 70),
+            action_selection_bg: Color::Rgb(70, 70, 70),
+            gain: Color::White,
+            loss: Color::Gray,
+            gain_selected: Color::White,
+            loss_selected: Color::Gray,
+            header: Color::White,
+            portfolio_header: Color::White,
+            accent: Color::White,
+            neutral: Color::DarkGray,
+            news_header: Color::White,
+            text: Color::White,
+            muted: Color::DarkGray,
+            backdrop: Color::Black,
+            series: vec![Color::White, Color::Gray, Color::DarkGray],
+        }
+    }
+
+    /// `--no-color`: every color is the terminal default. Selections show in
+    /// reverse video (see `selected`), gains and losses by sign and ▲/▼.
+    pub fn no_color() -> Self {
+        Self {
+            selection_bg: Color::Reset,
+            portfolio_selection_bg: Color::Reset,
+            news_selection_bg: Color::Reset,
+            bookmark_selection_bg: Color::Reset,
+            menu_selection_bg: Color::Reset,
+            action_selection_bg: Color::Reset,
+            gain: Color::Reset,
+            loss: Color::Reset,
+            gain_selected: Color::Reset,
+            loss_selected: Color::Reset,
+            header: Color::Reset,
+            portfolio_header: Color::Reset,
+            accent: Color::Reset,
+            neutral: Color::Reset,
+            news_header: Color::Reset,
+            text: Color::Reset,
+            muted: Color::Reset,
+            backdrop: Color::Reset,
+            series: Vec::new(),
+        }
+    }
+
+    /// Style marking a selected row or menu item with `bg`, or with reverse
+    /// video when `bg` is the terminal default.
+    pub fn selected(&self, bg: Color) -> Style {
+        if bg == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(bg)
+        }
+    }
+
+    /// The `index`th series color, wrapping around; the terminal default
+    /// when the theme has none.
+    pub fn series_color(&self, index: usize) -> Color {
+        if self.series.is_empty() {
+            return Color::Reset;
+        }
+        self.series[index % self.series.len()]
+    }
+
+    /// Color for a classified move: neutral inside the band, the bright
+    /// variant beyond the strong threshold or on the selected row.
+    pub fn change_class_color(&self, class: ChangeClass, selected: bool) -> Color {
+        match (class, selected) {
+            (ChangeClass::Neutral, _) => self.neutral,
+            (ChangeClass::Gain, false) => self.gain,
+            (ChangeClass::Loss, false) => self.loss,
+            (ChangeClass::Gain | ChangeClass::StrongGain, _) => self.gain_selected,
+            (ChangeClass::Loss | ChangeClass::StrongLoss, _) => self.loss_selected,
+        }
+    }
+}
+
+/// Cell highlights drawn over the theme colors, from `Config::highlights`.
+/// The table code gets them passed alongside the `Theme`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Highlights {
+    /// Moves smaller than this (in percent) are drawn in the neutral color
+    pub neutral_band_pct: f64,
+    /// Moves at least this large (in percent) are drawn in the bright color
+    pub strong_pct: f64,
+    /// Shade change% cells by the size of the move
+    pub heatmap: bool,
+    pub heatmap_max_pct: f64,
+    /// Relative volume at which Volume and Rel Vol cells stand out
+    pub volume_spike_ratio: f64,
+    /// Prefix gains and losses with ▲/▼; set for `--no-color`, where the
+    /// gain/loss colors are gone
+    pub change_arrows: bool,
+}
+
+impl Highlights {
+    /// These highlights for `--no-color`: ▲/▼ on gains and losses, and no
+    /// heatmap, whose backgrounds would read as selected rows.
+    pub fn without_color(mut self) -> Self {
+        self.heatmap = false;
+        self.change_arrows = true;
+        self
+    }
+
+    /// "▲" or "▼" for a gain or loss with `change_arrows`, otherwise (and
+    /// for no change) empty.
+    pub fn arrow(&self, change: f64) -> &'static str {
+        if !self.change_arrows || change == 0.0 {
+            ""
+        } else if change > 0.0 {
+            "▲"
+        } else {
+            "▼"
+        }
+    }
+
+    pub fn classify_change(&self, pct: f64) -> ChangeClass {
+        classify_change(pct, self.neutral_band_pct, self.strong_pct)
+    }
+
+    /// `theme`'s color for a percentage move, classified by these bands.
+    pub fn change_color(&self, theme: &Theme, pct: f64, selected: bool) -> Color {
+        theme.change_class_color(self.classify_change(pct), selected)
+    }
+
+    /// Heatmap background for a change% cell, or `None` when disabled.
+    pub fn heatmap_color(&self, pct: f64) -> Option<Color> {
+        self.heatmap
+            .then(|| heatmap_color(pct, self.heatmap_max_pct))
+    }
+
+    /// Whether a relative volume counts as a spike worth highlighting.
+    pub fn is_volume_spike(&self, relative_volume: Option<f64>) -> bool {
+        relative_volume.is_some_and(|r| is_volume_spike(r, self.volume_spike_ratio))
+    }
+}
+
+/// How large numbers are abbreviated and grouped in the UI.
+/// `En`: 1,234.50 and K/M/B/T. `Id`: 1.234,50 and rb/jt/M/T.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NumberLocale {
+    #[default]
+    En,
+    Id,
+}
+
+/// How much chrome surrounds the tables. `Compact` drops borders and
+/// shrinks the header and footer to a single row each.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Density {
+    #[default]
+    Normal,
+    Compact,
+}
+
+impl Density {
+    pub fn toggled(self) -> Self {
+        match self {
+            Density::Normal => Density::Compact,
+            Density::Compact => Density::Normal,
+        }
+    }
+
+    /// Height of the header and footer bars, including borders.
+    pub fn bar_height(self) -> u16 {
+        match self {
+            Density::Normal => 3,
+            Density::Compact => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TransactionKind {
+    Buy,
+    Sell,
+}
+
+/// A recorded buy or sell, used to compute realized P/L.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub symbol: String,
+    pub kind: TransactionKind,
+    pub lots: u32,
+    pub price: f64,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Portfolio {
     pub name: String,
     pub holdings: Vec<Holding>,
+    #[serde(default)]
+    pub transactions: Vec<Transaction>,
+    /// Target allocation per symbol in percent of portfolio value, for the
+    /// rebalancing helper
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, f64>,
+}
+
+/// Header names of the symbol, lots and average price columns in a broker
+/// holdings CSV, matched case-insensitively. `None` tries the names common
+/// brokers use.
+#[derive(Debug, Clone, Default)]
+pub struct HoldingsColumns {
+    pub symbol: Option<String>,
+    pub lots: Option<String>,
+    pub avg_price: Option<String>,
+}
+
+const SYMBOL_HEADERS: &[&str] = &["symbol", "stock", "code", "kode", "kode saham", "ticker"];
+const LOTS_HEADERS: &[&str] = &["lots", "lot", "balance lot", "jumlah lot"];
+const AVG_PRICE_HEADERS: &[&str] = &[
+    "avg price",
+    "average price",
+    "avg",
+    "avg. price",
+    "harga rata-rata",
+    "harga rata2",
+];
+
+/// Fields of one CSV line split on `delimiter`, trimmed. A field wrapped in
+/// double quotes may contain the delimiter, and `""` inside it is a quote.
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// A price from a broker export, written either way round: `9,025.50` or
+/// `9.025,50`. When both separators appear the last one is the decimal
+/// point; one that repeats (`1.234.567`) groups thousands. A lone one groups
+/// thousands when exactly three digits follow it (`9,025`, `1.234`) and is
+/// the decimal point otherwise (`3025,5`). Whatever remains goes through
+/// `parse_price`.
+fn parse_csv_price(field: &str) -> Option<f64> {
+    let field: String = field.chars().filter(|c| !c.is_whitespace()).collect();
+    let lone_decimal = |sep: char| {
+        field.matches(sep).count() == 1
+            && field.split_once(sep).is_some_and(|(_, rest)| {
+                !(rest.len() == 3 && rest.chars().all(|c| c.is_ascii_digit()))
+            })
+    };
+    let decimal = match (field.rfind('.'), field.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) if lone_decimal('.') => Some('.'),
+        (None, Some(_)) if lone_decimal(',') => Some(','),
+        _ => None,
+    };
+    let normalized: String = field
+        .chars()
+        .filter_map(|c| match c {
+            '.' | ',' if Some(c) == decimal => Some('.'),
+            '.' | ',' => None,
+            c => Some(c),
+        })
+        .collect();
+    parse_price(&normalized)
+}
+
+/// Outcome of a holdings import: rows added and a message per skipped row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HoldingsImport {
+    pub imported: usize,
+    pub errors: Vec<String>,
 }
 
+/// Read in place of the active list by `current_watchlist` and
+/// `current_portfolio` when a config has none.
+static NO_WATCHLIST: Watchlist = Watchlist {
+    name: String::new(),
+    symbols: Vec::new(),
+    sort_column: None,
+    sort_direction: SortDirection::Ascending,
+};
+static NO_PORTFOLIO: Portfolio = Portfolio {
+    name: String::new(),
+    holdings: Vec::new(),
+    transactions: Vec::new(),
+    targets: BTreeMap::new(),
+};
+
+/// Layout version written by this build. Bump it and add a step to
+/// `Config::migrate` whenever fields are renamed or reshaped.
+pub const CONFIG_SCHEMA_VERSION: u32 = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Format of this file; configs written before versioning count as 1
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub watchlists: Vec<Watchlist>,
     #[serde(default)]
     pub active_watchlist: usize,
     #[serde(default = "default_refresh_interval")]
     pub refresh_interval_secs: u64,
+    /// Trading session start and end as "HH:MM" WIB; quotes stop
+    /// auto-refreshing outside it and on weekends
+    #[serde(default = "default_market_open")]
+    pub market_open: String,
+    #[serde(default = "default_market_close")]
+    pub market_close: String,
     /// Old format field — consumed during deserialization for migration
     #[serde(default, skip_serializing)]
     portfolio: Vec<Holding>,
@@ -179,22 +721,241 @@ pub struct Config {
     pub portfolios: Vec<Portfolio>,
     #[serde(default)]
     pub active_portfolio: usize,
+    /// Watchlist opened at startup by name, overriding the last active one
+    #[serde(default)]
+    pub default_watchlist_name: Option<String>,
+    /// Portfolio opened at startup by name, overriding the last active one
+    #[serde(default)]
+    pub default_portfolio_name: Option<String>,
+    /// View shown at startup: "watchlist", "portfolio" or "news"; saved on quit
+    #[serde(default = "default_last_view_mode")]
+    pub last_view_mode: String,
+    /// Row selected in the watchlist table when the app last quit
+    #[serde(default)]
+    pub last_watchlist_selected: usize,
+    /// Row selected in the portfolio table when the app last quit
+    #[serde(default)]
+    pub last_portfolio_selected: usize,
     #[serde(default = "default_news_sources")]
     pub news_sources: Vec<String>,
+    /// Category per feed URL in `news_sources`, e.g. "Market" or "General";
+    /// colors the Source cell and drives the category filter
+    #[serde(default = "default_news_categories")]
+    pub news_categories: BTreeMap<String, String>,
+    /// Seconds between automatic news feed refreshes
+    #[serde(default = "default_news_refresh_secs")]
+    pub news_refresh_secs: u64,
+    /// Headlines kept in memory, newest first (0 = no limit)
+    #[serde(default = "default_news_max_items")]
+    pub news_max_items: usize,
     #[serde(default = "default_alerts")]
     pub alerts: Vec<Alert>,
     #[serde(default = "default_bookmarks")]
     pub bookmarks: Vec<Bookmark>,
+    /// Give up on an HTTP request after this many seconds
+    #[serde(default = "default_http_timeout_secs")]
+    pub http_timeout_secs: u64,
+    /// Proxy URL for all requests; when unset the usual proxy environment
+    /// variables apply
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// Reuse on-disk quotes younger than this many seconds (0 = always refetch)
+    #[serde(default)]
+    pub quote_cache_ttl_secs: u64,
+    /// Dim table rows whose quote was fetched longer ago than this many
+    /// seconds (0 = never dim)
+    #[serde(default = "default_stale_quote_secs")]
+    pub stale_quote_secs: u64,
+    /// Normal-mode key overrides: action name (`"toggle_view"`) to key
+    /// (`"v"`, `"F5"`). Actions left out keep their built-in keys.
+    #[serde(default)]
+    pub key_bindings: BTreeMap<String, String>,
+    /// How many gainers and losers the top movers popup lists
+    #[serde(default = "default_top_movers_count")]
+    pub top_movers_count: usize,
+    /// Symbols always shown above the watchlist table, outside its sort and
+    /// filter (e.g. `^JKSE` for the index)
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    /// Show an intraday mini-trend column in the watchlist (one extra chart
+    /// request per symbol every few minutes)
+    #[serde(default)]
+    pub watchlist_sparklines: bool,
+    /// Watchlist column indices to show, in display order. `None` shows
+    /// every column in the built-in order.
+    #[serde(default)]
+    pub watchlist_columns: Option<Vec<usize>>,
+    /// Portfolio column indices to show, in display order
+    #[serde(default)]
+    pub portfolio_columns: Option<Vec<usize>>,
+    /// Built-in theme name: "default", "mono", or "custom" to use `custom_theme`
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    #[serde(default)]
+    pub custom_theme: Theme,
+    /// Percentage moves smaller than this are drawn in the neutral color
+    #[serde(default = "default_change_neutral_band_pct")]
+    pub change_neutral_band_pct: f64,
+    /// Percentage moves at least this large are drawn in the bright color
+    #[serde(default = "default_change_strong_pct")]
+    pub change_strong_pct: f64,
+    /// Shade the change% cell background by the size of the move. Off with
+    /// the "mono" theme, whose palette has no gain/loss hues to shade with
+    #[serde(default)]
+    pub change_heatmap: bool,
+    /// Move (in percent) at which the heatmap reaches full intensity
+    #[serde(default = "default_heatmap_max_pct")]
+    pub heatmap_max_pct: f64,
+    /// Volume at least this many times the average is highlighted; 0 disables
+    #[serde(default = "default_volume_spike_ratio")]
+    pub volume_spike_ratio: f64,
+    /// Number formatting for prices and abbreviated values: "En" or "Id"
+    #[serde(default)]
+    pub number_locale: NumberLocale,
+    /// Decimal places for prices on screen (whole prices from 1,000 up drop them)
+    #[serde(default = "default_decimals")]
+    pub price_decimals: u8,
+    /// Decimal places for percentages on screen
+    #[serde(default = "default_decimals")]
+    pub percent_decimals: u8,
+    /// Table chrome: "Normal" (bordered) or "Compact" (borderless, toggled with `z`)
+    #[serde(default)]
+    pub density: Density,
+    /// Ring the terminal bell when an alert fires
+    #[serde(default = "default_alert_sound")]
+    pub alert_sound: bool,
+    /// Show a desktop notification when an alert fires, through the app's
+    /// `AlertNotifier` (notify-rust in the binary)
+    #[serde(default = "default_alert_desktop_notifications")]
+    pub alert_desktop_notifications: bool,
+    /// Shares per lot for holdings and trade sizes (100 on IDX)
+    #[serde(default = "default_lot_size")]
+    pub lot_size: u32,
+    /// Leave watchlist rows out while their first quote is still loading
+    /// instead of showing placeholders
+    #[serde(default)]
+    pub hide_loading_rows: bool,
+    /// Moving past the last row selects the first and vice versa, instead
+    /// of stopping at the ends
+    #[serde(default)]
+    pub wrap_navigation: bool,
+    /// Write export numbers the way the UI shows them (grouped, B/M
+    /// suffixes) instead of raw floats
+    #[serde(default)]
+    pub export_human_numbers: bool,
+    /// Look a symbol up before adding it and refuse ones with no quote
+    #[serde(default)]
+    pub validate_symbols: bool,
+    /// Ask for y/n confirmation before `d`/`D` delete a stock, holding or list
+    #[serde(default = "default_confirm_deletes")]
+    pub confirm_deletes: bool,
+    /// File this config was loaded from; `save()` writes back to it
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Set when the file on disk was corrupt and replaced by defaults,
+    /// describing where the bad file was moved
+    #[serde(skip)]
+    pub load_warning: Option<String>,
+}
+
+fn legacy_schema_version() -> u32 {
+    1
 }
 
 fn default_refresh_interval() -> u64 {
     1
 }
 
+fn default_http_timeout_secs() -> u64 {
+    DEFAULT_HTTP_TIMEOUT_SECS
+}
+
+fn default_stale_quote_secs() -> u64 {
+    300
+}
+
+fn default_top_movers_count() -> usize {
+    5
+}
+
+fn default_decimals() -> u8 {
+    crate::ui::formatters::DEFAULT_DECIMALS
+}
+
+fn default_market_open() -> String {
+    "09:00".to_string()
+}
+
+fn default_market_close() -> String {
+    "16:00".to_string()
+}
+
+fn default_last_view_mode() -> String {
+    "watchlist".to_string()
+}
+
+fn default_theme() -> String {
+    "default".to_string()
+}
+
+fn default_change_neutral_band_pct() -> f64 {
+    0.1
+}
+
+fn default_change_strong_pct() -> f64 {
+    3.0
+}
+
+fn default_heatmap_max_pct() -> f64 {
+    7.0
+}
+
+fn default_volume_spike_ratio() -> f64 {
+    2.0
+}
+
+fn default_alert_sound() -> bool {
+    true
+}
+
+fn default_lot_size() -> u32 {
+    100
+}
+
+fn default_confirm_deletes() -> bool {
+    true
+}
+
+fn default_alert_desktop_notifications() -> bool {
+    true
+}
+
+/// Write `content` to `path` through a `<name>.tmp` sibling that is synced
+/// and renamed over it, so a crash or full disk mid-write leaves the old
+/// file intact.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let written = fs::File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 fn default_portfolios() -> Vec<Portfolio> {
     vec![Portfolio {
         name: "Default".to_string(),
         holdings: Vec::new(),
+        transactions: Vec::new(),
+        targets: BTreeMap::new(),
     }]
 }
 
@@ -207,9 +968,30 @@ fn default_news_sources() -> Vec<String> {
     ]
 }
 
+fn default_news_refresh_secs() -> u64 {
+    300
+}
+
+fn default_news_max_items() -> usize {
+    500
+}
+
+fn default_news_categories() -> BTreeMap<String, String> {
+    [
+        ("https://www.cnbcindonesia.com/market/rss", "Market"),
+        ("https://www.cnbcindonesia.com/news/rss", "General"),
+        ("https://www.idxchannel.com/rss", "Market"),
+        ("https://rss.tempo.co/bisnis", "Business"),
+    ]
+    .into_iter()
+    .map(|(url, category)| (url.to_string(), category.to_string()))
+    .collect()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            schema_version: CONFIG_SCHEMA_VERSION,
             watchlists: vec![
                 Watchlist {
                     name: "Banking".to_string(),
@@ -219,10 +1001,14 @@ impl Default for Config {
                         "BMRI".to_string(),
                         "BBNI".to_string(),
                     ],
+                    sort_column: None,
+                    sort_direction: SortDirection::default(),
                 },
                 Watchlist {
                     name: "Tech".to_string(),
                     symbols: vec!["TLKM".to_string(), "GOTO".to_string(), "BUKA".to_string()],
+                    sort_column: None,
+                    sort_direction: SortDirection::default(),
                 },
                 Watchlist {
                     name: "Mining".to_string(),
@@ -232,19 +1018,59 @@ impl Default for Config {
                         "INCO".to_string(),
                         "PTBA".to_string(),
                     ],
+                    sort_column: None,
+                    sort_direction: SortDirection::default(),
                 },
             ],
             active_watchlist: 0,
             refresh_interval_secs: default_refresh_interval(),
+            market_open: default_market_open(),
+            market_close: default_market_close(),
             portfolio: Vec::new(),
-            portfolios: vec![Portfolio {
-                name: "Default".to_string(),
-                holdings: Vec::new(),
-            }],
+            portfolios: default_portfolios(),
             active_portfolio: 0,
+            last_view_mode: default_last_view_mode(),
+            last_watchlist_selected: 0,
+            last_portfolio_selected: 0,
             news_sources: default_news_sources(),
+            news_categories: default_news_categories(),
+            news_refresh_secs: default_news_refresh_secs(),
+            news_max_items: default_news_max_items(),
             alerts: default_alerts(),
             bookmarks: default_bookmarks(),
+            http_timeout_secs: default_http_timeout_secs(),
+            http_proxy: None,
+            quote_cache_ttl_secs: 0,
+            stale_quote_secs: default_stale_quote_secs(),
+            key_bindings: BTreeMap::new(),
+            default_watchlist_name: None,
+            default_portfolio_name: None,
+            top_movers_count: default_top_movers_count(),
+            pinned: Vec::new(),
+            watchlist_sparklines: false,
+            watchlist_columns: None,
+            portfolio_columns: None,
+            theme: default_theme(),
+            custom_theme: Theme::default(),
+            change_neutral_band_pct: default_change_neutral_band_pct(),
+            change_strong_pct: default_change_strong_pct(),
+            change_heatmap: false,
+            heatmap_max_pct: default_heatmap_max_pct(),
+            volume_spike_ratio: default_volume_spike_ratio(),
+            number_locale: NumberLocale::default(),
+            price_decimals: default_decimals(),
+            percent_decimals: default_decimals(),
+            density: Density::default(),
+            alert_sound: default_alert_sound(),
+            alert_desktop_notifications: default_alert_desktop_notifications(),
+            lot_size: default_lot_size(),
+            hide_loading_rows: false,
+            wrap_navigation: false,
+            export_human_numbers: false,
+            validate_symbols: false,
+            confirm_deletes: default_confirm_deletes(),
+            path: None,
+            load_warning: None,
         }
     }
 }
@@ -262,43 +1088,280 @@ impl Config {
         Ok(config_dir.join("config.json"))
     }
 
+    /// Validate a user-supplied config path and create its parent directory.
+    fn prepare_path(path: PathBuf) -> Result<PathBuf> {
+        if path.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Config path {} is a directory, expected a file",
+                path.display()
+            ));
+        }
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+            && !parent.exists()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(path)
+    }
+
     pub fn load() -> Result<Self> {
-        let path = Self::config_path()?;
+        Self::load_from(None)
+    }
+
+    /// Load from `path`, or the default location when `None`.
+    /// A missing file is created with defaults.
+    pub fn load_from(path: Option<PathBuf>) -> Result<Self> {
+        let path = match path {
+            Some(p) => Self::prepare_path(p)?,
+            None => Self::config_path()?,
+        };
 
         if !path.exists() {
-            let config = Config::default();
+            let config = Config {
+                path: Some(path),
+                ..Config::default()
+            };
             config.save()?;
             return Ok(config);
         }
 
         let content = fs::read_to_string(&path)?;
-        let mut config: Config = serde_json::from_str(&content)?;
-        if config.watchlists.is_empty() {
-            config.watchlists.push(Watchlist::default());
+        let (mut config, error) = Self::parse_or_default(&content);
+        if let Some(error) = error {
+            return Self::recover_corrupt(path, &error);
         }
-        if config.active_watchlist >= config.watchlists.len() {
-            config.active_watchlist = 0;
-        }
-        // Migrate old flat portfolio → portfolios
-        config.migrate_portfolio();
-        if config.migrate_news_sources() {
+        config.check_schema_version(&path)?;
+        config.path = Some(path);
+        if config.normalize() {
             let _ = config.save();
         }
         Ok(config)
     }
 
-    pub fn save(&self) -> Result<()> {
-        let path = Self::config_path()?;
+    /// Parse config JSON. Content that doesn't parse yields the defaults
+    /// together with the parse error.
+    pub fn parse_or_default(content: &str) -> (Self, Option<String>) {
+        match serde_json::from_str(content) {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(e.to_string())),
+        }
+    }
+
+    /// Move an unparseable config at `path` aside to
+    /// `config.json.bak-<timestamp>` and start over from defaults, so a
+    /// truncated write doesn't keep the app from starting.
+    fn recover_corrupt(path: PathBuf, error: &str) -> Result<Self> {
+        let mut backup = path.clone().into_os_string();
+        backup.push(format!(
+            ".bak-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let backup = PathBuf::from(backup);
+        fs::rename(&path, &backup)?;
+        let warning = format!(
+            "Config was unreadable ({}); moved to {} and reset to defaults",
+            error,
+            backup.display()
+        );
+        let config = Config {
+            path: Some(path),
+            load_warning: Some(warning),
+            ..Config::default()
+        };
+        config.save()?;
+        Ok(config)
+    }
+
+    /// Repair and migrate a freshly deserialized config: upgrade old
+    /// formats, ensure a watchlist and portfolio exist, and clamp active
+    /// indices. Returns true if migrations ran and the file should be rewritten.
+    fn normalize(&mut self) -> bool {
+        let migrated = self.migrate();
+        self.clamp_indices();
+        migrated
+    }
+
+    /// Ensure at least one watchlist and portfolio exist and that the active
+    /// indices point at one, falling back to the first list.
+    pub fn clamp_indices(&mut self) {
+        if self.watchlists.is_empty() {
+            self.watchlists.push(Watchlist::default());
+        }
+        if self.active_watchlist >= self.watchlists.len() {
+            self.active_watchlist = 0;
+        }
+        if self.portfolios.is_empty() {
+            self.portfolios = default_portfolios();
+        }
+        if self.active_portfolio >= self.portfolios.len() {
+            self.active_portfolio = 0;
+        }
+    }
+
+    /// Make the `default_watchlist_name` / `default_portfolio_name` lists
+    /// active. Names match case-insensitively; a name that matches no list
+    /// opens the first one. Unset names leave the active list alone.
+    pub fn apply_default_lists(&mut self) {
+        if let Some(name) = &self.default_watchlist_name {
+            self.active_watchlist = self.watchlist_index(name).unwrap_or(0);
+        }
+        if let Some(name) = &self.default_portfolio_name {
+            self.active_portfolio = self.portfolio_index(name).unwrap_or(0);
+        }
+    }
+
+    /// Index of the watchlist named `name`, ignoring case and surrounding
+    /// whitespace.
+    pub fn watchlist_index(&self, name: &str) -> Option<usize> {
+        self.watchlists
+            .iter()
+            .position(|w| w.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Index of the portfolio named `name`, ignoring case and surrounding
+    /// whitespace.
+    pub fn portfolio_index(&self, name: &str) -> Option<usize> {
+        self.portfolios
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Run each version step from `schema_version` up to
+    /// `CONFIG_SCHEMA_VERSION`. Returns true if any step ran.
+    pub fn migrate(&mut self) -> bool {
+        let start = self.schema_version;
+        while self.schema_version < CONFIG_SCHEMA_VERSION {
+            match self.schema_version {
+                // 1 → 2: flat `portfolio` into `portfolios`, dead RSS feeds
+                0 | 1 => {
+                    self.migrate_portfolio();
+                    self.migrate_news_sources();
+                    self.schema_version = 2;
+                }
+                // 2 → 3: Rel Vol column inserted before News and Trend
+                2 => {
+                    self.migrate_watchlist_columns();
+                    self.schema_version = 3;
+                }
+                _ => unreachable!("no migration from schema {}", self.schema_version),
+            }
+        }
+        self.schema_version != start
+    }
+
+    /// Refuse configs written by a newer build: saving them here would drop
+    /// fields this version doesn't know about.
+    fn check_schema_version(&self, path: &Path) -> Result<()> {
+        if self.schema_version > CONFIG_SCHEMA_VERSION {
+            bail!(
+                "{} uses config schema {} but this idx-cli only supports up to {}; please upgrade",
+                path.display(),
+                self.schema_version,
+                CONFIG_SCHEMA_VERSION
+            );
+        }
+        Ok(())
+    }
+
+    /// Write everything (watchlists, portfolios, alerts, bookmarks, news
+    /// sources and settings) to a single JSON backup file.
+    pub fn export_backup(&self, path: &Path) -> Result<()> {
         let content = serde_json::to_string_pretty(self)?;
-        fs::write(&path, content)?;
+        fs::write(path, content)?;
         Ok(())
     }
 
+    /// Replace this config with the backup at `path`, migrated like a loaded
+    /// config, and save it to this config's own file. A backup that doesn't
+    /// parse as a config is rejected and leaves this config untouched.
+    pub fn import_backup(&mut self, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        let mut restored: Config = match serde_json::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => bail!("{} is not a valid idx-cli backup: {}", path.display(), e),
+        };
+        restored.check_schema_version(path)?;
+        restored.normalize();
+        restored.path = self.path.take();
+        *self = restored;
+        self.save()
+    }
+
+    fn resolved_path(&self) -> Result<PathBuf> {
+        match &self.path {
+            Some(p) => Ok(p.clone()),
+            None => Self::config_path(),
+        }
+    }
+
+    /// Path for an auxiliary data file stored next to config.json.
+    pub fn data_file_path(&self, file_name: &str) -> Result<PathBuf> {
+        Ok(self.resolved_path()?.with_file_name(file_name))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = self.resolved_path()?;
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&path, &content)
+    }
+
+    /// Parsed `market_open`/`market_close`; an unparseable time falls back to
+    /// the 09:00–16:00 default.
+    pub fn trading_hours(&self) -> (NaiveTime, NaiveTime) {
+        let parse = |value: &str, default: String| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(&default, "%H:%M"))
+                .unwrap_or(NaiveTime::MIN)
+        };
+        (
+            parse(&self.market_open, default_market_open()),
+            parse(&self.market_close, default_market_close()),
+        )
+    }
+
+    /// How prices and percentages are written, from the locale and decimals.
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            locale: self.number_locale,
+            price_decimals: self.price_decimals,
+            percent_decimals: self.percent_decimals,
+        }
+    }
+
+    /// Resolve the active color theme; unknown names fall back to the default.
+    pub fn theme(&self) -> Theme {
+        match self.theme.as_str() {
+            "mono" => Theme::mono(),
+            "custom" => self.custom_theme.clone(),
+            _ => Theme::default(),
+        }
+    }
+
+    /// Highlights to draw with `theme()`. The heatmap stays off under the
+    /// "mono" theme, which is meant to be free of gain/loss hues.
+    pub fn highlights(&self) -> Highlights {
+        Highlights {
+            neutral_band_pct: self.change_neutral_band_pct,
+            strong_pct: self.change_strong_pct,
+            heatmap: self.change_heatmap && self.theme != "mono",
+            heatmap_max_pct: self.heatmap_max_pct,
+            volume_spike_ratio: self.volume_spike_ratio,
+            change_arrows: false,
+        }
+    }
+
+    /// The active watchlist. An out-of-range `active_watchlist` reads as
+    /// the first list, and a config without lists as an empty one.
     pub fn current_watchlist(&self) -> &Watchlist {
-        &self.watchlists[self.active_watchlist]
+        self.watchlists
+            .get(self.active_watchlist)
+            .or_else(|| self.watchlists.first())
+            .unwrap_or(&NO_WATCHLIST)
     }
 
     pub fn current_watchlist_mut(&mut self) -> &mut Watchlist {
+        self.clamp_indices();
         &mut self.watchlists[self.active_watchlist]
     }
 
@@ -326,6 +1389,28 @@ impl Config {
         }
     }
 
+    /// Swap the symbol at `index` with the one above it. Returns false at
+    /// the top or out of range.
+    pub fn move_symbol_up(&mut self, index: usize) -> bool {
+        let symbols = &mut self.current_watchlist_mut().symbols;
+        if index == 0 || index >= symbols.len() {
+            return false;
+        }
+        symbols.swap(index - 1, index);
+        true
+    }
+
+    /// Swap the symbol at `index` with the one below it. Returns false at
+    /// the bottom or out of range.
+    pub fn move_symbol_down(&mut self, index: usize) -> bool {
+        let symbols = &mut self.current_watchlist_mut().symbols;
+        if index + 1 >= symbols.len() {
+            return false;
+        }
+        symbols.swap(index, index + 1);
+        true
+    }
+
     pub fn remove_stock(&mut self, symbol: &str) {
         let symbol = symbol.to_uppercase();
         self.current_watchlist_mut()
@@ -337,8 +1422,93 @@ impl Config {
         self.watchlists.push(Watchlist {
             name: name.to_string(),
             symbols: Vec::new(),
+            sort_column: None,
+            sort_direction: SortDirection::default(),
+        });
+        self.active_watchlist = self.watchlists.len() - 1;
+    }
+
+    /// Create and switch to a watchlist holding every symbol of the current
+    /// portfolio. Returns the number of symbols; an empty portfolio creates
+    /// nothing.
+    pub fn watchlist_from_portfolio(&mut self, name: &str) -> usize {
+        let symbols: Vec<String> = self
+            .current_portfolio()
+            .holdings
+            .iter()
+            .map(|h| h.symbol.clone())
+            .collect();
+        if symbols.is_empty() {
+            return 0;
+        }
+        self.add_watchlist(name);
+        for symbol in &symbols {
+            self.add_stock(symbol);
+        }
+        self.current_watchlist().symbols.len()
+    }
+
+    /// Create and switch to a portfolio with a zero-lot holding for every
+    /// symbol of the current watchlist, to fill in with `e`. Returns the
+    /// number of holdings; an empty watchlist creates nothing.
+    pub fn portfolio_from_watchlist(&mut self, name: &str) -> usize {
+        let symbols = self.current_watchlist().symbols.clone();
+        if symbols.is_empty() {
+            return 0;
+        }
+        self.add_portfolio(name);
+        for symbol in &symbols {
+            self.add_holding(symbol, 0, 0.0);
+        }
+        self.current_portfolio().holdings.len()
+    }
+
+    /// Create a watchlist from a file of symbols (one per line, or a CSV with a
+    /// `Symbol` column), uppercased and deduplicated, then save. Returns the
+    /// number of symbols imported.
+    pub fn import_watchlist_from_csv(&mut self, path: &Path, name: &str) -> Result<usize> {
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty()).peekable();
+
+        let split = |line: &str| split_csv_line(line, ',');
+
+        // A header row with a "Symbol" column selects that column; otherwise
+        // take the first field of every line.
+        let mut column = 0;
+        if let Some(first) = lines.peek()
+            && let Some(idx) = split(first)
+                .iter()
+                .position(|f| f.eq_ignore_ascii_case("symbol"))
+        {
+            column = idx;
+            lines.next();
+        }
+
+        let mut symbols: Vec<String> = Vec::new();
+        for line in lines {
+            let Some(field) = split(line).into_iter().nth(column) else {
+                continue;
+            };
+            let symbol = field.to_uppercase();
+            if !symbol.is_empty() && !symbols.contains(&symbol) {
+                symbols.push(symbol);
+            }
+        }
+
+        if symbols.is_empty() {
+            bail!("No symbols found in {}", path.display());
+        }
+
+        let count = symbols.len();
+        self.watchlists.push(Watchlist {
+            name: name.to_string(),
+            symbols,
+            sort_column: None,
+            sort_direction: SortDirection::default(),
         });
         self.active_watchlist = self.watchlists.len() - 1;
+        self.save()?;
+        Ok(count)
     }
 
     pub fn remove_watchlist(&mut self) {
@@ -354,11 +1524,16 @@ impl Config {
         self.current_watchlist_mut().name = new_name.to_string();
     }
 
+    /// The active portfolio, read like `current_watchlist`.
     pub fn current_portfolio(&self) -> &Portfolio {
-        &self.portfolios[self.active_portfolio]
+        self.portfolios
+            .get(self.active_portfolio)
+            .or_else(|| self.portfolios.first())
+            .unwrap_or(&NO_PORTFOLIO)
     }
 
     pub fn current_portfolio_mut(&mut self) -> &mut Portfolio {
+        self.clamp_indices();
         &mut self.portfolios[self.active_portfolio]
     }
 
@@ -382,6 +1557,8 @@ impl Config {
         self.portfolios.push(Portfolio {
             name: name.to_string(),
             holdings: Vec::new(),
+            transactions: Vec::new(),
+            targets: BTreeMap::new(),
         });
         self.active_portfolio = self.portfolios.len() - 1;
     }
@@ -399,9 +1576,13 @@ impl Config {
         self.current_portfolio_mut().name = new_name.to_string();
     }
 
+    /// Alerts on `symbol`, matching `BBCA`, `bbca` and `BBCA.JK` alike.
     pub fn alerts_for_symbol(&self, symbol: &str) -> Vec<&Alert> {
-        let sym = symbol.to_uppercase();
-        self.alerts.iter().filter(|a| a.symbol == sym).collect()
+        let key = display_symbol(&symbol.to_uppercase());
+        self.alerts
+            .iter()
+            .filter(|a| display_symbol(&a.symbol.to_uppercase()) == key)
+            .collect()
     }
 
     pub fn add_alert(&mut self, alert: Alert) {
@@ -430,8 +1611,7 @@ impl Config {
     }
 
     pub fn has_active_alerts(&self, symbol: &str) -> bool {
-        let sym = symbol.to_uppercase();
-        self.alerts.iter().any(|a| a.symbol == sym && a.enabled)
+        self.alerts_for_symbol(symbol).iter().any(|a| a.enabled)
     }
 
     /// Check if an article is bookmarked by matching headline and url.
@@ -476,9 +1656,12 @@ impl Config {
         }
     }
 
-    /// Add a new holding or merge into an existing one.
+    /// Add a new holding or merge into an existing one. Zero lots add a
+    /// placeholder without recording a buy.
     pub fn add_holding(&mut self, symbol: &str, lots: u32, avg_price: f64) -> bool {
         let symbol = symbol.to_uppercase();
+        let lot_size = self.lot_size;
+        self.seed_opening_transaction(&symbol);
         // Check if holding exists, update it
         if let Some(holding) = self
             .current_portfolio_mut()
@@ -490,27 +1673,173 @@ impl Config {
                 Some(t) => t,
                 None => return false,
             };
-            let total_cost = holding.cost_basis() + (lots as u64 * 100) as f64 * avg_price;
-            holding.avg_price = total_cost / (total_lots as u64 * 100) as f64;
+            let total_cost =
+                holding.cost_basis(lot_size) + lots_to_shares(lots, lot_size) as f64 * avg_price;
+            if total_lots > 0 {
+                holding.avg_price = total_cost / lots_to_shares(total_lots, lot_size) as f64;
+            }
             holding.lots = total_lots;
         } else {
             self.current_portfolio_mut().holdings.push(Holding {
-                symbol,
+                symbol: symbol.clone(),
                 lots,
                 avg_price,
+                note: None,
             });
         }
+        if lots > 0 {
+            self.record_transaction(&symbol, TransactionKind::Buy, lots, avg_price);
+        }
         true
     }
 
-    pub fn remove_holding(&mut self, symbol: &str) {
+    /// Add every row of a broker holdings CSV to the active portfolio through
+    /// `add_holding`, so repeated symbols merge into one weighted average.
+    /// The header row picks the columns (`,` or `;` separated, fields may be
+    /// quoted) and prices may use a decimal comma; a bad row is reported and
+    /// skipped. Saves when anything was imported.
+    pub fn import_holdings_from_csv(
+        &mut self,
+        path: &Path,
+        columns: &HoldingsColumns,
+    ) -> Result<HoldingsImport> {
+        let content = fs::read_to_string(path)?;
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            bail!("{} is empty", path.display());
+        };
+        let delimiter = if header.contains(';') && !header.contains(',') {
+            ';'
+        } else {
+            ','
+        };
+        let split = |line: &str| split_csv_line(line, delimiter);
+
+        let header = split(header);
+        let find = |name: &Option<String>, fallbacks: &[&str], what: &str| -> Result<usize> {
+            let position = match name {
+                Some(name) => header.iter().position(|h| h.eq_ignore_ascii_case(name)),
+                None => header
+                    .iter()
+                    .position(|h| fallbacks.iter().any(|f| h.eq_ignore_ascii_case(f))),
+            };
+            match (position, name) {
+                (Some(idx), _) => Ok(idx),
+                (None, Some(name)) => bail!("No '{}' column in {}", name, path.display()),
+                (None, None) => bail!("No {} column in {}", what, path.display()),
+            }
+        };
+        let symbol_col = find(&columns.symbol, SYMBOL_HEADERS, "symbol")?;
+        let lots_col = find(&columns.lots, LOTS_HEADERS, "lots")?;
+        let price_col = find(&columns.avg_price, AVG_PRICE_HEADERS, "average price")?;
+
+        let mut result = HoldingsImport::default();
+        for (idx, line) in lines {
+            let row = idx + 1;
+            let fields = split(line);
+            let field = |col: usize| fields.get(col).map(String::as_str).unwrap_or("");
+
+            let symbol = field(symbol_col).to_uppercase();
+            if symbol.is_empty() {
+                result.errors.push(format!("Row {}: missing symbol", row));
+                continue;
+            }
+            let lots = match field(lots_col).parse::<u32>() {
+                Ok(lots) if lots > 0 => lots,
+                _ => {
+                    result.errors.push(format!(
+                        "Row {} ({}): lots '{}' is not a positive whole number",
+                        row,
+                        symbol,
+                        field(lots_col)
+                    ));
+                    continue;
+                }
+            };
+            let avg_price = match parse_csv_price(field(price_col)) {
+                Some(price) if price.is_finite() && price > 0.0 => price,
+                _ => {
+                    result.errors.push(format!(
+                        "Row {} ({}): price '{}' is not a positive number",
+                        row,
+                        symbol,
+                        field(price_col)
+                    ));
+                    continue;
+                }
+            };
+            if self.add_holding(&symbol, lots, avg_price) {
+                result.imported += 1;
+            } else {
+                result
+                    .errors
+                    .push(format!("Row {} ({}): too many lots", row, symbol));
+            }
+        }
+
+        if result.imported > 0 {
+            self.save()?;
+        }
+        Ok(result)
+    }
+
+    /// Remove a holding, recording a sell of all its lots at `sell_price`
+    /// (the holding's average price when no market price is known).
+    pub fn remove_holding(&mut self, symbol: &str, sell_price: Option<f64>) {
         let symbol = symbol.to_uppercase();
+        let Some(holding) = self.find_holding(&symbol).cloned() else {
+            return;
+        };
+        self.seed_opening_transaction(&symbol);
+        self.record_transaction(
+            &symbol,
+            TransactionKind::Sell,
+            holding.lots,
+            sell_price.unwrap_or(holding.avg_price),
+        );
         self.current_portfolio_mut()
             .holdings
             .retain(|h| h.symbol != symbol);
     }
 
-    pub fn update_holding(&mut self, symbol: &str, lots: u32, avg_price: f64) {
+    /// Overwrite a holding's lots and average price. A lot increase is
+    /// recorded as a buy at the implied price; a decrease as a sell at
+    /// `sell_price` (or the previous average price).
+    pub fn update_holding(
+        &mut self,
+        symbol: &str,
+        lots: u32,
+        avg_price: f64,
+        sell_price: Option<f64>,
+    ) {
+        let Some(old) = self.find_holding(symbol).cloned() else {
+            return;
+        };
+        self.seed_opening_transaction(symbol);
+        match lots.cmp(&old.lots) {
+            Ordering::Greater => {
+                let added = lots - old.lots;
+                let added_cost = lots as f64 * avg_price - old.lots as f64 * old.avg_price;
+                let price = if added_cost > 0.0 {
+                    added_cost / added as f64
+                } else {
+                    avg_price
+                };
+                self.record_transaction(symbol, TransactionKind::Buy, added, price);
+            }
+            Ordering::Less => {
+                self.record_transaction(
+                    symbol,
+                    TransactionKind::Sell,
+                    old.lots - lots,
+                    sell_price.unwrap_or(old.avg_price),
+                );
+            }
+            Ordering::Equal => {}
+        }
         if let Some(holding) = self
             .current_portfolio_mut()
             .holdings
@@ -522,6 +1851,177 @@ impl Config {
         }
     }
 
+    /// Set or clear (with an empty string) a holding's note. Returns false
+    /// if the holding is missing.
+    pub fn set_holding_note(&mut self, symbol: &str, note: &str) -> bool {
+        let note = note.trim();
+        match self
+            .current_portfolio_mut()
+            .holdings
+            .iter_mut()
+            .find(|h| h.symbol == symbol)
+        {
+            Some(holding) => {
+                holding.note = (!note.is_empty()).then(|| note.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set or clear (`None`) the target allocation of `symbol` in the
+    /// current portfolio.
+    pub fn set_target(&mut self, symbol: &str, target_pct: Option<f64>) {
+        let targets = &mut self.current_portfolio_mut().targets;
+        match target_pct {
+            Some(pct) => {
+                targets.insert(symbol.to_string(), pct);
+            }
+            None => {
+                targets.remove(symbol);
+            }
+        }
+    }
+
+    /// Apply a `ratio_num:ratio_den` split (e.g. 2:1 doubles lots) to a
+    /// holding, preserving its cost basis. The split is worked out in shares,
+    /// and the holding must come out as a whole, non-zero number of lots of
+    /// `lot_size`; otherwise nothing changes and the reason is returned.
+    /// Logged transactions are rescaled so FIFO stays consistent, rounding to
+    /// the nearest lot; returns how many of them had to be rounded.
+    pub fn apply_split(
+        &mut self,
+        symbol: &str,
+        ratio_num: u32,
+        ratio_den: u32,
+    ) -> Result<usize, String> {
+        if ratio_num == 0 || ratio_den == 0 {
+            return Err("ratio must not be zero".to_string());
+        }
+        let symbol = symbol.to_uppercase();
+        let Some(holding) = self.find_holding(&symbol) else {
+            return Err(format!("no {} holding", symbol));
+        };
+        let lot_size = self.lot_size.max(1) as u64;
+        let scaled = holding.shares(self.lot_size) * ratio_num as u64;
+        let per_lot = ratio_den as u64 * lot_size;
+        if scaled < per_lot {
+            return Err("it would leave less than one lot".to_string());
+        }
+        if !scaled.is_multiple_of(per_lot) {
+            return Err(format!(
+                "it would leave {:.2} lots, not a whole number",
+                scaled as f64 / per_lot as f64
+            ));
+        }
+        let new_lots = u32::try_from(scaled / per_lot).map_err(|_| "too many lots".to_string())?;
+        let cost = holding.cost_basis(self.lot_size);
+        let new_shares = holding.shares(self.lot_size) as f64 * ratio_num as f64 / ratio_den as f64;
+
+        let portfolio = self.current_portfolio_mut();
+        if let Some(holding) = portfolio.holdings.iter_mut().find(|h| h.symbol == symbol) {
+            holding.lots = new_lots;
+            holding.avg_price = cost / new_shares;
+        }
+        let mut rounded = 0;
+        for tx in portfolio
+            .transactions
+            .iter_mut()
+            .filter(|t| t.symbol == symbol)
+        {
+            let scaled = tx.lots as u64 * lot_size * ratio_num as u64;
+            if !scaled.is_multiple_of(per_lot) {
+                rounded += 1;
+            }
+            let lots = (scaled + per_lot / 2) / per_lot;
+            tx.lots = u32::try_from(lots).unwrap_or(u32::MAX);
+            tx.price = tx.price * ratio_den as f64 / ratio_num as f64;
+        }
+        Ok(rounded)
+    }
+
+    fn find_holding(&self, symbol: &str) -> Option<&Holding> {
+        self.current_portfolio()
+            .holdings
+            .iter()
+            .find(|h| h.symbol == symbol)
+    }
+
+    fn record_transaction(&mut self, symbol: &str, kind: TransactionKind, lots: u32, price: f64) {
+        self.current_portfolio_mut().transactions.push(Transaction {
+            symbol: symbol.to_string(),
+            kind,
+            lots,
+            price,
+            timestamp: chrono::Utc::now().timestamp(),
+        });
+    }
+
+    /// Holdings created before the transaction log existed have no buys on
+    /// record; add an opening buy at the average price so FIFO has lots to match.
+    fn seed_opening_transaction(&mut self, symbol: &str) {
+        let Some(holding) = self.find_holding(symbol).cloned() else {
+            return;
+        };
+        let logged: i64 = self
+            .current_portfolio()
+            .transactions
+            .iter()
+            .filter(|t| t.symbol == symbol)
+            .map(|t| match t.kind {
+                TransactionKind::Buy => t.lots as i64,
+                TransactionKind::Sell => -(t.lots as i64),
+            })
+            .sum();
+        let missing = holding.lots as i64 - logged;
+        if missing > 0 {
+            self.current_portfolio_mut().transactions.insert(
+                0,
+                Transaction {
+                    symbol: symbol.to_string(),
+                    kind: TransactionKind::Buy,
+                    lots: missing as u32,
+                    price: holding.avg_price,
+                    timestamp: 0,
+                },
+            );
+        }
+    }
+
+    /// Realized P/L for a symbol in the current portfolio, matching each
+    /// sell against the oldest remaining buy lots (FIFO).
+    pub fn realized_pl(&self, symbol: &str) -> f64 {
+        let mut open: VecDeque<(u32, f64)> = VecDeque::new();
+        let mut realized = 0.0;
+        for tx in self
+            .current_portfolio()
+            .transactions
+            .iter()
+            .filter(|t| t.symbol == symbol)
+        {
+            match tx.kind {
+                TransactionKind::Buy => open.push_back((tx.lots, tx.price)),
+                TransactionKind::Sell => {
+                    let mut remaining = tx.lots;
+                    while remaining > 0 {
+                        let Some(front) = open.front_mut() else {
+                            break;
+                        };
+                        let matched = remaining.min(front.0);
+                        realized +=
+                            (tx.price - front.1) * lots_to_shares(matched, self.lot_size) as f64;
+                        front.0 -= matched;
+                        remaining -= matched;
+                        if front.0 == 0 {
+                            open.pop_front();
+                        }
+                    }
+                }
+            }
+        }
+        realized
+    }
+
     pub fn portfolio_symbols(&self) -> Vec<String> {
         self.current_portfolio()
             .holdings
@@ -532,15 +2032,57 @@ impl Config {
 
     pub fn test_config() -> Self {
         Self {
+            schema_version: CONFIG_SCHEMA_VERSION,
             watchlists: vec![Watchlist::default()],
             active_watchlist: 0,
             refresh_interval_secs: 1,
+            market_open: default_market_open(),
+            market_close: default_market_close(),
             portfolio: Vec::new(),
             portfolios: default_portfolios(),
             active_portfolio: 0,
+            last_view_mode: default_last_view_mode(),
+            last_watchlist_selected: 0,
+            last_portfolio_selected: 0,
             news_sources: Vec::new(),
+            news_categories: BTreeMap::new(),
+            news_refresh_secs: default_news_refresh_secs(),
+            news_max_items: default_news_max_items(),
             alerts: Vec::new(),
             bookmarks: Vec::new(),
+            http_timeout_secs: default_http_timeout_secs(),
+            http_proxy: None,
+            quote_cache_ttl_secs: 0,
+            stale_quote_secs: default_stale_quote_secs(),
+            key_bindings: BTreeMap::new(),
+            default_watchlist_name: None,
+            default_portfolio_name: None,
+            top_movers_count: default_top_movers_count(),
+            pinned: Vec::new(),
+            watchlist_sparklines: false,
+            watchlist_columns: None,
+            portfolio_columns: None,
+            theme: default_theme(),
+            custom_theme: Theme::default(),
+            change_neutral_band_pct: default_change_neutral_band_pct(),
+            change_strong_pct: default_change_strong_pct(),
+            change_heatmap: false,
+            heatmap_max_pct: default_heatmap_max_pct(),
+            volume_spike_ratio: default_volume_spike_ratio(),
+            number_locale: NumberLocale::default(),
+            price_decimals: default_decimals(),
+            percent_decimals: default_decimals(),
+            density: Density::default(),
+            alert_sound: false,
+            alert_desktop_notifications: false,
+            lot_size: default_lot_size(),
+            hide_loading_rows: false,
+            wrap_navigation: false,
+            export_human_numbers: false,
+            validate_symbols: false,
+            confirm_deletes: default_confirm_deletes(),
+            path: None,
+            load_warning: None,
         }
     }
 
@@ -553,9 +2095,10 @@ impl Config {
                 self.portfolios.push(Portfolio {
                     name: "Imported".to_string(),
                     holdings: std::mem::take(&mut self.portfolio),
+                    transactions: Vec::new(),
+                    targets: BTreeMap::new(),
                 });
             }
-            let _ = self.save();
         }
         if self.portfolios.is_empty() {
             self.portfolios = default_portfolios();
@@ -565,18 +2108,24 @@ impl Config {
         }
     }
 
-    /// Replace dead RSS feeds with working alternatives. Returns true if changed.
-    fn migrate_news_sources(&mut self) -> bool {
+    /// Shift saved watchlist column indices past the Rel Vol column, which
+    /// took index 10 from News (now 11) and Trend (now 12).
+    fn migrate_watchlist_columns(&mut self) {
+        if let Some(columns) = &mut self.watchlist_columns {
+            for i in columns.iter_mut().filter(|i| **i >= 10) {
+                *i += 1;
+            }
+        }
+    }
+
+    /// Replace dead RSS feeds with working alternatives.
+    fn migrate_news_sources(&mut self) {
         const DEAD_KONTAN: &str = "https://www.kontan.co.id/rss/investasi";
         self.news_sources.retain(|u| u != DEAD_KONTAN);
-        let defaults = default_news_sources();
-        let mut changed = false;
-        for url in &defaults {
-            if !self.news_sources.contains(url) {
-                self.news_sources.push(url.clone());
-                changed = true;
+        for url in default_news_sources() {
+            if !self.news_sources.contains(&url) {
+                self.news_sources.push(url);
             }
         }
-        changed
     }
 }
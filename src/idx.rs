@@ -0,0 +1,85 @@
+//! IDX trading rules: auto-reject (ARA/ARB) price bands and tick sizes.
+
+/// Auto-reject percentage by previous close: `(upper bound, pct)`. A stock
+/// whose previous close is at or below the bound may move at most `pct`
+/// either way.
+pub const AUTO_REJECT_TIERS: &[(f64, f64)] =
+    &[(200.0, 35.0), (5_000.0, 25.0), (f64::INFINITY, 20.0)];
+
+/// Price fraction (tick size) by price level: `(upper bound, tick)`. Unlike
+/// the auto-reject tiers, a price at the bound takes the next tier's tick.
+pub const TICK_SIZES: &[(f64, f64)] = &[
+    (200.0, 1.0),
+    (500.0, 2.0),
+    (2_000.0, 5.0),
+    (5_000.0, 10.0),
+    (f64::INFINITY, 25.0),
+];
+
+/// Lowest price on the regular board; ARB never goes below it.
+pub const MIN_PRICE: f64 = 50.0;
+
+/// How close (in rupiah) a price must be to a limit to count as at it.
+const LIMIT_EPSILON: f64 = 0.5;
+
+/// Which auto-reject band a quote is sitting on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceLimit {
+    /// Auto-reject atas: the daily upper limit
+    Ara,
+    /// Auto-reject bawah: the daily lower limit
+    Arb,
+}
+
+impl PriceLimit {
+    pub fn marker(self) -> &'static str {
+        match self {
+            PriceLimit::Ara => "⇈",
+            PriceLimit::Arb => "⇊",
+        }
+    }
+}
+
+/// Value of the first tier whose upper bound satisfies `in_tier`.
+fn tier_value(table: &[(f64, f64)], in_tier: impl Fn(f64) -> bool) -> f64 {
+    table
+        .iter()
+        .find(|(upper, _)| in_tier(*upper))
+        .map_or(table[table.len() - 1].1, |(_, value)| *value)
+}
+
+/// Maximum daily move in percent for a stock that closed at `prev_close`.
+pub fn auto_reject_pct(prev_close: f64) -> f64 {
+    tier_value(AUTO_REJECT_TIERS, |upper| prev_close <= upper)
+}
+
+/// Tick size for orders at `price`.
+pub fn tick_size(price: f64) -> f64 {
+    tier_value(TICK_SIZES, |upper| price < upper)
+}
+
+/// `(arb, ara)` prices for today, rounded inward to a valid tick.
+pub fn auto_reject_limits(prev_close: f64) -> (f64, f64) {
+    let pct = auto_reject_pct(prev_close) / 100.0;
+    let upper = prev_close * (1.0 + pct);
+    let lower = prev_close * (1.0 - pct);
+    let ara = (upper / tick_size(upper)).floor() * tick_size(upper);
+    let arb = ((lower / tick_size(lower)).ceil() * tick_size(lower)).max(MIN_PRICE);
+    (arb, ara)
+}
+
+/// Whether `price` has hit today's ARA or ARB. Returns `None` without a
+/// usable previous close.
+pub fn price_limit(prev_close: f64, price: f64) -> Option<PriceLimit> {
+    if prev_close <= 0.0 || price <= 0.0 {
+        return None;
+    }
+    let (arb, ara) = auto_reject_limits(prev_close);
+    if price >= ara - LIMIT_EPSILON {
+        Some(PriceLimit::Ara)
+    } else if price <= arb + LIMIT_EPSILON && price < prev_close {
+        Some(PriceLimit::Arb)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,408 @@
+//! Normal-mode key bindings: which key triggers which action, with user
+//! overrides from the `key_bindings` config map.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+/// Something a key press does in the main tables. What an action means can
+/// still depend on the view (`add` adds a stock or a holding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    RefreshFaster,
+    RefreshSlower,
+    ToggleDensity,
+    Search,
+    QuickJump,
+    Edit,
+    ToggleView,
+    Add,
+    Bookmark,
+    TickerFilter,
+    CategoryFilter,
+    LoadMore,
+    NewsSources,
+    Delete,
+    ToggleRead,
+    Refresh,
+    MoveSymbolUp,
+    MoveSymbolDown,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    PrevList,
+    NextList,
+    NewList,
+    Columns,
+    CopySymbol,
+    CopySummary,
+    Undo,
+    RenameList,
+    DeleteList,
+    Open,
+    AllWatchlists,
+    SortColumn,
+    SortDirection,
+    AllocationChart,
+    ProfitLoss,
+    ValueHistory,
+    Note,
+    Split,
+    Alerts,
+    TopMovers,
+    Compare,
+    EventLog,
+    Rebalance,
+    OpenPage,
+    ConvertList,
+}
+
+impl Action {
+    /// Every action, in the order conflicts are resolved: when two actions
+    /// end up on the same key, the earlier one keeps it.
+    pub const ALL: &[Action] = &[
+        Action::Quit,
+        Action::Help,
+        Action::RefreshFaster,
+        Action::RefreshSlower,
+        Action::ToggleDensity,
+        Action::Search,
+        Action::QuickJump,
+        Action::Edit,
+        Action::ToggleView,
+        Action::Add,
+        Action::Bookmark,
+        Action::TickerFilter,
+        Action::CategoryFilter,
+        Action::LoadMore,
+        Action::NewsSources,
+        Action::Delete,
+        Action::ToggleRead,
+        Action::Refresh,
+        Action::MoveSymbolUp,
+        Action::MoveSymbolDown,
+        Action::Up,
+        Action::Down,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::PrevList,
+        Action::NextList,
+        Action::NewList,
+        Action::Columns,
+        Action::CopySymbol,
+        Action::CopySummary,
+        Action::Undo,
+        Action::RenameList,
+        Action::DeleteList,
+        Action::Open,
+        Action::AllWatchlists,
+        Action::SortColumn,
+        Action::SortDirection,
+        Action::AllocationChart,
+        Action::ProfitLoss,
+        Action::ValueHistory,
+        Action::Note,
+        Action::Split,
+        Action::Alerts,
+        Action::TopMovers,
+        Action::Compare,
+        Action::EventLog,
+        Action::Rebalance,
+        Action::OpenPage,
+        Action::ConvertList,
+    ];
+
+    /// Name used for the action in the `key_bindings` config map.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::RefreshFaster => "refresh_faster",
+            Action::RefreshSlower => "refresh_slower",
+            Action::ToggleDensity => "toggle_density",
+            Action::Search => "search",
+            Action::QuickJump => "quick_jump",
+            Action::Edit => "edit",
+            Action::ToggleView => "toggle_view",
+            Action::Add => "add",
+            Action::Bookmark => "bookmark",
+            Action::TickerFilter => "ticker_filter",
+            Action::CategoryFilter => "category_filter",
+            Action::LoadMore => "load_more",
+            Action::NewsSources => "news_sources",
+            Action::Delete => "delete",
+            Action::ToggleRead => "toggle_read",
+            Action::Refresh => "refresh",
+            Action::MoveSymbolUp => "move_symbol_up",
+            Action::MoveSymbolDown => "move_symbol_down",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::PrevList => "prev_list",
+            Action::NextList => "next_list",
+            Action::NewList => "new_list",
+            Action::Columns => "columns",
+            Action::CopySymbol => "copy_symbol",
+            Action::CopySummary => "copy_summary",
+            Action::Undo => "undo",
+            Action::RenameList => "rename_list",
+            Action::DeleteList => "delete_list",
+            Action::Open => "open",
+            Action::AllWatchlists => "all_watchlists",
+            Action::SortColumn => "sort_column",
+            Action::SortDirection => "sort_direction",
+            Action::AllocationChart => "allocation_chart",
+            Action::ProfitLoss => "profit_loss",
+            Action::ValueHistory => "value_history",
+            Action::Note => "note",
+            Action::Split => "split",
+            Action::Alerts => "alerts",
+            Action::TopMovers => "top_movers",
+            Action::Compare => "compare",
+            Action::EventLog => "event_log",
+            Action::Rebalance => "rebalance",
+            Action::OpenPage => "open_page",
+            Action::ConvertList => "convert_list",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|a| a.name() == name)
+    }
+
+    /// Built-in keys, used unless the config overrides the action.
+    pub fn default_keys(self) -> &'static [KeyCode] {
+        match self {
+            Action::Quit => &[KeyCode::Char('q')],
+            Action::Help => &[KeyCode::Char('?')],
+            Action::RefreshFaster => &[KeyCode::Char('+'), KeyCode::Char('=')],
+            Action::RefreshSlower => &[KeyCode::Char('-')],
+            Action::ToggleDensity => &[KeyCode::Char('z')],
+            Action::Search => &[KeyCode::Char('/')],
+            Action::QuickJump => &[KeyCode::Char('g')],
+            Action::Edit => &[KeyCode::Char('e')],
+            Action::ToggleView => &[KeyCode::Char('p')],
+            Action::Add => &[KeyCode::Char('a')],
+            Action::Bookmark => &[KeyCode::Char('b')],
+            Action::TickerFilter => &[KeyCode::Char('f')],
+            Action::CategoryFilter => &[KeyCode::Char('F')],
+            Action::LoadMore => &[KeyCode::Char('O')],
+            Action::NewsSources => &[KeyCode::Char('N')],
+            Action::Delete => &[KeyCode::Char('d')],
+            Action::ToggleRead => &[KeyCode::Char('m')],
+            Action::Refresh => &[KeyCode::Char('r')],
+            Action::MoveSymbolUp => &[KeyCode::Char('K')],
+            Action::MoveSymbolDown => &[KeyCode::Char('J')],
+            Action::Up => &[KeyCode::Up],
+            Action::Down => &[KeyCode::Down],
+            Action::PageUp => &[KeyCode::PageUp],
+            Action::PageDown => &[KeyCode::PageDown],
+            Action::Top => &[KeyCode::Home],
+            Action::Bottom => &[KeyCode::End],
+            Action::PrevList => &[KeyCode::Left, KeyCode::Char('h')],
+            Action::NextList => &[KeyCode::Right, KeyCode::Char('l')],
+            Action::NewList => &[KeyCode::Char('n')],
+            Action::Columns => &[KeyCode::Char('C')],
+            Action::CopySymbol => &[KeyCode::Char('y')],
+            Action::CopySummary => &[KeyCode::Char('Y')],
+            Action::Undo => &[KeyCode::Char('u')],
+            Action::RenameList => &[KeyCode::Char('R')],
+            Action::DeleteList => &[KeyCode::Char('D')],
+            Action::Open => &[KeyCode::Enter],
+            Action::AllWatchlists => &[KeyCode::Char('w')],
+            Action::SortColumn => &[KeyCode::Char('s')],
+            Action::SortDirection => &[KeyCode::Char('S')],
+            Action::AllocationChart => &[KeyCode::Char('c')],
+            Action::ProfitLoss => &[KeyCode::Char('P')],
+            Action::ValueHistory => &[KeyCode::Char('H')],
+            Action::Note => &[KeyCode::Char('t')],
+            Action::Split => &[KeyCode::Char('x')],
+            Action::Alerts => &[KeyCode::Char('A')],
+            Action::TopMovers => &[KeyCode::Char('M')],
+            Action::Compare => &[KeyCode::Char('v')],
+            Action::EventLog => &[KeyCode::Char('L')],
+            Action::Rebalance => &[KeyCode::Char('B')],
+            Action::OpenPage => &[KeyCode::Char('o')],
+            Action::ConvertList => &[KeyCode::Char('W')],
+        }
+    }
+}
+
+/// Parse a key as written in the config: a single character (`"v"`, `"?"`)
+/// or a named key (`"Enter"`, `"PageUp"`, `"F5"`, `"Space"`), case-insensitive.
+pub fn parse_key(spec: &str) -> Option<KeyCode> {
+    let mut chars = spec.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let lower = spec.to_lowercase();
+    let code = match lower.as_str() {
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        _ => {
+            let n = lower.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            KeyCode::F(n)
+        }
+    };
+    Some(code)
+}
+
+/// Display form of a key for warnings; the inverse of `parse_key`.
+pub fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Short form of a key for on-screen hints: arrows as glyphs, page keys
+/// abbreviated, anything else as `key_label`.
+pub fn hint_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => key_label(other),
+    }
+}
+
+/// Resolved key → action table for Normal mode.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: HashMap<KeyCode, Action>,
+    /// Keys each action ended up on, in binding order
+    keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).0
+    }
+}
+
+impl KeyMap {
+    /// Build the map from `key_bindings` overrides (action name → key). An
+    /// override replaces all of the action's default keys. Unknown actions,
+    /// unparseable keys and keys claimed by two actions are skipped and
+    /// described in the returned warnings.
+    pub fn from_config(overrides: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut custom: HashMap<Action, KeyCode> = HashMap::new();
+        for (name, spec) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(format!("Unknown key binding action '{}'", name));
+                continue;
+            };
+            match parse_key(spec) {
+                Some(code) => {
+                    custom.insert(action, code);
+                }
+                None => warnings.push(format!("Invalid key '{}' for {}", spec, name)),
+            }
+        }
+
+        let mut bindings: HashMap<KeyCode, Action> = HashMap::new();
+        let mut bound: HashMap<Action, Vec<KeyCode>> = HashMap::new();
+        for &action in Action::ALL {
+            let keys = match custom.get(&action) {
+                Some(code) => std::slice::from_ref(code),
+                None => action.default_keys(),
+            };
+            for &code in keys {
+                if let Some(&existing) = bindings.get(&code) {
+                    warnings.push(format!(
+                        "Key '{}' is bound to both {} and {}; keeping {}",
+                        key_label(code),
+                        existing.name(),
+                        action.name(),
+                        existing.name()
+                    ));
+                } else {
+                    bindings.insert(code, action);
+                    bound.entry(action).or_default().push(code);
+                }
+            }
+        }
+        (
+            Self {
+                bindings,
+                keys: bound,
+            },
+            warnings,
+        )
+    }
+
+    /// Keys that trigger `action`, primary key first. Empty when every key
+    /// it had was claimed by an earlier action.
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether `code` is bound to `action`; lets a modal close on the key
+    /// that opened it, wherever that key was remapped.
+    pub fn triggers(&self, action: Action, code: KeyCode) -> bool {
+        self.bindings.get(&code) == Some(&action)
+    }
+
+    /// The action's primary key for footer hints ("?" for help), or "-"
+    /// when it has no key.
+    pub fn hint(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map_or_else(|| "-".to_string(), |&code| hint_label(code))
+    }
+
+    /// Every key of the action for the help modal, e.g. "←/h".
+    pub fn hint_all(&self, action: Action) -> String {
+        let labels: Vec<String> = self.keys(action).iter().map(|&c| hint_label(c)).collect();
+        if labels.is_empty() {
+            "-".to_string()
+        } else {
+            labels.join("/")
+        }
+    }
+
+    /// Action for a key press. Shift+Up/Down always reorder the watchlist,
+    /// alongside whatever keys `move_symbol_up`/`move_symbol_down` are on.
+    pub fn action(&self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            match key.code {
+                KeyCode::Up => return Some(Action::MoveSymbolUp),
+                KeyCode::Down => return Some(Action::MoveSymbolDown),
+                _ => {}
+            }
+        }
+        self.bindings.get(&key.code).copied()
+    }
+}
@@ -1,4 +1,8 @@
 pub mod api;
 pub mod app;
+pub mod browser;
+pub mod clipboard;
 pub mod config;
+pub mod idx;
+pub mod keymap;
 pub mod ui;
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::{
     event::{
         self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
@@ -7,10 +7,18 @@ use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use idx_cli::app::{App, InputMode, NewsTab, ViewMode};
+use idx_cli::api::{YahooClient, yahoo::display_symbol};
+use idx_cli::app::{
+    AlertNotifier, App, ExportFormat, ExportScope, InputMode, MiniChartFetch, NewsTab, ViewMode,
+    quotes_json, quotes_table,
+};
+use idx_cli::config::HoldingsColumns;
+use idx_cli::keymap::Action;
 use idx_cli::ui;
+use notify_rust::Notification;
 use ratatui::prelude::*;
-use std::io;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::time::Instant;
 
@@ -18,15 +26,174 @@ use tokio::time::Instant;
 #[command(name = "idx-cli")]
 #[command(about = "Terminal UI for Indonesian stock market data", long_about = None)]
 struct Cli {
-    /// Refresh interval in seconds
-    #[arg(short, long, default_value = "1")]
-    interval: u64,
+    /// Refresh interval in seconds (overrides the saved setting)
+    #[arg(short, long)]
+    interval: Option<u64>,
+
+    /// Refresh prices only, skipping sector and fundamentals for a smaller,
+    /// faster request; the detail view fetches them when opened
+    #[arg(long)]
+    lite: bool,
+
+    /// Draw without colors, marking gains and losses with +/- and ▲/▼ only
+    /// (also set by a non-empty NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Path to the config file (defaults to the platform config directory)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Create a watchlist from a CSV/text file of symbols and exit
+    #[arg(long, value_name = "PATH")]
+    import_watchlist: Option<PathBuf>,
+
+    /// Write the whole config (watchlists, portfolios, alerts, bookmarks,
+    /// news sources, settings) to a backup file and exit
+    #[arg(long, value_name = "PATH", conflicts_with = "restore")]
+    backup: Option<PathBuf>,
+
+    /// Replace the config with a backup written by --backup and exit
+    #[arg(long, value_name = "PATH")]
+    restore: Option<PathBuf>,
+
+    /// Export quotes once in the given format and exit without starting the TUI
+    #[arg(long, value_enum)]
+    export: Option<ExportFormat>,
+
+    /// What to export with --export
+    #[arg(long, value_enum, default_value = "watchlist")]
+    export_scope: ExportScope,
+
+    /// File to write the export to (defaults to ~/Downloads/idx_<scope>_<time>.<ext>)
+    #[arg(short, long, requires = "export")]
+    output: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print current quotes for one or more symbols and exit
+    Quote {
+        /// Symbols to look up, e.g. BBCA TLKM
+        #[arg(required = true)]
+        symbols: Vec<String>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Manage the active portfolio from the command line
+    Portfolio {
+        #[command(subcommand)]
+        command: PortfolioCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PortfolioCommand {
+    /// Add holdings from a broker CSV (symbol, lots, average price) and exit
+    Import {
+        /// CSV file exported from the broker
+        file: PathBuf,
+
+        /// Header of the symbol column (default: Symbol, Stock, Kode, ...)
+        #[arg(long, value_name = "NAME")]
+        symbol_column: Option<String>,
+
+        /// Header of the lots column (default: Lots, Lot, ...)
+        #[arg(long, value_name = "NAME")]
+        lots_column: Option<String>,
+
+        /// Header of the average price column (default: Avg Price, ...)
+        #[arg(long, value_name = "NAME")]
+        price_column: Option<String>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(Command::Quote { symbols, json }) = &cli.command {
+        return run_quote_command(symbols, *json).await;
+    }
+
+    // Load config before touching the terminal so errors print normally
+    let mut app = App::new(cli.config)?;
+
+    if let Some(Command::Portfolio {
+        command:
+            PortfolioCommand::Import {
+                file,
+                symbol_column,
+                lots_column,
+                price_column,
+            },
+    }) = cli.command
+    {
+        let columns = HoldingsColumns {
+            symbol: symbol_column,
+            lots: lots_column,
+            avg_price: price_column,
+        };
+        let result = app.config.import_holdings_from_csv(&file, &columns)?;
+        for error in &result.errors {
+            eprintln!("{}", error);
+        }
+        println!(
+            "Imported {} rows into portfolio '{}' ({} skipped)",
+            result.imported,
+            app.config.current_portfolio().name,
+            result.errors.len()
+        );
+        if result.imported == 0 {
+            anyhow::bail!("No holdings imported from {}", file.display());
+        }
+        return Ok(());
+    }
+    if let Some(interval) = cli.interval {
+        app.config.refresh_interval_secs = interval;
+    }
+    if cli.lite {
+        app.enable_lite_mode()?;
+    }
+    app.no_color = cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+    if let Some(path) = cli.import_watchlist {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported".to_string());
+        let count = app.config.import_watchlist_from_csv(&path, &name)?;
+        println!("Imported {} symbols into watchlist '{}'", count, name);
+        return Ok(());
+    }
+
+    if let Some(path) = cli.backup {
+        app.config.export_backup(&path)?;
+        println!("Backed up config to {}", path.display());
+        return Ok(());
+    }
+
+    if let Some(path) = cli.restore {
+        app.config.import_backup(&path)?;
+        println!("Restored config from {}", path.display());
+        return Ok(());
+    }
+
+    if let Some(format) = cli.export {
+        let path = app
+            .run_headless_export(format, cli.export_scope, cli.output)
+            .await?;
+        println!("{}", path);
+        return Ok(());
+    }
+
+    let mut app = app.with_notifier(Box::new(DesktopNotifier));
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -34,10 +201,6 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let mut app = App::new()?;
-    app.config.refresh_interval_secs = cli.interval;
-
     let result = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
@@ -56,17 +219,76 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Draw, fetch quotes, and reset the refresh timer.
+/// One-shot `quote` subcommand: fetch and print without loading config or
+/// entering raw mode. Fails when none of the symbols resolve.
+async fn run_quote_command(symbols: &[String], json: bool) -> Result<()> {
+    let symbols: Vec<String> = symbols
+        .iter()
+        .map(|s| display_symbol(&s.trim().to_uppercase()))
+        .collect();
+    let mut client = YahooClient::new();
+    let quotes = client.get_quotes(&symbols).await?;
+    if json {
+        println!("{}", quotes_json(&symbols, &quotes));
+    } else {
+        print!("{}", quotes_table(&symbols, &quotes));
+    }
+    if !symbols.iter().any(|s| quotes.contains_key(s)) {
+        anyhow::bail!("No quotes found for {}", symbols.join(", "));
+    }
+    Ok(())
+}
+
+/// Rings the bell through the terminal the TUI owns and shows desktop
+/// notifications with notify-rust on a background thread, so a slow
+/// notification daemon can't stall the UI.
+struct DesktopNotifier;
+
+impl AlertNotifier for DesktopNotifier {
+    fn bell(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+
+    fn desktop(&mut self, summary: &str, body: &str) {
+        let mut notification = Notification::new();
+        notification
+            .summary(summary)
+            .body(body)
+            .icon("dialog-warning");
+        std::thread::spawn(move || {
+            let _ = notification.show();
+        });
+    }
+}
+
+/// Fetch quotes, redrawing every 100ms so the spinner turns while the
+/// request is in flight, then reset the refresh timer.
 /// The caller must have already called `app.prepare_refresh()` so that
-/// `loading = true` is visible in the draw that happens here.
+/// `loading = true` is visible in the draws that happen here.
 async fn refresh_and_draw<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     symbols: &[String],
     last_refresh: &mut Instant,
 ) -> Result<()> {
-    terminal.draw(|frame| ui::draw(frame, app))?;
-    app.execute_refresh(symbols).await?;
+    let pending = app.start_refresh(symbols);
+    let fetched = match app.spawn_quote_fetch(&pending) {
+        Some(mut fetch) => {
+            let mut redraw = tokio::time::interval(Duration::from_millis(100));
+            loop {
+                tokio::select! {
+                    result = &mut fetch => break Some(result?),
+                    _ = redraw.tick() => {
+                        app.tick_spinner();
+                        terminal.draw(|frame| ui::draw(frame, app))?;
+                    }
+                }
+            }
+        }
+        None => None,
+    };
+    app.finish_refresh(pending, fetched).await?;
     *last_refresh = Instant::now();
     Ok(())
 }
@@ -83,32 +305,41 @@ async fn refresh_news_and_draw<B: Backend>(
 }
 
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
-    let refresh_interval = Duration::from_secs(app.config.refresh_interval_secs);
-    let news_refresh_interval = Duration::from_secs(300); // 5 minutes
-    let mut last_refresh = Instant::now() - refresh_interval; // Force immediate refresh
+    let news_refresh_interval = Duration::from_secs(app.config.news_refresh_secs.max(1));
+    // Force immediate refresh
+    let mut last_refresh = Instant::now() - Duration::from_secs(app.config.refresh_interval_secs);
 
     let urls = app.prepare_news_refresh();
     refresh_news_and_draw(terminal, app, &urls).await?;
 
+    // Mini-trend charts load in the background; at most one fetch at a time
+    let mut mini_chart_fetch: Option<MiniChartFetch> = None;
+
     loop {
-        // Auto-refresh quotes silently (skip in News view).
+        // Re-read each iteration so +/- changes apply immediately
+        let refresh_interval = Duration::from_secs(app.config.refresh_interval_secs);
+
+        // Auto-refresh quotes silently (skip in News view, and outside
+        // trading hours once there is a closing quote to show).
         // Uses refresh_symbols() instead of prepare_refresh() to avoid
         // setting loading=true, which would flicker the clock display.
         if app.view_mode != ViewMode::News
+            && (app.market_open_now() || app.quotes.is_empty())
             && last_refresh.elapsed() >= refresh_interval
             && let Some(symbols) = app.refresh_symbols()
         {
             app.execute_refresh(&symbols).await?;
-            let triggered = app.check_alerts();
-            if let Some((_, msg)) = triggered.last() {
-                app.status_message = Some(msg.clone());
-                print!("\x07");
-                let _ = std::process::Command::new("notify-send")
-                    .args(["IDX Alert", msg, "--icon=dialog-warning"])
-                    .spawn();
+            if app.view_mode == ViewMode::Watchlist && mini_chart_fetch.is_none() {
+                mini_chart_fetch = app.spawn_mini_chart_fetch();
             }
             last_refresh = Instant::now();
         }
+        if let Some(fetch) = mini_chart_fetch.take_if(|fetch| fetch.is_finished()) {
+            match fetch.await {
+                Ok(results) => app.finish_mini_chart_fetch(results),
+                Err(e) => app.log_event(format!("Mini-chart fetch failed: {}", e)),
+            }
+        }
 
         // Auto-refresh news when in News view
         if app.view_mode == ViewMode::News {
@@ -122,16 +353,25 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
             }
         }
 
+        // Debounced symbol search for the add prompt
+        app.update_add_suggestions().await;
+
         // Draw UI
+        app.tick_spinner();
         terminal.draw(|frame| ui::draw(frame, app))?;
 
         // Handle input with timeout for refresh
         // Use 100ms timeout to keep clock updating smoothly
         let timeout = Duration::from_millis(100);
 
-        if event::poll(timeout)?
-            && let Event::Key(key) = event::read()?
-        {
+        let event = if event::poll(timeout)? {
+            Some(event::read()?)
+        } else {
+            None
+        };
+        if let Some(Event::Mouse(mouse)) = event {
+            app.handle_mouse(mouse);
+        } else if let Some(Event::Key(key)) = event {
             if key.kind != KeyEventKind::Press {
                 continue;
             }
@@ -142,6 +382,7 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                     .ctrl_c_at
                     .is_some_and(|t| t.elapsed() < Duration::from_secs(2))
                 {
+                    app.save_session()?;
                     return Ok(());
                 }
                 app.ctrl_c_at = Some(Instant::now());
@@ -156,144 +397,232 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
             let mut needs_refresh = false;
 
             match app.input_mode {
-                InputMode::Normal => match key.code {
-                    KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Char('?') => app.show_help(),
-                    KeyCode::Char('/') => app.start_search(),
-                    KeyCode::Char('e') => match app.view_mode {
-                        ViewMode::Portfolio => app.start_portfolio_edit(),
-                        ViewMode::Watchlist => app.start_export(),
-                        ViewMode::News => {}
-                    },
-                    KeyCode::Char('p') => {
-                        app.toggle_view();
-                        if app.view_mode == ViewMode::News {
-                            if app.news_last_refresh.is_none() {
-                                let urls = app.prepare_news_refresh();
-                                refresh_news_and_draw(terminal, app, &urls).await?;
+                InputMode::Normal => {
+                    let Some(action) = app.keymap.action(key) else {
+                        continue;
+                    };
+                    match action {
+                        Action::Quit => {
+                            app.save_session()?;
+                            return Ok(());
+                        }
+                        Action::Help => app.show_help(),
+                        Action::RefreshFaster => app.adjust_refresh_interval(true)?,
+                        Action::RefreshSlower => app.adjust_refresh_interval(false)?,
+                        Action::ToggleDensity => app.toggle_density()?,
+                        Action::Search => app.start_search(),
+                        Action::QuickJump => app.start_quick_jump(),
+                        Action::Edit => match app.view_mode {
+                            ViewMode::Portfolio => app.start_portfolio_edit(),
+                            ViewMode::Watchlist => app.start_export(),
+                            ViewMode::News => {}
+                        },
+                        Action::ToggleView => {
+                            app.toggle_view();
+                            if app.view_mode == ViewMode::News {
+                                if app.news_last_refresh.is_none() {
+                                    let urls = app.prepare_news_refresh();
+                                    refresh_news_and_draw(terminal, app, &urls).await?;
+                                }
+                            } else {
+                                needs_refresh = true;
                             }
-                        } else {
-                            needs_refresh = true;
                         }
-                    }
-                    KeyCode::Char('a') => match app.view_mode {
-                        ViewMode::Watchlist => app.start_adding(),
-                        ViewMode::Portfolio => app.start_portfolio_add(),
-                        ViewMode::News => {}
-                    },
-                    KeyCode::Char('b') => {
-                        if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed {
+                        Action::Add => match app.view_mode {
+                            ViewMode::Watchlist => app.start_adding(),
+                            ViewMode::Portfolio => app.start_portfolio_add(),
+                            ViewMode::News => {}
+                        },
+                        Action::Bookmark
+                            if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed =>
+                        {
                             app.toggle_news_bookmark();
                         }
-                    }
-                    KeyCode::Char('d') => {
-                        match app.view_mode {
-                            ViewMode::Watchlist => app.remove_selected()?,
-                            ViewMode::Portfolio => app.remove_selected_holding()?,
-                            ViewMode::News => {
-                                if app.news_tab == NewsTab::Bookmarks {
-                                    app.remove_selected_bookmark();
+                        Action::TickerFilter
+                            if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed =>
+                        {
+                            app.toggle_news_ticker_filter();
+                        }
+                        Action::CategoryFilter
+                            if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed =>
+                        {
+                            app.cycle_news_category_filter();
+                        }
+                        Action::LoadMore
+                            if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed =>
+                        {
+                            app.status_message = Some("Loading older headlines...".to_string());
+                            terminal.draw(|frame| ui::draw(frame, app))?;
+                            app.load_more_news().await;
+                        }
+                        Action::NewsSources
+                            if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Feed =>
+                        {
+                            app.open_news_sources();
+                        }
+                        Action::Delete => {
+                            match app.view_mode {
+                                ViewMode::Watchlist | ViewMode::Portfolio => {
+                                    app.request_delete_selected()?
+                                }
+                                ViewMode::News => {
+                                    if app.news_tab == NewsTab::Bookmarks {
+                                        app.remove_selected_bookmark();
+                                    }
                                 }
                             }
+                            needs_refresh = true;
                         }
-                        needs_refresh = true;
-                    }
-                    KeyCode::Char('m') => {
-                        if app.view_mode == ViewMode::News && app.news_tab == NewsTab::Bookmarks {
+                        Action::ToggleRead
+                            if app.view_mode == ViewMode::News
+                                && app.news_tab == NewsTab::Bookmarks =>
+                        {
                             app.toggle_selected_bookmark_read();
                         }
-                    }
-                    KeyCode::Char('r') => {
-                        if app.view_mode == ViewMode::News {
-                            let urls = app.prepare_news_refresh();
-                            refresh_news_and_draw(terminal, app, &urls).await?;
-                        } else if app.view_mode != ViewMode::News {
-                            needs_refresh = true;
+                        Action::Refresh => {
+                            if app.view_mode == ViewMode::News {
+                                let urls = app.prepare_news_refresh();
+                                refresh_news_and_draw(terminal, app, &urls).await?;
+                            } else if app.view_mode != ViewMode::News {
+                                needs_refresh = true;
+                            }
                         }
-                    }
-                    KeyCode::Up => app.move_up(),
-                    KeyCode::Down => app.move_down(),
-                    KeyCode::Left | KeyCode::Char('h') => match app.view_mode {
-                        ViewMode::Watchlist => {
-                            app.prev_watchlist();
-                            needs_refresh = true;
+                        Action::MoveSymbolUp if app.view_mode == ViewMode::Watchlist => {
+                            app.move_selected_symbol(true)?
+                        }
+                        Action::MoveSymbolDown if app.view_mode == ViewMode::Watchlist => {
+                            app.move_selected_symbol(false)?
                         }
-                        ViewMode::Portfolio => {
-                            app.prev_portfolio();
+                        // Shift+arrows elsewhere still just move the selection
+                        Action::MoveSymbolUp if key.code == KeyCode::Up => app.move_up(),
+                        Action::MoveSymbolDown if key.code == KeyCode::Down => app.move_down(),
+                        Action::Up => app.move_up(),
+                        Action::Down => app.move_down(),
+                        Action::PageUp => app.page_up(),
+                        Action::PageDown => app.page_down(),
+                        Action::Top => app.go_top(),
+                        Action::Bottom => app.go_bottom(),
+                        Action::PrevList => match app.view_mode {
+                            ViewMode::Watchlist => {
+                                app.prev_watchlist();
+                                needs_refresh = true;
+                            }
+                            ViewMode::Portfolio => {
+                                app.prev_portfolio();
+                                needs_refresh = true;
+                            }
+                            ViewMode::News => {
+                                app.toggle_news_tab();
+                            }
+                        },
+                        Action::NextList => match app.view_mode {
+                            ViewMode::Watchlist => {
+                                app.next_watchlist();
+                                needs_refresh = true;
+                            }
+                            ViewMode::Portfolio => {
+                                app.next_portfolio();
+                                needs_refresh = true;
+                            }
+                            ViewMode::News => {
+                                app.toggle_news_tab();
+                            }
+                        },
+                        Action::NewList => match app.view_mode {
+                            ViewMode::Watchlist => app.start_watchlist_add(),
+                            ViewMode::Portfolio => app.start_portfolio_new(),
+                            _ => {}
+                        },
+                        Action::ConvertList => {
+                            app.convert_list()?;
                             needs_refresh = true;
                         }
-                        ViewMode::News => {
-                            app.toggle_news_tab();
+                        Action::Columns => app.open_column_picker(),
+                        Action::CopySymbol => app.copy_selected(false),
+                        Action::OpenPage => app.open_stock_page(),
+                        Action::CopySummary => app.copy_selected(true),
+                        Action::Undo => {
+                            app.undo()?;
+                            needs_refresh = true;
                         }
-                    },
-                    KeyCode::Right | KeyCode::Char('l') => match app.view_mode {
-                        ViewMode::Watchlist => {
-                            app.next_watchlist();
+                        Action::RenameList => match app.view_mode {
+                            ViewMode::Watchlist => app.start_watchlist_rename(),
+                            ViewMode::Portfolio => app.start_portfolio_rename(),
+                            _ => {}
+                        },
+                        Action::DeleteList => match app.view_mode {
+                            ViewMode::Watchlist | ViewMode::Portfolio => {
+                                app.request_delete_list()?;
+                                needs_refresh = true;
+                            }
+                            ViewMode::News => {
+                                if app.news_tab == NewsTab::Bookmarks {
+                                    app.start_clear_bookmarks();
+                                }
+                            }
+                        },
+                        Action::Open => match app.view_mode {
+                            ViewMode::Watchlist => app.show_stock_detail().await,
+                            ViewMode::Portfolio => app.show_portfolio_detail().await,
+                            ViewMode::News => {
+                                if app.news_tab == NewsTab::Bookmarks {
+                                    app.open_bookmark_detail();
+                                } else {
+                                    app.open_news_detail();
+                                }
+                            }
+                        },
+                        Action::TopMovers if app.view_mode == ViewMode::Watchlist => {
+                            app.show_top_movers();
                             needs_refresh = true;
                         }
-                        ViewMode::Portfolio => {
-                            app.next_portfolio();
+                        Action::Compare => app.start_compare(),
+                        Action::EventLog => app.show_event_log(),
+                        Action::AllWatchlists if app.view_mode == ViewMode::Watchlist => {
+                            app.toggle_all_watchlists();
                             needs_refresh = true;
                         }
-                        ViewMode::News => {
-                            app.toggle_news_tab();
+                        Action::SortColumn => app.cycle_sort_column(),
+                        Action::SortDirection => app.toggle_sort_direction(),
+                        Action::AllocationChart if app.view_mode == ViewMode::Portfolio => {
+                            app.show_portfolio_chart();
                         }
-                    },
-                    KeyCode::Char('n') => match app.view_mode {
-                        ViewMode::Watchlist => app.start_watchlist_add(),
-                        ViewMode::Portfolio => app.start_portfolio_new(),
-                        _ => {}
-                    },
-                    KeyCode::Char('R') => match app.view_mode {
-                        ViewMode::Watchlist => app.start_watchlist_rename(),
-                        ViewMode::Portfolio => app.start_portfolio_rename(),
-                        _ => {}
-                    },
-                    KeyCode::Char('D') => match app.view_mode {
-                        ViewMode::Watchlist => {
-                            app.remove_current_watchlist()?;
-                            needs_refresh = true;
+                        Action::ProfitLoss if app.view_mode == ViewMode::Portfolio => {
+                            app.show_portfolio_pl();
                         }
-                        ViewMode::Portfolio => {
-                            app.remove_current_portfolio()?;
-                            needs_refresh = true;
+                        Action::Rebalance if app.view_mode == ViewMode::Portfolio => {
+                            app.show_rebalance();
                         }
-                        ViewMode::News => {
-                            if app.news_tab == NewsTab::Bookmarks {
-                                app.start_clear_bookmarks();
-                            }
+                        Action::ValueHistory if app.view_mode == ViewMode::Portfolio => {
+                            app.show_portfolio_history().await;
                         }
-                    },
-                    KeyCode::Enter => match app.view_mode {
-                        ViewMode::Watchlist => app.show_stock_detail().await,
-                        ViewMode::Portfolio => app.show_portfolio_detail().await,
-                        ViewMode::News => {
-                            if app.news_tab == NewsTab::Bookmarks {
-                                app.open_bookmark_detail();
-                            } else {
-                                app.open_news_detail();
-                            }
+                        Action::Note if app.view_mode == ViewMode::Portfolio => {
+                            app.start_portfolio_note();
                         }
-                    },
-                    KeyCode::Char('s') => app.cycle_sort_column(),
-                    KeyCode::Char('S') => app.toggle_sort_direction(),
-                    KeyCode::Char('c') => {
-                        if app.view_mode == ViewMode::Portfolio {
-                            app.show_portfolio_chart();
+                        Action::Split if app.view_mode == ViewMode::Portfolio => {
+                            app.start_portfolio_split();
                         }
+                        Action::Alerts => match app.view_mode {
+                            ViewMode::Watchlist | ViewMode::Portfolio => app.open_alert_modal(),
+                            ViewMode::News => {}
+                        },
+                        _ => {}
                     }
-                    KeyCode::Char('A') => match app.view_mode {
-                        ViewMode::Watchlist | ViewMode::Portfolio => app.open_alert_modal(),
-                        ViewMode::News => {}
-                    },
-                    _ => {}
-                },
+                }
                 InputMode::StockDetail => match key.code {
                     KeyCode::Esc | KeyCode::Enter => app.close_stock_detail(),
+                    KeyCode::Char(']') => app.cycle_chart_range(true).await,
+                    KeyCode::Char('[') => app.cycle_chart_range(false).await,
+                    KeyCode::Char('r') => needs_refresh = true,
+                    KeyCode::Char('o') => app.open_stock_page(),
                     _ => {}
                 },
                 InputMode::Help => match key.code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') => app.close_help(),
+                    KeyCode::Enter => app.close_help(),
+                    code if app.keymap.triggers(Action::Help, code) => app.close_help(),
+                    KeyCode::Esc => app.help_escape(),
+                    KeyCode::Backspace => app.help_filter_pop(),
+                    KeyCode::Char(c) => app.help_filter_push(c),
                     _ => {}
                 },
                 InputMode::ExportMenu => match key.code {
@@ -311,11 +640,51 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                     _ => {}
                 },
                 InputMode::PortfolioChart => match key.code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('c') => {
+                    KeyCode::Esc | KeyCode::Enter => app.close_portfolio_chart(),
+                    code if app.keymap.triggers(Action::AllocationChart, code) => {
                         app.close_portfolio_chart()
                     }
                     _ => {}
                 },
+                InputMode::EventLog => match key.code {
+                    KeyCode::Esc | KeyCode::Enter => app.close_event_log(),
+                    KeyCode::Up | KeyCode::Char('k') => app.event_log_scroll_up(),
+                    KeyCode::Down | KeyCode::Char('j') => app.event_log_scroll_down(),
+                    code if app.keymap.triggers(Action::EventLog, code) => app.close_event_log(),
+                    _ => {}
+                },
+                InputMode::Compare => match key.code {
+                    KeyCode::Esc | KeyCode::Enter => app.close_compare(),
+                    code if app.keymap.triggers(Action::Compare, code) => app.close_compare(),
+                    _ => {}
+                },
+                InputMode::TopMovers => match key.code {
+                    KeyCode::Esc | KeyCode::Enter => app.close_top_movers(),
+                    code if app.keymap.triggers(Action::TopMovers, code) => app.close_top_movers(),
+                    _ => {}
+                },
+                InputMode::PortfolioPL => match key.code {
+                    KeyCode::Esc | KeyCode::Enter => app.close_portfolio_pl(),
+                    code if app.keymap.triggers(Action::ProfitLoss, code) => {
+                        app.close_portfolio_pl()
+                    }
+                    _ => {}
+                },
+                InputMode::Rebalance => match key.code {
+                    KeyCode::Esc => app.close_rebalance(),
+                    KeyCode::Up | KeyCode::Char('k') => app.rebalance_up(),
+                    KeyCode::Down | KeyCode::Char('j') => app.rebalance_down(),
+                    KeyCode::Enter | KeyCode::Char('e') => app.start_rebalance_target(),
+                    code if app.keymap.triggers(Action::Rebalance, code) => app.close_rebalance(),
+                    _ => {}
+                },
+                InputMode::PortfolioHistory => match key.code {
+                    KeyCode::Esc | KeyCode::Enter => app.close_portfolio_history(),
+                    code if app.keymap.triggers(Action::ValueHistory, code) => {
+                        app.close_portfolio_history()
+                    }
+                    _ => {}
+                },
                 InputMode::NewsDetail => match key.code {
                     KeyCode::Esc | KeyCode::Char('q') => {
                         app.input_mode = InputMode::Normal;
@@ -335,7 +704,7 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                             .get(app.news_selected)
                             .and_then(|item| item.url.clone());
                         if let Some(url) = url {
-                            let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                            let _ = idx_cli::browser::open_url(&url);
                         }
                     }
                     _ => {}
@@ -359,11 +728,39 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                             .get(app.bookmark_selected)
                             .and_then(|b| b.url.clone());
                         if let Some(url) = url {
-                            let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                            let _ = idx_cli::browser::open_url(&url);
                         }
                     }
                     _ => {}
                 },
+                InputMode::NewsSources => match key.code {
+                    KeyCode::Esc => app.close_news_sources(),
+                    KeyCode::Up | KeyCode::Char('k') => app.news_source_up(),
+                    KeyCode::Down | KeyCode::Char('j') => app.news_source_down(),
+                    KeyCode::Char('a') => app.start_news_source_add(),
+                    KeyCode::Char('d') => app.remove_selected_news_source()?,
+                    KeyCode::Char('c') => app.start_news_source_category(),
+                    _ => {}
+                },
+                InputMode::ColumnPicker => match key.code {
+                    KeyCode::Esc => app.close_column_picker(),
+                    KeyCode::Up | KeyCode::Char('k') => app.column_picker_up(),
+                    KeyCode::Down | KeyCode::Char('j') => app.column_picker_down(),
+                    KeyCode::Char(' ') | KeyCode::Enter => app.toggle_picker_column()?,
+                    KeyCode::Char('K') => app.move_picker_column(-1)?,
+                    KeyCode::Char('J') => app.move_picker_column(1)?,
+                    KeyCode::Char('r') => app.reset_columns()?,
+                    code if app.keymap.triggers(Action::Columns, code) => app.close_column_picker(),
+                    _ => {}
+                },
+                InputMode::ConfirmDelete => match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        app.confirm_delete()?;
+                        needs_refresh = true;
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_delete(),
+                    _ => {}
+                },
                 InputMode::BookmarkClearConfirm => match key.code {
                     KeyCode::Enter => app.confirm_clear_bookmarks(),
                     KeyCode::Esc => app.cancel_clear_bookmarks(),
@@ -375,6 +772,7 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                     KeyCode::Down | KeyCode::Char('j') => app.alert_list_down(),
                     KeyCode::Enter => app.alert_list_confirm(),
                     KeyCode::Char('d') => app.alert_list_delete()?,
+                    KeyCode::Char('c') => app.start_alert_cooldown_edit(),
                     _ => {}
                 },
                 InputMode::AlertAddType => match key.code {
@@ -390,17 +788,26 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                         InputMode::PortfolioAddSymbol
                         | InputMode::PortfolioAddLots
                         | InputMode::PortfolioAddPrice => app.cancel_portfolio_add(),
-                        InputMode::PortfolioEditLots | InputMode::PortfolioEditPrice => {
-                            app.cancel_portfolio_edit()
-                        }
+                        InputMode::PortfolioEditLots
+                        | InputMode::PortfolioEditPrice
+                        | InputMode::PortfolioSplit
+                        | InputMode::PortfolioNote => app.cancel_portfolio_edit(),
                         InputMode::PortfolioNew | InputMode::PortfolioRename => app.cancel_input(),
                         InputMode::Search => app.cancel_search(),
-                        InputMode::AlertAddValue => app.cancel_alert_add(),
+                        InputMode::AlertAddValue | InputMode::AlertCooldown => {
+                            app.cancel_alert_add()
+                        }
+                        InputMode::NewsSourceAdd | InputMode::NewsSourceCategory => {
+                            app.cancel_news_source_add()
+                        }
+                        InputMode::QuickJump => app.end_quick_jump(),
+                        InputMode::ComparePick => app.close_compare(),
+                        InputMode::RebalanceTarget => app.cancel_rebalance_target(),
                         _ => app.cancel_input(),
                     },
                     KeyCode::Enter => match app.input_mode {
                         InputMode::Adding => {
-                            app.confirm_add()?;
+                            app.confirm_add().await?;
                             needs_refresh = true;
                         }
                         InputMode::WatchlistAdd => {
@@ -418,7 +825,7 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                         InputMode::PortfolioRename => {
                             app.confirm_portfolio_rename()?;
                         }
-                        InputMode::PortfolioAddSymbol => app.confirm_portfolio_symbol(),
+                        InputMode::PortfolioAddSymbol => app.confirm_portfolio_symbol().await,
                         InputMode::PortfolioAddLots => app.confirm_portfolio_lots(),
                         InputMode::PortfolioAddPrice => {
                             app.confirm_portfolio_price()?;
@@ -429,36 +836,70 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                             app.confirm_portfolio_edit_price()?;
                             needs_refresh = true;
                         }
+                        InputMode::PortfolioSplit => app.confirm_portfolio_split()?,
+                        InputMode::PortfolioNote => app.confirm_portfolio_note()?,
+                        InputMode::RebalanceTarget => app.confirm_rebalance_target()?,
                         InputMode::Search => app.confirm_search(),
-                        InputMode::AlertAddValue => app.alert_value_confirm()?,
+                        InputMode::QuickJump => app.end_quick_jump(),
+                        InputMode::ComparePick => app.confirm_compare().await,
+                        InputMode::AlertAddValue => app.alert_value_confirm(),
+                        InputMode::AlertCooldown => app.alert_cooldown_confirm()?,
+                        InputMode::NewsSourceCategory => app.confirm_news_source_category()?,
+                        InputMode::NewsSourceAdd => {
+                            app.status_message = Some("Checking feed...".to_string());
+                            terminal.draw(|frame| ui::draw(frame, app))?;
+                            app.confirm_news_source_add().await?;
+                        }
                         _ => {}
                     },
+                    KeyCode::Up if app.input_mode == InputMode::Adding => app.add_suggestion_up(),
+                    KeyCode::Down if app.input_mode == InputMode::Adding => {
+                        app.add_suggestion_down()
+                    }
                     KeyCode::Backspace => {
                         app.input_buffer.pop();
+                        match app.input_mode {
+                            InputMode::Adding => app.add_input_changed(),
+                            InputMode::QuickJump => app.quick_jump_changed(),
+                            _ => {}
+                        }
                     }
                     KeyCode::Char(c) => {
                         let allowed = match app.input_mode {
-                            InputMode::Adding | InputMode::PortfolioAddSymbol => {
-                                c.is_alphanumeric()
-                            }
-                            InputMode::PortfolioAddLots | InputMode::PortfolioEditLots => {
-                                c.is_ascii_digit()
-                            }
+                            InputMode::Adding => c.is_alphanumeric() || ", ^=-.$".contains(c),
+                            InputMode::ComparePick => c.is_alphanumeric() || "^=-.$".contains(c),
+                            InputMode::PortfolioAddSymbol => c.is_alphanumeric(),
+                            InputMode::PortfolioAddLots
+                            | InputMode::PortfolioEditLots
+                            | InputMode::AlertCooldown => c.is_ascii_digit(),
                             InputMode::PortfolioAddPrice | InputMode::PortfolioEditPrice => {
-                                c.is_ascii_digit() || c == '.'
+                                c.is_ascii_digit() || "+-*/(). ".contains(c)
                             }
+                            InputMode::PortfolioSplit => c.is_ascii_digit() || c == ':',
                             InputMode::AlertAddValue => c.is_ascii_digit() || c == '.',
+                            InputMode::RebalanceTarget => c.is_ascii_digit() || ".%".contains(c),
                             InputMode::WatchlistAdd
                             | InputMode::WatchlistRename
                             | InputMode::PortfolioNew
                             | InputMode::PortfolioRename => {
                                 c.is_alphanumeric() || c == ' ' || c == '-' || c == '_'
                             }
-                            InputMode::Search => true,
+                            InputMode::Search | InputMode::QuickJump | InputMode::PortfolioNote => {
+                                true
+                            }
+                            InputMode::NewsSourceAdd => !c.is_whitespace(),
+                            InputMode::NewsSourceCategory => {
+                                c.is_alphanumeric() || c == ' ' || c == '-' || c == '_'
+                            }
                             _ => false,
                         };
                         if allowed {
                             app.input_buffer.push(c);
+                            match app.input_mode {
+                                InputMode::Adding => app.add_input_changed(),
+                                InputMode::QuickJump => app.quick_jump_changed(),
+                                _ => {}
+                            }
                         }
                     }
                     _ => {}
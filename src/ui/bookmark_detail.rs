@@ -2,15 +2,16 @@ use super::centered_rect;
 use super::formatters::*;
 use super::news_detail::word_wrap;
 use crate::app::App;
+use crate::config::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
+pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App, theme: &Theme) {
     let area = centered_rect(70, 55, frame.area());
     frame.render_widget(Clear, area);
 
@@ -57,8 +58,8 @@ pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
     let outer_block = Block::default()
         .title(format!(" {} ", source))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.gain))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -85,25 +86,25 @@ pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
 
     // Published date
     all_lines.push(Line::from(vec![
-        Span::styled("Published: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(pub_full, Style::default().fg(Color::DarkGray)),
+        Span::styled("Published: ", Style::default().fg(theme.muted)),
+        Span::styled(pub_full, Style::default().fg(theme.muted)),
         Span::styled(
             format!("  ({})", pub_relative),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ]));
 
     // Bookmarked date
     all_lines.push(Line::from(vec![
-        Span::styled("Bookmarked: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(bm_full, Style::default().fg(Color::DarkGray)),
+        Span::styled("Bookmarked: ", Style::default().fg(theme.muted)),
+        Span::styled(bm_full, Style::default().fg(theme.muted)),
     ]));
 
     // Read status
     let read_label = if read { "Read" } else { "Unread" };
-    let read_color = if read { Color::DarkGray } else { Color::Yellow };
+    let read_color = if read { theme.muted } else { theme.header };
     all_lines.push(Line::from(vec![
-        Span::styled("Status: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Status: ", Style::default().fg(theme.muted)),
         Span::styled(
             read_label,
             Style::default().fg(read_color).add_modifier(Modifier::BOLD),
@@ -124,13 +125,13 @@ pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
         };
         all_lines.push(Line::from(Span::styled(
             display,
-            Style::default().fg(Color::Blue),
+            Style::default().fg(theme.news_header),
         )));
     }
 
     all_lines.push(Line::from(Span::styled(
         "─".repeat(inner_width),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
 
     // Summary from live news if available
@@ -141,7 +142,7 @@ pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
     } else {
         all_lines.push(Line::from(Span::styled(
             "Summary not available (article no longer in feed).",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     }
 
@@ -156,14 +157,14 @@ pub fn draw_bookmark_detail(frame: &mut Frame, app: &mut App) {
     frame.render_widget(Paragraph::new(visible), body_area);
 
     let footer_line = Line::from(vec![
-        Span::styled("[o] ", Style::default().fg(Color::Cyan)),
-        Span::styled("browser  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("[m] ", Style::default().fg(Color::Cyan)),
-        Span::styled("toggle read  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("[↑/↓] ", Style::default().fg(Color::Cyan)),
-        Span::styled("scroll  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("[Esc] ", Style::default().fg(Color::Cyan)),
-        Span::styled("close", Style::default().fg(Color::DarkGray)),
+        Span::styled("[o] ", Style::default().fg(theme.accent)),
+        Span::styled("browser  ", Style::default().fg(theme.muted)),
+        Span::styled("[m] ", Style::default().fg(theme.accent)),
+        Span::styled("toggle read  ", Style::default().fg(theme.muted)),
+        Span::styled("[↑/↓] ", Style::default().fg(theme.accent)),
+        Span::styled("scroll  ", Style::default().fg(theme.muted)),
+        Span::styled("[Esc] ", Style::default().fg(theme.accent)),
+        Span::styled("close", Style::default().fg(theme.muted)),
     ]);
     frame.render_widget(Paragraph::new(footer_line), footer_area);
 }
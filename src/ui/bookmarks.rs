@@ -1,12 +1,13 @@
 use super::formatters::*;
 use super::tables::{ColumnDef, column_constraints, sort_header_row, visible_columns};
-use crate::app::App;
-use crate::config::Bookmark;
+use super::{chrome_block, table_metrics};
+use crate::app::{App, TableHitArea};
+use crate::config::{Bookmark, Theme};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table},
+    style::{Modifier, Style},
+    widgets::{Cell, Row, Table},
 };
 
 const BOOKMARK_COLUMNS: &[ColumnDef] = &[
@@ -33,9 +34,9 @@ const BOOKMARK_COLUMNS: &[ColumnDef] = &[
 ];
 pub(crate) const BOOKMARK_SORTABLE_COLUMNS: usize = 4;
 
-fn bookmark_row(bookmark: &Bookmark, vis: &[usize]) -> Row<'static> {
+fn bookmark_row(bookmark: &Bookmark, vis: &[usize], theme: &Theme) -> Row<'static> {
     let read_style = if bookmark.read {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     } else {
         Style::default()
     };
@@ -60,23 +61,23 @@ fn bookmark_row(bookmark: &Bookmark, vis: &[usize]) -> Row<'static> {
     Row::new(cells)
 }
 
-pub fn draw_bookmarks(frame: &mut Frame, area: Rect, app: &mut App) {
-    app.table_viewport_height = area.height.saturating_sub(3) as usize;
-    let available_width = area.width.saturating_sub(2);
+pub fn draw_bookmarks(frame: &mut Frame, area: Rect, app: &mut App, theme: &Theme) {
+    let (viewport_height, available_width) = table_metrics(area, app.config.density);
+    app.table_viewport_height = viewport_height;
     let vis = visible_columns(BOOKMARK_COLUMNS, available_width);
     let header = sort_header_row(
         BOOKMARK_COLUMNS,
         &vis,
         app.bookmark_sort_column,
         &app.bookmark_sort_direction,
-        Color::Green,
+        theme.gain,
     );
 
     let filtered = app.get_filtered_bookmarks();
     let unread_count = filtered.iter().filter(|b| !b.read).count();
     let rows: Vec<Row> = filtered
         .iter()
-        .map(|bookmark| bookmark_row(bookmark, &vis))
+        .map(|bookmark| bookmark_row(bookmark, &vis, theme))
         .collect();
 
     let title = if filtered.is_empty() {
@@ -92,15 +93,17 @@ pub fn draw_bookmarks(frame: &mut Frame, area: Rect, app: &mut App) {
     };
 
     let constraints = column_constraints(BOOKMARK_COLUMNS, &vis, Some(3), available_width);
+    let block = chrome_block(app.config.density).title(title);
+    app.table_hit_area = Some(TableHitArea::new(block.inner(area), &vis, &constraints));
     let table = Table::new(rows, constraints)
         .header(header)
         .row_highlight_style(
-            Style::default()
-                .bg(Color::Rgb(40, 80, 40))
-                .fg(Color::White)
+            theme
+                .selected(theme.bookmark_selection_bg)
+                .fg(theme.text)
                 .add_modifier(Modifier::BOLD),
         )
-        .block(Block::default().borders(Borders::ALL).title(title));
+        .block(block);
 
     app.bookmark_table_state.select(Some(app.bookmark_selected));
     frame.render_stateful_widget(table, area, &mut app.bookmark_table_state);
@@ -1,78 +1,83 @@
 use super::centered_rect;
 use super::formatters::*;
-use crate::api::{NewsItem, StockQuote};
+use crate::api::{ChartData, ChartRange, NewsItem, StockQuote};
 use crate::app::App;
+use crate::config::{Density, Highlights, Theme};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Sparkline},
 };
 
-fn section_divider<'a>(title: &str) -> Line<'a> {
+fn section_divider<'a>(title: &str, theme: &Theme) -> Line<'a> {
     Line::from(vec![
-        Span::styled(
-            format!("─── {} ", title),
-            Style::default().fg(Color::Yellow),
-        ),
+        Span::styled(format!("─── {} ", title), Style::default().fg(theme.header)),
         Span::styled(
             "───────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ])
 }
 
-fn detail_header(q: &StockQuote) -> Vec<Line<'static>> {
+fn detail_header(q: &StockQuote, note: Option<&str>, theme: &Theme) -> Vec<Line<'static>> {
     let sector = q.sector.as_deref().unwrap_or("N/A");
     let industry = q.industry.as_deref().unwrap_or("N/A");
-    vec![
+    let mut lines = vec![
         Line::from(Span::styled(
             q.long_name.as_deref().unwrap_or(&q.short_name).to_string(),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
             format!("{} | {}", sector, industry),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
-        Line::from(""),
-    ]
+    ];
+    if let Some(note) = note {
+        lines.push(Line::from(vec![
+            Span::styled("Note: ", Style::default().fg(theme.muted)),
+            Span::styled(note.to_string(), Style::default().fg(theme.header)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines
 }
 
-fn detail_price_section(q: &StockQuote) -> Vec<Line<'static>> {
-    let change_color = if q.change >= 0.0 {
-        Color::Green
-    } else {
-        Color::Red
-    };
+fn detail_price_section(
+    q: &StockQuote,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) -> Vec<Line<'static>> {
+    let change_color = highlights.change_color(theme, q.change_percent, false);
     let gap_percent = if q.prev_close > 0.0 {
         ((q.open - q.prev_close) / q.prev_close) * 100.0
     } else {
         0.0
     };
-    let gap_color = if gap_percent >= 0.0 {
-        Color::Green
-    } else {
-        Color::Red
-    };
+    let gap_color = highlights.change_color(theme, gap_percent, false);
 
     vec![
-        section_divider("Price"),
+        section_divider("Price", theme),
         Line::from(vec![
             Span::raw("Current:        "),
             Span::styled(
-                format_price(q.price),
+                fmt.price(q.price),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
             Span::raw("Change:         "),
             Span::styled(
-                format!("{} ({:+.2}%)", format_change(q.change), q.change_percent),
+                format!(
+                    "{}{} ({})",
+                    highlights.arrow(q.change_percent),
+                    format_change(q.change),
+                    fmt.signed_percent(q.change_percent)
+                ),
                 Style::default()
                     .fg(change_color)
                     .add_modifier(Modifier::BOLD),
@@ -80,14 +85,18 @@ fn detail_price_section(q: &StockQuote) -> Vec<Line<'static>> {
         ]),
         Line::from(vec![
             Span::raw("Open:           "),
-            Span::raw(format_price(q.open)),
+            Span::raw(fmt.price(q.open)),
             Span::raw("  Prev Close: "),
-            Span::raw(format_price(q.prev_close)),
+            Span::raw(fmt.price(q.prev_close)),
         ]),
         Line::from(vec![
             Span::raw("Gap:            "),
             Span::styled(
-                format!("{:+.2}%", gap_percent),
+                format!(
+                    "{}{}",
+                    highlights.arrow(gap_percent),
+                    fmt.signed_percent(gap_percent)
+                ),
                 Style::default().fg(gap_color),
             ),
         ]),
@@ -95,7 +104,7 @@ fn detail_price_section(q: &StockQuote) -> Vec<Line<'static>> {
     ]
 }
 
-fn detail_range_section(q: &StockQuote) -> Vec<Line<'static>> {
+fn detail_range_section(q: &StockQuote, theme: &Theme, fmt: &NumberFormat) -> Vec<Line<'static>> {
     let day_range = q.high - q.low;
     let day_range_percent = if day_range > 0.0 {
         ((q.price - q.low) / day_range) * 100.0
@@ -104,34 +113,34 @@ fn detail_range_section(q: &StockQuote) -> Vec<Line<'static>> {
     };
 
     let mut lines = vec![
-        section_divider("Day Range"),
+        section_divider("Day Range", theme),
         Line::from(vec![
             Span::raw("High:           "),
-            Span::styled(format_price(q.high), Style::default().fg(Color::Green)),
+            Span::styled(fmt.price(q.high), Style::default().fg(theme.gain)),
             Span::raw("  Low: "),
-            Span::styled(format_price(q.low), Style::default().fg(Color::Red)),
+            Span::styled(fmt.price(q.low), Style::default().fg(theme.loss)),
         ]),
         Line::from(vec![
             Span::raw("Position:       "),
             Span::raw(format!("{:.1}% from low", day_range_percent)),
         ]),
         Line::from(""),
-        section_divider("52-Week Range"),
+        section_divider("52-Week Range", theme),
     ];
 
     let w52_high = q
         .fifty_two_week_high
-        .map(format_price)
+        .map(|p| fmt.price(p))
         .unwrap_or_else(|| "N/A".to_string());
     let w52_low = q
         .fifty_two_week_low
-        .map(format_price)
+        .map(|p| fmt.price(p))
         .unwrap_or_else(|| "N/A".to_string());
     lines.push(Line::from(vec![
         Span::raw("52W High:       "),
-        Span::styled(w52_high, Style::default().fg(Color::Green)),
+        Span::styled(w52_high, Style::default().fg(theme.gain)),
         Span::raw("  52W Low: "),
-        Span::styled(w52_low, Style::default().fg(Color::Red)),
+        Span::styled(w52_low, Style::default().fg(theme.loss)),
     ]));
     if let (Some(high), Some(low)) = (q.fifty_two_week_high, q.fifty_two_week_low)
         && high > low
@@ -145,10 +154,14 @@ fn detail_range_section(q: &StockQuote) -> Vec<Line<'static>> {
     lines
 }
 
-fn detail_fundamentals_section(q: &StockQuote) -> Vec<Line<'static>> {
+fn detail_fundamentals_section(
+    q: &StockQuote,
+    theme: &Theme,
+    fmt: &NumberFormat,
+) -> Vec<Line<'static>> {
     let market_cap_str = q
         .market_cap
-        .map(format_market_cap)
+        .map(|cap| fmt.compact(cap as f64))
         .unwrap_or_else(|| "N/A".to_string());
     let pe_str = q
         .trailing_pe
@@ -156,15 +169,15 @@ fn detail_fundamentals_section(q: &StockQuote) -> Vec<Line<'static>> {
         .unwrap_or_else(|| "N/A".to_string());
     let div_yield_str = q
         .dividend_yield
-        .map(|v| format!("{:.2}%", v * 100.0))
+        .map(|v| fmt.percent(v * 100.0))
         .unwrap_or_else(|| "N/A".to_string());
 
     vec![
         Line::from(""),
-        section_divider("Fundamentals"),
+        section_divider("Fundamentals", theme),
         Line::from(vec![
             Span::raw("Market Cap:     "),
-            Span::styled(market_cap_str, Style::default().fg(Color::Cyan)),
+            Span::styled(market_cap_str, Style::default().fg(theme.accent)),
         ]),
         Line::from(vec![
             Span::raw("P/E Ratio:      "),
@@ -175,7 +188,7 @@ fn detail_fundamentals_section(q: &StockQuote) -> Vec<Line<'static>> {
     ]
 }
 
-fn detail_risk_section(q: &StockQuote) -> Vec<Line<'static>> {
+fn detail_risk_section(q: &StockQuote, theme: &Theme, fmt: &NumberFormat) -> Vec<Line<'static>> {
     let value = q.price * q.volume as f64;
     let beta_str = q
         .beta
@@ -183,33 +196,37 @@ fn detail_risk_section(q: &StockQuote) -> Vec<Line<'static>> {
         .unwrap_or_else(|| "N/A".to_string());
     let avg_vol_str = q
         .average_volume
-        .map(format_volume)
+        .map(|v| fmt.volume(v))
         .unwrap_or_else(|| "N/A".to_string());
 
     vec![
         Line::from(""),
-        section_divider("Risk & Liquidity"),
+        section_divider("Risk & Liquidity", theme),
         Line::from(vec![Span::raw("Beta:           "), Span::raw(beta_str)]),
         Line::from(vec![
             Span::raw("Volume:         "),
-            Span::raw(format_volume(q.volume)),
+            Span::raw(fmt.volume(q.volume)),
             Span::raw("  Avg Vol: "),
             Span::raw(avg_vol_str),
         ]),
         Line::from(vec![
             Span::raw("Value:          "),
-            Span::styled(format_value(value), Style::default().fg(Color::Cyan)),
+            Span::styled(fmt.value(value), Style::default().fg(theme.accent)),
         ]),
     ]
 }
 
-fn detail_news_section(news: Option<&[NewsItem]>, loading: bool) -> Vec<Line<'static>> {
-    let mut lines = vec![Line::from(""), section_divider("News")];
+fn detail_news_section(
+    news: Option<&[NewsItem]>,
+    loading: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(""), section_divider("News", theme)];
 
     if loading {
         lines.push(Line::from(Span::styled(
             "Loading news...",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
         return lines;
     }
@@ -219,7 +236,7 @@ fn detail_news_section(news: Option<&[NewsItem]>, loading: bool) -> Vec<Line<'st
         _ => {
             lines.push(Line::from(Span::styled(
                 "No news available",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )));
             return lines;
         }
@@ -235,15 +252,26 @@ fn detail_news_section(news: Option<&[NewsItem]>, loading: bool) -> Vec<Line<'st
         };
         lines.push(Line::from(Span::styled(
             meta,
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     }
 
     lines
 }
 
-pub fn draw_stock_detail(frame: &mut Frame, app: &App) {
-    let area = centered_rect(55, 85, frame.area());
+pub fn draw_stock_detail(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let compact = app.config.density == Density::Compact;
+    let area = if compact {
+        centered_rect(70, 95, frame.area())
+    } else {
+        centered_rect(55, 85, frame.area())
+    };
     frame.render_widget(Clear, area);
 
     let quote = match app.get_detail_quote() {
@@ -255,8 +283,8 @@ pub fn draw_stock_detail(frame: &mut Frame, app: &App) {
     let outer_block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -266,30 +294,119 @@ pub fn draw_stock_detail(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(20), Constraint::Length(5)])
         .split(inner_area);
 
-    let mut content = detail_header(quote);
-    content.extend(detail_price_section(quote));
-    content.extend(detail_range_section(quote));
-    content.extend(detail_fundamentals_section(quote));
-    content.extend(detail_risk_section(quote));
+    let mut content = detail_header(quote, app.get_detail_note(), theme);
+    content.extend(detail_price_section(quote, theme, highlights, fmt));
+    content.extend(detail_range_section(quote, theme, fmt));
+    content.extend(detail_fundamentals_section(quote, theme, fmt));
+    content.extend(detail_risk_section(quote, theme, fmt));
     content.extend(detail_news_section(
         app.detail_news.as_deref(),
         app.news_loading,
+        theme,
     ));
     content.push(Line::from(""));
     content.push(Line::from(Span::styled(
-        "[Enter/Esc] Close",
-        Style::default().fg(Color::DarkGray),
+        "[[/]] Chart range  [r] Refresh  [Enter/Esc] Close",
+        Style::default().fg(theme.muted),
     )));
+    if compact {
+        // Drop the blank spacer lines between sections
+        content.retain(|line| line.width() > 0);
+    }
 
     frame.render_widget(
         Paragraph::new(content).alignment(Alignment::Left),
         chunks[0],
     );
-    draw_sparkline(frame, chunks[1], app);
+    draw_sparkline(
+        frame,
+        chunks[1],
+        app.detail_chart.as_ref(),
+        app.chart_loading,
+        app.chart_range,
+        theme,
+        fmt,
+    );
+}
+
+/// Two symbols side by side: each column gets the detail popup's price,
+/// range, fundamentals and risk sections over its own sparkline.
+pub fn draw_compare(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let compact = app.config.density == Density::Compact;
+    let area = if compact {
+        centered_rect(95, 95, frame.area())
+    } else {
+        centered_rect(90, 85, frame.area())
+    };
+    frame.render_widget(Clear, area);
+
+    let title = format!(" Compare {} ", app.compare_symbols.join(" vs "));
+    let outer_block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.backdrop));
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let count = app.compare_symbols.len().max(1) as u32;
+    let columns = Layout::horizontal(vec![Constraint::Ratio(1, count); count as usize])
+        .spacing(2)
+        .split(inner_area);
+    for (symbol, &column) in app.compare_symbols.iter().zip(columns.iter()) {
+        let [text_area, chart_area] =
+            Layout::vertical([Constraint::Min(10), Constraint::Length(5)]).areas(column);
+        let mut content = vec![Line::from(Span::styled(
+            symbol.clone(),
+            Style::default()
+                .fg(theme.header)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        match app.compare_quote(symbol) {
+            Some(quote) => {
+                content.extend(detail_header(quote, None, theme));
+                content.extend(detail_price_section(quote, theme, highlights, fmt));
+                content.extend(detail_range_section(quote, theme, fmt));
+                content.extend(detail_fundamentals_section(quote, theme, fmt));
+                content.extend(detail_risk_section(quote, theme, fmt));
+            }
+            None => content.push(Line::from(Span::styled(
+                format!("No quote for {}", symbol),
+                Style::default().fg(theme.muted),
+            ))),
+        }
+        if compact {
+            content.retain(|line| line.width() > 0);
+        }
+        frame.render_widget(Paragraph::new(content), text_area);
+        draw_sparkline(
+            frame,
+            chart_area,
+            app.compare_charts.get(symbol),
+            app.chart_loading,
+            app.chart_range,
+            theme,
+            fmt,
+        );
+    }
 }
 
-fn draw_sparkline(frame: &mut Frame, area: Rect, app: &App) {
-    if let Some(ref chart) = app.detail_chart {
+fn draw_sparkline(
+    frame: &mut Frame,
+    area: Rect,
+    chart: Option<&ChartData>,
+    loading: bool,
+    chart_range: ChartRange,
+    theme: &Theme,
+    fmt: &NumberFormat,
+) {
+    if let Some(chart) = chart {
         let chart_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Length(10), Constraint::Min(20)])
@@ -298,46 +415,52 @@ fn draw_sparkline(frame: &mut Frame, area: Rect, app: &App) {
         let min = chart.low;
         let max = chart.high;
         let range = max - min;
+        // Longer ranges can exceed the available width; keep the most recent closes
+        let width = chart_chunks[1].width as usize;
+        let closes = &chart.closes[chart.closes.len().saturating_sub(width)..];
         let data: Vec<u64> = if range > 0.0 {
-            chart
-                .closes
+            closes
                 .iter()
                 .map(|&v| ((v - min) / range * 100.0) as u64)
                 .collect()
         } else {
-            chart.closes.iter().map(|_| 50u64).collect()
+            closes.iter().map(|_| 50u64).collect()
         };
 
         let y_axis_content = vec![
             Line::from(Span::styled(
-                format_price(max),
-                Style::default().fg(Color::Green),
+                fmt.price(max),
+                Style::default().fg(theme.gain),
             )),
             Line::from(""),
             Line::from(""),
             Line::from(Span::styled(
-                format_price(min),
-                Style::default().fg(Color::Red),
+                fmt.price(min),
+                Style::default().fg(theme.loss),
             )),
         ];
         let y_axis = Paragraph::new(y_axis_content)
             .alignment(Alignment::Right)
-            .block(Block::default().title(" 3M ").borders(Borders::TOP));
+            .block(
+                Block::default()
+                    .title(format!(" {} ", chart_range.label()))
+                    .borders(Borders::TOP),
+            );
         frame.render_widget(y_axis, chart_chunks[0]);
 
         let sparkline = Sparkline::default()
             .block(Block::default().borders(Borders::TOP))
             .data(&data)
-            .style(Style::default().fg(Color::Cyan));
+            .style(Style::default().fg(theme.accent));
         frame.render_widget(sparkline, chart_chunks[1]);
-    } else if app.chart_loading {
+    } else if loading {
         let loading = Paragraph::new("Loading chart...")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(Block::default().borders(Borders::TOP));
         frame.render_widget(loading, area);
     } else {
         let no_data = Paragraph::new("Chart data unavailable")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(Block::default().borders(Borders::TOP));
         frame.render_widget(no_data, area);
     }
@@ -1,6 +1,89 @@
+use crate::config::NumberLocale;
+
+/// How the UI writes prices, percentages and abbreviated values, from
+/// `Config::number_format`. Draw code gets it passed alongside the `Theme`;
+/// the plain `format_*` functions below use the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub locale: NumberLocale,
+    pub price_decimals: u8,
+    pub percent_decimals: u8,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            locale: NumberLocale::default(),
+            price_decimals: DEFAULT_DECIMALS,
+            percent_decimals: DEFAULT_DECIMALS,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn price(&self, price: f64) -> String {
+        format_price_places(price, self.locale, self.price_decimals)
+    }
+
+    /// `1.23%` with the configured percent decimals.
+    pub fn percent(&self, pct: f64) -> String {
+        format_percent_places(pct, self.percent_decimals, false)
+    }
+
+    /// `+1.23%` / `-1.23%` with the configured percent decimals.
+    pub fn signed_percent(&self, pct: f64) -> String {
+        format_percent_places(pct, self.percent_decimals, true)
+    }
+
+    pub fn compact(&self, value: f64) -> String {
+        format_compact_with(value, self.locale)
+    }
+
+    /// Signed compact value: `+1.50M` / `-1.50M`.
+    pub fn pl(&self, pl: f64) -> String {
+        let prefix = if pl >= 0.0 { "+" } else { "-" };
+        format!("{}{}", prefix, self.compact(pl))
+    }
+
+    pub fn volume(&self, volume: u64) -> String {
+        self.compact(volume as f64)
+    }
+
+    pub fn value(&self, value: f64) -> String {
+        self.compact(value)
+    }
+}
+
+/// Decimal places for prices and percentages unless configured otherwise.
+pub const DEFAULT_DECIMALS: u8 = 2;
+
+/// Most decimal places the display settings accept.
+pub const MAX_DECIMALS: u8 = 6;
+
+/// (thousands separator, decimal separator)
+fn separators(locale: NumberLocale) -> (&'static str, char) {
+    match locale {
+        NumberLocale::En => (",", '.'),
+        NumberLocale::Id => (".", ','),
+    }
+}
+
 pub fn format_price(price: f64) -> String {
+    NumberFormat::default().price(price)
+}
+
+pub fn format_price_with(price: f64, locale: NumberLocale) -> String {
+    format_price_places(price, locale, DEFAULT_DECIMALS)
+}
+
+/// `price` with `places` decimals. From 1,000 up the thousands are grouped
+/// and a fraction that rounds to zero is left off.
+pub fn format_price_places(price: f64, locale: NumberLocale, places: u8) -> String {
+    let (group, decimal) = separators(locale);
+    let places = places.min(MAX_DECIMALS) as usize;
+    let scale = 10f64.powi(places as i32);
     if price >= 1000.0 {
-        let rounded = (price * 100.0).round() / 100.0;
+        let rounded = (price * scale).round() / scale;
         let int_part = rounded as u64;
         let formatted_int: String = int_part
             .to_string()
@@ -9,15 +92,32 @@ pub fn format_price(price: f64) -> String {
             .rev()
             .map(|chunk| std::str::from_utf8(chunk).unwrap())
             .collect::<Vec<_>>()
-            .join(",");
-        let frac = rounded - int_part as f64;
-        if frac.abs() >= 0.005 {
-            format!("{}.{:02}", formatted_int, (frac * 100.0).round() as u64)
+            .join(group);
+        let frac = ((rounded - int_part as f64) * scale).round() as u64;
+        if frac > 0 {
+            format!("{}{}{:0places$}", formatted_int, decimal, frac)
         } else {
             formatted_int
         }
     } else {
-        format!("{:.2}", price)
+        format!("{:.places$}", price).replace('.', &decimal.to_string())
+    }
+}
+
+pub fn format_percent(pct: f64) -> String {
+    NumberFormat::default().percent(pct)
+}
+
+pub fn format_signed_percent(pct: f64) -> String {
+    NumberFormat::default().signed_percent(pct)
+}
+
+pub fn format_percent_places(pct: f64, places: u8, signed: bool) -> String {
+    let places = places.min(MAX_DECIMALS) as usize;
+    if signed {
+        format!("{:+.places$}%", pct)
+    } else {
+        format!("{:.places$}%", pct)
     }
 }
 
@@ -30,47 +130,109 @@ pub fn format_change(change: f64) -> String {
 }
 
 pub fn format_compact(value: f64) -> String {
+    NumberFormat::default().compact(value)
+}
+
+pub fn format_compact_with(value: f64, locale: NumberLocale) -> String {
+    let suffixes = match locale {
+        NumberLocale::En => ["T", "B", "M", "K"],
+        NumberLocale::Id => ["T", "M", "jt", "rb"],
+    };
+    let (_, decimal) = separators(locale);
     let abs = value.abs();
-    if abs >= 1_000_000_000_000.0 {
-        format!("{:.2}T", abs / 1_000_000_000_000.0)
+    let (scaled, suffix) = if abs >= 1_000_000_000_000.0 {
+        (abs / 1_000_000_000_000.0, suffixes[0])
     } else if abs >= 1_000_000_000.0 {
-        format!("{:.2}B", abs / 1_000_000_000.0)
+        (abs / 1_000_000_000.0, suffixes[1])
     } else if abs >= 1_000_000.0 {
-        format!("{:.2}M", abs / 1_000_000.0)
+        (abs / 1_000_000.0, suffixes[2])
     } else if abs >= 1_000.0 {
-        format!("{:.2}K", abs / 1_000.0)
+        (abs / 1_000.0, suffixes[3])
     } else {
-        format!("{:.0}", abs)
-    }
+        return format!("{:.0}", abs);
+    };
+    format!("{:.2}{}", scaled, suffix).replace('.', &decimal.to_string())
 }
 
 pub fn format_pl(pl: f64) -> String {
-    let prefix = if pl >= 0.0 { "+" } else { "-" };
-    format!("{}{}", prefix, format_compact(pl))
+    NumberFormat::default().pl(pl)
 }
 
 pub fn format_volume(volume: u64) -> String {
-    format_compact(volume as f64)
+    NumberFormat::default().volume(volume)
 }
 
 pub fn format_value(value: f64) -> String {
-    format_compact(value)
+    NumberFormat::default().value(value)
 }
 
 pub fn format_market_cap(cap: u64) -> String {
     format_compact(cap as f64)
 }
 
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Braille spinner glyph for animation frame `frame` (wraps around).
+pub fn spinner_glyph(frame: usize) -> char {
+    SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]
+}
+
+/// Truncate to at most `max_len` chars, ending in "..." when shortened.
+/// Cuts on char boundaries so multi-byte names never panic.
 pub fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.chars().count() > max_len {
-        let end = s
-            .char_indices()
-            .nth(max_len - 3)
-            .map(|(i, _)| i)
-            .unwrap_or(s.len());
-        format!("{}...", &s[..end])
-    } else {
-        s.to_string()
+    if s.chars().count() <= max_len {
+        return s.to_string();
+    }
+    if max_len < 3 {
+        return s.chars().take(max_len).collect();
+    }
+    let end = s
+        .char_indices()
+        .nth(max_len - 3)
+        .map(|(i, _)| i)
+        .unwrap_or(s.len());
+    format!("{}...", &s[..end])
+}
+
+/// Render values as a block sparkline of at most `width` chars, sampling
+/// evenly across the series when it is longer than the width.
+pub fn sparkline_str(values: &[f64], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let n = values.len().min(width);
+    let sampled: Vec<f64> = (0..n)
+        .map(|i| {
+            let idx = if n == 1 {
+                values.len() - 1
+            } else {
+                i * (values.len() - 1) / (n - 1)
+            };
+            values[idx]
+        })
+        .collect();
+    let min = sampled.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = sampled.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    sampled
+        .iter()
+        .map(|&v| {
+            if range > 0.0 {
+                BARS[(((v - min) / range) * 7.0).round() as usize]
+            } else {
+                BARS[3]
+            }
+        })
+        .collect()
+}
+
+/// Compact age for the header: `42s`, `5m`, `3h`.
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
     }
 }
 
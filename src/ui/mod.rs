@@ -9,20 +9,30 @@ mod tables;
 
 pub(crate) use bookmarks::BOOKMARK_SORTABLE_COLUMNS;
 pub(crate) use news::NEWS_SORTABLE_COLUMNS;
-pub(crate) use tables::{PORTFOLIO_SORTABLE_COLUMNS, WATCHLIST_SORTABLE_COLUMNS};
+pub(crate) use tables::{
+    PORTFOLIO_SORTABLE_COLUMNS, WATCHLIST_SORTABLE_COLUMNS, portfolio_column_names,
+    watchlist_column_names,
+};
 
-use formatters::format_price;
+use formatters::{NumberFormat, format_age};
 
-use crate::app::{App, InputMode, NewsTab, ViewMode};
+use crate::app::{App, InputMode, NewsTab, RefreshHealth, ViewMode, parse_price};
+use crate::config::{Density, Highlights, Theme};
+use crate::keymap::Action;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 use std::time::Duration;
 
+/// Smallest terminal the normal layout is drawn in; below it `draw` shows
+/// only a resize hint.
+pub const MIN_TERMINAL_WIDTH: u16 = 60;
+pub const MIN_TERMINAL_HEIGHT: u16 = 16;
+
 pub(super) fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -42,77 +52,209 @@ pub(super) fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect
         .split(popup_layout[1])[1]
 }
 
+/// Block around the tables, header and footer: bordered normally, bare
+/// (title row only) when compact.
+fn chrome_block(density: Density) -> Block<'static> {
+    match density {
+        Density::Normal => Block::default().borders(Borders::ALL),
+        Density::Compact => Block::default(),
+    }
+}
+
+/// Body rows and usable width of a table drawn in `area` inside
+/// `chrome_block`, after the block chrome and the column header row.
+fn table_metrics(area: Rect, density: Density) -> (usize, u16) {
+    match density {
+        Density::Normal => (
+            area.height.saturating_sub(3) as usize,
+            area.width.saturating_sub(2),
+        ),
+        Density::Compact => (area.height.saturating_sub(2) as usize, area.width),
+    }
+}
+
 pub fn draw(frame: &mut Frame, app: &mut App) {
+    // `--no-color` draws with the terminal's default colors throughout
+    let (theme, highlights) = if app.no_color {
+        (Theme::no_color(), app.config.highlights().without_color())
+    } else {
+        (app.config.theme(), app.config.highlights())
+    };
+
+    let area = frame.area();
+    if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+        draw_too_small(frame, area, &theme);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
+            Constraint::Length(app.config.density.bar_height()),
             Constraint::Min(10),
-            Constraint::Length(3),
+            Constraint::Length(app.config.density.bar_height()),
         ])
         .split(frame.area());
 
-    draw_header(frame, chunks[0], app);
+    let fmt = app.config.number_format();
+    draw_header(frame, chunks[0], app, &theme, &highlights, &fmt);
 
     match app.view_mode {
-        ViewMode::Watchlist => tables::draw_watchlist(frame, chunks[1], app),
-        ViewMode::Portfolio => tables::draw_portfolio(frame, chunks[1], app),
+        ViewMode::Watchlist => {
+            tables::draw_watchlist(frame, chunks[1], app, &theme, &highlights, &fmt)
+        }
+        ViewMode::Portfolio => {
+            tables::draw_portfolio(frame, chunks[1], app, &theme, &highlights, &fmt)
+        }
         ViewMode::News => {
             if app.news_tab == NewsTab::Bookmarks {
-                bookmarks::draw_bookmarks(frame, chunks[1], app);
+                bookmarks::draw_bookmarks(frame, chunks[1], app, &theme);
             } else {
-                news::draw_news(frame, chunks[1], app);
+                news::draw_news(frame, chunks[1], app, &theme);
             }
         }
     }
 
-    draw_footer(frame, chunks[2], app);
+    draw_footer(frame, chunks[2], app, &theme, &fmt);
+    if app.input_mode == InputMode::Adding && !app.add_suggestions.is_empty() {
+        modals::draw_add_suggestions(frame, app, chunks[2], &theme);
+    }
 
     if app.input_mode == InputMode::StockDetail {
-        modals::draw_stock_detail(frame, app);
+        modals::draw_stock_detail(frame, app, &theme, &highlights, &fmt);
+    }
+    if app.input_mode == InputMode::EventLog {
+        modals::draw_event_log(frame, app, &theme);
+    }
+    if app.input_mode == InputMode::Compare {
+        modals::draw_compare(frame, app, &theme, &highlights, &fmt);
     }
     if app.input_mode == InputMode::Help {
-        modals::draw_help(frame, app);
+        modals::draw_help(frame, app, &theme);
     }
     if app.input_mode == InputMode::ExportMenu {
-        modals::draw_export_menu(frame, app);
+        modals::draw_export_menu(frame, app, &theme);
     }
     if app.input_mode == InputMode::PortfolioChart {
-        modals::draw_portfolio_chart(frame, app);
+        modals::draw_portfolio_chart(frame, app, &theme, &fmt);
+    }
+    if app.input_mode == InputMode::TopMovers {
+        modals::draw_top_movers(frame, app, &theme, &highlights, &fmt);
+    }
+    if app.input_mode == InputMode::PortfolioPL {
+        modals::draw_portfolio_pl(frame, app, &theme, &highlights, &fmt);
+    }
+    if matches!(
+        app.input_mode,
+        InputMode::Rebalance | InputMode::RebalanceTarget
+    ) {
+        modals::draw_rebalance(frame, app, &theme);
+    }
+    if app.input_mode == InputMode::PortfolioHistory {
+        modals::draw_portfolio_history(frame, app, &theme, &highlights, &fmt);
     }
     if app.input_mode == InputMode::NewsDetail {
-        news_detail::draw_news_detail(frame, app);
+        news_detail::draw_news_detail(frame, app, &theme);
     }
     if app.input_mode == InputMode::BookmarkDetail {
-        bookmark_detail::draw_bookmark_detail(frame, app);
+        bookmark_detail::draw_bookmark_detail(frame, app, &theme);
+    }
+    if matches!(
+        app.input_mode,
+        InputMode::NewsSources | InputMode::NewsSourceAdd | InputMode::NewsSourceCategory
+    ) {
+        modals::draw_news_sources(frame, app, &theme);
+    }
+    if app.input_mode == InputMode::ColumnPicker {
+        modals::draw_column_picker(frame, app, &theme);
     }
     if app.input_mode == InputMode::BookmarkClearConfirm {
-        modals::draw_bookmark_clear_confirm(frame);
+        modals::draw_bookmark_clear_confirm(frame, &theme);
     }
     if matches!(
         app.input_mode,
-        InputMode::AlertList | InputMode::AlertAddType | InputMode::AlertAddValue
+        InputMode::AlertList
+            | InputMode::AlertAddType
+            | InputMode::AlertAddValue
+            | InputMode::AlertCooldown
     ) {
-        modals::draw_alert_modal(frame, app);
+        modals::draw_alert_modal(frame, app, &theme);
+    }
+}
+
+/// " → Cost X, P/L Y" for the holding edit in progress, with "—" for
+/// values that can't be computed yet.
+fn edit_preview_span(app: &App, theme: &Theme, fmt: &NumberFormat) -> Span<'static> {
+    let (cost, pl) = match app.portfolio_edit_preview() {
+        Some((cost, pl)) => (fmt.value(cost), pl.map_or("—".to_string(), |pl| fmt.pl(pl))),
+        None => ("—".to_string(), "—".to_string()),
+    };
+    Span::styled(
+        format!(" → Cost {}, P/L {}", cost, pl),
+        Style::default().fg(theme.muted),
+    )
+}
+
+/// `= 8080` after a price prompt holding an expression rather than a number.
+fn price_expression_span(app: &App, theme: &Theme, fmt: &NumberFormat) -> Span<'static> {
+    let input = app.input_buffer.trim();
+    match parse_price(input) {
+        Some(price) if input.parse::<f64>().is_err() => Span::styled(
+            format!(" = {}", fmt.price(price)),
+            Style::default().fg(theme.accent),
+        ),
+        _ => Span::raw(""),
     }
 }
 
-fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+fn draw_too_small(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let message = format!(
+        "Terminal too small (need ≥ {}x{}, have {}x{})",
+        MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT, area.width, area.height
+    );
+    // Narrow terminals wrap the message onto a few lines
+    let [middle] = Layout::vertical([Constraint::Length(3)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(
+        Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(theme.header)),
+        middle,
+    );
+}
+
+fn draw_header(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
     let current_time = chrono::Local::now().format("%H:%M:%S").to_string();
     let status = if app.loading {
         "[Loading...]".to_string()
+    } else if !app.market_open_now() {
+        format!("[Market closed] [{}]", current_time)
     } else {
         format!("[{}]", current_time)
     };
+    let status = if app.offline {
+        format!("[Offline] {}", status)
+    } else {
+        status
+    };
 
     let (view_indicator, view_color) = match app.view_mode {
-        ViewMode::Watchlist => (app.watchlist_indicator(), Color::Yellow),
-        ViewMode::Portfolio => (app.portfolio_indicator(), Color::Magenta),
+        ViewMode::Watchlist => (app.watchlist_indicator(), theme.header),
+        ViewMode::Portfolio => (app.portfolio_indicator(), theme.portfolio_header),
         ViewMode::News => match app.news_tab {
-            NewsTab::Feed => ("News > Feed".to_string(), Color::Blue),
+            NewsTab::Feed => ("News > Feed".to_string(), theme.news_header),
             NewsTab::Bookmarks => (
                 format!("News > Bookmarks ({})", app.config.bookmarks.len()),
-                Color::Green,
+                theme.gain,
             ),
         },
     };
@@ -120,7 +262,7 @@ fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     let filter_span = if app.search_active {
         Span::styled(
             format!(" (filtered: {})", app.search_query),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         )
     } else {
         Span::raw("")
@@ -128,21 +270,19 @@ fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 
     // Build IHSG display
     let ihsg_spans: Vec<Span> = if let Some(q) = app.get_ihsg_quote() {
-        let change_color = if q.change_percent >= 0.0 {
-            Color::Green
-        } else {
-            Color::Red
-        };
+        let change_color = highlights.change_color(theme, q.change_percent, false);
         vec![
-            Span::styled("IHSG ", Style::default().fg(Color::White)),
+            Span::styled("IHSG ", Style::default().fg(theme.text)),
             Span::styled(
-                format_price(q.price),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
+                fmt.price(q.price),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                format!(" {:+.2}%", q.change_percent),
+                format!(
+                    " {}{}",
+                    highlights.arrow(q.change_percent),
+                    fmt.signed_percent(q.change_percent)
+                ),
                 Style::default()
                     .fg(change_color)
                     .add_modifier(Modifier::BOLD),
@@ -151,8 +291,8 @@ fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         ]
     } else {
         vec![
-            Span::styled("IHSG ", Style::default().fg(Color::DarkGray)),
-            Span::styled("---", Style::default().fg(Color::DarkGray)),
+            Span::styled("IHSG ", Style::default().fg(theme.muted)),
+            Span::styled("---", Style::default().fg(theme.muted)),
             Span::raw(" "),
         ]
     };
@@ -162,10 +302,10 @@ fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         Span::styled(
             " IDX Stock Tracker ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled("| ", Style::default().fg(Color::DarkGray)),
+        Span::styled("| ", Style::default().fg(theme.muted)),
         Span::styled(
             view_indicator,
             Style::default().fg(view_color).add_modifier(Modifier::BOLD),
@@ -173,86 +313,269 @@ fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         filter_span,
     ];
 
-    // Right side: IHSG + clock
-    let mut right_spans = ihsg_spans;
-    right_spans.push(Span::styled(status, Style::default().fg(Color::DarkGray)));
+    // Portfolio total value and day change (Portfolio view only)
+    let portfolio_spans: Vec<Span> = if app.view_mode != ViewMode::Portfolio {
+        Vec::new()
+    } else if let Some((day_pl, day_pct)) = app.portfolio_day_change() {
+        let total_value: f64 = app.portfolio_allocation().iter().map(|(_, v, _)| v).sum();
+        let change_color = highlights.change_color(theme, day_pct, false);
+        vec![
+            Span::styled("Value ", Style::default().fg(theme.text)),
+            Span::styled(
+                fmt.value(total_value),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " {}{} ({})",
+                    highlights.arrow(day_pl),
+                    fmt.pl(day_pl),
+                    fmt.signed_percent(day_pct)
+                ),
+                Style::default()
+                    .fg(change_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" | ", Style::default().fg(theme.muted)),
+        ]
+    } else {
+        vec![
+            Span::styled("Value ", Style::default().fg(theme.muted)),
+            Span::styled("---", Style::default().fg(theme.muted)),
+            Span::styled(" | ", Style::default().fg(theme.muted)),
+        ]
+    };
+
+    let health_span = match app.refresh_health() {
+        RefreshHealth::NeverLoaded if app.loading => Span::raw(""),
+        RefreshHealth::NeverLoaded => {
+            Span::styled("not loaded ", Style::default().fg(theme.header))
+        }
+        RefreshHealth::Fresh(age) => Span::styled(
+            format!("updated {} ago ", format_age(age)),
+            Style::default().fg(theme.muted),
+        ),
+        RefreshHealth::Late(age) => Span::styled(
+            format!("updated {} ago ", format_age(age)),
+            Style::default().fg(theme.header),
+        ),
+        RefreshHealth::Stale(age) => Span::styled(
+            format!("stale {} ", format_age(age)),
+            Style::default().fg(theme.loss).add_modifier(Modifier::BOLD),
+        ),
+    };
+
+    // Right side: portfolio day change + IHSG + refresh age + clock
+    let mut right_spans = portfolio_spans;
+    right_spans.extend(ihsg_spans);
+    right_spans.push(health_span);
+    right_spans.push(Span::styled(status, Style::default().fg(theme.muted)));
     right_spans.push(Span::raw(" "));
 
     // Calculate widths to insert spacer
     let left_width: usize = left_spans.iter().map(|s| s.width()).sum();
     let right_width: usize = right_spans.iter().map(|s| s.width()).sum();
-    let inner_width = area.width.saturating_sub(2) as usize; // -2 for borders
+    let inner_width = chrome_block(app.config.density).inner(area).width as usize;
     let spacer_width = inner_width.saturating_sub(left_width + right_width);
 
     let mut all_spans = left_spans;
     all_spans.push(Span::raw(" ".repeat(spacer_width)));
     all_spans.extend(right_spans);
 
-    let header =
-        Paragraph::new(Line::from(all_spans)).block(Block::default().borders(Borders::ALL));
+    let header = Paragraph::new(Line::from(all_spans)).block(chrome_block(app.config.density));
 
     frame.render_widget(header, area);
 }
 
-fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+/// " [a] Add [d] Del ... " for the footer, with each action's key from the
+/// keymap. Actions grouped in one entry share its brackets ("[↑↓] Nav").
+fn key_hints(app: &App, hints: &[(&[Action], &str)]) -> String {
+    let mut text = String::new();
+    for (actions, label) in hints {
+        let keys: String = actions.iter().map(|&a| app.keymap.hint(a)).collect();
+        text.push_str(&format!(" [{}] {}", keys, label));
+    }
+    text.push(' ');
+    text
+}
+
+/// " [c/Enter/Esc] Close allocation chart " for a modal opened by `action`,
+/// which its key also closes.
+fn close_hint(app: &App, action: Action, what: &str) -> String {
+    format!(" [{}/Enter/Esc] Close {} ", app.keymap.hint(action), what)
+}
+
+/// " ▲12 ▼5 =3 avg +0.42% |" for the footer, empty until something is quoted.
+fn breadth_spans(app: &App, theme: &Theme, fmt: &NumberFormat) -> Vec<Span<'static>> {
+    let breadth = app.watchlist_breadth();
+    let Some(avg) = breadth.avg_change_pct else {
+        return Vec::new();
+    };
+    let avg_color = if avg > 0.0 {
+        theme.gain
+    } else if avg < 0.0 {
+        theme.loss
+    } else {
+        theme.muted
+    };
+    vec![
+        Span::styled(
+            format!(" ▲{}", breadth.advancers),
+            Style::default().fg(theme.gain),
+        ),
+        Span::styled(
+            format!(" ▼{}", breadth.decliners),
+            Style::default().fg(theme.loss),
+        ),
+        Span::styled(
+            format!(" ={}", breadth.unchanged),
+            Style::default().fg(theme.muted),
+        ),
+        Span::styled(
+            format!(" avg {}", fmt.signed_percent(avg)),
+            Style::default().fg(avg_color),
+        ),
+        Span::raw(" |"),
+    ]
+}
+
+fn draw_footer(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    theme: &Theme,
+    fmt: &NumberFormat,
+) {
     let content = match app.input_mode {
         InputMode::Normal => {
             let help = match app.view_mode {
-                ViewMode::Watchlist => {
-                    " [a] Add [d] Del [A] Alerts [e] Export [r] Refresh [s] Sort [p] Portfolio [Enter] Detail [↑↓] Nav [←→] WL [?] Help "
-                }
-                ViewMode::Portfolio => {
-                    " [a] Add [e] Edit [A] Alerts [d] Del [r] Refresh [s] Sort [c] Chart [p] News [Enter] Detail [↑↓] Nav [←→] Port [?] Help "
-                }
+                ViewMode::Watchlist => key_hints(
+                    app,
+                    &[
+                        (&[Action::Add], "Add"),
+                        (&[Action::Delete], "Del"),
+                        (&[Action::Alerts], "Alerts"),
+                        (&[Action::Edit], "Export"),
+                        (&[Action::Refresh], "Refresh"),
+                        (&[Action::SortColumn], "Sort"),
+                        (&[Action::ToggleView], "Portfolio"),
+                        (&[Action::Open], "Detail"),
+                        (&[Action::Up, Action::Down], "Nav"),
+                        (&[Action::PrevList, Action::NextList], "WL"),
+                        (&[Action::AllWatchlists], "All"),
+                        (&[Action::Help], "Help"),
+                    ],
+                ),
+                ViewMode::Portfolio => key_hints(
+                    app,
+                    &[
+                        (&[Action::Add], "Add"),
+                        (&[Action::Edit], "Edit"),
+                        (&[Action::Alerts], "Alerts"),
+                        (&[Action::Delete], "Del"),
+                        (&[Action::Refresh], "Refresh"),
+                        (&[Action::SortColumn], "Sort"),
+                        (&[Action::AllocationChart], "Chart"),
+                        (&[Action::ToggleView], "News"),
+                        (&[Action::Open], "Detail"),
+                        (&[Action::Up, Action::Down], "Nav"),
+                        (&[Action::PrevList, Action::NextList], "Port"),
+                        (&[Action::Help], "Help"),
+                    ],
+                ),
                 ViewMode::News => {
                     if app.news_tab == NewsTab::Bookmarks {
-                        " [d] Remove [D] Clear all [m] Toggle read [s] Sort [/] Search [Enter] Detail [↑↓] Nav [←→] Tab [?] Help "
+                        key_hints(
+                            app,
+                            &[
+                                (&[Action::Delete], "Remove"),
+                                (&[Action::DeleteList], "Clear all"),
+                                (&[Action::ToggleRead], "Toggle read"),
+                                (&[Action::SortColumn], "Sort"),
+                                (&[Action::Search], "Search"),
+                                (&[Action::Open], "Detail"),
+                                (&[Action::Up, Action::Down], "Nav"),
+                                (&[Action::PrevList, Action::NextList], "Tab"),
+                                (&[Action::Help], "Help"),
+                            ],
+                        )
                     } else {
-                        " [b] Bookmark [r] Refresh [s] Sort [/] Search [Enter] Preview [↑↓] Nav [←→] Tab [?] Help "
+                        key_hints(
+                            app,
+                            &[
+                                (&[Action::Bookmark], "Bookmark"),
+                                (&[Action::Refresh], "Refresh"),
+                                (&[Action::TickerFilter], "Ticker"),
+                                (&[Action::SortColumn], "Sort"),
+                                (&[Action::Search], "Search"),
+                                (&[Action::Open], "Preview"),
+                                (&[Action::Up, Action::Down], "Nav"),
+                                (&[Action::PrevList, Action::NextList], "Tab"),
+                                (&[Action::Help], "Help"),
+                            ],
+                        )
                     }
                 }
             };
-            if let Some(msg) = &app.status_message {
-                Line::from(vec![
-                    Span::styled(msg, Style::default().fg(Color::Yellow)),
-                    Span::raw(" | "),
-                    Span::styled(help, Style::default().fg(Color::DarkGray)),
-                ])
+            let mut spans = if app.view_mode == ViewMode::Watchlist {
+                breadth_spans(app, theme, fmt)
             } else {
-                Line::from(Span::styled(help, Style::default().fg(Color::DarkGray)))
+                Vec::new()
+            };
+            if let Some(msg) = &app.status_message {
+                if !spans.is_empty() {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme.header),
+                ));
+                spans.push(Span::raw(" | "));
             }
+            spans.push(Span::styled(help, Style::default().fg(theme.muted)));
+            Line::from(spans)
         }
         InputMode::Adding => Line::from(vec![
             Span::raw(" Add stock: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Cyan)),
-            Span::styled("█", Style::default().fg(Color::Cyan)),
-            Span::raw(" | [Enter] Confirm | [Esc] Cancel"),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.accent)),
+            Span::styled("█", Style::default().fg(theme.accent)),
+            Span::raw(if app.add_suggestions.is_empty() {
+                " | [Enter] Confirm | [Esc] Cancel"
+            } else {
+                " | [↑↓] Pick match | [Enter] Confirm | [Esc] Cancel"
+            }),
         ]),
         InputMode::WatchlistAdd => Line::from(vec![
             Span::raw(" New watchlist name: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Green)),
-            Span::styled("█", Style::default().fg(Color::Green)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.gain)),
+            Span::styled("█", Style::default().fg(theme.gain)),
             Span::raw(" | [Enter] Confirm | [Esc] Cancel"),
         ]),
         InputMode::WatchlistRename => Line::from(vec![
             Span::raw(" Rename watchlist: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Yellow)),
-            Span::styled("█", Style::default().fg(Color::Yellow)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.header)),
+            Span::styled("█", Style::default().fg(theme.header)),
             Span::raw(" | [Enter] Confirm | [Esc] Cancel"),
         ]),
         InputMode::PortfolioAddSymbol => Line::from(vec![
             Span::raw(" Symbol: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Magenta)),
-            Span::styled("█", Style::default().fg(Color::Magenta)),
+            Span::styled(
+                &app.input_buffer,
+                Style::default().fg(theme.portfolio_header),
+            ),
+            Span::styled("█", Style::default().fg(theme.portfolio_header)),
             Span::raw(" | [Enter] Next | [Esc] Cancel"),
         ]),
         InputMode::PortfolioAddLots => {
             let symbol = app.pending_symbol.as_deref().unwrap_or("");
             Line::from(vec![
-                Span::styled(format!("{} ", symbol), Style::default().fg(Color::Green)),
+                Span::styled(format!("{} ", symbol), Style::default().fg(theme.gain)),
                 Span::raw("Lots: "),
-                Span::styled(&app.input_buffer, Style::default().fg(Color::Magenta)),
-                Span::styled("█", Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
                 Span::raw(" | [Enter] Next | [Esc] Cancel"),
             ])
         }
@@ -262,11 +585,15 @@ fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
             Line::from(vec![
                 Span::styled(
                     format!("{} {}lot ", symbol, lots),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.gain),
                 ),
                 Span::raw("Avg Price: "),
-                Span::styled(&app.input_buffer, Style::default().fg(Color::Magenta)),
-                Span::styled("█", Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                price_expression_span(app, theme, fmt),
                 Span::raw(" | [Enter] Add | [Esc] Cancel"),
             ])
         }
@@ -274,84 +601,223 @@ fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
             let symbol = app.pending_edit_symbol.as_deref().unwrap_or("");
             Line::from(vec![
                 Span::raw(format!(" Edit {} Lots: ", symbol)),
-                Span::styled(&app.input_buffer, Style::default().fg(Color::Magenta)),
-                Span::styled("█", Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                edit_preview_span(app, theme, fmt),
                 Span::raw(" | [Enter] Next | [Esc] Cancel"),
             ])
         }
+        InputMode::PortfolioSplit => {
+            let symbol = app.pending_edit_symbol.as_deref().unwrap_or("");
+            Line::from(vec![
+                Span::raw(format!(" Split {} ratio (new:old): ", symbol)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                Span::raw(" | [Enter] Apply | [Esc] Cancel"),
+            ])
+        }
+        InputMode::PortfolioNote => {
+            let symbol = app.pending_edit_symbol.as_deref().unwrap_or("");
+            Line::from(vec![
+                Span::raw(format!(" Note for {}: ", symbol)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                Span::raw(" | [Enter] Save (empty clears) | [Esc] Cancel"),
+            ])
+        }
         InputMode::PortfolioEditPrice => {
             let symbol = app.pending_edit_symbol.as_deref().unwrap_or("");
             let lots = app.pending_lots.unwrap_or(0);
             Line::from(vec![
                 Span::styled(
                     format!(" Edit {} {}lot ", symbol, lots),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.gain),
                 ),
                 Span::raw("Avg Price: "),
-                Span::styled(&app.input_buffer, Style::default().fg(Color::Magenta)),
-                Span::styled("█", Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                price_expression_span(app, theme, fmt),
+                edit_preview_span(app, theme, fmt),
                 Span::raw(" | [Enter] Save | [Esc] Cancel"),
             ])
         }
         InputMode::StockDetail => Line::from(Span::styled(
-            " [Enter/Esc] Close detail view ",
-            Style::default().fg(Color::DarkGray),
+            " [[/]] Chart range  [r] Refresh  [o] Open in browser  [Enter/Esc] Close detail view ",
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::ComparePick => Line::from(vec![
+            Span::raw(format!(
+                " Compare {} with: ",
+                app.compare_symbols
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or("")
+            )),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.accent)),
+            Span::styled("█", Style::default().fg(theme.accent)),
+            Span::raw(" | [Enter] Compare | [Esc] Cancel"),
+        ]),
+        InputMode::EventLog => Line::from(Span::styled(
+            format!(
+                " [↑↓] Scroll  [{}/Enter/Esc] Close event log ",
+                app.keymap.hint(Action::EventLog)
+            ),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::Compare => Line::from(Span::styled(
+            close_hint(app, Action::Compare, "comparison"),
+            Style::default().fg(theme.muted),
         )),
         InputMode::Help => Line::from(Span::styled(
-            " [?/Enter/Esc] Close help ",
-            Style::default().fg(Color::DarkGray),
+            format!(
+                " Type to filter  [Esc] Clear filter / close  [{}/Enter] Close help ",
+                app.keymap.hint(Action::Help)
+            ),
+            Style::default().fg(theme.muted),
         )),
         InputMode::Search => Line::from(vec![
             Span::raw(" Search: /"),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Cyan)),
-            Span::styled("█", Style::default().fg(Color::Cyan)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.accent)),
+            Span::styled("█", Style::default().fg(theme.accent)),
             Span::raw(" | [Enter] Apply | [Esc] Cancel"),
         ]),
+        InputMode::QuickJump => Line::from(vec![
+            Span::raw(" Jump to: "),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.accent)),
+            Span::styled("█", Style::default().fg(theme.accent)),
+            Span::raw(" | [Enter/Esc] Done"),
+        ]),
         InputMode::ExportMenu => Line::from(Span::styled(
             " [↑↓/jk] Navigate | [←→/hl] Toggle | [Enter] Confirm | [Esc] Cancel ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         InputMode::PortfolioChart => Line::from(Span::styled(
-            " [c/Enter/Esc] Close allocation chart ",
-            Style::default().fg(Color::DarkGray),
+            close_hint(app, Action::AllocationChart, "allocation chart"),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::TopMovers => Line::from(Span::styled(
+            close_hint(app, Action::TopMovers, "top movers"),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::PortfolioPL => Line::from(Span::styled(
+            close_hint(app, Action::ProfitLoss, "P/L breakdown"),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::PortfolioHistory => Line::from(Span::styled(
+            close_hint(app, Action::ValueHistory, "value history"),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::Rebalance => Line::from(Span::styled(
+            format!(
+                " [Enter/e] Set target  [↑↓/jk] Nav  [{}/Esc] Close rebalance ",
+                app.keymap.hint(Action::Rebalance)
+            ),
+            Style::default().fg(theme.muted),
         )),
+        InputMode::RebalanceTarget => {
+            let symbol = app.pending_edit_symbol.as_deref().unwrap_or("");
+            Line::from(vec![
+                Span::raw(format!(" Target % for {}: ", symbol)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default().fg(theme.portfolio_header),
+                ),
+                Span::styled("█", Style::default().fg(theme.portfolio_header)),
+                Span::raw(" | [Enter] Save (empty clears) | [Esc] Back"),
+            ])
+        }
         InputMode::NewsDetail => Line::from(Span::styled(
             " [b] Bookmark  [o] Open in browser  [↑↓] Scroll  [Esc] Close ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         InputMode::PortfolioNew => Line::from(vec![
             Span::raw(" New portfolio name: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Green)),
-            Span::styled("█", Style::default().fg(Color::Green)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.gain)),
+            Span::styled("█", Style::default().fg(theme.gain)),
             Span::raw(" | [Enter] Confirm | [Esc] Cancel"),
         ]),
         InputMode::PortfolioRename => Line::from(vec![
             Span::raw(" Rename portfolio: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Yellow)),
-            Span::styled("█", Style::default().fg(Color::Yellow)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.header)),
+            Span::styled("█", Style::default().fg(theme.header)),
             Span::raw(" | [Enter] Confirm | [Esc] Cancel"),
         ]),
         InputMode::BookmarkDetail => Line::from(Span::styled(
             " [o] Open in browser  [m] Toggle read  [↑↓] Scroll  [Esc] Close ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         InputMode::BookmarkClearConfirm => Line::from(Span::styled(
             " [Enter] Confirm clear all  [Esc] Cancel ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::ConfirmDelete => {
+            let target = app
+                .pending_delete
+                .as_ref()
+                .map(|t| t.describe())
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::styled(
+                    format!(" Delete {}? ", target),
+                    Style::default().fg(theme.loss).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("[y] Yes  [n/Esc] No"),
+            ])
+        }
+        InputMode::ColumnPicker => Line::from(Span::styled(
+            format!(
+                " [Space/Enter] Show/hide  [K/J] Move  [r] Reset  [↑↓/jk] Nav  [{}/Esc] Close ",
+                app.keymap.hint(Action::Columns)
+            ),
+            Style::default().fg(theme.muted),
         )),
+        InputMode::NewsSources => Line::from(Span::styled(
+            " [a] Add feed  [c] Category  [d] Remove  [↑↓/jk] Nav  [Esc] Close ",
+            Style::default().fg(theme.muted),
+        )),
+        InputMode::NewsSourceAdd => Line::from(vec![
+            Span::raw(" Feed URL: "),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.news_header)),
+            Span::styled("█", Style::default().fg(theme.news_header)),
+            Span::raw(" | [Enter] Check & add | [Esc] Back"),
+        ]),
+        InputMode::NewsSourceCategory => Line::from(vec![
+            Span::raw(" Feed category: "),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.news_header)),
+            Span::styled("█", Style::default().fg(theme.news_header)),
+            Span::raw(" | [Enter] Save (empty clears) | [Esc] Back"),
+        ]),
         InputMode::AlertList => Line::from(Span::styled(
-            " [Enter] Toggle/Add  [d] Delete  [↑↓/jk] Nav  [Esc] Close ",
-            Style::default().fg(Color::DarkGray),
+            " [Enter] Toggle/Add  [c] Cooldown  [d] Delete  [↑↓/jk] Nav  [Esc] Close ",
+            Style::default().fg(theme.muted),
         )),
         InputMode::AlertAddType => Line::from(Span::styled(
             " [↑↓/jk] Navigate types  [Enter] Confirm  [Esc] Back ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         InputMode::AlertAddValue => Line::from(vec![
             Span::raw(" Target value: "),
-            Span::styled(&app.input_buffer, Style::default().fg(Color::Red)),
-            Span::styled("█", Style::default().fg(Color::Red)),
-            Span::raw(" | [Enter] Add | [Esc] Back"),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.loss)),
+            Span::styled("█", Style::default().fg(theme.loss)),
+            Span::raw(" | [Enter] Next | [Esc] Back"),
+        ]),
+        InputMode::AlertCooldown => Line::from(vec![
+            Span::raw(" Cooldown (seconds): "),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.loss)),
+            Span::styled("█", Style::default().fg(theme.loss)),
+            Span::raw(" | [Enter] Save | [Esc] Back"),
         ]),
     };
 
@@ -364,12 +830,12 @@ fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         let left_spans: Vec<Span> = content.spans;
         let right_spans = vec![Span::styled(
             "Press Ctrl+C again to exit ",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.header),
         )];
 
         let left_width: usize = left_spans.iter().map(|s| s.width()).sum();
         let right_width: usize = right_spans.iter().map(|s| s.width()).sum();
-        let inner_width = area.width.saturating_sub(2) as usize;
+        let inner_width = chrome_block(app.config.density).inner(area).width as usize;
         let spacer_width = inner_width.saturating_sub(left_width + right_width);
 
         let mut all_spans = left_spans;
@@ -380,6 +846,6 @@ fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         content
     };
 
-    let footer = Paragraph::new(line).block(Block::default().borders(Borders::ALL));
+    let footer = Paragraph::new(line).block(chrome_block(app.config.density));
     frame.render_widget(footer, area);
 }
@@ -1,29 +1,35 @@
 use super::centered_rect;
-use super::formatters::format_value;
+use super::formatters::{NumberFormat, format_relative_time};
+use crate::api::{ChartRange, StockQuote};
 use crate::app::{App, ExportFormat, ExportScope};
+use crate::config::{Highlights, Theme};
+use crate::keymap::Action;
 use ratatui::{
     Frame,
-    layout::Alignment,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Sparkline},
 };
 
-pub use super::detail::draw_stock_detail;
+pub use super::detail::{draw_compare, draw_stock_detail};
+use super::news::category_color;
 
-fn export_menu_content(app: &App) -> Vec<Line<'static>> {
+fn export_menu_content(app: &App, theme: &Theme) -> Vec<Line<'static>> {
     let sel = app.export_menu_selection;
     let format_str = match app.export_format {
         ExportFormat::Csv => "CSV",
         ExportFormat::Json => "JSON",
+        ExportFormat::JsonLines => "JSON Lines (append)",
     };
     let scope_str = match app.export_scope {
         ExportScope::Watchlist => "Watchlist",
         ExportScope::Portfolio => "Portfolio",
+        ExportScope::Allocation => "Allocation",
     };
     let row_style = |selected: bool| -> Style {
         if selected {
-            Style::default().bg(Color::Rgb(40, 80, 40)).fg(Color::White)
+            theme.selected(theme.action_selection_bg).fg(theme.text)
         } else {
             Style::default()
         }
@@ -35,7 +41,7 @@ fn export_menu_content(app: &App) -> Vec<Line<'static>> {
             Span::styled(
                 format!("< {} >", format_str),
                 row_style(sel == 0)
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled("              ", row_style(sel == 0)),
@@ -46,7 +52,7 @@ fn export_menu_content(app: &App) -> Vec<Line<'static>> {
             Span::styled(
                 format!("< {} >", scope_str),
                 row_style(sel == 1)
-                    .fg(Color::Magenta)
+                    .fg(theme.portfolio_header)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled("            ", row_style(sel == 1)),
@@ -55,73 +61,69 @@ fn export_menu_content(app: &App) -> Vec<Line<'static>> {
         Line::from(vec![Span::styled(
             "        [ Export ]        ",
             if sel == 2 {
-                Style::default()
-                    .bg(Color::Green)
-                    .fg(Color::Black)
+                theme
+                    .selected(theme.gain)
+                    .fg(theme.backdrop)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::Green)
+                Style::default().fg(theme.gain)
             },
         )]),
         Line::from(""),
         Line::from(Span::styled(
             "  [←→] Toggle  [Enter] Confirm",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
     ]
 }
 
-pub fn draw_export_menu(frame: &mut Frame, app: &App) {
+pub fn draw_export_menu(frame: &mut Frame, app: &App, theme: &Theme) {
     let area = centered_rect(40, 30, frame.area());
     frame.render_widget(Clear, area);
 
     let outer_block = Block::default()
         .title(" Export Data ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.gain))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
-    let menu = Paragraph::new(export_menu_content(app)).alignment(Alignment::Left);
+    let menu = Paragraph::new(export_menu_content(app, theme)).alignment(Alignment::Left);
     frame.render_widget(menu, inner_area);
 }
 
-pub fn draw_portfolio_chart(frame: &mut Frame, app: &App) {
+pub fn draw_portfolio_chart(frame: &mut Frame, app: &App, theme: &Theme, fmt: &NumberFormat) {
     let area = centered_rect(60, 70, frame.area());
     frame.render_widget(Clear, area);
 
     let outer_block = Block::default()
         .title(" Portfolio Allocation ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.portfolio_header))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
     let allocations = app.portfolio_allocation();
     let total_value: f64 = allocations.iter().map(|(_, v, _)| v).sum();
-    let bar_colors = [
-        Color::Cyan,
-        Color::Green,
-        Color::Yellow,
-        Color::Magenta,
-        Color::Blue,
-        Color::Red,
-        Color::LightCyan,
-        Color::LightGreen,
-    ];
-    let bar_max_width = inner_area.width.saturating_sub(24) as usize;
+    let sectors = app.sector_allocation();
+    let sector_width = sectors
+        .iter()
+        .map(|(name, _, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 18);
 
     let mut content = vec![
         Line::from(vec![
             Span::raw("  Total Value: "),
             Span::styled(
-                format_value(total_value),
+                fmt.value(total_value),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -129,127 +131,802 @@ pub fn draw_portfolio_chart(frame: &mut Frame, app: &App) {
     ];
 
     for (i, (symbol, value, pct)) in allocations.iter().enumerate() {
-        let color = bar_colors[i % bar_colors.len()];
-        let filled = ((pct / 100.0) * bar_max_width as f64).round() as usize;
-        let empty = bar_max_width.saturating_sub(filled);
-        content.push(Line::from(vec![
+        let color = theme.series_color(i);
+        content.push(allocation_bar(
+            symbol,
+            6,
+            *value,
+            *pct,
+            color,
+            inner_area.width,
+            theme,
+            fmt,
+        ));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        "  By sector",
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+    for (i, (sector, value, pct)) in sectors.iter().enumerate() {
+        let color = theme.series_color(i);
+        content.push(allocation_bar(
+            sector,
+            sector_width,
+            *value,
+            *pct,
+            color,
+            inner_area.width,
+            theme,
+            fmt,
+        ));
+    }
+
+    content.push(Line::from(""));
+    content.push(match app.projected_annual_income() {
+        Some((income, yield_pct, covered, total)) => Line::from(vec![
+            Span::raw("  Projected dividends: "),
             Span::styled(
-                format!("  {:6} ", symbol),
-                Style::default().fg(color).add_modifier(Modifier::BOLD),
+                format!("{}/yr", fmt.value(income)),
+                Style::default().fg(theme.gain),
             ),
-            Span::styled("█".repeat(filled), Style::default().fg(color)),
-            Span::styled("░".repeat(empty), Style::default().fg(Color::DarkGray)),
-            Span::raw(format!(" {:5.1}% ", pct)),
-            Span::styled(format_value(*value), Style::default().fg(Color::DarkGray)),
-        ]));
-    }
+            Span::raw(format!(" ({} yield", fmt.percent(yield_pct))),
+            Span::styled(
+                if covered < total {
+                    format!(", based on {}/{} holdings)", covered, total)
+                } else {
+                    ")".to_string()
+                },
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        None => Line::from(Span::styled(
+            "  Projected dividends: no yield data",
+            Style::default().fg(theme.muted),
+        )),
+    });
 
     content.push(Line::from(""));
     content.push(Line::from(Span::styled(
-        "  [c/Enter/Esc] Close",
-        Style::default().fg(Color::DarkGray),
+        format!(
+            "  [{}/Enter/Esc] Close",
+            app.keymap.hint(Action::AllocationChart)
+        ),
+        Style::default().fg(theme.muted),
     )));
 
     let chart = Paragraph::new(content).alignment(Alignment::Left);
     frame.render_widget(chart, inner_area);
 }
 
-fn help_section(title: &str) -> Line<'static> {
+/// One labelled bar of the allocation chart, sized to fill `width` after the
+/// label, percentage and value.
+#[allow(clippy::too_many_arguments)]
+fn allocation_bar(
+    label: &str,
+    label_width: usize,
+    value: f64,
+    pct: f64,
+    color: Color,
+    width: u16,
+    theme: &Theme,
+    fmt: &NumberFormat,
+) -> Line<'static> {
+    let label: String = label.chars().take(label_width).collect();
+    let bar_max_width = (width as usize).saturating_sub(label_width + 18);
+    let filled = ((pct / 100.0) * bar_max_width as f64).round() as usize;
+    let empty = bar_max_width.saturating_sub(filled);
     Line::from(vec![
         Span::styled(
-            format!("─── {} ", title),
-            Style::default().fg(Color::Yellow),
+            format!("  {:width$} ", label, width = label_width),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled("░".repeat(empty), Style::default().fg(theme.muted)),
+        Span::raw(format!(" {:5.1}% ", pct)),
+        Span::styled(fmt.value(value), Style::default().fg(theme.muted)),
+    ])
+}
+
+pub fn draw_rebalance(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = centered_rect(60, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Rebalance ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.portfolio_header))
+        .style(Style::default().bg(theme.backdrop));
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let plan = app.rebalance_plan();
+    let header_style = Style::default()
+        .fg(theme.portfolio_header)
+        .add_modifier(Modifier::BOLD);
+    let mut content = vec![
+        Line::from(Span::styled(
+            format!(
+                "  {:8} {:>9} {:>9} {:>12}",
+                "Symbol", "Current", "Target", "Adjust"
+            ),
+            header_style,
+        )),
+        Line::from(""),
+    ];
+    for (i, row) in plan.rows.iter().enumerate() {
+        let target = row
+            .target_pct
+            .map_or("-".to_string(), |t| format!("{:.1}%", t));
+        let (adjust, color) = match (row.target_pct, row.lots) {
+            (None, _) => ("-".to_string(), theme.muted),
+            (Some(_), None) => ("no quote".to_string(), theme.muted),
+            (Some(_), Some(0)) => ("hold".to_string(), theme.muted),
+            (Some(_), Some(lots)) if lots > 0 => (format!("buy {} lot", lots), theme.gain),
+            (Some(_), Some(lots)) => (format!("sell {} lot", -lots), theme.loss),
+        };
+        let style = if i == app.rebalance_selected {
+            theme.selected(theme.menu_selection_bg).fg(theme.text)
+        } else {
+            Style::default()
+        };
+        content.push(
+            Line::from(vec![
+                Span::raw(format!(
+                    "  {:8} {:>9} {:>9} ",
+                    row.symbol,
+                    format!("{:.1}%", row.current_pct),
+                    target
+                )),
+                Span::styled(format!("{:>12}", adjust), Style::default().fg(color)),
+            ])
+            .style(style),
+        );
+    }
+
+    content.push(Line::from(""));
+    if let Some(warning) = plan.warning() {
+        content.push(Line::from(Span::styled(
+            format!("  {}", warning),
+            Style::default().fg(theme.header),
+        )));
+    } else if plan.target_sum == 0.0 {
+        content.push(Line::from(Span::styled(
+            "  No targets yet: press Enter on a holding to set one",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    content.push(Line::from(Span::styled(
+        format!(
+            "  [Enter/e] Set target  [↑↓/jk] Nav  [{}/Esc] Close",
+            app.keymap.hint(Action::Rebalance)
+        ),
+        Style::default().fg(theme.muted),
+    )));
+
+    frame.render_widget(Paragraph::new(content), inner_area);
+}
+
+fn top_movers_lines(
+    title: &str,
+    quotes: &[&StockQuote],
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        format!("  {}", title),
+        Style::default()
+            .fg(theme.header)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    if quotes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    none",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for q in quotes {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {:8}", q.symbol),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" {:>12}", fmt.price(q.price))),
+            Span::styled(
+                format!(
+                    " {:>10}",
+                    format!(
+                        "{}{}",
+                        highlights.arrow(q.change_percent),
+                        fmt.signed_percent(q.change_percent)
+                    )
+                ),
+                Style::default().fg(highlights.change_color(theme, q.change_percent, false)),
+            ),
+        ]));
+    }
+    lines
+}
+
+pub fn draw_top_movers(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let area = centered_rect(50, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let outer_block = Block::default()
+        .title(" Top Movers (all watchlists) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.header))
+        .style(Style::default().bg(theme.backdrop));
+
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let (gainers, losers) = app.top_movers();
+    let mut content = top_movers_lines("▲ Gainers", &gainers, theme, highlights, fmt);
+    content.push(Line::from(""));
+    content.extend(top_movers_lines(
+        "▼ Losers",
+        &losers,
+        theme,
+        highlights,
+        fmt,
+    ));
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        format!("  [{}/Enter/Esc] Close", app.keymap.hint(Action::TopMovers)),
+        Style::default().fg(theme.muted),
+    )));
+
+    frame.render_widget(
+        Paragraph::new(content).alignment(Alignment::Left),
+        inner_area,
+    );
+}
+
+/// Event log lines, newest first, starting `event_log_scroll` entries back.
+fn event_log_lines(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    if app.event_log.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  No events yet",
+            Style::default().fg(theme.muted),
+        ))];
+    }
+    app.event_log
+        .iter()
+        .rev()
+        .skip(app.event_log_scroll)
+        .map(|(at, msg)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {}  ", at.format("%H:%M:%S")),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw(msg.clone()),
+            ])
+        })
+        .collect()
+}
+
+pub fn draw_event_log(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let outer_block = Block::default()
+        .title(format!(" Event Log ({}) ", app.event_log.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.header))
+        .style(Style::default().bg(theme.backdrop));
+
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let [list_area, hint_area] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner_area);
+    frame.render_widget(Paragraph::new(event_log_lines(app, theme)), list_area);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!(
+                "  [↑↓] Scroll  [{}/Enter/Esc] Close",
+                app.keymap.hint(Action::EventLog)
+            ),
+            Style::default().fg(theme.muted),
+        )),
+        hint_area,
+    );
+}
+
+pub fn draw_portfolio_pl(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let area = centered_rect(60, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let outer_block = Block::default()
+        .title(" Realized vs Unrealized P/L ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.portfolio_header))
+        .style(Style::default().bg(theme.backdrop));
+
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let breakdown = app.portfolio_pl_breakdown();
+    let total_realized: f64 = breakdown.iter().map(|(_, r, _)| r).sum();
+    let total_unrealized: f64 = breakdown.iter().filter_map(|(_, _, u)| *u).sum();
+    let pl_color = |v: f64| if v >= 0.0 { theme.gain } else { theme.loss };
+    let pl_label = |v: f64| format!("{}{}", highlights.arrow(v), fmt.pl(v));
+
+    let mut content = vec![
+        Line::from(Span::styled(
+            format!("  {:8} {:>16} {:>16}", "Symbol", "Realized", "Unrealized"),
+            Style::default()
+                .fg(theme.header)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for (symbol, realized, unrealized) in &breakdown {
+        let unrealized_span = match unrealized {
+            Some(u) => Span::styled(
+                format!(" {:>16}", pl_label(*u)),
+                Style::default().fg(pl_color(*u)),
+            ),
+            None => Span::styled(format!(" {:>16}", "-"), Style::default().fg(theme.muted)),
+        };
+        content.push(Line::from(vec![
+            Span::styled(
+                format!("  {:8}", symbol),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" {:>16}", pl_label(*realized)),
+                Style::default().fg(pl_color(*realized)),
+            ),
+            unrealized_span,
+        ]));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled("  Total   ", Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!("{:>16}", pl_label(total_realized)),
+            Style::default().fg(pl_color(total_realized)),
+        ),
+        Span::styled(
+            format!(" {:>16}", pl_label(total_unrealized)),
+            Style::default().fg(pl_color(total_unrealized)),
+        ),
+    ]));
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        format!(
+            "  [{}/Enter/Esc] Close",
+            app.keymap.hint(Action::ProfitLoss)
+        ),
+        Style::default().fg(theme.muted),
+    )));
+
+    let table = Paragraph::new(content).alignment(Alignment::Left);
+    frame.render_widget(table, inner_area);
+}
+
+pub fn draw_portfolio_history(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let outer_block = Block::default()
+        .title(format!(
+            " Portfolio Value ({}) ",
+            ChartRange::default().label()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.portfolio_header))
+        .style(Style::default().bg(theme.backdrop));
+
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(4),
+            Constraint::Length(1),
+        ])
+        .split(inner_area);
+
+    draw_recorded_history(frame, app, chunks[3], chunks[4], theme, highlights, fmt);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!(
+                "  [{}/Enter/Esc] Close",
+                app.keymap.hint(Action::ValueHistory)
+            ),
+            Style::default().fg(theme.muted),
+        )),
+        chunks[5],
+    );
+
+    let Some((values, excluded)) = app.portfolio_history.as_ref() else {
+        return;
+    };
+    let (Some(first), Some(last)) = (values.first(), values.last()) else {
+        let no_data =
+            Paragraph::new("  Chart data unavailable").style(Style::default().fg(theme.muted));
+        frame.render_widget(no_data, chunks[0]);
+        return;
+    };
+
+    let change = last - first;
+    let change_pct = if *first > 0.0 {
+        change / first * 100.0
+    } else {
+        0.0
+    };
+    let color = if change >= 0.0 {
+        theme.gain
+    } else {
+        theme.loss
+    };
+    let summary = Line::from(vec![
+        Span::raw("  Value: "),
+        Span::styled(
+            fmt.value(*last),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {}{} ({})",
+                highlights.arrow(change),
+                fmt.pl(change),
+                fmt.signed_percent(change_pct)
+            ),
+            Style::default().fg(color),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let data = sparkline_data(values, chunks[1].width.saturating_sub(2) as usize);
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::LEFT | Borders::RIGHT))
+        .data(&data)
+        .style(Style::default().fg(color));
+    frame.render_widget(sparkline, chunks[1]);
+
+    let holdings = app.config.current_portfolio().holdings.len();
+    let coverage = if excluded.is_empty() {
+        format!(
+            "  Low {}  High {}  All {} holdings",
+            fmt.value(min),
+            fmt.value(max),
+            holdings
+        )
+    } else {
+        format!(
+            "  Low {}  High {}  {}/{} holdings (no chart: {})",
+            fmt.value(min),
+            fmt.value(max),
+            holdings - excluded.len(),
+            holdings,
+            excluded.join(", ")
+        )
+    };
+    let footer = Paragraph::new(Line::from(Span::styled(
+        coverage,
+        Style::default().fg(theme.muted),
+    )));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// The once-a-day values recorded while the app ran, below the chart-based
+/// series: a summary line in `title_area` and a sparkline in `chart_area`.
+fn draw_recorded_history(
+    frame: &mut Frame,
+    app: &App,
+    title_area: Rect,
+    chart_area: Rect,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let snapshots = app.current_portfolio_snapshots();
+    let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "  Recorded: nothing yet (one value per trading day the app runs)",
+                Style::default().fg(theme.muted),
+            )),
+            title_area,
+        );
+        return;
+    };
+    let color = if last.pl >= 0.0 {
+        theme.gain
+    } else {
+        theme.loss
+    };
+    let title = Line::from(vec![
+        Span::styled(
+            format!(
+                "  Recorded: {} {} since {}  ",
+                snapshots.len(),
+                if snapshots.len() == 1 { "day" } else { "days" },
+                first.date.format("%Y-%m-%d")
+            ),
+            Style::default().fg(theme.muted),
         ),
+        Span::styled(
+            format!("P/L {}{}", highlights.arrow(last.pl), fmt.pl(last.pl)),
+            Style::default().fg(color),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(title), title_area);
+
+    let values: Vec<f64> = snapshots.iter().map(|s| s.value).collect();
+    let data = sparkline_data(&values, chart_area.width.saturating_sub(2) as usize);
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::LEFT | Borders::RIGHT))
+        .data(&data)
+        .style(Style::default().fg(color));
+    frame.render_widget(sparkline, chart_area);
+}
+
+/// The last `width` values scaled to 0–100 between their overall low and
+/// high, for a `Sparkline`. A flat series sits in the middle.
+fn sparkline_data(values: &[f64], width: usize) -> Vec<u64> {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values[values.len().saturating_sub(width)..]
+        .iter()
+        .map(|&v| {
+            if range > 0.0 {
+                ((v - min) / range * 100.0) as u64
+            } else {
+                50
+            }
+        })
+        .collect()
+}
+
+fn help_section(title: &str, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("─── {} ", title), Style::default().fg(theme.header)),
         Span::styled(
             "───────────────────────────",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ])
 }
 
-fn help_binding(key: &str, desc: &str) -> Line<'static> {
+fn help_binding(key: &str, desc: &str, theme: &Theme) -> Line<'static> {
     Line::from(vec![
-        Span::styled(format!("  {:12}", key), Style::default().fg(Color::Cyan)),
+        Span::styled(format!("  {:12}", key), Style::default().fg(theme.accent)),
         Span::raw(desc.to_string()),
     ])
 }
 
-fn help_content(app: &crate::app::App) -> Vec<Line<'static>> {
+type HelpSection = (&'static str, Vec<(String, &'static str)>);
+
+/// Bindings shown in the help modal for the current view, grouped by section.
+/// Keys come from the keymap, so remapped actions show their new keys.
+fn help_sections(app: &crate::app::App) -> Vec<HelpSection> {
     use crate::app::{NewsTab, ViewMode};
 
-    let mut lines = vec![
-        help_section("General"),
-        help_binding("q", "Quit"),
-        help_binding("p", "Cycle Watchlist / Portfolio / News"),
-        help_binding("?", "Show this help"),
-        help_binding("↑ / ↓", "Move selection"),
-        help_binding("s", "Cycle sort column"),
-        help_binding("S", "Toggle sort direction"),
-        help_binding("/", "Search / filter"),
-        Line::from(""),
+    let keys = |actions: &[Action]| -> String {
+        actions
+            .iter()
+            .map(|&action| app.keymap.hint_all(action))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    };
+    let bind = |actions: &[Action], desc: &'static str| (keys(actions), desc);
+
+    let general = vec![
+        bind(&[Action::Quit], "Quit"),
+        bind(&[Action::EventLog], "Recent events log"),
+        bind(&[Action::ToggleView], "Cycle Watchlist / Portfolio / News"),
+        bind(&[Action::Help], "Show this help"),
+        bind(
+            &[Action::RefreshFaster, Action::RefreshSlower],
+            "Faster / slower quote refresh",
+        ),
+        bind(&[Action::ToggleDensity], "Toggle compact layout"),
+        bind(
+            &[Action::CopySymbol, Action::CopySummary],
+            "Copy symbol / quote summary",
+        ),
+        bind(&[Action::OpenPage], "Open stock page in browser"),
+        bind(&[Action::Up, Action::Down], "Move selection"),
+        bind(
+            &[Action::PageUp, Action::PageDown],
+            "Move selection by one page",
+        ),
+        bind(&[Action::Top, Action::Bottom], "Jump to first / last row"),
+        bind(&[Action::SortColumn], "Cycle sort column"),
+        bind(&[Action::SortDirection], "Toggle sort direction"),
+        bind(
+            &[Action::Undo],
+            "Undo last stock / holding / watchlist removal",
+        ),
+        bind(&[Action::Search], "Search / filter"),
+        bind(
+            &[Action::QuickJump],
+            "Jump to first row starting with a prefix",
+        ),
     ];
 
-    match app.view_mode {
-        ViewMode::Watchlist => {
-            lines.push(help_section("Watchlist"));
-            lines.push(help_binding("a", "Add stock symbol"));
-            lines.push(help_binding("d", "Delete selected stock"));
-            lines.push(help_binding("e", "Export data (CSV/JSON)"));
-            lines.push(help_binding("r", "Refresh quotes"));
-            lines.push(help_binding("A", "Manage alerts"));
-            lines.push(help_binding("Enter", "Stock detail popup"));
-            lines.push(help_binding("h / ←", "Previous watchlist"));
-            lines.push(help_binding("l / →", "Next watchlist"));
-            lines.push(help_binding("n", "New watchlist"));
-            lines.push(help_binding("R", "Rename watchlist"));
-            lines.push(help_binding("D", "Delete watchlist"));
-        }
-        ViewMode::Portfolio => {
-            lines.push(help_section("Portfolio"));
-            lines.push(help_binding("a", "Add holding (step-by-step)"));
-            lines.push(help_binding("e", "Edit selected holding"));
-            lines.push(help_binding("d", "Delete selected holding"));
-            lines.push(help_binding("r", "Refresh quotes"));
-            lines.push(help_binding("A", "Manage alerts"));
-            lines.push(help_binding("c", "Portfolio allocation chart"));
-            lines.push(help_binding("Enter", "Stock detail popup"));
-            lines.push(help_binding("h / ←", "Previous portfolio"));
-            lines.push(help_binding("l / →", "Next portfolio"));
-            lines.push(help_binding("n", "New portfolio"));
-            lines.push(help_binding("R", "Rename portfolio"));
-            lines.push(help_binding("D", "Delete portfolio"));
-        }
+    let view = match app.view_mode {
+        ViewMode::Watchlist => (
+            "Watchlist",
+            vec![
+                bind(&[Action::Add], "Add stock symbols (comma/space separated)"),
+                bind(&[Action::Delete], "Delete selected stock (asks y/n)"),
+                bind(
+                    &[Action::MoveSymbolUp, Action::MoveSymbolDown],
+                    "Move selected stock up / down",
+                ),
+                bind(&[Action::Edit], "Export data (CSV/JSON)"),
+                bind(&[Action::Refresh], "Refresh quotes"),
+                bind(&[Action::Alerts], "Manage alerts"),
+                bind(&[Action::Open], "Stock detail popup"),
+                bind(&[Action::Compare], "Compare with another symbol"),
+                bind(&[Action::PrevList], "Previous watchlist"),
+                bind(&[Action::NextList], "Next watchlist"),
+                bind(&[Action::AllWatchlists], "Toggle all-watchlists view"),
+                bind(
+                    &[Action::TopMovers],
+                    "Top gainers / losers across all watchlists",
+                ),
+                bind(&[Action::Columns], "Choose and reorder columns"),
+                bind(&[Action::NewList], "New watchlist"),
+                bind(
+                    &[Action::ConvertList],
+                    "New portfolio from this watchlist (0 lots)",
+                ),
+                bind(&[Action::RenameList], "Rename watchlist"),
+                bind(&[Action::DeleteList], "Delete watchlist (asks y/n)"),
+            ],
+        ),
+        ViewMode::Portfolio => (
+            "Portfolio",
+            vec![
+                bind(&[Action::Add], "Add holding (step-by-step)"),
+                bind(&[Action::Edit], "Edit selected holding"),
+                bind(&[Action::Note], "Edit note / tags on selected holding"),
+                bind(&[Action::Delete], "Delete selected holding (asks y/n)"),
+                bind(&[Action::Refresh], "Refresh quotes"),
+                bind(&[Action::Alerts], "Manage alerts"),
+                bind(&[Action::AllocationChart], "Portfolio allocation chart"),
+                bind(&[Action::ProfitLoss], "Realized vs unrealized P/L"),
+                bind(&[Action::ValueHistory], "Portfolio value history"),
+                bind(&[Action::Rebalance], "Rebalance toward target allocations"),
+                bind(&[Action::Columns], "Choose and reorder columns"),
+                bind(&[Action::Split], "Apply stock split (e.g. 2:1)"),
+                bind(&[Action::Open], "Stock detail popup"),
+                bind(&[Action::Compare], "Compare with another symbol"),
+                bind(&[Action::PrevList], "Previous portfolio"),
+                bind(&[Action::NextList], "Next portfolio"),
+                bind(&[Action::NewList], "New portfolio"),
+                bind(&[Action::ConvertList], "New watchlist from this portfolio"),
+                bind(&[Action::RenameList], "Rename portfolio"),
+                bind(&[Action::DeleteList], "Delete portfolio (asks y/n)"),
+            ],
+        ),
         ViewMode::News => {
-            lines.push(help_section("News"));
-            lines.push(help_binding("h / ←  l / →", "Switch Feed / Bookmarks tab"));
+            let mut bindings = vec![bind(
+                &[Action::PrevList, Action::NextList],
+                "Switch Feed / Bookmarks tab",
+            )];
             match app.news_tab {
-                NewsTab::Feed => {
-                    lines.push(help_binding("b", "Toggle bookmark on article"));
-                    lines.push(help_binding("r", "Refresh news feeds"));
-                    lines.push(help_binding("Enter", "Open article preview"));
-                    lines.push(help_binding("o", "Open in browser (in preview)"));
-                }
-                NewsTab::Bookmarks => {
-                    lines.push(help_binding("Enter", "Open bookmark detail"));
-                    lines.push(help_binding("o", "Open in browser (in detail)"));
-                    lines.push(help_binding("d", "Remove selected bookmark"));
-                    lines.push(help_binding("D", "Clear all bookmarks"));
-                    lines.push(help_binding("m", "Toggle read / unread"));
-                }
+                NewsTab::Feed => bindings.extend([
+                    bind(&[Action::Bookmark], "Toggle bookmark on article"),
+                    bind(&[Action::Refresh], "Refresh news feeds"),
+                    bind(&[Action::TickerFilter], "Filter to last selected stock"),
+                    bind(&[Action::CategoryFilter], "Cycle feed category filter"),
+                    bind(&[Action::LoadMore], "Load older headlines"),
+                    bind(&[Action::NewsSources], "Manage RSS news sources"),
+                    bind(&[Action::Open], "Open article preview"),
+                    ("o".to_string(), "Open in browser (in preview)"),
+                ]),
+                NewsTab::Bookmarks => bindings.extend([
+                    bind(&[Action::Open], "Open bookmark detail"),
+                    ("o".to_string(), "Open in browser (in detail)"),
+                    bind(&[Action::Delete], "Remove selected bookmark"),
+                    bind(&[Action::DeleteList], "Clear all bookmarks"),
+                    bind(&[Action::ToggleRead], "Toggle read / unread"),
+                ]),
             }
+            ("News", bindings)
         }
+    };
+
+    vec![("General", general), view]
+}
+
+/// Help lines, keeping only bindings whose key or description contains the
+/// filter (case-insensitive). Sections left empty are dropped.
+fn help_content(app: &crate::app::App, theme: &Theme) -> Vec<Line<'static>> {
+    let query = app.help_filter.to_lowercase();
+    let mut lines = Vec::new();
+
+    for (title, bindings) in help_sections(app) {
+        let matching: Vec<_> = bindings
+            .into_iter()
+            .filter(|(key, desc)| {
+                key.to_lowercase().contains(&query) || desc.to_lowercase().contains(&query)
+            })
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(help_section(title, theme));
+        lines.extend(
+            matching
+                .iter()
+                .map(|(key, desc)| help_binding(key, desc, theme)),
+        );
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  No bindings match \"{}\"", app.help_filter),
+            Style::default().fg(theme.muted),
+        )));
     }
 
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "  [?/Enter/Esc] Close",
-        Style::default().fg(Color::DarkGray),
-    )));
+    let help_key = app.keymap.hint(Action::Help);
+    if app.help_filter.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  Type to filter  [{}/Enter/Esc] Close", help_key),
+            Style::default().fg(theme.muted),
+        )));
+    } else {
+        lines.push(Line::from(vec![
+            Span::raw("  Filter: "),
+            Span::styled(app.help_filter.clone(), Style::default().fg(theme.accent)),
+            Span::styled(
+                format!("  [Esc] Clear  [{}/Enter] Close", help_key),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
     lines
 }
 
-pub fn draw_help(frame: &mut Frame, app: &crate::app::App) {
-    let content = help_content(app);
+pub fn draw_help(frame: &mut Frame, app: &crate::app::App, theme: &Theme) {
+    let content = help_content(app, theme);
     let content_height = content.len() as u16 + 2; // +2 for borders
     let percent_y = ((content_height * 100) / frame.area().height.max(1)).clamp(30, 80);
 
@@ -259,8 +936,8 @@ pub fn draw_help(frame: &mut Frame, app: &crate::app::App) {
     let outer_block = Block::default()
         .title(" Help - Keyboard Shortcuts ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.header))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -271,7 +948,7 @@ pub fn draw_help(frame: &mut Frame, app: &crate::app::App) {
     );
 }
 
-fn alert_modal_content(app: &crate::app::App) -> Vec<Line<'static>> {
+fn alert_modal_content(app: &crate::app::App, theme: &Theme) -> Vec<Line<'static>> {
     use std::borrow::Cow;
     let sym = match &app.alert_symbol {
         Some(s) => s.clone(),
@@ -284,46 +961,61 @@ fn alert_modal_content(app: &crate::app::App) -> Vec<Line<'static>> {
     if alerts.is_empty() {
         lines.push(Line::from(Span::styled(
             " No alerts set",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     }
 
     for (i, alert) in alerts.iter().enumerate() {
         let is_sel = i == app.alert_list_selected;
         let icon = if alert.enabled { "●" } else { "○" };
+        let last = match alert.last_triggered {
+            Some(ts) => format!("  fired {}", format_relative_time(ts as i64)),
+            None => String::new(),
+        };
         let label = Cow::from(format!(
-            " {} {} {:.0}  {}",
+            " {} {}  {}  cd {}s{}",
             icon,
-            alert.alert_type.label(),
-            alert.target_value,
+            alert_target_label(&alert.alert_type, alert.target_value),
             if alert.enabled { "ON" } else { "OFF" },
+            alert.cooldown_seconds,
+            last,
         ));
         let style = if is_sel {
-            Style::default().bg(Color::Rgb(40, 40, 80)).fg(Color::White)
+            theme.selected(theme.menu_selection_bg).fg(theme.text)
         } else if alert.enabled {
-            Style::default().fg(Color::Green)
+            Style::default().fg(theme.gain)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(theme.muted)
         };
         lines.push(Line::from(Span::styled(label, style)));
     }
 
     let add_sel = app.alert_list_selected == count;
     let add_style = if add_sel {
-        Style::default().bg(Color::Rgb(40, 80, 40)).fg(Color::Green)
+        theme.selected(theme.action_selection_bg).fg(theme.gain)
     } else {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.gain)
     };
     lines.push(Line::from(Span::styled(" + Add Alert", add_style)));
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        " [Enter] Toggle/Add [d] Del [↑↓] Nav [Esc] Close",
-        Style::default().fg(Color::DarkGray),
+        " [Enter] Toggle/Add [c] Cooldown [d] Del [Esc] Close",
+        Style::default().fg(theme.muted),
     )));
     lines
 }
 
-fn alert_add_type_content(app: &crate::app::App) -> Vec<Line<'static>> {
+/// Alert type with its target, e.g. "Above 9500"; day high/low alerts have
+/// no target to show.
+fn alert_target_label(alert_type: &crate::config::AlertType, value: f64) -> String {
+    if alert_type.needs_target() {
+        format!("{} {:.0}", alert_type.label(), value)
+    } else {
+        alert_type.label().to_string()
+    }
+}
+
+fn alert_add_type_content(app: &crate::app::App, theme: &Theme) -> Vec<Line<'static>> {
     use crate::config::AlertType;
     use std::borrow::Cow;
     let types = [
@@ -331,16 +1023,20 @@ fn alert_add_type_content(app: &crate::app::App) -> Vec<Line<'static>> {
         AlertType::Below,
         AlertType::PercentGain,
         AlertType::PercentLoss,
+        AlertType::CrossAbove,
+        AlertType::CrossBelow,
+        AlertType::NewDayHigh,
+        AlertType::NewDayLow,
     ];
     let mut lines: Vec<Line<'static>> = Vec::new();
     for t in &types {
         let is_sel = &app.pending_alert_type == t;
         let style = if is_sel {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.header)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(theme.muted)
         };
         let label = Cow::from(format!(" {} {}", if is_sel { ">" } else { " " }, t.label()));
         lines.push(Line::from(Span::styled(label, style)));
@@ -348,41 +1044,73 @@ fn alert_add_type_content(app: &crate::app::App) -> Vec<Line<'static>> {
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         " [↑↓] Navigate  [Enter] Confirm  [Esc] Back",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
     lines
 }
 
-fn alert_add_value_content(app: &crate::app::App) -> Vec<Line<'static>> {
+fn alert_add_value_content(app: &crate::app::App, theme: &Theme) -> Vec<Line<'static>> {
     use std::borrow::Cow;
     vec![
         Line::from(Span::styled(
             Cow::from(format!(" Type: {}", app.pending_alert_type.label())),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         Line::from(vec![
             Span::raw(" Value: "),
             Span::styled(
                 Cow::from(app.input_buffer.clone()),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.header),
             ),
-            Span::styled("█", Style::default().fg(Color::Yellow)),
+            Span::styled("█", Style::default().fg(theme.header)),
         ]),
         Line::from(""),
         Line::from(Span::styled(
+            " [Enter] Next  [Esc] Back",
+            Style::default().fg(theme.muted),
+        )),
+    ]
+}
+
+fn alert_cooldown_content(app: &crate::app::App, theme: &Theme) -> Vec<Line<'static>> {
+    use std::borrow::Cow;
+    let (context, action) = if app.alert_cooldown_edit.is_some() {
+        (" Edit cooldown".to_string(), " [Enter] Save  [Esc] Back")
+    } else {
+        (
+            format!(
+                " {}",
+                alert_target_label(&app.pending_alert_type, app.pending_alert_value)
+            ),
             " [Enter] Add  [Esc] Back",
-            Style::default().fg(Color::DarkGray),
+        )
+    };
+    vec![
+        Line::from(Span::styled(
+            Cow::from(context),
+            Style::default().fg(theme.muted),
         )),
+        Line::from(vec![
+            Span::raw(" Cooldown (s): "),
+            Span::styled(
+                Cow::from(app.input_buffer.clone()),
+                Style::default().fg(theme.header),
+            ),
+            Span::styled("█", Style::default().fg(theme.header)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(action, Style::default().fg(theme.muted))),
     ]
 }
 
-pub fn draw_alert_modal(frame: &mut Frame, app: &crate::app::App) {
+pub fn draw_alert_modal(frame: &mut Frame, app: &crate::app::App, theme: &Theme) {
     use crate::app::InputMode;
 
     let content = match app.input_mode {
-        InputMode::AlertAddType => alert_add_type_content(app),
-        InputMode::AlertAddValue => alert_add_value_content(app),
-        _ => alert_modal_content(app),
+        InputMode::AlertAddType => alert_add_type_content(app, theme),
+        InputMode::AlertAddValue => alert_add_value_content(app, theme),
+        InputMode::AlertCooldown => alert_cooldown_content(app, theme),
+        _ => alert_modal_content(app, theme),
     };
 
     // Size the modal to fit content: 2 for border, content lines for height
@@ -400,13 +1128,14 @@ pub fn draw_alert_modal(frame: &mut Frame, app: &crate::app::App) {
     let title = match app.input_mode {
         InputMode::AlertAddType => format!(" {} > Alert Type ", sym),
         InputMode::AlertAddValue => format!(" {} > Alert Value ", sym),
+        InputMode::AlertCooldown => format!(" {} > Alert Cooldown ", sym),
         _ => format!(" Alerts: {} ", sym),
     };
     let outer_block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.loss))
+        .style(Style::default().bg(theme.backdrop));
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
@@ -416,15 +1145,173 @@ pub fn draw_alert_modal(frame: &mut Frame, app: &crate::app::App) {
     );
 }
 
-pub fn draw_bookmark_clear_confirm(frame: &mut Frame) {
+fn column_picker_content(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for (i, (_, name, enabled)) in app.column_picker_rows().into_iter().enumerate() {
+        let icon = if enabled { "[x]" } else { "[ ]" };
+        let style = if i == app.column_picker_selected {
+            theme.selected(theme.menu_selection_bg).fg(theme.text)
+        } else if enabled {
+            Style::default().fg(theme.gain)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {} {}", icon, name),
+            style,
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " [Space] Show/hide [K/J] Move [r] Reset",
+        Style::default().fg(theme.muted),
+    )));
+    lines
+}
+
+pub fn draw_column_picker(frame: &mut Frame, app: &App, theme: &Theme) {
+    use crate::app::ViewMode;
+
+    let content = column_picker_content(app, theme);
+    let height = (content.len() as u16 + 2).min(frame.area().height.saturating_sub(6));
+    let width = 44u16.min(frame.area().width.saturating_sub(4));
+    let area = centered_rect(
+        width * 100 / frame.area().width.max(1),
+        height * 100 / frame.area().height.max(1),
+        frame.area(),
+    );
+    frame.render_widget(Clear, area);
+
+    let title = match app.view_mode {
+        ViewMode::Portfolio => " Portfolio Columns ",
+        _ => " Watchlist Columns ",
+    };
+    let outer_block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.backdrop));
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    frame.render_widget(
+        Paragraph::new(content).alignment(Alignment::Left),
+        inner_area,
+    );
+}
+
+fn news_sources_content(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    use crate::app::InputMode;
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    if app.config.news_sources.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No feeds configured",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for (i, url) in app.config.news_sources.iter().enumerate() {
+        let style = if i == app.news_source_selected && app.input_mode == InputMode::NewsSources {
+            theme.selected(theme.menu_selection_bg).fg(theme.text)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![Span::styled(format!(" {}", url), style)];
+        if let Some(count) = app.news_feed_counts.get(url) {
+            spans.push(Span::styled(format!(" ({})", count), style.fg(theme.muted)));
+        }
+        if let Some(category) = app.config.news_categories.get(url) {
+            spans.push(Span::styled(
+                format!(" [{}]", category),
+                style.fg(category_color(category, theme)),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    if app.input_mode == InputMode::NewsSourceAdd {
+        lines.push(Line::from(vec![
+            Span::raw(" + "),
+            Span::styled(app.input_buffer.clone(), Style::default().fg(theme.header)),
+            Span::styled("█", Style::default().fg(theme.header)),
+        ]));
+    } else {
+        lines.push(Line::from(Span::styled(
+            " [a] Add [c] Category [d] Remove [Esc] Close",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    lines
+}
+
+pub fn draw_news_sources(frame: &mut Frame, app: &App, theme: &Theme) {
+    let content = news_sources_content(app, theme);
+    let height = (content.len() as u16 + 2).min(frame.area().height.saturating_sub(6));
+    let width = 70u16.min(frame.area().width.saturating_sub(4));
+    let area = centered_rect(
+        width * 100 / frame.area().width.max(1),
+        height * 100 / frame.area().height.max(1),
+        frame.area(),
+    );
+    frame.render_widget(Clear, area);
+
+    let outer_block = Block::default()
+        .title(" News Sources ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.news_header))
+        .style(Style::default().bg(theme.backdrop));
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+
+    frame.render_widget(
+        Paragraph::new(content).alignment(Alignment::Left),
+        inner_area,
+    );
+}
+
+/// Dropdown of symbol search matches, drawn just above the footer prompt.
+pub fn draw_add_suggestions(frame: &mut Frame, app: &App, footer: Rect, theme: &Theme) {
+    let lines: Vec<Line<'static>> = app
+        .add_suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, (symbol, name))| {
+            let style = if app.add_suggestion_selected == Some(i) {
+                theme.selected(theme.menu_selection_bg).fg(theme.text)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<6} ", symbol),
+                    style.fg(theme.accent).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(name.clone(), style),
+            ])
+        })
+        .collect();
+
+    let height = (lines.len() as u16 + 2).min(footer.y);
+    let width = 50u16.min(footer.width);
+    let area = Rect::new(footer.x, footer.y.saturating_sub(height), width, height);
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(" Matches ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.backdrop));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+pub fn draw_bookmark_clear_confirm(frame: &mut Frame, theme: &Theme) {
     let area = centered_rect(40, 20, frame.area());
     frame.render_widget(Clear, area);
 
     let outer_block = Block::default()
         .title(" Clear All Bookmarks ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.loss))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -434,19 +1321,19 @@ pub fn draw_bookmark_clear_confirm(frame: &mut Frame) {
         Line::from(Span::styled(
             "  Remove all bookmarks?",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.header)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
             "  This cannot be undone.",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  [Enter] ", Style::default().fg(Color::Red)),
-            Span::styled("Confirm  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Esc] ", Style::default().fg(Color::Cyan)),
-            Span::styled("Cancel", Style::default().fg(Color::DarkGray)),
+            Span::styled("  [Enter] ", Style::default().fg(theme.loss)),
+            Span::styled("Confirm  ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc] ", Style::default().fg(theme.accent)),
+            Span::styled("Cancel", Style::default().fg(theme.muted)),
         ]),
     ];
 
@@ -1,17 +1,19 @@
 use super::formatters::*;
 use super::tables::{ColumnDef, column_constraints, sort_header_row, visible_columns};
+use super::{chrome_block, table_metrics};
 use crate::api::NewsItem;
-use crate::app::App;
+use crate::app::{App, TableHitArea};
+use crate::config::Theme;
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table},
+    widgets::{Cell, Row, Table},
 };
 
 const NEWS_COLUMNS: &[ColumnDef] = &[
     ColumnDef {
-        name: "Time",
+        name: "Age",
         width: 10,
         priority: 1,
     },
@@ -28,16 +30,32 @@ const NEWS_COLUMNS: &[ColumnDef] = &[
 ];
 pub(crate) const NEWS_SORTABLE_COLUMNS: usize = 3;
 
-fn news_row(item: &NewsItem, vis: &[usize], is_bookmarked: bool) -> Row<'static> {
+/// Stable color for a feed category, so each one keeps its color across
+/// refreshes and sessions.
+pub(super) fn category_color(category: &str, theme: &Theme) -> Color {
+    let hash = category.to_lowercase().bytes().fold(0usize, |acc, b| {
+        acc.wrapping_mul(31).wrapping_add(b as usize)
+    });
+    theme.series_color(hash)
+}
+
+fn news_row(item: &NewsItem, vis: &[usize], is_bookmarked: bool, theme: &Theme) -> Row<'static> {
     let cells: Vec<Cell> = vis
         .iter()
         .map(|&col| match col {
             0 => Cell::from(format_relative_time(item.published_at)),
-            1 => Cell::from(truncate_str(&item.publisher, 18)),
+            1 => {
+                let cell = Cell::from(truncate_str(&item.publisher, 18));
+                match &item.category {
+                    Some(category) => {
+                        cell.style(Style::default().fg(category_color(category, theme)))
+                    }
+                    None => cell,
+                }
+            }
             2 => {
                 if is_bookmarked {
-                    Cell::from(format!("★ {}", item.title))
-                        .style(Style::default().fg(Color::Yellow))
+                    Cell::from(format!("★ {}", item.title)).style(Style::default().fg(theme.header))
                 } else {
                     Cell::from(item.title.clone())
                 }
@@ -48,17 +66,17 @@ fn news_row(item: &NewsItem, vis: &[usize], is_bookmarked: bool) -> Row<'static>
     Row::new(cells)
 }
 
-pub fn draw_news(frame: &mut Frame, area: Rect, app: &mut App) {
+pub fn draw_news(frame: &mut Frame, area: Rect, app: &mut App, theme: &Theme) {
     // rows visible = area height - 2 (borders) - 1 (header)
-    app.table_viewport_height = area.height.saturating_sub(3) as usize;
-    let available_width = area.width.saturating_sub(2);
+    let (viewport_height, available_width) = table_metrics(area, app.config.density);
+    app.table_viewport_height = viewport_height;
     let vis = visible_columns(NEWS_COLUMNS, available_width);
     let header = sort_header_row(
         NEWS_COLUMNS,
         &vis,
         app.news_sort_column,
         &app.news_sort_direction,
-        Color::Blue,
+        theme.news_header,
     );
 
     let filtered = app.get_filtered_news();
@@ -66,26 +84,45 @@ pub fn draw_news(frame: &mut Frame, area: Rect, app: &mut App) {
         .iter()
         .map(|item| {
             let bookmarked = app.config.is_bookmarked(&item.title, item.url.as_deref());
-            news_row(item, &vis, bookmarked)
+            news_row(item, &vis, bookmarked, theme)
         })
         .collect();
 
+    let mut filters = match &app.news_ticker_filter {
+        Some(t) => format!(" [{}]", t),
+        None => String::new(),
+    };
+    if let Some(category) = &app.news_category_filter {
+        filters.push_str(&format!(" [{}]", category));
+    }
     let title = if app.rss_loading {
-        " News [Loading...] ".to_string()
+        format!(" News{} [Loading...] ", filters)
     } else {
-        format!(" News ({} articles) ", filtered.len())
+        let total = app.news_items.len();
+        if filtered.len() == total {
+            format!(" News{} ({} articles) ", filters, total)
+        } else {
+            format!(
+                " News{} ({} of {} articles) ",
+                filters,
+                filtered.len(),
+                total
+            )
+        }
     };
 
     let constraints = column_constraints(NEWS_COLUMNS, &vis, Some(2), available_width);
+    let block = chrome_block(app.config.density).title(title);
+    app.table_hit_area = Some(TableHitArea::new(block.inner(area), &vis, &constraints));
     let table = Table::new(rows, constraints)
         .header(header)
         .row_highlight_style(
-            Style::default()
-                .bg(Color::Rgb(40, 60, 100))
-                .fg(Color::White)
+            theme
+                .selected(theme.news_selection_bg)
+                .fg(theme.text)
                 .add_modifier(Modifier::BOLD),
         )
-        .block(Block::default().borders(Borders::ALL).title(title));
+        .block(block);
 
     app.news_table_state.select(Some(app.news_selected));
     frame.render_stateful_widget(table, area, &mut app.news_table_state);
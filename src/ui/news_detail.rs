@@ -1,10 +1,11 @@
 use super::centered_rect;
 use super::formatters::*;
 use crate::app::App;
+use crate::config::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
@@ -102,7 +103,7 @@ fn strip_tag_content(html: &str) -> String {
     out.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
+pub fn draw_news_detail(frame: &mut Frame, app: &mut App, theme: &Theme) {
     let area = centered_rect(70, 55, frame.area());
     frame.render_widget(Clear, area);
 
@@ -134,8 +135,8 @@ pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
     let outer_block = Block::default()
         .title(format!(" {} ", publisher))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.news_header))
+        .style(Style::default().bg(theme.backdrop));
 
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -162,10 +163,10 @@ pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
 
     // Metadata: full datetime + relative
     all_lines.push(Line::from(vec![
-        Span::styled(full_dt, Style::default().fg(Color::DarkGray)),
+        Span::styled(full_dt, Style::default().fg(theme.muted)),
         Span::styled(
             format!("  ({})", relative),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ]));
 
@@ -183,13 +184,13 @@ pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
         };
         all_lines.push(Line::from(Span::styled(
             display,
-            Style::default().fg(Color::Blue),
+            Style::default().fg(theme.news_header),
         )));
     }
 
     all_lines.push(Line::from(Span::styled(
         "─".repeat(inner_width),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
 
     // Body: RSS summary with HTML stripped
@@ -198,7 +199,7 @@ pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
     if body_clean.is_empty() {
         all_lines.push(Line::from(Span::styled(
             "No summary available.",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
     } else {
         for line in word_wrap(&body_clean, inner_width) {
@@ -218,19 +219,19 @@ pub fn draw_news_detail(frame: &mut Frame, app: &mut App) {
 
     let footer_line = if url.is_some() {
         Line::from(vec![
-            Span::styled("[o] ", Style::default().fg(Color::Cyan)),
-            Span::styled("browser  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[↑/↓] ", Style::default().fg(Color::Cyan)),
-            Span::styled("scroll  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Esc] ", Style::default().fg(Color::Cyan)),
-            Span::styled("close", Style::default().fg(Color::DarkGray)),
+            Span::styled("[o] ", Style::default().fg(theme.accent)),
+            Span::styled("browser  ", Style::default().fg(theme.muted)),
+            Span::styled("[↑/↓] ", Style::default().fg(theme.accent)),
+            Span::styled("scroll  ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc] ", Style::default().fg(theme.accent)),
+            Span::styled("close", Style::default().fg(theme.muted)),
         ])
     } else {
         Line::from(vec![
-            Span::styled("[↑/↓] ", Style::default().fg(Color::Cyan)),
-            Span::styled("scroll  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Esc] ", Style::default().fg(Color::Cyan)),
-            Span::styled("close", Style::default().fg(Color::DarkGray)),
+            Span::styled("[↑/↓] ", Style::default().fg(theme.accent)),
+            Span::styled("scroll  ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc] ", Style::default().fg(theme.accent)),
+            Span::styled("close", Style::default().fg(theme.muted)),
         ])
     };
     frame.render_widget(Paragraph::new(footer_line), footer_area);
@@ -1,11 +1,15 @@
 use super::formatters::*;
+use super::{chrome_block, table_metrics};
 use crate::api::StockQuote;
-use crate::app::App;
+use crate::api::yahoo::display_symbol;
+use crate::app::{App, TableHitArea, column_layout, relative_volume, vs_ihsg_pct};
+use crate::config::{Density, Highlights, Theme};
+use crate::idx::price_limit;
 use ratatui::{
     Frame,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table},
+    widgets::{Cell, Row, Table},
 };
 
 pub(super) struct ColumnDef {
@@ -65,14 +69,26 @@ const WATCHLIST_COLUMNS: &[ColumnDef] = &[
         width: 14,
         priority: 3,
     },
+    ColumnDef {
+        name: "Rel Vol",
+        width: 8,
+        priority: 4,
+    },
     ColumnDef {
         name: "News",
         width: 5,
         priority: 4,
     },
+    // Must stay last: dropped from the slice when `watchlist_sparklines` is off
+    ColumnDef {
+        name: "Trend",
+        width: 14,
+        priority: 3,
+    },
 ];
 /// Number of sortable columns (excludes non-sortable indicator columns like News)
-pub(crate) const WATCHLIST_SORTABLE_COLUMNS: usize = 10;
+pub(crate) const WATCHLIST_SORTABLE_COLUMNS: usize = 11;
+const TREND_WIDTH: usize = 12;
 
 const PORTFOLIO_COLUMNS: &[ColumnDef] = &[
     ColumnDef {
@@ -120,6 +136,11 @@ const PORTFOLIO_COLUMNS: &[ColumnDef] = &[
         width: 10,
         priority: 1,
     },
+    ColumnDef {
+        name: "vs IHSG",
+        width: 9,
+        priority: 3,
+    },
     ColumnDef {
         name: "News",
         width: 5,
@@ -127,17 +148,42 @@ const PORTFOLIO_COLUMNS: &[ColumnDef] = &[
     },
 ];
 /// Number of sortable columns (excludes non-sortable indicator columns like News)
-pub(crate) const PORTFOLIO_SORTABLE_COLUMNS: usize = 9;
+pub(crate) const PORTFOLIO_SORTABLE_COLUMNS: usize = 10;
+
+/// Column names of the watchlist table, by column index.
+pub(crate) fn watchlist_column_names() -> Vec<&'static str> {
+    WATCHLIST_COLUMNS.iter().map(|c| c.name).collect()
+}
+
+/// Column names of the portfolio table, by column index.
+pub(crate) fn portfolio_column_names() -> Vec<&'static str> {
+    PORTFOLIO_COLUMNS.iter().map(|c| c.name).collect()
+}
 
 pub(super) fn visible_columns(columns: &[ColumnDef], available_width: u16) -> Vec<usize> {
+    let layout: Vec<usize> = (0..columns.len()).collect();
+    visible_columns_in(columns, &layout, available_width)
+}
+
+/// Like `visible_columns`, but only considers the columns in `layout` and
+/// keeps them in that order. Indices past the end of `columns` are skipped.
+pub(super) fn visible_columns_in(
+    columns: &[ColumnDef],
+    layout: &[usize],
+    available_width: u16,
+) -> Vec<usize> {
+    let layout: Vec<usize> = layout
+        .iter()
+        .copied()
+        .filter(|&i| i < columns.len())
+        .collect();
     let max_priority = columns.iter().map(|c| c.priority).max().unwrap_or(1);
     let mut visible: Vec<usize> = Vec::new();
     for priority_cutoff in 1..=max_priority {
-        let candidate: Vec<usize> = columns
+        let candidate: Vec<usize> = layout
             .iter()
-            .enumerate()
-            .filter(|(_, c)| c.priority <= priority_cutoff)
-            .map(|(i, _)| i)
+            .copied()
+            .filter(|&i| columns[i].priority <= priority_cutoff)
             .collect();
         let total_width: u16 = candidate.iter().map(|&i| columns[i].width).sum();
         if total_width <= available_width {
@@ -147,16 +193,26 @@ pub(super) fn visible_columns(columns: &[ColumnDef], available_width: u16) -> Ve
         }
     }
     if visible.is_empty() {
-        visible = columns
+        visible = layout
             .iter()
-            .enumerate()
-            .filter(|(_, c)| c.priority == 1)
-            .map(|(i, _)| i)
+            .copied()
+            .filter(|&i| columns[i].priority == 1)
             .collect();
     }
+    if visible.is_empty() {
+        visible = layout.into_iter().take(1).collect();
+    }
     visible
 }
 
+/// Append the loading spinner glyph to a price while its symbol is in flight.
+fn with_marker(price: String, marker: Option<char>) -> String {
+    match marker {
+        Some(glyph) => format!("{} {}", price, glyph),
+        None => price,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn watchlist_cell(
     col_idx: usize,
@@ -167,7 +223,18 @@ fn watchlist_cell(
     is_selected: bool,
     has_news: bool,
     has_alert: bool,
+    trend: Option<&[f64]>,
+    marker: Option<char>,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
 ) -> Cell<'static> {
+    let rel_volume = relative_volume(q);
+    let volume_style = if highlights.is_volume_spike(rel_volume) {
+        text_style.fg(theme.accent).add_modifier(Modifier::BOLD)
+    } else {
+        text_style
+    };
     match col_idx {
         0 => {
             let label = if has_alert {
@@ -176,40 +243,84 @@ fn watchlist_cell(
                 q.symbol.clone()
             };
             let style = if has_alert {
-                bold_text.fg(Color::Red)
+                bold_text.fg(theme.loss)
             } else {
                 bold_text
             };
             Cell::from(label).style(style)
         }
         1 => Cell::from(truncate_str(&q.short_name, 20)).style(text_style),
-        2 => Cell::from(format_price(q.price)).style(bold_text),
+        2 => Cell::from(with_marker(fmt.price(q.price), marker)).style(bold_text),
         3 => Cell::from(format_change(q.change)).style(chg_style),
-        4 => Cell::from(format!("{:+.2}%", q.change_percent)).style(chg_style),
-        5 => Cell::from(format_price(q.open)).style(text_style),
-        6 => Cell::from(format_price(q.high)).style(text_style),
-        7 => Cell::from(format_price(q.low)).style(text_style),
-        8 => Cell::from(format_volume(q.volume)).style(text_style),
+        4 => {
+            let style = match highlights.heatmap_color(q.change_percent) {
+                Some(bg) => chg_style.fg(theme.text).bg(bg),
+                None => chg_style,
+            };
+            let arrow = highlights.arrow(q.change_percent);
+            let label = match price_limit(q.prev_close, q.price) {
+                Some(limit) => {
+                    format!(
+                        "{}{} {}",
+                        arrow,
+                        fmt.signed_percent(q.change_percent),
+                        limit.marker()
+                    )
+                }
+                None => format!("{}{}", arrow, fmt.signed_percent(q.change_percent)),
+            };
+            Cell::from(label).style(style)
+        }
+        5 => Cell::from(fmt.price(q.open)).style(text_style),
+        6 => Cell::from(fmt.price(q.high)).style(text_style),
+        7 => Cell::from(fmt.price(q.low)).style(text_style),
+        8 => Cell::from(fmt.volume(q.volume)).style(volume_style),
         9 => {
             let value = q.price * q.volume as f64;
             let style = if is_selected {
-                text_style.fg(Color::Cyan)
+                text_style.fg(theme.accent)
             } else {
                 Style::default()
             };
-            Cell::from(format_value(value)).style(style)
+            Cell::from(fmt.value(value)).style(style)
         }
-        10 => {
+        10 => match rel_volume {
+            Some(ratio) => Cell::from(format!("{:.1}x", ratio)).style(volume_style),
+            None => Cell::from("-").style(text_style),
+        },
+        11 => {
             if has_news {
-                Cell::from(" * ").style(Style::default().fg(Color::Yellow))
+                Cell::from(" * ").style(Style::default().fg(theme.header))
             } else {
                 Cell::from("")
             }
         }
+        12 => {
+            match trend {
+                Some(closes) if !closes.is_empty() => {
+                    let up = closes.last() >= closes.first();
+                    Cell::from(sparkline_str(closes, TREND_WIDTH)).style(Style::default().fg(
+                        highlights.change_color(theme, if up { 1.0 } else { -1.0 }, is_selected),
+                    ))
+                }
+                _ => Cell::from(""),
+            }
+        }
         _ => Cell::from(""),
     }
 }
 
+/// Dim a row whose quote is older than `stale_quote_secs`, so after-hours
+/// or failed-refresh prices don't pass for live ones.
+fn stale_style(style: Style, stale: bool) -> Style {
+    if stale {
+        style.add_modifier(Modifier::DIM)
+    } else {
+        style
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn watchlist_row(
     i: usize,
     symbol: &str,
@@ -218,21 +329,18 @@ fn watchlist_row(
     selected_index: usize,
     has_news: bool,
     has_alert: bool,
+    trend: Option<&[f64]>,
+    marker: Option<char>,
+    stale: bool,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
 ) -> Row<'static> {
     let is_selected = i == selected_index;
     if let Some(q) = quote {
-        let (change_color, selected_change_color) = if q.change >= 0.0 {
-            (Color::Green, Color::LightGreen)
-        } else {
-            (Color::Red, Color::LightRed)
-        };
-        let chg_color = if is_selected {
-            selected_change_color
-        } else {
-            change_color
-        };
+        let chg_color = highlights.change_color(theme, q.change_percent, is_selected);
         let text_style = if is_selected {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         } else {
             Style::default()
         };
@@ -254,20 +362,23 @@ fn watchlist_row(
                     is_selected,
                     has_news,
                     has_alert,
+                    trend,
+                    marker,
+                    theme,
+                    highlights,
+                    fmt,
                 )
             })
             .collect();
         let row_style = if is_selected {
-            Style::default().bg(Color::Rgb(40, 80, 120))
+            theme.selected(theme.selection_bg)
         } else {
             Style::default()
         };
-        Row::new(cells).style(row_style)
+        Row::new(cells).style(stale_style(row_style, stale))
     } else {
         let style = if is_selected {
-            Style::default()
-                .bg(Color::Rgb(40, 80, 120))
-                .fg(Color::White)
+            theme.selected(theme.selection_bg).fg(theme.text)
         } else {
             Style::default()
         };
@@ -281,18 +392,25 @@ fn watchlist_row(
                         symbol.to_string()
                     };
                     if has_alert {
-                        Cell::from(label).style(Style::default().fg(Color::Red))
+                        Cell::from(label).style(Style::default().fg(theme.loss))
                     } else {
                         Cell::from(label)
                     }
                 }
-                10 => {
+                11 => {
                     if has_news {
-                        Cell::from(" * ").style(Style::default().fg(Color::Yellow))
+                        Cell::from(" * ").style(Style::default().fg(theme.header))
                     } else {
                         Cell::from("")
                     }
                 }
+                2 => match marker {
+                    Some(glyph) => {
+                        Cell::from(glyph.to_string()).style(Style::default().fg(theme.accent))
+                    }
+                    None => Cell::from("-"),
+                },
+                12 => Cell::from(""),
                 _ => Cell::from("-"),
             })
             .collect();
@@ -333,7 +451,7 @@ pub(super) fn column_constraints(
 
     match stretch_col {
         // Single stretch column absorbs all extra space (e.g. Name in watchlist)
-        Some(sc) if extra > 0 => vis
+        Some(sc) if extra > 0 && vis.contains(&sc) => vis
             .iter()
             .map(|&i| {
                 if i == sc {
@@ -357,16 +475,48 @@ pub(super) fn column_constraints(
     }
 }
 
-pub fn draw_watchlist(frame: &mut Frame, area: Rect, app: &mut App) {
-    app.table_viewport_height = area.height.saturating_sub(3) as usize;
-    let available_width = area.width.saturating_sub(2);
-    let vis = visible_columns(WATCHLIST_COLUMNS, available_width);
+/// Height of the pinned-rows block: its rows plus borders (or the title row
+/// when compact). Zero when nothing is pinned.
+fn pinned_block_height(rows: usize, density: Density) -> u16 {
+    if rows == 0 {
+        return 0;
+    }
+    let chrome = match density {
+        Density::Normal => 2,
+        Density::Compact => 1,
+    };
+    rows as u16 + chrome
+}
+
+pub fn draw_watchlist(
+    frame: &mut Frame,
+    area: Rect,
+    app: &mut App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let pinned_height = pinned_block_height(app.get_pinned_rows().len(), app.config.density);
+    let [pinned_area, area] =
+        Layout::vertical([Constraint::Length(pinned_height), Constraint::Min(0)]).areas(area);
+    let (viewport_height, available_width) = table_metrics(area, app.config.density);
+    app.table_viewport_height = viewport_height;
+    let columns = if app.config.watchlist_sparklines {
+        WATCHLIST_COLUMNS
+    } else {
+        &WATCHLIST_COLUMNS[..WATCHLIST_COLUMNS.len() - 1]
+    };
+    let layout = column_layout(
+        app.config.watchlist_columns.as_deref(),
+        WATCHLIST_COLUMNS.len(),
+    );
+    let vis = visible_columns_in(columns, &layout, available_width);
     let header = sort_header_row(
-        WATCHLIST_COLUMNS,
+        columns,
         &vis,
         app.watchlist_sort_column,
         &app.watchlist_sort_direction,
-        Color::Yellow,
+        theme.header,
     );
 
     let watchlist = app.get_filtered_watchlist();
@@ -374,8 +524,13 @@ pub fn draw_watchlist(frame: &mut Frame, area: Rect, app: &mut App) {
         .iter()
         .enumerate()
         .map(|(i, (symbol, quote))| {
-            let has_news = app.has_recent_news(symbol);
-            let has_alert = app.config.has_active_alerts(symbol);
+            let key = display_symbol(&symbol.to_uppercase());
+            let has_news = app.has_recent_news(&key);
+            let has_alert = app.config.has_active_alerts(&key);
+            let trend = app
+                .mini_charts
+                .get(&key)
+                .map(|(_, chart)| chart.closes.as_slice());
             watchlist_row(
                 i,
                 symbol,
@@ -384,27 +539,67 @@ pub fn draw_watchlist(frame: &mut Frame, area: Rect, app: &mut App) {
                 app.selected_index,
                 has_news,
                 has_alert,
+                trend,
+                app.price_marker(&key),
+                app.quote_is_stale(&key),
+                theme,
+                highlights,
+                fmt,
             )
         })
         .collect();
 
-    let constraints = column_constraints(WATCHLIST_COLUMNS, &vis, Some(1), available_width);
-    let table = Table::new(rows, constraints)
-        .header(header)
-        .block(Block::default().borders(Borders::ALL).title(" Watchlist "));
+    let constraints = column_constraints(columns, &vis, Some(1), available_width);
+    if pinned_height > 0 {
+        let pinned: Vec<Row> = app
+            .get_pinned_rows()
+            .into_iter()
+            .map(|(symbol, quote)| {
+                let key = display_symbol(&symbol.to_uppercase());
+                watchlist_row(
+                    0,
+                    symbol,
+                    quote,
+                    &vis,
+                    // Pinned rows are never selected
+                    usize::MAX,
+                    app.has_recent_news(&key),
+                    app.config.has_active_alerts(&key),
+                    None,
+                    app.price_marker(&key),
+                    app.quote_is_stale(&key),
+                    theme,
+                    highlights,
+                    fmt,
+                )
+            })
+            .collect();
+        let pinned_table = Table::new(pinned, constraints.clone())
+            .block(chrome_block(app.config.density).title(" Pinned "));
+        frame.render_widget(pinned_table, pinned_area);
+    }
+    let block = chrome_block(app.config.density).title(" Watchlist ");
+    app.table_hit_area = Some(TableHitArea::new(block.inner(area), &vis, &constraints));
+    let table = Table::new(rows, constraints).header(header).block(block);
 
     app.watchlist_table_state.select(Some(app.selected_index));
     frame.render_stateful_widget(table, area, &mut app.watchlist_table_state);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn portfolio_cell(
     col_idx: usize,
     holding: &crate::config::Holding,
     short_name: &str,
     metrics: (f64, f64, f64, f64, f64),
     styles: (Style, Style, Style),
+    vs_ihsg: Option<(f64, Style)>,
+    marker: Option<char>,
     has_news: bool,
     has_alert: bool,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
 ) -> Cell<'static> {
     let (curr_price, value, cost, pl, pl_percent) = metrics;
     let (bold_text, text_style, pl_style) = styles;
@@ -416,7 +611,7 @@ fn portfolio_cell(
                 holding.symbol.clone()
             };
             let style = if has_alert {
-                bold_text.fg(Color::Red)
+                bold_text.fg(theme.loss)
             } else {
                 bold_text
             };
@@ -424,15 +619,29 @@ fn portfolio_cell(
         }
         1 => Cell::from(truncate_str(short_name, 20)).style(text_style),
         2 => Cell::from(format!("{}", holding.lots)).style(text_style),
-        3 => Cell::from(format_price(holding.avg_price)).style(text_style),
-        4 => Cell::from(format_price(curr_price)).style(text_style),
-        5 => Cell::from(format_value(value)).style(text_style),
-        6 => Cell::from(format_value(cost)).style(text_style),
-        7 => Cell::from(format_pl(pl)).style(pl_style),
-        8 => Cell::from(format!("{:+.2}%", pl_percent)).style(pl_style),
-        9 => {
+        3 => Cell::from(fmt.price(holding.avg_price)).style(text_style),
+        4 => Cell::from(with_marker(fmt.price(curr_price), marker)).style(text_style),
+        5 => Cell::from(fmt.value(value)).style(text_style),
+        6 => Cell::from(fmt.value(cost)).style(text_style),
+        7 => Cell::from(format!("{}{}", highlights.arrow(pl), fmt.pl(pl))).style(pl_style),
+        8 => Cell::from(format!(
+            "{}{}",
+            highlights.arrow(pl_percent),
+            fmt.signed_percent(pl_percent)
+        ))
+        .style(pl_style),
+        9 => match vs_ihsg {
+            Some((diff, style)) => Cell::from(format!(
+                "{}{}",
+                highlights.arrow(diff),
+                fmt.signed_percent(diff)
+            ))
+            .style(style),
+            None => Cell::from("-").style(text_style),
+        },
+        10 => {
             if has_news {
-                Cell::from(" * ").style(Style::default().fg(Color::Yellow))
+                Cell::from(" * ").style(Style::default().fg(theme.header))
             } else {
                 Cell::from("")
             }
@@ -441,6 +650,7 @@ fn portfolio_cell(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn portfolio_row(
     i: usize,
     holding: &crate::config::Holding,
@@ -448,26 +658,19 @@ fn portfolio_row(
     vis: &[usize],
     has_news: bool,
     has_alert: bool,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
 ) -> (Row<'static>, f64, f64) {
     let is_selected = i == app.portfolio_selected;
     let quote = app.quotes.get(&holding.symbol);
     let curr_price = quote.map(|q| q.price).unwrap_or(0.0);
     let short_name = quote.map(|q| q.short_name.as_str()).unwrap_or("-");
-    let (value, cost, pl, pl_percent) = holding.pl_metrics(curr_price);
+    let (value, cost, pl, pl_percent) = holding.pl_metrics(curr_price, app.config.lot_size);
 
-    let pl_color = if pl >= 0.0 { Color::Green } else { Color::Red };
-    let selected_pl_color = if pl >= 0.0 {
-        Color::LightGreen
-    } else {
-        Color::LightRed
-    };
-    let chg_color = if is_selected {
-        selected_pl_color
-    } else {
-        pl_color
-    };
+    let chg_color = highlights.change_color(theme, pl_percent, is_selected);
     let text_style = if is_selected {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     } else {
         Style::default()
     };
@@ -477,6 +680,12 @@ fn portfolio_row(
         text_style
     };
     let pl_style = Style::default().fg(chg_color).add_modifier(Modifier::BOLD);
+    let vs_ihsg = vs_ihsg_pct(&holding.symbol, &app.quotes).map(|diff| {
+        (
+            diff,
+            Style::default().fg(highlights.change_color(theme, diff, is_selected)),
+        )
+    });
 
     let cells: Vec<Cell> = vis
         .iter()
@@ -487,29 +696,46 @@ fn portfolio_row(
                 short_name,
                 (curr_price, value, cost, pl, pl_percent),
                 (bold_text, text_style, pl_style),
+                vs_ihsg,
+                app.price_marker(&holding.symbol),
                 has_news,
                 has_alert,
+                theme,
+                highlights,
+                fmt,
             )
         })
         .collect();
     let row_style = if is_selected {
-        Style::default().bg(Color::Rgb(80, 40, 80))
+        theme.selected(theme.portfolio_selection_bg)
     } else {
         Style::default()
     };
+    let row_style = stale_style(row_style, app.quote_is_stale(&holding.symbol));
     (Row::new(cells).style(row_style), value, cost)
 }
 
-pub fn draw_portfolio(frame: &mut Frame, area: Rect, app: &mut App) {
-    app.table_viewport_height = area.height.saturating_sub(3) as usize;
-    let available_width = area.width.saturating_sub(2);
-    let vis = visible_columns(PORTFOLIO_COLUMNS, available_width);
+pub fn draw_portfolio(
+    frame: &mut Frame,
+    area: Rect,
+    app: &mut App,
+    theme: &Theme,
+    highlights: &Highlights,
+    fmt: &NumberFormat,
+) {
+    let (viewport_height, available_width) = table_metrics(area, app.config.density);
+    app.table_viewport_height = viewport_height;
+    let layout = column_layout(
+        app.config.portfolio_columns.as_deref(),
+        PORTFOLIO_COLUMNS.len(),
+    );
+    let vis = visible_columns_in(PORTFOLIO_COLUMNS, &layout, available_width);
     let header = sort_header_row(
         PORTFOLIO_COLUMNS,
         &vis,
         app.portfolio_sort_column,
         &app.portfolio_sort_direction,
-        Color::Magenta,
+        theme.portfolio_header,
     );
 
     let mut total_value = 0.0;
@@ -521,7 +747,9 @@ pub fn draw_portfolio(frame: &mut Frame, area: Rect, app: &mut App) {
         .map(|(i, (_orig_idx, holding))| {
             let has_news = app.has_recent_news(&holding.symbol);
             let has_alert = app.config.has_active_alerts(&holding.symbol);
-            let (row, value, cost) = portfolio_row(i, holding, app, &vis, has_news, has_alert);
+            let (row, value, cost) = portfolio_row(
+                i, holding, app, &vis, has_news, has_alert, theme, highlights, fmt,
+            );
             total_value += value;
             total_cost += cost;
             row
@@ -534,25 +762,21 @@ pub fn draw_portfolio(frame: &mut Frame, area: Rect, app: &mut App) {
     } else {
         0.0
     };
-    let total_pl_color = if total_pl >= 0.0 {
-        Color::Green
-    } else {
-        Color::Red
-    };
+    let total_pl_color = highlights.change_color(theme, total_pl_pct, false);
     let title = format!(
-        " Portfolio | Value: {} | P/L: {} ({:+.2}%) ",
-        format_value(total_value),
-        format_pl(total_pl),
-        total_pl_pct
+        " Portfolio | Value: {} | P/L: {}{} ({}) ",
+        fmt.value(total_value),
+        highlights.arrow(total_pl),
+        fmt.pl(total_pl),
+        fmt.signed_percent(total_pl_pct)
     );
 
     let constraints = column_constraints(PORTFOLIO_COLUMNS, &vis, Some(1), available_width);
-    let table = Table::new(rows, constraints).header(header).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(title)
-            .title_style(Style::default().fg(total_pl_color)),
-    );
+    let block = chrome_block(app.config.density)
+        .title(title)
+        .title_style(Style::default().fg(total_pl_color));
+    app.table_hit_area = Some(TableHitArea::new(block.inner(area), &vis, &constraints));
+    let table = Table::new(rows, constraints).header(header).block(block);
 
     app.portfolio_table_state
         .select(Some(app.portfolio_selected));
@@ -1,13 +1,31 @@
 mod common;
 
-use common::test_app;
+use common::{MockProvider, make_quote, temp_config_app, test_app};
 use idx_cli::api::StockQuote;
-use idx_cli::app::{InputMode, ViewMode};
-use idx_cli::config::{Alert, AlertType};
+use idx_cli::app::{AlertNotifier, InputMode, ViewMode};
+use idx_cli::config::{Alert, AlertType, validate_alert_value};
+use std::sync::{Arc, Mutex};
+
+/// Records what `check_alerts` asked it to announce.
+#[derive(Clone, Default)]
+struct RecordingNotifier(Arc<Mutex<Vec<String>>>);
+
+impl AlertNotifier for RecordingNotifier {
+    fn bell(&mut self) {
+        self.0.lock().unwrap().push("bell".to_string());
+    }
+
+    fn desktop(&mut self, summary: &str, body: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{}: {}", summary, body));
+    }
+}
 
 #[test]
 fn check_alerts_fires_when_price_matches() {
-    let mut app = test_app();
+    let (mut app, path) = temp_config_app("check-alerts-fires-when-price-matches");
     let alert = Alert::new("BBCA", AlertType::Above, 8000.0);
     app.config.add_alert(alert);
 
@@ -38,6 +56,98 @@ fn check_alerts_fires_when_price_matches() {
     let triggered = app.check_alerts();
     assert_eq!(triggered.len(), 1);
     assert!(triggered[0].1.contains("crossed above"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn cross_above_alert_fires_once_across_refreshes() {
+    let (app, path) = temp_config_app("cross-above-alert-fires-once");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![make_quote(
+        "BBCA", 8100.0, 100.0, 1.0,
+    )])));
+    let mut alert = Alert::new("BBCA", AlertType::CrossAbove, 8000.0);
+    alert.cooldown_seconds = 0;
+    app.config.add_alert(alert);
+    // The previous refresh saw BBCA below the target
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 7950.0, 0.0, 0.0));
+    let symbols = app.refresh_symbols().unwrap();
+
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.config.alerts[0].last_triggered.is_some());
+    assert!(
+        app.status_message
+            .as_deref()
+            .is_some_and(|m| m.contains("crossed up through 8000"))
+    );
+
+    // Still above on the next refresh: no second fire despite no cooldown
+    app.config.alerts[0].last_triggered = None;
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.config.alerts[0].last_triggered.is_none());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn above_alert_keeps_firing_while_above() {
+    let (mut app, path) = temp_config_app("above-alert-keeps-firing-while-above");
+    let mut alert = Alert::new("BBCA", AlertType::Above, 8000.0);
+    alert.cooldown_seconds = 0;
+    app.config.add_alert(alert);
+    app.prev_quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8050.0, 0.0, 0.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8100.0, 0.0, 0.0));
+    assert_eq!(app.check_alerts().len(), 1);
+    assert_eq!(app.check_alerts().len(), 1);
+
+    app.config.alerts[0].alert_type = AlertType::CrossAbove;
+    assert!(app.check_alerts().is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn check_alerts_sets_status_and_marks_triggered() {
+    let (mut app, path) = temp_config_app("check-alerts-sets-status-and-marks-triggered");
+    app.config
+        .add_alert(Alert::new("BBCA", AlertType::Below, 9000.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8500.0, -100.0, -1.0));
+
+    app.check_alerts();
+    assert!(
+        app.status_message
+            .as_deref()
+            .is_some_and(|m| m.contains("crossed below"))
+    );
+    assert!(app.config.alerts[0].last_triggered.is_some());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn check_alerts_goes_through_notifier_per_config() {
+    let (app, path) = temp_config_app("check-alerts-notifier");
+    let notifier = RecordingNotifier::default();
+    let mut app = app.with_notifier(Box::new(notifier.clone()));
+    app.config.alert_sound = true;
+    app.config.alert_desktop_notifications = false;
+    app.config
+        .add_alert(Alert::new("BBCA", AlertType::Below, 9000.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8500.0, -100.0, -1.0));
+
+    app.check_alerts();
+    assert_eq!(*notifier.0.lock().unwrap(), vec!["bell"]);
+
+    app.config.alert_sound = false;
+    app.config.alert_desktop_notifications = true;
+    app.config.alerts[0].last_triggered = None;
+    app.check_alerts();
+    assert_eq!(
+        notifier.0.lock().unwrap().last().map(String::as_str),
+        Some("IDX Alert: BBCA crossed below 9000")
+    );
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
@@ -50,3 +160,121 @@ fn open_alert_modal_returns_to_normal_when_no_symbol() {
     assert_eq!(app.input_mode, InputMode::Normal);
     assert_eq!(app.alert_symbol, None);
 }
+
+// --- validate_alert_value ---
+
+#[test]
+fn validate_alert_value_price_types_need_positive_price() {
+    for alert_type in [
+        AlertType::Above,
+        AlertType::Below,
+        AlertType::CrossAbove,
+        AlertType::CrossBelow,
+    ] {
+        assert_eq!(validate_alert_value(&alert_type, 9500.0), Ok(()));
+        // Prices aren't capped like percentages
+        assert_eq!(validate_alert_value(&alert_type, 150.0), Ok(()));
+        assert_eq!(
+            validate_alert_value(&alert_type, 0.0),
+            Err("Price must be > 0".to_string())
+        );
+        assert!(validate_alert_value(&alert_type, -10.0).is_err());
+    }
+}
+
+#[test]
+fn validate_alert_value_percent_types_need_0_to_100() {
+    for alert_type in [AlertType::PercentGain, AlertType::PercentLoss] {
+        assert_eq!(validate_alert_value(&alert_type, 5.0), Ok(()));
+        assert_eq!(validate_alert_value(&alert_type, 100.0), Ok(()));
+        assert_eq!(
+            validate_alert_value(&alert_type, 150.0),
+            Err("% must be between 0 and 100".to_string())
+        );
+        assert!(validate_alert_value(&alert_type, -5.0).is_err());
+        assert!(validate_alert_value(&alert_type, 0.0).is_err());
+    }
+    assert!(validate_alert_value(&AlertType::Above, f64::NAN).is_err());
+}
+
+#[test]
+fn alert_value_confirm_keeps_prompt_open_on_implausible_value() {
+    let mut app = test_app();
+    app.open_alert_modal();
+    app.alert_list_confirm();
+    app.pending_alert_type = AlertType::PercentLoss;
+    app.alert_type_confirm();
+    app.input_buffer = "250".to_string();
+    app.alert_value_confirm();
+    assert_eq!(app.input_mode, InputMode::AlertAddValue);
+    assert_eq!(app.input_buffer, "250");
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("% must be between 0 and 100")
+    );
+}
+
+#[test]
+fn alert_type_confirm_skips_value_for_day_high_low() {
+    let mut app = test_app();
+    app.open_alert_modal();
+    app.alert_list_confirm();
+    app.pending_alert_type = AlertType::NewDayHigh;
+    app.alert_type_confirm();
+    assert_eq!(app.input_mode, InputMode::AlertCooldown);
+    assert_eq!(app.input_buffer, "300");
+    assert_eq!(validate_alert_value(&AlertType::NewDayLow, 0.0), Ok(()));
+}
+
+// --- alert cooldown step ---
+
+#[test]
+fn alert_add_flow_sets_cooldown() {
+    let (mut app, path) = temp_config_app("alert-add-flow-sets-cooldown");
+    app.open_alert_modal();
+    app.alert_list_confirm();
+    app.alert_type_confirm();
+    app.input_buffer = "9500".to_string();
+    app.alert_value_confirm();
+    assert_eq!(app.input_mode, InputMode::AlertCooldown);
+    assert_eq!(app.input_buffer, "300");
+
+    app.input_buffer = "60".to_string();
+    app.alert_cooldown_confirm().unwrap();
+    assert_eq!(app.input_mode, InputMode::AlertList);
+    let alert = &app.config.alerts[0];
+    assert_eq!(alert.target_value, 9500.0);
+    assert_eq!(alert.cooldown_seconds, 60);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn alert_cooldown_rejects_empty_input() {
+    let mut app = test_app();
+    app.open_alert_modal();
+    app.pending_alert_value = 9000.0;
+    app.input_mode = InputMode::AlertCooldown;
+    app.input_buffer.clear();
+    app.alert_cooldown_confirm().unwrap();
+    assert_eq!(app.input_mode, InputMode::AlertCooldown);
+    assert!(app.config.alerts.is_empty());
+    assert!(app.status_message.is_some());
+}
+
+#[test]
+fn alert_cooldown_edit_updates_existing_alert() {
+    let (mut app, path) = temp_config_app("alert-cooldown-edit-updates-existing-alert");
+    app.config
+        .add_alert(Alert::new("BBCA", AlertType::Above, 9000.0));
+    app.open_alert_modal();
+    app.start_alert_cooldown_edit();
+    assert_eq!(app.input_mode, InputMode::AlertCooldown);
+    assert_eq!(app.input_buffer, "300");
+
+    app.input_buffer = "0".to_string();
+    app.alert_cooldown_confirm().unwrap();
+    assert_eq!(app.config.alerts.len(), 1);
+    assert_eq!(app.config.alerts[0].cooldown_seconds, 0);
+    assert_eq!(app.alert_cooldown_edit, None);
+    let _ = std::fs::remove_file(path);
+}
@@ -1,9 +1,15 @@
 mod common;
 
-use common::{make_news_item, make_quote, test_app};
-use idx_cli::api::ChartData;
-use idx_cli::app::{InputMode, SortDirection, ViewMode, title_contains_ticker};
-use idx_cli::config::Holding;
+use common::{MockProvider, make_news_item, make_quote, mock_app, temp_config_app, test_app};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use idx_cli::api::{ChartData, StockQuote};
+use idx_cli::app::{
+    App, DeleteTarget, EVENT_LOG_CAPACITY, ExportFormat, ExportScope, InputMode, PortfolioHistory,
+    PortfolioSnapshot, RefreshHealth, SortDirection, ViewMode, WATCHLIST_CSV_HEADER, column_layout,
+    market_breadth, market_is_open, quotes_json, quotes_table, refresh_health,
+    title_contains_ticker, trading_date,
+};
+use idx_cli::config::{Alert, AlertType, Config, Density, Holding, NumberLocale};
 
 // --- title_contains_ticker ---
 
@@ -97,11 +103,13 @@ fn test_move_down_portfolio_view() {
         symbol: "BBCA".to_string(),
         lots: 10,
         avg_price: 8000.0,
+        note: None,
     });
     app.config.portfolios[0].holdings.push(Holding {
         symbol: "BBRI".to_string(),
         lots: 5,
         avg_price: 5000.0,
+        note: None,
     });
     assert_eq!(app.portfolio_selected, 0);
     app.move_down();
@@ -119,45 +127,176 @@ fn test_move_down_news_view() {
     assert_eq!(app.news_selected, 1);
 }
 
+#[test]
+fn test_wrap_navigation_down_from_last_row() {
+    let mut app = test_app();
+    app.config.wrap_navigation = true;
+    app.table_viewport_height = 2;
+    app.go_bottom();
+    assert_eq!(app.watchlist_table_state.offset(), 2);
+    app.move_down();
+    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.watchlist_table_state.selected(), Some(0));
+    assert_eq!(app.watchlist_table_state.offset(), 0);
+}
+
+#[test]
+fn test_wrap_navigation_up_from_first_row() {
+    let mut app = test_app();
+    app.config.wrap_navigation = true;
+    app.table_viewport_height = 2;
+    app.move_up();
+    assert_eq!(app.selected_index, 3);
+    assert_eq!(app.watchlist_table_state.selected(), Some(3));
+    assert_eq!(app.watchlist_table_state.offset(), 2);
+    // Away from the ends it moves one row as usual
+    app.move_up();
+    assert_eq!(app.selected_index, 2);
+}
+
+#[test]
+fn test_wrap_navigation_empty_list() {
+    let mut app = test_app();
+    app.config.wrap_navigation = true;
+    app.config.watchlists[0].symbols.clear();
+    app.move_up();
+    app.move_down();
+    assert_eq!(app.selected_index, 0);
+}
+
+// --- page_up / page_down / go_top / go_bottom ---
+
+#[test]
+fn test_page_down_moves_by_viewport_and_clamps() {
+    let mut app = test_app();
+    app.table_viewport_height = 2;
+    app.page_down();
+    assert_eq!(app.selected_index, 2);
+    app.page_down();
+    assert_eq!(app.selected_index, 3);
+    assert_eq!(app.watchlist_table_state.offset(), 2);
+}
+
+#[test]
+fn test_page_up_moves_by_viewport_and_clamps() {
+    let mut app = test_app();
+    app.table_viewport_height = 2;
+    app.selected_index = 3;
+    app.page_up();
+    assert_eq!(app.selected_index, 1);
+    app.page_up();
+    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.watchlist_table_state.offset(), 0);
+}
+
+#[test]
+fn test_go_top_and_go_bottom() {
+    let mut app = test_app();
+    app.go_bottom();
+    assert_eq!(app.selected_index, 3);
+    app.go_top();
+    assert_eq!(app.selected_index, 0);
+}
+
+#[test]
+fn test_paging_empty_list_is_noop() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::News;
+    app.page_down();
+    app.go_bottom();
+    assert_eq!(app.news_selected, 0);
+}
+
 // --- cycle_sort_column ---
 
 #[test]
 fn test_cycle_sort_column_from_none() {
-    let mut app = test_app();
+    let (mut app, path) = temp_config_app("cycle-sort-column-from-none");
     assert_eq!(app.watchlist_sort_column, None);
     app.cycle_sort_column();
     assert_eq!(app.watchlist_sort_column, Some(0));
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
 fn test_cycle_sort_column_increment() {
-    let mut app = test_app();
+    let (mut app, path) = temp_config_app("cycle-sort-column-increment");
     app.watchlist_sort_column = Some(0);
     app.cycle_sort_column();
     assert_eq!(app.watchlist_sort_column, Some(1));
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
 fn test_cycle_sort_column_wrap_to_none() {
-    let mut app = test_app();
-    app.watchlist_sort_column = Some(9);
+    let (mut app, path) = temp_config_app("cycle-sort-column-wrap-to-none");
+    app.watchlist_sort_column = Some(10);
     app.cycle_sort_column();
     assert_eq!(app.watchlist_sort_column, None);
+    let _ = std::fs::remove_file(path);
+}
+
+// Set the active watchlist to BBCA, BBRI, TLKM, ASII, all quoted.
+fn quote_watchlist(app: &mut App) {
+    app.config.current_watchlist_mut().symbols.clear();
+    for (sym, price) in [
+        ("BBCA", 9000.0),
+        ("BBRI", 5000.0),
+        ("TLKM", 3500.0),
+        ("ASII", 6000.0),
+    ] {
+        app.config
+            .current_watchlist_mut()
+            .symbols
+            .push(sym.to_string());
+        app.quotes
+            .insert(sym.to_string(), make_quote(sym, price, 0.0, 0.0));
+    }
+}
+
+#[test]
+fn test_cycle_sort_column_keeps_selected_symbol() {
+    let (mut app, path) = temp_config_app("cycle-sort-column-keeps-selected-symbol");
+    quote_watchlist(&mut app);
+    app.selected_index = 2;
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("TLKM"));
+    // Symbol ascending: ASII, BBCA, BBRI, TLKM
+    app.cycle_sort_column();
+    assert_eq!(app.selected_index, 3);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("TLKM"));
+    assert_eq!(app.watchlist_table_state.selected(), Some(3));
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
-fn test_cycle_sort_column_resets_selected() {
+fn test_cycle_sort_column_keeps_selected_holding() {
     let mut app = test_app();
-    app.selected_index = 3;
+    app.view_mode = ViewMode::Portfolio;
+    for (sym, lots) in [("BBRI", 5), ("ASII", 20), ("BBCA", 10)] {
+        app.config.portfolios[0].holdings.push(Holding {
+            symbol: sym.to_string(),
+            lots,
+            avg_price: 1000.0,
+            note: None,
+        });
+    }
+    app.portfolio_selected = 0;
+    app.portfolio_sort_column = Some(1);
+    // Lots ascending: BBRI, BBCA, ASII
     app.cycle_sort_column();
-    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.portfolio_sort_column, Some(2));
+    assert_eq!(app.selected_portfolio_symbol().as_deref(), Some("BBRI"));
+    assert_eq!(app.portfolio_selected, 0);
+    app.toggle_sort_direction();
+    assert_eq!(app.portfolio_selected, 2);
+    assert_eq!(app.selected_portfolio_symbol().as_deref(), Some("BBRI"));
 }
 
 #[test]
 fn test_cycle_sort_column_portfolio_view() {
     let mut app = test_app();
     app.view_mode = ViewMode::Portfolio;
-    app.portfolio_sort_column = Some(8);
+    app.portfolio_sort_column = Some(9);
     app.cycle_sort_column();
     assert_eq!(app.portfolio_sort_column, None);
 }
@@ -175,26 +314,49 @@ fn test_cycle_sort_column_news_view() {
 
 #[test]
 fn test_toggle_sort_ascending_to_descending() {
-    let mut app = test_app();
+    let (mut app, path) = temp_config_app("toggle-sort-ascending-to-descending");
     assert_eq!(app.watchlist_sort_direction, SortDirection::Ascending);
     app.toggle_sort_direction();
     assert_eq!(app.watchlist_sort_direction, SortDirection::Descending);
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
 fn test_toggle_sort_descending_to_ascending() {
-    let mut app = test_app();
+    let (mut app, path) = temp_config_app("toggle-sort-descending-to-ascending");
     app.watchlist_sort_direction = SortDirection::Descending;
     app.toggle_sort_direction();
     assert_eq!(app.watchlist_sort_direction, SortDirection::Ascending);
+    let _ = std::fs::remove_file(path);
 }
 
 #[test]
-fn test_toggle_sort_resets_selected() {
+fn test_toggle_sort_keeps_selected_symbol() {
+    let (mut app, path) = temp_config_app("toggle-sort-keeps-selected-symbol");
+    quote_watchlist(&mut app);
+    // Price ascending: TLKM, BBRI, ASII, BBCA
+    app.watchlist_sort_column = Some(2);
+    app.selected_index = 1;
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("BBRI"));
+    app.toggle_sort_direction();
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("BBRI"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_toggle_sort_keeps_selected_news_item() {
     let mut app = test_app();
-    app.selected_index = 3;
+    app.view_mode = ViewMode::News;
+    app.news_items.push(make_news_item("Old", "CNBC", 1000));
+    app.news_items.push(make_news_item("Mid", "CNBC", 2000));
+    app.news_items.push(make_news_item("New", "CNBC", 3000));
+    app.news_sort_column = Some(0);
+    app.news_sort_direction = SortDirection::Ascending;
+    app.news_selected = 0;
     app.toggle_sort_direction();
-    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.news_selected, 2);
+    assert_eq!(app.get_filtered_news()[2].title, "Old");
 }
 
 // --- toggle_view ---
@@ -266,6 +428,200 @@ fn test_close_help_resets_mode() {
     assert_eq!(app.input_mode, InputMode::Normal);
 }
 
+#[test]
+fn test_help_escape_clears_filter_then_closes() {
+    let mut app = test_app();
+    app.show_help();
+    app.help_filter_push('x');
+    app.help_escape();
+    assert!(app.help_filter.is_empty());
+    assert_eq!(app.input_mode, InputMode::Help);
+    app.help_escape();
+    assert_eq!(app.input_mode, InputMode::Normal);
+}
+
+#[test]
+fn test_help_filter_reset_on_reopen() {
+    let mut app = test_app();
+    app.show_help();
+    app.help_filter_push('a');
+    app.help_filter_push('l');
+    app.help_filter_pop();
+    assert_eq!(app.help_filter, "a");
+    app.close_help();
+    app.show_help();
+    assert!(app.help_filter.is_empty());
+}
+
+fn rendered_text(app: &mut App) -> String {
+    rendered_text_at(app, 100, 50)
+}
+
+#[test]
+fn test_no_color_draws_without_colors_and_keeps_selection_visible() {
+    let mut app = test_app();
+    app.no_color = true;
+    app.config.change_heatmap = true;
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    app.quotes
+        .insert("BBRI".to_string(), make_quote("BBRI", 4000.0, -20.0, -0.5));
+
+    let text = rendered_text(&mut app);
+    assert!(text.contains("▲+1.12%"), "{}", text);
+    assert!(text.contains("▼-0.50%"), "{}", text);
+
+    let backend = ratatui::backend::TestBackend::new(100, 50);
+    let mut terminal = ratatui::Terminal::new(backend).unwrap();
+    terminal.draw(|f| idx_cli::ui::draw(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    assert!(
+        buffer.content().iter().all(|c| {
+            c.fg == ratatui::style::Color::Reset && c.bg == ratatui::style::Color::Reset
+        })
+    );
+    // The selected row is reversed instead of colored
+    let width = buffer.area.width as usize;
+    let reversed_rows: Vec<String> = buffer
+        .content()
+        .chunks(width)
+        .filter(|row| {
+            row.iter()
+                .any(|c| c.modifier.contains(ratatui::style::Modifier::REVERSED))
+        })
+        .map(|row| row.iter().map(|c| c.symbol()).collect())
+        .collect();
+    assert_eq!(reversed_rows.len(), 1);
+    assert!(reversed_rows[0].contains("BBCA"));
+}
+
+#[test]
+fn test_no_color_covers_every_view_and_modal() {
+    let mut app = test_app();
+    app.no_color = true;
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    app.quotes
+        .insert("BBRI".to_string(), make_quote("BBRI", 4000.0, -20.0, -0.5));
+    app.config.add_holding("BBCA", 10, 8000.0);
+    app.config.add_holding("BBRI", 5, 5000.0);
+
+    app.view_mode = ViewMode::Portfolio;
+    let text = rendered_text(&mut app);
+    assert!(text.contains("P/L: ▲+500.00K"), "{}", text);
+    assert!(text.contains("▲+12.50%"), "{}", text);
+    assert!(text.contains("▼-500.00K"), "{}", text);
+
+    let views = [
+        (ViewMode::Portfolio, InputMode::Normal),
+        (ViewMode::Portfolio, InputMode::PortfolioPL),
+        (ViewMode::Portfolio, InputMode::PortfolioChart),
+        (ViewMode::Portfolio, InputMode::PortfolioHistory),
+        (ViewMode::Watchlist, InputMode::StockDetail),
+        (ViewMode::Watchlist, InputMode::Help),
+        (ViewMode::Watchlist, InputMode::ExportMenu),
+        (ViewMode::Watchlist, InputMode::ColumnPicker),
+        (ViewMode::Watchlist, InputMode::EventLog),
+        (ViewMode::Watchlist, InputMode::AlertList),
+        (ViewMode::News, InputMode::Normal),
+        (ViewMode::News, InputMode::NewsSources),
+    ];
+    for (view, mode) in views {
+        app.view_mode = view;
+        app.input_mode = mode;
+        app.detail_symbol = Some("BBCA".to_string());
+        let backend = ratatui::backend::TestBackend::new(100, 50);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| idx_cli::ui::draw(f, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert!(
+            buffer.content().iter().all(|c| {
+                c.fg == ratatui::style::Color::Reset && c.bg == ratatui::style::Color::Reset
+            }),
+            "{:?} {:?} drew a color",
+            view,
+            mode
+        );
+    }
+
+    app.view_mode = ViewMode::Watchlist;
+    app.input_mode = InputMode::StockDetail;
+    let text = rendered_text(&mut app);
+    assert!(text.contains("▲+100"), "{}", text);
+}
+
+#[test]
+fn test_number_locale_only_affects_its_own_app() {
+    let mut id_app = test_app();
+    id_app.config.number_locale = NumberLocale::Id;
+    let mut en_app = test_app();
+    for app in [&mut id_app, &mut en_app] {
+        app.quotes
+            .insert("BBCA".to_string(), make_quote("BBCA", 9025.5, 25.0, 0.28));
+    }
+
+    assert!(rendered_text(&mut id_app).contains("9.025,50"));
+    // Drawing the Id app must not leak its locale into the next draw
+    let text = rendered_text(&mut en_app);
+    assert!(text.contains("9,025.50"), "{}", text);
+}
+
+fn rendered_text_at(app: &mut App, width: u16, height: u16) -> String {
+    let backend = ratatui::backend::TestBackend::new(width, height);
+    let mut terminal = ratatui::Terminal::new(backend).unwrap();
+    terminal.draw(|f| idx_cli::ui::draw(f, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|c| c.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_help_filter_limits_rendered_bindings() {
+    let mut app = test_app();
+    app.show_help();
+    let all = rendered_text(&mut app);
+    assert!(all.contains("Manage alerts"));
+    assert!(all.contains("Quit"));
+
+    for c in "ALERT".chars() {
+        app.help_filter_push(c);
+    }
+    let filtered = rendered_text(&mut app);
+    assert!(filtered.contains("Manage alerts"));
+    assert!(!filtered.contains("Quit"));
+    // The General section has no match, so its header is dropped
+    assert!(!filtered.contains("General"));
+
+    app.help_filter = "zzz".to_string();
+    assert!(rendered_text(&mut app).contains("No bindings match"));
+}
+
+#[test]
+fn test_remapped_keys_show_in_footer_and_help() {
+    let mut app = test_app();
+    app.config
+        .key_bindings
+        .insert("help".to_string(), "F1".to_string());
+    app.config
+        .key_bindings
+        .insert("refresh".to_string(), "F5".to_string());
+    app.keymap = idx_cli::keymap::KeyMap::from_config(&app.config.key_bindings).0;
+
+    let footer = rendered_text_at(&mut app, 200, 50);
+    assert!(footer.contains("[F5] Refresh"), "{}", footer);
+    assert!(footer.contains("[F1] Help"), "{}", footer);
+    assert!(!footer.contains("[?] Help"));
+
+    app.show_help();
+    let help = rendered_text(&mut app);
+    assert!(help.contains("F5          Refresh quotes"), "{}", help);
+    assert!(help.contains("[F1/Enter/Esc] Close"), "{}", help);
+}
+
 #[test]
 fn test_close_stock_detail() {
     let mut app = test_app();
@@ -331,3 +687,2064 @@ fn test_cancel_search() {
     assert!(app.input_buffer.is_empty());
     assert_eq!(app.input_mode, InputMode::Normal);
 }
+
+// --- portfolio_day_change ---
+
+#[test]
+fn test_portfolio_day_change_empty_is_none() {
+    let app = test_app();
+    assert!(app.portfolio_day_change().is_none());
+}
+
+#[test]
+fn test_portfolio_day_change_skips_unquoted_holdings() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.config.add_holding("TLKM", 5, 3000.0);
+    // BBCA: prev close 9900, +100 per share on 1000 shares
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 10000.0, 100.0, 1.01));
+
+    let (day_pl, pct) = app.portfolio_day_change().unwrap();
+    assert_eq!(day_pl, 100_000.0);
+    assert!((pct - 100_000.0 / 9_900_000.0 * 100.0).abs() < 1e-9);
+}
+
+// --- projected_annual_income ---
+
+#[test]
+fn test_projected_income_none_without_yields() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 10000.0, 0.0, 0.0));
+    assert!(app.projected_annual_income().is_none());
+}
+
+#[test]
+fn test_projected_income_reports_coverage() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.config.add_holding("TLKM", 5, 3000.0);
+    let mut bbca = make_quote("BBCA", 10000.0, 0.0, 0.0);
+    bbca.dividend_yield = Some(0.03);
+    app.quotes.insert("BBCA".to_string(), bbca);
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 3000.0, 0.0, 0.0));
+
+    let (income, yield_pct, covered, total) = app.projected_annual_income().unwrap();
+    // 1000 shares * 10000 * 3%
+    assert!((income - 300_000.0).abs() < 1e-6);
+    assert!((yield_pct - 3.0).abs() < 1e-9);
+    assert_eq!((covered, total), (1, 2));
+}
+
+// --- sector_allocation ---
+
+#[test]
+fn test_sector_allocation_groups_by_sector_with_unknown_bucket() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.config.add_holding("BBRI", 10, 4000.0);
+    app.config.add_holding("GOTO", 100, 50.0);
+    for (symbol, price, sector) in [
+        ("BBCA", 10000.0, Some("Financial Services")),
+        ("BBRI", 5000.0, Some("Financial Services")),
+        ("GOTO", 50.0, None),
+    ] {
+        let mut quote = make_quote(symbol, price, 0.0, 0.0);
+        quote.sector = sector.map(str::to_string);
+        app.quotes.insert(symbol.to_string(), quote);
+    }
+
+    let sectors = app.sector_allocation();
+    assert_eq!(sectors.len(), 2);
+    assert_eq!(sectors[0].0, "Financial Services");
+    assert_eq!(sectors[0].1, 1_500_000.0 * 10.0);
+    assert_eq!(sectors[1].0, "Unknown");
+    assert_eq!(sectors[1].1, 500_000.0);
+    let total: f64 = sectors.iter().map(|(_, _, pct)| pct).sum();
+    assert!((total - 100.0).abs() < 1e-9);
+}
+
+// --- adjust_refresh_interval ---
+
+#[test]
+fn test_adjust_refresh_interval_steps_and_clamps() {
+    let (mut app, path) = temp_config_app("adjust-refresh-interval-steps-and-clamps");
+    app.config.refresh_interval_secs = 1;
+    app.adjust_refresh_interval(false).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 1);
+
+    app.config.refresh_interval_secs = 9;
+    app.adjust_refresh_interval(true).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 10);
+    app.adjust_refresh_interval(true).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 15);
+    app.adjust_refresh_interval(false).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 10);
+    app.adjust_refresh_interval(false).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 9);
+
+    app.config.refresh_interval_secs = 290;
+    app.adjust_refresh_interval(true).unwrap();
+    assert_eq!(app.config.refresh_interval_secs, 300);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Refresh interval: 300s")
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// --- merge_quotes ---
+
+#[test]
+fn test_merge_quotes_keeps_symbols_missing_from_partial_response() {
+    let mut app = test_app();
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 0.0));
+    app.quotes
+        .insert("BBRI".to_string(), make_quote("BBRI", 4500.0, 50.0, 1.1));
+
+    let mut partial = std::collections::HashMap::new();
+    partial.insert("BBCA".to_string(), make_quote("BBCA", 9100.0, 100.0, 1.1));
+    app.merge_quotes(partial);
+
+    assert_eq!(app.quotes["BBCA"].price, 9100.0);
+    assert_eq!(app.quotes["BBRI"].price, 4500.0);
+}
+
+// --- quotes_table / quotes_json ---
+
+#[test]
+fn test_quotes_table_marks_missing_symbols() {
+    let mut quotes = std::collections::HashMap::new();
+    quotes.insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    let symbols = vec!["BBCA".to_string(), "XXXX".to_string()];
+    let table = quotes_table(&symbols, &quotes);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("BBCA") && lines[1].contains("+1.12%"));
+    assert!(lines[2].starts_with("XXXX") && lines[2].contains("not found"));
+}
+
+#[test]
+fn test_quotes_json_uses_export_shape() {
+    let mut quotes = std::collections::HashMap::new();
+    quotes.insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    let symbols = vec!["BBCA".to_string(), "XXXX".to_string()];
+    let data: serde_json::Value = serde_json::from_str(&quotes_json(&symbols, &quotes)).unwrap();
+    assert_eq!(data[0]["symbol"], "BBCA");
+    assert_eq!(data[0]["price"], 9000.0);
+    assert_eq!(data[0]["change_percent"], 1.12);
+    assert_eq!(data[0]["value"], 9_000_000_000.0);
+    assert!(data[0]["relative_volume"].is_null());
+    assert_eq!(data[1]["symbol"], "XXXX");
+    assert!(data[1]["price"].is_null());
+}
+
+#[test]
+fn test_export_watchlist_csv_includes_value_and_rel_vol() {
+    let mut app = test_app();
+    let mut bbca = make_quote("BBCA", 9000.0, 100.0, 1.12);
+    bbca.average_volume = Some(400_000);
+    app.quotes.insert("BBCA".to_string(), bbca);
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 3500.0, -50.0, -1.41));
+
+    let csv = app.export_watchlist_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], WATCHLIST_CSV_HEADER);
+    assert!(lines[0].ends_with(",Volume,Value,RelVol"));
+    let header: Vec<&str> = lines[0].split(',').collect();
+    let bbca: Vec<&str> = lines
+        .iter()
+        .find(|l| l.starts_with("BBCA"))
+        .unwrap()
+        .split(',')
+        .collect();
+    assert_eq!(bbca.len(), header.len());
+    assert_eq!(bbca[9], "9000000000.00");
+    assert_eq!(bbca[10], "2.50");
+    // No average volume: RelVol left empty
+    let tlkm = lines.iter().find(|l| l.starts_with("TLKM")).unwrap();
+    assert!(tlkm.ends_with(",3500000000.00,"));
+    // Unquoted rows keep the column count
+    let bbri = lines.iter().find(|l| l.starts_with("BBRI")).unwrap();
+    assert_eq!(bbri.split(',').count(), header.len());
+}
+
+// --- restore_session / remember_session ---
+
+#[test]
+fn test_restore_session_restores_view_and_clamps_selection() {
+    let mut app = test_app();
+    app.config.last_view_mode = "portfolio".to_string();
+    app.config.last_watchlist_selected = 2;
+    app.config.last_portfolio_selected = 5;
+    app.restore_session();
+    assert_eq!(app.view_mode, ViewMode::Portfolio);
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.watchlist_table_state.selected(), Some(2));
+    // Default portfolio is empty
+    assert_eq!(app.portfolio_selected, 0);
+}
+
+#[test]
+fn test_restore_session_unknown_view_falls_back_to_watchlist() {
+    let mut app = test_app();
+    app.config.last_view_mode = "bogus".to_string();
+    app.restore_session();
+    assert_eq!(app.view_mode, ViewMode::Watchlist);
+}
+
+#[test]
+fn test_remember_session_round_trips() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::News;
+    app.selected_index = 3;
+    app.remember_session();
+    assert_eq!(app.config.last_view_mode, "news");
+
+    let mut restored = App::test_new(app.config.clone());
+    restored.restore_session();
+    assert_eq!(restored.view_mode, ViewMode::News);
+    assert_eq!(restored.selected_index, 3);
+}
+
+// --- execute_refresh with a QuoteProvider ---
+
+#[tokio::test]
+async fn test_execute_refresh_populates_quotes_from_provider() {
+    let mut app = mock_app(vec![
+        make_quote("BBCA", 9000.0, 100.0, 1.12),
+        make_quote("TLKM", 3500.0, -50.0, -1.41),
+        make_quote("IHSG", 7200.0, 20.0, 0.28),
+    ]);
+    let symbols = app.refresh_symbols().unwrap();
+    app.loading = true;
+    app.execute_refresh(&symbols).await.unwrap();
+
+    assert!(!app.loading);
+    assert_eq!(app.status_message, None);
+    assert_eq!(app.quotes["BBCA"].price, 9000.0);
+    assert_eq!(app.quotes["TLKM"].change_percent, -1.41);
+    assert!(app.get_ihsg_quote().is_some());
+    // Unknown to the provider, so not quoted
+    assert!(!app.quotes.contains_key("BBRI"));
+}
+
+#[tokio::test]
+async fn test_pending_symbols_tracked_during_refresh() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    let symbols = app.prepare_refresh().unwrap();
+    assert!(app.pending_symbols.contains("BBCA"));
+    assert!(app.pending_symbols.contains("IHSG"));
+    let first = app.pending_spinner("BBCA").unwrap();
+    app.tick_spinner();
+    assert_ne!(app.pending_spinner("BBCA"), Some(first));
+
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.pending_symbols.is_empty());
+    assert_eq!(app.pending_spinner("BBRI"), None);
+}
+
+#[tokio::test]
+async fn test_spawned_fetch_leaves_app_free_to_draw() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    let symbols = app.prepare_refresh().unwrap();
+    let pending = app.start_refresh(&symbols);
+    let fetch = app.spawn_quote_fetch(&pending).unwrap();
+
+    // The fetch doesn't hold the app, so the spinner can keep turning
+    let first = app.pending_spinner("BBCA").unwrap();
+    app.tick_spinner();
+    assert_ne!(app.pending_spinner("BBCA"), Some(first));
+    assert!(app.loading);
+
+    let fetched = fetch.await.unwrap();
+    app.finish_refresh(pending, Some(fetched)).await.unwrap();
+    assert!(!app.loading);
+    assert!(app.pending_symbols.is_empty());
+    assert_eq!(app.quotes["BBCA"].price, 9000.0);
+}
+
+#[tokio::test]
+async fn test_spawned_mini_chart_fetch_leaves_app_free() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    assert!(app.spawn_mini_chart_fetch().is_none());
+
+    app.config.watchlist_sparklines = true;
+    let fetch = app.spawn_mini_chart_fetch().unwrap();
+    // The app stays usable while the charts load
+    app.move_down();
+    assert!(app.mini_charts.is_empty());
+
+    app.finish_mini_chart_fetch(fetch.await.unwrap());
+    assert_eq!(app.mini_charts["BBCA"].1.closes, vec![8900.0, 9000.0]);
+}
+
+#[tokio::test]
+async fn test_slow_mini_charts_do_not_block_refresh() {
+    let mut provider = MockProvider::new(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    provider.chart_delay = std::time::Duration::from_secs(60);
+    let mut app = test_app().with_provider(Box::new(provider));
+    app.config.watchlist_sparklines = true;
+
+    let fetch = app.spawn_mini_chart_fetch().unwrap();
+    // Let the chart task start its requests
+    tokio::task::yield_now().await;
+    let symbols = app.refresh_symbols().unwrap();
+    let refresh = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        app.execute_refresh(&symbols),
+    );
+    refresh
+        .await
+        .expect("refresh waited on the chart fetch")
+        .unwrap();
+    assert!(app.quotes.contains_key("BBCA"));
+    fetch.abort();
+}
+
+#[tokio::test]
+async fn test_tick_arrow_shows_move_since_previous_refresh() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    // First refresh has nothing to compare against
+    assert_eq!(app.tick_delta("BBCA"), None);
+    assert_eq!(app.price_marker("BBCA"), None);
+
+    // Pretend the last refresh saw a lower price
+    app.quotes.get_mut("BBCA").unwrap().price = 8950.0;
+    app.execute_refresh(&symbols).await.unwrap();
+    assert_eq!(app.tick_delta("BBCA"), Some(50.0));
+    assert_eq!(app.price_marker("BBCA"), Some('▲'));
+
+    // Unchanged on the next refresh, so the arrow clears
+    app.execute_refresh(&symbols).await.unwrap();
+    assert_eq!(app.tick_delta("BBCA"), None);
+    assert_eq!(app.price_marker("BBCA"), None);
+}
+
+#[tokio::test]
+async fn test_refresh_updates_open_detail_modal() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.input_mode = InputMode::StockDetail;
+    app.detail_symbol = Some("BBCA".to_string());
+    app.detail_news = Some(vec![make_news_item("Yahoo story", "Yahoo", 1000)]);
+    app.news_items = vec![make_news_item("BBCA books record profit", "CNBC", 2000)];
+
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+
+    let chart = app.detail_chart.as_ref().unwrap();
+    assert_eq!(chart.closes.last(), Some(&9000.0));
+    let titles: Vec<&str> = app
+        .detail_news
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|n| n.title.as_str())
+        .collect();
+    // New RSS match picked up, earlier headlines kept
+    assert_eq!(titles, vec!["BBCA books record profit", "Yahoo story"]);
+    assert_eq!(app.input_mode, InputMode::StockDetail);
+}
+
+#[tokio::test]
+async fn test_refresh_leaves_detail_alone_without_new_quote() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.input_mode = InputMode::StockDetail;
+    // Not served by the provider
+    app.detail_symbol = Some("BBRI".to_string());
+
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.detail_chart.is_none());
+    assert!(app.detail_news.is_none());
+}
+
+#[tokio::test]
+async fn test_yahoo_style_watchlist_symbols_resolve_to_quotes() {
+    let (app, path) = temp_config_app("yahoo-style-watchlist-symbols");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![
+        make_quote("IHSG", 7000.0, 70.0, 1.0),
+        make_quote("BBCA", 9000.0, 100.0, 1.12),
+    ])));
+    app.config.current_watchlist_mut().symbols = vec!["^JKSE".to_string(), "BBCA.JK".to_string()];
+    app.config.watchlist_sparklines = true;
+    app.config
+        .add_alert(Alert::new("BBCA.JK", AlertType::Above, 8000.0));
+
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    let rows: Vec<(String, Option<f64>)> = app
+        .get_filtered_watchlist()
+        .into_iter()
+        .map(|(s, q)| (s.clone(), q.map(|q| q.price)))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("^JKSE".to_string(), Some(7000.0)),
+            ("BBCA.JK".to_string(), Some(9000.0)),
+        ]
+    );
+
+    assert!(app.config.has_active_alerts("BBCA"));
+    // The refresh checked alerts against the BBCA quote
+    assert!(app.config.alerts[0].last_triggered.is_some());
+
+    let fetch = app.spawn_mini_chart_fetch().unwrap();
+    app.finish_mini_chart_fetch(fetch.await.unwrap());
+    assert!(app.mini_charts.contains_key("IHSG"));
+    assert!(app.mini_charts.contains_key("BBCA"));
+    let _ = std::fs::remove_file(path);
+}
+
+// --- lite mode ---
+
+fn quote_with_sector(symbol: &str, price: f64) -> StockQuote {
+    StockQuote {
+        sector: Some("Financial Services".to_string()),
+        market_cap: Some(1_000_000_000),
+        ..make_quote(symbol, price, 0.0, 0.0)
+    }
+}
+
+#[test]
+fn test_lite_merge_keeps_earlier_fundamentals() {
+    let mut app = test_app();
+    app.lite = true;
+    app.quotes
+        .insert("BBCA".to_string(), quote_with_sector("BBCA", 9000.0));
+
+    let mut lite = quote_with_sector("BBCA", 9100.0);
+    lite.strip_fundamentals();
+    app.merge_quotes(std::collections::HashMap::from([(
+        "BBCA".to_string(),
+        lite,
+    )]));
+
+    let quote = &app.quotes["BBCA"];
+    assert_eq!(quote.price, 9100.0);
+    assert_eq!(quote.sector.as_deref(), Some("Financial Services"));
+    assert_eq!(quote.market_cap, Some(1_000_000_000));
+}
+
+#[test]
+fn test_merge_without_lite_replaces_whole_quote() {
+    let mut app = test_app();
+    app.quotes
+        .insert("BBCA".to_string(), quote_with_sector("BBCA", 9000.0));
+    let mut stripped = quote_with_sector("BBCA", 9100.0);
+    stripped.strip_fundamentals();
+    app.merge_quotes(std::collections::HashMap::from([(
+        "BBCA".to_string(),
+        stripped,
+    )]));
+    assert_eq!(app.quotes["BBCA"].sector, None);
+}
+
+#[tokio::test]
+async fn test_lite_detail_fetches_fundamentals() {
+    let mut app = mock_app(vec![quote_with_sector("BBCA", 9000.0)]);
+    app.lite = true;
+    let mut lite = quote_with_sector("BBCA", 9000.0);
+    lite.strip_fundamentals();
+    app.quotes.insert("BBCA".to_string(), lite);
+    // Skip the RSS download
+    app.news_items = vec![make_news_item("Market wrap", "CNBC", 1000)];
+
+    app.show_stock_detail().await;
+    assert_eq!(app.input_mode, InputMode::StockDetail);
+    assert_eq!(
+        app.get_detail_quote().and_then(|q| q.sector.as_deref()),
+        Some("Financial Services")
+    );
+}
+
+#[test]
+fn test_tick_arrow_down_and_spinner_precedence() {
+    let mut app = test_app();
+    app.prev_quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 0.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8975.0, 0.0, 0.0));
+    assert_eq!(app.tick_delta("BBCA"), Some(-25.0));
+    assert_eq!(app.price_marker("BBCA"), Some('▼'));
+
+    app.pending_symbols.insert("BBCA".to_string());
+    assert_eq!(app.price_marker("BBCA"), app.pending_spinner("BBCA"));
+}
+
+// --- confirm before delete ---
+
+#[test]
+fn test_delete_asks_before_removing_stock() {
+    let mut app = test_app();
+    let before = app.config.current_watchlist().symbols.len();
+    app.request_delete_selected().unwrap();
+
+    assert_eq!(app.input_mode, InputMode::ConfirmDelete);
+    assert_eq!(
+        app.pending_delete,
+        Some(DeleteTarget::Stock("BBCA".to_string()))
+    );
+    assert_eq!(app.config.current_watchlist().symbols.len(), before);
+
+    app.cancel_delete();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.pending_delete, None);
+    assert_eq!(app.config.current_watchlist().symbols.len(), before);
+}
+
+#[test]
+fn test_delete_list_prompts_names_portfolio() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::Portfolio;
+    app.config.add_portfolio("Second");
+    app.request_delete_list().unwrap();
+
+    let target = app.pending_delete.clone().unwrap();
+    assert!(
+        target
+            .describe()
+            .contains(&app.config.current_portfolio().name)
+    );
+    assert_eq!(app.input_mode, InputMode::ConfirmDelete);
+}
+
+#[test]
+fn test_delete_last_list_does_not_prompt() {
+    let mut app = test_app();
+    app.request_delete_list().unwrap();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.pending_delete, None);
+    assert_eq!(app.config.watchlists.len(), 1);
+}
+
+#[test]
+fn test_confirm_delete_removes_stock() {
+    let (mut app, path) = temp_config_app("confirm-delete");
+    let before = app.config.current_watchlist().symbols.len();
+
+    app.request_delete_selected().unwrap();
+    app.confirm_delete().unwrap();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.config.current_watchlist().symbols.len(), before - 1);
+    assert!(
+        !app.config
+            .current_watchlist()
+            .symbols
+            .contains(&"BBCA".to_string())
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_confirm_delete_ignores_selection_change() {
+    let (mut app, path) = temp_config_app("confirm-delete-moved");
+    app.request_delete_selected().unwrap();
+    // A refresh re-sorts the table while the prompt is open
+    app.selected_index = 1;
+
+    app.confirm_delete().unwrap();
+    let symbols = &app.config.current_watchlist().symbols;
+    assert!(!symbols.contains(&"BBCA".to_string()));
+    assert!(symbols.contains(&"BBRI".to_string()));
+    assert_eq!(app.status_message.as_deref(), Some("Removed BBCA"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_confirm_delete_skips_target_that_is_gone() {
+    let (mut app, path) = temp_config_app("confirm-delete-gone");
+    app.view_mode = ViewMode::Portfolio;
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.config.add_holding("TLKM", 5, 3000.0);
+    app.request_delete_selected().unwrap();
+    app.config.current_portfolio_mut().holdings.remove(0);
+
+    app.confirm_delete().unwrap();
+    assert_eq!(app.config.current_portfolio().holdings.len(), 1);
+    assert_eq!(app.config.current_portfolio().holdings[0].symbol, "TLKM");
+    assert!(app.last_undo.is_none());
+    let _ = std::fs::remove_file(path);
+}
+
+// --- undo ---
+
+#[test]
+fn test_undo_restores_holding_at_original_index() {
+    let (mut app, path) = temp_config_app("undo-holding");
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.config.add_holding("TLKM", 5, 3000.0);
+    app.config.add_holding("ASII", 2, 5000.0);
+    app.view_mode = ViewMode::Portfolio;
+    app.portfolio_selected = 1;
+    let transactions = app.config.current_portfolio().transactions.len();
+
+    app.remove_selected_holding().unwrap();
+    assert_eq!(app.config.current_portfolio().holdings.len(), 2);
+
+    app.undo().unwrap();
+    let holdings = &app.config.current_portfolio().holdings;
+    assert_eq!(holdings[1].symbol, "TLKM");
+    assert_eq!(holdings[1].lots, 5);
+    assert_eq!(holdings.len(), 3);
+    // The sell recorded by the removal is rolled back too
+    assert_eq!(
+        app.config.current_portfolio().transactions.len(),
+        transactions
+    );
+    assert!(app.last_undo.is_none());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_undo_restores_stock_and_watchlist() {
+    let (mut app, path) = temp_config_app("undo-watchlist");
+    app.selected_index = 2;
+    let symbols = app.config.current_watchlist().symbols.clone();
+    app.remove_selected().unwrap();
+    app.undo().unwrap();
+    assert_eq!(app.config.current_watchlist().symbols, symbols);
+    assert_eq!(app.selected_index, 2);
+
+    let count = app.config.watchlists.len();
+    app.config.active_watchlist = 1;
+    let name = app.config.current_watchlist().name.clone();
+    app.remove_current_watchlist().unwrap();
+    app.undo().unwrap();
+    assert_eq!(app.config.watchlists.len(), count);
+    assert_eq!(app.config.active_watchlist, 1);
+    assert_eq!(app.config.current_watchlist().name, name);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_undo_cleared_by_other_changes() {
+    let (mut app, path) = temp_config_app("undo-cleared");
+    app.remove_selected().unwrap();
+    assert!(app.last_undo.is_some());
+
+    app.input_buffer = "GOTO".to_string();
+    app.confirm_add().await.unwrap();
+    assert!(app.last_undo.is_none());
+
+    app.undo().unwrap();
+    assert_eq!(app.status_message.as_deref(), Some("Nothing to undo"));
+    let _ = std::fs::remove_file(path);
+}
+
+// --- duplicate list names ---
+
+#[test]
+fn test_watchlist_add_switches_to_existing_name() {
+    let (mut app, path) = temp_config_app("watchlist-duplicate");
+    let count = app.config.watchlists.len();
+    let name = app.config.watchlists[1].name.clone();
+    app.selected_index = 2;
+
+    app.input_buffer = format!("  {}  ", name.to_uppercase());
+    app.confirm_watchlist_add().unwrap();
+    assert_eq!(app.config.watchlists.len(), count);
+    assert_eq!(app.config.active_watchlist, 1);
+    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(
+        app.status_message,
+        Some(format!(
+            "Watchlist '{}' already exists, switched to it",
+            name
+        ))
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_portfolio_new_switches_to_existing_name() {
+    let (mut app, path) = temp_config_app("portfolio-duplicate");
+    app.input_buffer = "Growth".to_string();
+    app.confirm_portfolio_new().unwrap();
+    app.config.active_portfolio = 0;
+
+    app.input_buffer = "growth".to_string();
+    app.confirm_portfolio_new().unwrap();
+    assert_eq!(app.config.portfolios.len(), 2);
+    assert_eq!(app.config.active_portfolio, 1);
+    assert_eq!(app.config.current_portfolio().name, "Growth");
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Portfolio 'Growth' already exists, switched to it")
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// --- column layout / picker ---
+
+#[test]
+fn test_column_layout_defaults_and_sanitizes() {
+    assert_eq!(column_layout(None, 4), vec![0, 1, 2, 3]);
+    assert_eq!(column_layout(Some(&[]), 3), vec![0, 1, 2]);
+    assert_eq!(column_layout(Some(&[4, 0, 9, 4, 2]), 6), vec![4, 0, 2]);
+}
+
+#[test]
+fn test_column_picker_hides_and_reorders() {
+    let (mut app, path) = temp_config_app("columns");
+    app.open_column_picker();
+    assert_eq!(app.input_mode, InputMode::ColumnPicker);
+
+    // Hide Name (index 1): it moves below the enabled columns
+    app.column_picker_down();
+    app.toggle_picker_column().unwrap();
+    let layout = app.config.watchlist_columns.clone().unwrap();
+    assert!(!layout.contains(&1));
+    let rows = app.column_picker_rows();
+    assert_eq!(rows.last().map(|r| (r.0, r.2)), Some((1, false)));
+    assert_eq!(rows[app.column_picker_selected].0, 1);
+
+    // Move Price (now second) ahead of Symbol
+    app.column_picker_selected = 1;
+    app.move_picker_column(-1).unwrap();
+    assert_eq!(&app.enabled_columns()[..2], &[2, 0]);
+    assert_eq!(app.column_picker_selected, 0);
+
+    app.reset_columns().unwrap();
+    assert_eq!(app.config.watchlist_columns, None);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_column_picker_keeps_one_column() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::Portfolio;
+    app.config.portfolio_columns = Some(vec![0]);
+    app.open_column_picker();
+    app.toggle_picker_column().unwrap();
+    assert_eq!(app.config.portfolio_columns, Some(vec![0]));
+    assert!(app.status_message.is_some());
+}
+
+// --- density ---
+
+fn rendered_viewport_height(app: &mut App) -> usize {
+    let backend = ratatui::backend::TestBackend::new(80, 24);
+    let mut terminal = ratatui::Terminal::new(backend).unwrap();
+    terminal.draw(|f| idx_cli::ui::draw(f, app)).unwrap();
+    app.table_viewport_height
+}
+
+#[test]
+fn test_compact_density_grows_table_viewport() {
+    let mut app = test_app();
+    // 24 rows minus 3-row header and footer, table borders and column header
+    assert_eq!(rendered_viewport_height(&mut app), 15);
+
+    app.config.density = Density::Compact;
+    // 1-row header and footer, title row and column header
+    assert_eq!(rendered_viewport_height(&mut app), 20);
+
+    app.view_mode = ViewMode::Portfolio;
+    assert_eq!(rendered_viewport_height(&mut app), 20);
+}
+
+#[test]
+fn test_tiny_terminal_shows_resize_hint() {
+    let mut app = test_app();
+    let text = rendered_text_at(&mut app, 40, 10);
+    assert!(text.contains("Terminal too small"), "{}", text);
+    assert!(!text.contains("Watchlist"));
+}
+
+#[test]
+fn test_minimum_terminal_draws_every_modal() {
+    let mut app = test_app();
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    app.detail_symbol = Some("BBCA".to_string());
+    for mode in [
+        InputMode::Normal,
+        InputMode::Help,
+        InputMode::StockDetail,
+        InputMode::ExportMenu,
+        InputMode::TopMovers,
+    ] {
+        app.input_mode = mode;
+        let text = rendered_text_at(&mut app, 60, 16);
+        assert!(!text.contains("Terminal too small"));
+    }
+}
+
+// --- market_is_open ---
+
+fn wib(date: &str, time: &str) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::parse_from_str(&format!("{} {} +0700", date, time), "%Y-%m-%d %H:%M %z")
+        .unwrap()
+        .with_timezone(&chrono::Utc)
+}
+
+#[test]
+fn test_market_is_open_during_weekday_session() {
+    let (open, close) = Config::test_config().trading_hours();
+    // 2024-03-04 is a Monday
+    assert!(market_is_open(wib("2024-03-04", "09:00"), open, close));
+    assert!(market_is_open(wib("2024-03-04", "15:59"), open, close));
+    assert!(!market_is_open(wib("2024-03-04", "08:59"), open, close));
+    assert!(!market_is_open(wib("2024-03-04", "16:00"), open, close));
+    // Saturday
+    assert!(!market_is_open(wib("2024-03-09", "10:00"), open, close));
+}
+
+#[test]
+fn test_trading_hours_configurable_with_fallback() {
+    let mut config = Config::test_config();
+    config.market_open = "08:30".to_string();
+    config.market_close = "nonsense".to_string();
+    let (open, close) = config.trading_hours();
+    assert_eq!(open.format("%H:%M").to_string(), "08:30");
+    assert_eq!(close.format("%H:%M").to_string(), "16:00");
+    assert!(market_is_open(wib("2024-03-04", "08:45"), open, close));
+}
+
+// --- clipboard_text ---
+
+#[test]
+fn test_clipboard_text_symbol_and_summary() {
+    let mut app = test_app();
+    assert_eq!(app.clipboard_text(false), Some("BBCA".to_string()));
+    // No quote yet: the summary falls back to the symbol
+    assert_eq!(app.clipboard_text(true), Some("BBCA".to_string()));
+
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 100.0, 1.12));
+    let summary = app.clipboard_text(true).unwrap();
+    assert!(summary.starts_with("BBCA 9"));
+    assert!(summary.contains("+1.12%"));
+}
+
+#[test]
+fn test_clipboard_text_empty_selection() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::Portfolio;
+    assert_eq!(app.clipboard_text(false), None);
+    app.copy_selected(false);
+    assert_eq!(app.status_message, None);
+}
+
+// --- stock page ---
+
+#[test]
+fn test_stock_page_symbol_follows_detail_then_selection() {
+    let mut app = test_app();
+    assert_eq!(app.stock_page_symbol(), Some("BBCA".to_string()));
+
+    app.detail_symbol = Some("TLKM".to_string());
+    app.input_mode = InputMode::StockDetail;
+    assert_eq!(app.stock_page_symbol(), Some("TLKM".to_string()));
+
+    app.close_stock_detail();
+    app.view_mode = ViewMode::News;
+    assert_eq!(app.stock_page_symbol(), None);
+}
+
+// --- add prompt symbol search ---
+
+#[tokio::test]
+async fn test_add_suggestions_debounced_and_selectable() {
+    let (app, path) = temp_config_app("add-suggest");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![
+        make_quote("BMRI", 6000.0, 0.0, 0.0),
+        make_quote("GOTO", 70.0, 0.0, 0.0),
+    ])));
+    app.start_adding();
+    app.input_buffer = "bm".to_string();
+    app.add_input_changed();
+
+    // Still inside the debounce window: no request yet
+    app.update_add_suggestions().await;
+    assert!(app.add_suggestions.is_empty());
+
+    app.add_suggest_due = Some(tokio::time::Instant::now());
+    app.update_add_suggestions().await;
+    assert_eq!(
+        app.add_suggestions,
+        vec![("BMRI".to_string(), "BMRI Corp".to_string())]
+    );
+
+    app.add_suggestion_down();
+    assert_eq!(app.add_suggestion_selected, Some(0));
+    app.confirm_add().await.unwrap();
+    assert!(
+        app.config
+            .current_watchlist()
+            .symbols
+            .contains(&"BMRI".to_string())
+    );
+    assert!(app.add_suggestions.is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_confirm_add_multiple_symbols() {
+    let (mut app, path) = temp_config_app("add-batch");
+    app.start_adding();
+    app.input_buffer = "goto, bbca buka,,GOTO  tlkm".to_string();
+    app.confirm_add().await.unwrap();
+    assert_eq!(
+        app.config.current_watchlist().symbols,
+        vec!["BBCA", "BBRI", "BMRI", "BBNI", "GOTO", "BUKA", "TLKM"]
+    );
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Added 3 of 4 symbols (1 already present)")
+    );
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.start_adding();
+    app.input_buffer = "bbca".to_string();
+    app.confirm_add().await.unwrap();
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("BBCA is already in the watchlist")
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_validate_symbols_leaves_out_unknown_symbols() {
+    let (app, path) = temp_config_app("validate-add");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![make_quote(
+        "GOTO", 70.0, 1.0, 1.4,
+    )])));
+    app.config.validate_symbols = true;
+
+    app.start_adding();
+    app.input_buffer = "goto bcca".to_string();
+    app.confirm_add().await.unwrap();
+    assert!(
+        app.config
+            .current_watchlist()
+            .symbols
+            .contains(&"GOTO".to_string())
+    );
+    assert!(
+        !app.config
+            .current_watchlist()
+            .symbols
+            .contains(&"BCCA".to_string())
+    );
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Added GOTO; not found: BCCA")
+    );
+    // The lookup's quote is kept, so the row doesn't show Loading...
+    assert!(app.quotes.contains_key("GOTO"));
+
+    // Nothing valid: the prompt stays open for a fix
+    let before = app.config.current_watchlist().symbols.clone();
+    app.start_adding();
+    app.input_buffer = "bcca".to_string();
+    app.confirm_add().await.unwrap();
+    assert_eq!(app.input_mode, InputMode::Adding);
+    assert_eq!(app.input_buffer, "bcca");
+    assert_eq!(app.status_message.as_deref(), Some("Not found: BCCA"));
+    assert_eq!(app.config.current_watchlist().symbols, before);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_validate_symbols_adds_anyway_when_offline() {
+    let (app, path) = temp_config_app("validate-offline");
+    let mut provider = MockProvider::new(Vec::new());
+    provider.offline = true;
+    let mut app = app.with_provider(Box::new(provider));
+    app.config.validate_symbols = true;
+
+    app.start_adding();
+    app.input_buffer = "bcca".to_string();
+    app.confirm_add().await.unwrap();
+    assert!(
+        app.config
+            .current_watchlist()
+            .symbols
+            .contains(&"BCCA".to_string())
+    );
+    assert_eq!(app.input_mode, InputMode::Normal);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_validate_symbols_in_portfolio_add() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.config.validate_symbols = true;
+
+    app.start_portfolio_add();
+    app.input_buffer = "bcca".to_string();
+    app.confirm_portfolio_symbol().await;
+    assert_eq!(app.input_mode, InputMode::PortfolioAddSymbol);
+    assert_eq!(app.status_message.as_deref(), Some("Not found: BCCA"));
+
+    app.input_buffer = "bbca".to_string();
+    app.confirm_portfolio_symbol().await;
+    assert_eq!(app.input_mode, InputMode::PortfolioAddLots);
+    assert_eq!(app.pending_symbol.as_deref(), Some("BBCA"));
+}
+
+#[tokio::test]
+async fn test_non_idx_symbols_are_quoted_under_their_own_name() {
+    let (app, path) = temp_config_app("add-non-idx");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![
+        make_quote("IDR=X", 16250.0, 25.0, 0.15),
+        make_quote("BTC-USD", 65000.0, -500.0, -0.76),
+    ])));
+    app.start_adding();
+    app.input_buffer = "idr=x btc-usd".to_string();
+    app.confirm_add().await.unwrap();
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+
+    let watchlist = app.get_raw_watchlist();
+    let quoted: Vec<&str> = watchlist
+        .iter()
+        .filter(|(_, q)| q.is_some())
+        .map(|(s, _)| s.as_str())
+        .collect();
+    assert_eq!(quoted, vec!["IDR=X", "BTC-USD"]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_add_suggestion_completes_last_symbol_in_list() {
+    let (app, path) = temp_config_app("add-batch-suggest");
+    let mut app = app.with_provider(Box::new(MockProvider::new(vec![make_quote(
+        "GOTO", 70.0, 0.0, 0.0,
+    )])));
+    app.start_adding();
+    app.input_buffer = "ASII, go".to_string();
+    app.add_input_changed();
+    app.add_suggest_due = Some(tokio::time::Instant::now());
+    app.update_add_suggestions().await;
+    assert_eq!(app.add_suggestions.len(), 1);
+
+    app.add_suggestion_down();
+    app.confirm_add().await.unwrap();
+    let symbols = &app.config.current_watchlist().symbols;
+    assert!(symbols.contains(&"ASII".to_string()));
+    assert!(symbols.contains(&"GOTO".to_string()));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_add_suggestions_cleared_for_short_input() {
+    let mut app = test_app();
+    app.start_adding();
+    app.add_suggestions = vec![("BBCA".to_string(), "BCA".to_string())];
+    app.input_buffer = "B".to_string();
+    app.add_input_changed();
+    assert!(app.add_suggestions.is_empty());
+    assert_eq!(app.add_suggest_due, None);
+}
+
+// --- portfolio value history ---
+
+#[tokio::test]
+async fn test_portfolio_value_series_pads_and_excludes() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.config.add_holding("BBCA", 1, 8000.0);
+    app.config.add_holding("TLKM", 2, 3000.0);
+    app.config.add_holding("BBRI", 1, 4000.0);
+    // Cached chart is used as-is; the provider has no TLKM data
+    app.history_charts.insert(
+        "TLKM".to_string(),
+        ChartData {
+            closes: vec![3000.0, 3100.0, 3200.0],
+            high: 3200.0,
+            low: 3000.0,
+        },
+    );
+
+    let (values, excluded) = app.portfolio_value_series().await;
+    // BBCA [8900, 9000] is padded at the front to match TLKM's three closes
+    assert_eq!(values, vec![1_490_000.0, 1_510_000.0, 1_540_000.0]);
+    assert_eq!(excluded, vec!["BBRI".to_string()]);
+    assert!(app.history_charts.contains_key("BBCA"));
+}
+
+#[tokio::test]
+async fn test_portfolio_history_modal_opens_only_with_holdings() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.show_portfolio_history().await;
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.config.add_holding("BBCA", 1, 8000.0);
+    app.show_portfolio_history().await;
+    assert_eq!(app.input_mode, InputMode::PortfolioHistory);
+    assert_eq!(
+        app.portfolio_history,
+        Some((vec![890_000.0, 900_000.0], Vec::new()))
+    );
+    app.close_portfolio_history();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.portfolio_history.is_none());
+}
+
+// --- daily portfolio snapshots ---
+
+fn snapshot(date: &str, value: f64) -> PortfolioSnapshot {
+    PortfolioSnapshot {
+        date: date.parse().unwrap(),
+        value,
+        pl: value - 1000.0,
+    }
+}
+
+#[test]
+fn test_portfolio_history_keeps_one_entry_per_date() {
+    let mut history = PortfolioHistory::default();
+    assert!(history.record("Main", snapshot("2026-03-03", 1200.0)));
+    assert!(history.record("Main", snapshot("2026-03-02", 1100.0)));
+    // Later refreshes the same day replace the value, ending on the close
+    assert!(history.record("Main", snapshot("2026-03-03", 1300.0)));
+    assert!(!history.record("Main", snapshot("2026-03-03", 1300.0)));
+    assert!(history.record("Other", snapshot("2026-03-03", 50.0)));
+
+    let values: Vec<f64> = history.snapshots("Main").iter().map(|s| s.value).collect();
+    assert_eq!(values, vec![1100.0, 1300.0]);
+    assert!(history.snapshots("Missing").is_empty());
+}
+
+#[test]
+fn test_portfolio_history_round_trips_through_disk() {
+    let path = std::env::temp_dir().join(format!(
+        "idx-cli-test-{}-portfolio-history.json",
+        std::process::id()
+    ));
+    let mut history = PortfolioHistory::default();
+    history.record("Main", snapshot("2026-03-02", 1100.0));
+    history.save(&path).unwrap();
+    let loaded = PortfolioHistory::load(&path);
+    assert_eq!(loaded.snapshots("Main"), &[snapshot("2026-03-02", 1100.0)]);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_trading_date_starts_at_the_open() {
+    let (open, _) = Config::test_config().trading_hours();
+    // Before the open, quotes still show Monday's close
+    assert_eq!(trading_date(wib("2026-03-03", "08:59"), open), None);
+    // From the open onwards, and after the close
+    let tuesday = "2026-03-03".parse().ok();
+    assert_eq!(trading_date(wib("2026-03-03", "09:00"), open), tuesday);
+    assert_eq!(trading_date(wib("2026-03-03", "19:00"), open), tuesday);
+    // Saturday
+    assert_eq!(trading_date(wib("2026-03-07", "12:00"), open), None);
+}
+
+#[test]
+fn test_portfolio_snapshot_needs_every_holding_quoted() {
+    let mut app = test_app();
+    let date = "2026-03-02".parse().unwrap();
+    assert_eq!(app.portfolio_snapshot(date), None);
+
+    app.config.add_holding("BBCA", 2, 8000.0);
+    app.config.add_holding("TLKM", 1, 3000.0);
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 0.0));
+    assert_eq!(app.portfolio_snapshot(date), None);
+
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 2500.0, 0.0, 0.0));
+    let snap = app.portfolio_snapshot(date).unwrap();
+    assert_eq!(snap.value, 2_050_000.0);
+    assert_eq!(snap.pl, 2_050_000.0 - 1_900_000.0);
+}
+
+#[test]
+fn test_portfolio_history_modal_shows_recorded_days() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 1, 8000.0);
+    app.input_mode = InputMode::PortfolioHistory;
+    assert!(rendered_text(&mut app).contains("Recorded: nothing yet"));
+
+    let name = app.config.current_portfolio().name.clone();
+    app.value_snapshots
+        .record(&name, snapshot("2026-03-02", 1100.0));
+    assert!(rendered_text(&mut app).contains("Recorded: 1 day since 2026-03-02"));
+    app.value_snapshots
+        .record(&name, snapshot("2026-03-03", 1200.0));
+    assert!(rendered_text(&mut app).contains("Recorded: 2 days since 2026-03-02"));
+}
+
+// --- hide_loading_rows ---
+
+#[tokio::test]
+async fn test_hide_loading_rows_keeps_selection_on_same_symbol() {
+    let mut app = mock_app(
+        ["BBCA", "BBRI", "TLKM", "ASII"]
+            .into_iter()
+            .map(|s| make_quote(s, 1000.0, 0.0, 0.0))
+            .collect(),
+    );
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 3000.0, 0.0, 0.0));
+
+    // Off by default: loading rows stay as placeholders
+    app.prepare_refresh();
+    assert_eq!(app.get_filtered_watchlist().len(), 4);
+    app.pending_symbols.clear();
+
+    app.config.hide_loading_rows = true;
+    let symbols = app.prepare_refresh().unwrap();
+    assert_eq!(app.get_filtered_watchlist().len(), 1);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("TLKM"));
+
+    app.execute_refresh(&symbols).await.unwrap();
+    assert_eq!(app.get_filtered_watchlist().len(), 4);
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("TLKM"));
+}
+
+// --- offline refresh ---
+
+#[tokio::test]
+async fn test_offline_refresh_keeps_last_quotes() {
+    let mut provider = MockProvider::new(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    provider.offline = true;
+    let mut app = test_app().with_provider(Box::new(provider));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 8900.0, 0.0, 0.0));
+
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.offline);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Offline — showing last data")
+    );
+    assert_eq!(app.quotes["BBCA"].price, 8900.0);
+}
+
+#[tokio::test]
+async fn test_offline_is_logged_once_per_outage() {
+    let mut provider = MockProvider::new(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    provider.offline = true;
+    let mut app = test_app().with_provider(Box::new(provider));
+    let symbols = app.refresh_symbols().unwrap();
+    for _ in 0..5 {
+        app.execute_refresh(&symbols).await.unwrap();
+    }
+    let offline_entries = |app: &App| {
+        app.event_log
+            .iter()
+            .filter(|(_, msg)| msg.starts_with("Offline"))
+            .count()
+    };
+    assert_eq!(offline_entries(&app), 1);
+
+    // Back online, then a new outage is logged again
+    app.offline = false;
+    app.execute_refresh(&symbols).await.unwrap();
+    assert_eq!(offline_entries(&app), 2);
+}
+
+#[tokio::test]
+async fn test_successful_refresh_clears_offline() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.offline = true;
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(!app.offline);
+    assert_eq!(app.quotes["BBCA"].price, 9000.0);
+}
+
+// --- stale quotes ---
+
+#[tokio::test]
+async fn test_refresh_records_fetch_time_and_quotes_go_stale() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+
+    let fetched = app.quote_fetched_at["BBCA"];
+    assert!(!app.quote_is_stale("BBCA"));
+    assert!(!app.quote_is_stale_at("BBCA", fetched + 300));
+    assert!(app.quote_is_stale_at("BBCA", fetched + 301));
+    // Never-fetched symbols aren't flagged
+    assert!(!app.quote_is_stale_at("TLKM", fetched + 10_000));
+
+    app.config.stale_quote_secs = 0;
+    assert!(!app.quote_is_stale_at("BBCA", fetched + 10_000));
+}
+
+// --- refresh health ---
+
+#[test]
+fn test_refresh_health_ages_against_interval() {
+    assert_eq!(refresh_health(None, 1, true), RefreshHealth::NeverLoaded);
+    assert_eq!(refresh_health(Some(3), 1, true), RefreshHealth::Fresh(3));
+    assert_eq!(refresh_health(Some(15), 1, true), RefreshHealth::Late(15));
+    assert_eq!(
+        refresh_health(Some(120), 1, true),
+        RefreshHealth::Stale(120)
+    );
+    // A slow interval stretches the thresholds
+    assert_eq!(refresh_health(Some(60), 30, true), RefreshHealth::Fresh(60));
+    assert_eq!(
+        refresh_health(Some(120), 30, true),
+        RefreshHealth::Late(120)
+    );
+    // Nothing is refreshed after the close, so old quotes aren't stale
+    assert_eq!(
+        refresh_health(Some(7200), 1, false),
+        RefreshHealth::Fresh(7200)
+    );
+}
+
+#[tokio::test]
+async fn test_refresh_records_success_but_not_failure() {
+    let mut provider = MockProvider::new(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    provider.offline = true;
+    let mut app = test_app().with_provider(Box::new(provider));
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    assert_eq!(app.refresh_health(), RefreshHealth::NeverLoaded);
+
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.last_successful_refresh.is_some());
+    assert!(matches!(app.refresh_health(), RefreshHealth::Fresh(_)));
+}
+
+// --- market breadth ---
+
+#[test]
+fn test_market_breadth_counts_and_skips_ihsg() {
+    let quotes = [
+        make_quote("BBCA", 9000.0, 100.0, 1.0),
+        make_quote("BBRI", 5000.0, -150.0, -3.0),
+        make_quote("TLKM", 4000.0, 0.0, 0.0),
+        make_quote("ASII", 6000.0, 120.0, 2.0),
+        make_quote("IHSG", 7000.0, 140.0, 2.0),
+    ];
+    let breadth = market_breadth(&quotes);
+    assert_eq!(
+        (breadth.advancers, breadth.decliners, breadth.unchanged),
+        (2, 1, 1)
+    );
+    assert_eq!(breadth.avg_change_pct, Some(0.0));
+    assert_eq!(market_breadth(&[]).avg_change_pct, None);
+}
+
+#[test]
+fn test_watchlist_breadth_follows_search_filter() {
+    let mut app = test_app();
+    for q in [
+        make_quote("BBCA", 9000.0, 100.0, 1.0),
+        make_quote("BBRI", 5000.0, -150.0, -3.0),
+    ] {
+        app.quotes.insert(q.symbol.clone(), q);
+    }
+    assert_eq!(app.watchlist_breadth().advancers, 1);
+    assert_eq!(app.watchlist_breadth().decliners, 1);
+
+    app.search_active = true;
+    app.search_query = "BBRI".to_string();
+    let breadth = app.watchlist_breadth();
+    assert_eq!((breadth.advancers, breadth.decliners), (0, 1));
+    assert!(rendered_text(&mut app).contains("▼1"));
+}
+
+// --- rebalance ---
+
+fn rebalance_app() -> App {
+    let mut app = test_app();
+    // 10 lots of BBCA at 9000 (9M) and 30 lots of TLKM at 3000 (9M)
+    app.config.add_holding("BBCA", 10, 8000.0);
+    app.config.add_holding("TLKM", 30, 3000.0);
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 0.0));
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 3000.0, 0.0, 0.0));
+    app
+}
+
+#[test]
+fn test_rebalance_plan_suggests_whole_lots() {
+    let mut app = rebalance_app();
+    app.config.set_target("BBCA", Some(75.0));
+    app.config.set_target("TLKM", Some(25.0));
+
+    let plan = app.rebalance_plan();
+    assert!(plan.warning().is_none());
+    let row = |s: &str| plan.rows.iter().find(|r| r.symbol == s).unwrap().clone();
+    assert_eq!(row("BBCA").current_pct, 50.0);
+    // 75% of 18M is 13.5M: 4.5M more BBCA is 5 lots, 4.5M less TLKM is 15
+    assert_eq!(row("BBCA").lots, Some(5));
+    assert_eq!(row("TLKM").lots, Some(-15));
+}
+
+#[test]
+fn test_rebalance_plan_normalizes_targets_and_warns() {
+    let mut app = rebalance_app();
+    app.config.set_target("BBCA", Some(60.0));
+    app.config.set_target("TLKM", Some(20.0));
+
+    let plan = app.rebalance_plan();
+    assert!(plan.normalized());
+    assert!(plan.warning().unwrap().contains("80.0%"));
+    let bbca = plan.rows.iter().find(|r| r.symbol == "BBCA").unwrap();
+    assert_eq!(bbca.target_pct, Some(75.0));
+    assert_eq!(bbca.lots, Some(5));
+}
+
+#[test]
+fn test_rebalance_plan_leaves_untargeted_holdings_alone() {
+    let mut app = rebalance_app();
+    app.config.set_target("BBCA", Some(50.0));
+
+    let plan = app.rebalance_plan();
+    assert!(!plan.normalized());
+    let tlkm = plan.rows.iter().find(|r| r.symbol == "TLKM").unwrap();
+    assert_eq!(tlkm.target_pct, None);
+    assert_eq!(tlkm.lots, None);
+    let bbca = plan.rows.iter().find(|r| r.symbol == "BBCA").unwrap();
+    assert_eq!(bbca.lots, Some(0));
+}
+
+#[test]
+fn test_rebalance_target_prompt_rejects_out_of_range() {
+    let (mut app, path) = temp_config_app("rebalance-target");
+    app.config.add_holding("BBCA", 10, 8000.0);
+    app.view_mode = ViewMode::Portfolio;
+    app.show_rebalance();
+    assert_eq!(app.input_mode, InputMode::Rebalance);
+
+    app.start_rebalance_target();
+    app.input_buffer = "150".to_string();
+    app.confirm_rebalance_target().unwrap();
+    assert_eq!(app.input_mode, InputMode::RebalanceTarget);
+    assert!(app.config.current_portfolio().targets.is_empty());
+
+    app.input_buffer = "40%".to_string();
+    app.confirm_rebalance_target().unwrap();
+    assert_eq!(app.input_mode, InputMode::Rebalance);
+    assert_eq!(
+        app.config.current_portfolio().targets.get("BBCA"),
+        Some(&40.0)
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// --- portfolio edit preview ---
+
+#[test]
+fn test_portfolio_edit_preview_follows_input() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 10000.0, 100.0, 1.01));
+    app.start_portfolio_edit();
+    assert_eq!(app.input_mode, InputMode::PortfolioEditLots);
+
+    // 20 lots at the existing 9000 average
+    app.input_buffer = "20".to_string();
+    assert_eq!(
+        app.portfolio_edit_preview(),
+        Some((18_000_000.0, Some(2_000_000.0)))
+    );
+    app.input_buffer = "2x".to_string();
+    assert_eq!(app.portfolio_edit_preview(), None);
+
+    app.input_buffer = "20".to_string();
+    app.confirm_portfolio_edit_lots();
+    assert_eq!(app.input_mode, InputMode::PortfolioEditPrice);
+    app.input_buffer = "9500".to_string();
+    assert_eq!(
+        app.portfolio_edit_preview(),
+        Some((19_000_000.0, Some(1_000_000.0)))
+    );
+    app.input_buffer = "0".to_string();
+    assert_eq!(app.portfolio_edit_preview(), None);
+}
+
+#[test]
+fn test_portfolio_edit_preview_without_quote() {
+    let mut app = test_app();
+    app.config.add_holding("TLKM", 5, 3000.0);
+    app.start_portfolio_edit();
+    assert_eq!(app.portfolio_edit_preview(), Some((1_500_000.0, None)));
+}
+
+// --- reorder watchlist ---
+
+#[test]
+fn test_move_selected_symbol_follows_selection() {
+    let (mut app, path) = temp_config_app("reorder");
+    app.selected_index = 1;
+    app.move_selected_symbol(false).unwrap();
+    assert_eq!(
+        app.config.current_watchlist().symbols,
+        vec!["BBCA", "BMRI", "BBRI", "BBNI"]
+    );
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("BBRI"));
+
+    app.move_selected_symbol(true).unwrap();
+    app.move_selected_symbol(true).unwrap();
+    assert_eq!(app.selected_index, 0);
+    assert_eq!(
+        app.config.current_watchlist().symbols,
+        vec!["BBRI", "BBCA", "BMRI", "BBNI"]
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_move_selected_symbol_refused_while_sorted() {
+    let mut app = test_app();
+    app.watchlist_sort_column = Some(2);
+    app.move_selected_symbol(false).unwrap();
+    assert_eq!(
+        app.config.current_watchlist().symbols,
+        vec!["BBCA", "BBRI", "TLKM", "ASII"]
+    );
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Clear the sort (s) to reorder")
+    );
+}
+
+#[test]
+fn test_reorder_steps_over_hidden_pinned_symbol() {
+    let (mut app, path) = temp_config_app("reorder-pinned");
+    app.config.pinned = vec!["BMRI".to_string()];
+    // Body is BBCA, BBRI, BBNI; BMRI sits hidden between BBRI and BBNI
+    app.selected_index = 2;
+    app.move_selected_symbol(true).unwrap();
+    assert_eq!(
+        app.config.current_watchlist().symbols,
+        vec!["BBCA", "BBNI", "BBRI", "BMRI"]
+    );
+    assert_eq!(app.selected_index, 1);
+    assert_eq!(app.selected_watchlist_symbol().as_deref(), Some("BBNI"));
+    let _ = std::fs::remove_file(path);
+}
+
+// --- holding notes ---
+
+#[test]
+fn test_portfolio_note_edit_and_detail() {
+    let (mut app, path) = temp_config_app("holding-note");
+    app.config.add_holding("BBCA", 1, 9000.0);
+    app.view_mode = ViewMode::Portfolio;
+    app.start_portfolio_note();
+    assert_eq!(app.input_mode, InputMode::PortfolioNote);
+    assert!(app.input_buffer.is_empty());
+
+    app.input_buffer = "core".to_string();
+    app.confirm_portfolio_note().unwrap();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.status_message.as_deref(), Some("Saved note on BBCA"));
+
+    app.detail_symbol = Some("BBCA".to_string());
+    assert_eq!(app.get_detail_note(), Some("core"));
+
+    // Pre-filled on the next edit
+    app.start_portfolio_note();
+    assert_eq!(app.input_buffer, "core");
+    app.cancel_portfolio_edit();
+    let _ = std::fs::remove_file(path);
+}
+
+// --- JSON Lines export ---
+
+#[tokio::test]
+async fn test_json_lines_export_appends_one_line_per_run() {
+    let mut app = mock_app(vec![
+        make_quote("BBCA", 9000.0, 100.0, 1.12),
+        make_quote("BBRI", 4000.0, -20.0, -0.5),
+    ]);
+    let path =
+        std::env::temp_dir().join(format!("idx-cli-test-{}-export.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    for _ in 0..2 {
+        let written = app
+            .run_headless_export(
+                ExportFormat::JsonLines,
+                ExportScope::Watchlist,
+                Some(path.clone()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(written, path.to_string_lossy());
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["timestamp"].is_string());
+        assert_eq!(record["scope"], "watchlist");
+        assert_eq!(record["rows"][0]["symbol"], "BBCA");
+        assert_eq!(record["rows"][0]["price"], 9000.0);
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_toggle_export_format_cycles_through_json_lines() {
+    let mut app = test_app();
+    assert_eq!(app.export_format, ExportFormat::Csv);
+    app.toggle_export_format();
+    assert_eq!(app.export_format, ExportFormat::Json);
+    app.toggle_export_format();
+    assert_eq!(app.export_format, ExportFormat::JsonLines);
+    app.toggle_export_format();
+    assert_eq!(app.export_format, ExportFormat::Csv);
+}
+
+#[test]
+fn test_export_allocation_csv_lists_weights_largest_first() {
+    let mut app = test_app();
+    app.config.add_holding("TLKM", 10, 3000.0);
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 2500.0, 0.0, 0.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 7500.0, 0.0, 0.0));
+
+    let csv = app.export_allocation_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Symbol,Value,Percent",
+            "BBCA,7500000.00,75.00",
+            "TLKM,2500000.00,25.00"
+        ]
+    );
+}
+
+#[test]
+fn test_export_allocation_csv_human_numbers() {
+    let mut app = test_app();
+    app.config.export_human_numbers = true;
+    app.config.add_holding("TLKM", 10, 3000.0);
+    app.config.add_holding("BBCA", 10, 9000.0);
+    app.quotes
+        .insert("TLKM".to_string(), make_quote("TLKM", 2500.0, 0.0, 0.0));
+    app.quotes
+        .insert("BBCA".to_string(), make_quote("BBCA", 7500.0, 0.0, 0.0));
+
+    let csv = app.export_allocation_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Symbol,Value,Percent",
+            "BBCA,7.50M,75.00%",
+            "TLKM,2.50M,25.00%"
+        ]
+    );
+}
+
+#[test]
+fn test_export_watchlist_csv_human_numbers_quotes_grouped_fields() {
+    let mut app = test_app();
+    app.config.export_human_numbers = true;
+    let mut bbca = make_quote("BBCA", 9000.0, 100.0, 1.12);
+    bbca.average_volume = Some(400_000);
+    app.quotes.insert("BBCA".to_string(), bbca);
+
+    let csv = app.export_watchlist_csv();
+    let bbca = csv.lines().find(|l| l.starts_with("BBCA")).unwrap();
+    assert_eq!(
+        bbca,
+        "BBCA,BBCA Corp,\"9,000\",+100,+1.12%,\"8,990\",\"9,020\",\"8,980\",1.00M,9.00B,2.50"
+    );
+}
+
+#[tokio::test]
+async fn test_export_portfolio_json_human_numbers_follow_locale() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.config.add_holding("BBCA", 10, 8000.0);
+    app.config.export_human_numbers = true;
+    app.config.number_locale = NumberLocale::Id;
+    let path = std::env::temp_dir().join(format!(
+        "idx-cli-test-{}-human-export.json",
+        std::process::id()
+    ));
+
+    app.run_headless_export(
+        ExportFormat::Json,
+        ExportScope::Portfolio,
+        Some(path.clone()),
+    )
+    .await
+    .unwrap();
+    let data: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let row = &data[0];
+    assert_eq!(row["lots"], 10);
+    assert_eq!(row["current_price"], "9.000");
+    assert_eq!(row["value"], "9,00jt");
+    assert_eq!(row["pl"], "+1,00jt");
+    assert_eq!(row["pl_percent"], "+12.50%");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_toggle_export_scope_includes_allocation() {
+    let mut app = test_app();
+    app.toggle_export_scope();
+    assert_eq!(app.export_scope, ExportScope::Portfolio);
+    app.toggle_export_scope();
+    assert_eq!(app.export_scope, ExportScope::Allocation);
+    app.toggle_export_scope();
+    assert_eq!(app.export_scope, ExportScope::Watchlist);
+}
+
+// --- key bindings ---
+
+#[test]
+fn test_app_new_reports_conflicting_key_bindings() {
+    let (mut app, path) = temp_config_app("key-bindings");
+    app.config
+        .key_bindings
+        .insert("refresh".to_string(), "q".to_string());
+    app.config.save().unwrap();
+
+    let app = App::new(Some(path.clone())).unwrap();
+    let status = app.status_message.unwrap();
+    assert!(status.starts_with("Key bindings:"), "{}", status);
+    let _ = std::fs::remove_file(&path);
+}
+
+// --- watchlist / portfolio conversion ---
+
+#[test]
+fn test_convert_list_switches_to_new_portfolio() {
+    let (mut app, path) = temp_config_app("convert-list");
+    let name = app.config.current_watchlist().name.clone();
+    let symbols = app.config.current_watchlist().symbols.len();
+
+    app.convert_list().unwrap();
+    assert_eq!(app.view_mode, ViewMode::Portfolio);
+    assert_eq!(app.config.current_portfolio().name, name);
+    assert_eq!(app.config.current_portfolio().holdings.len(), symbols);
+
+    app.convert_list().unwrap();
+    assert_eq!(app.view_mode, ViewMode::Watchlist);
+    assert_eq!(
+        app.config
+            .watchlists
+            .iter()
+            .filter(|w| w.name == name)
+            .count(),
+        2
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// --- default lists ---
+
+#[test]
+fn test_app_new_opens_default_lists_by_name() {
+    let (mut app, path) = temp_config_app("default-lists");
+    app.config.add_watchlist("Coal Plays");
+    app.config.add_portfolio("Dividends");
+    app.config.active_watchlist = 0;
+    app.config.active_portfolio = 0;
+    app.config.default_watchlist_name = Some("coal plays".to_string());
+    app.config.default_portfolio_name = Some("Dividends".to_string());
+    let coal = app.config.watchlists.len() - 1;
+    let dividends = app.config.portfolios.len() - 1;
+    app.config.save().unwrap();
+
+    let app = App::new(Some(path.clone())).unwrap();
+    assert_eq!(app.config.active_watchlist, coal);
+    assert_eq!(app.config.current_watchlist().name, "Coal Plays");
+    assert_eq!(app.config.active_portfolio, dividends);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_app_new_falls_back_to_first_list_for_missing_default() {
+    let (mut app, path) = temp_config_app("default-lists-missing");
+    app.config.add_watchlist("Mining");
+    app.config.active_watchlist = 1;
+    app.config.default_watchlist_name = Some("Gone".to_string());
+    app.config.save().unwrap();
+
+    let app = App::new(Some(path.clone())).unwrap();
+    assert_eq!(app.config.active_watchlist, 0);
+    let _ = std::fs::remove_file(&path);
+}
+
+// --- top movers ---
+
+#[test]
+fn test_top_movers_ranks_all_watchlists() {
+    let mut app = test_app();
+    app.config.add_watchlist("Mining");
+    app.config.watchlists[1].symbols = vec!["ADRO".to_string(), "PTBA".to_string()];
+    for q in [
+        make_quote("BBCA", 9000.0, 100.0, 1.1),
+        make_quote("BBRI", 5000.0, -150.0, -2.9),
+        make_quote("TLKM", 4000.0, 0.0, 0.0),
+        make_quote("ASII", 6000.0, -30.0, -0.5),
+        make_quote("ADRO", 2500.0, 150.0, 6.4),
+    ] {
+        app.quotes.insert(q.symbol.clone(), q);
+    }
+    app.config.top_movers_count = 5;
+
+    let (gainers, losers) = app.top_movers();
+    let gainers: Vec<&str> = gainers.iter().map(|q| q.symbol.as_str()).collect();
+    let losers: Vec<&str> = losers.iter().map(|q| q.symbol.as_str()).collect();
+    // Fewer than five on each side; flat and unquoted symbols are skipped
+    assert_eq!(gainers, vec!["ADRO", "BBCA"]);
+    assert_eq!(losers, vec!["BBRI", "ASII"]);
+}
+
+#[test]
+fn test_top_movers_respects_count() {
+    let mut app = test_app();
+    for q in [
+        make_quote("BBCA", 9000.0, 100.0, 1.1),
+        make_quote("BBRI", 5000.0, 150.0, 2.9),
+        make_quote("TLKM", 4000.0, 10.0, 0.2),
+    ] {
+        app.quotes.insert(q.symbol.clone(), q);
+    }
+    app.config.top_movers_count = 2;
+    let (gainers, losers) = app.top_movers();
+    assert_eq!(gainers.len(), 2);
+    assert_eq!(gainers[0].symbol, "BBRI");
+    assert!(losers.is_empty());
+}
+
+#[test]
+fn test_top_movers_refreshes_every_watchlist() {
+    let mut app = test_app();
+    app.config.add_watchlist("Mining");
+    app.config.watchlists[1].symbols = vec!["ADRO".to_string()];
+    app.config.active_watchlist = 0;
+    assert!(!app.refresh_symbols().unwrap().contains(&"ADRO".to_string()));
+
+    app.show_top_movers();
+    assert_eq!(app.input_mode, InputMode::TopMovers);
+    assert!(app.refresh_symbols().unwrap().contains(&"ADRO".to_string()));
+    app.close_top_movers();
+    assert_eq!(app.input_mode, InputMode::Normal);
+}
+
+// --- price expressions ---
+
+#[test]
+fn test_add_holding_with_price_expression() {
+    let (mut app, path) = temp_config_app("price-expression");
+    app.view_mode = ViewMode::Portfolio;
+    app.input_mode = InputMode::PortfolioAddPrice;
+    app.pending_symbol = Some("BBCA".to_string());
+    app.pending_lots = Some(2);
+    app.input_buffer = "8000*1.01".to_string();
+    app.confirm_portfolio_price().unwrap();
+    let holding = app.config.current_portfolio().holdings.last().unwrap();
+    assert_eq!(holding.symbol, "BBCA");
+    assert_eq!(holding.avg_price, 8080.0);
+    let _ = std::fs::remove_file(&path);
+}
+
+// --- compare modal ---
+
+#[tokio::test]
+async fn test_compare_opens_with_both_quotes_and_charts() {
+    let mut app = mock_app(vec![
+        make_quote("BBCA", 9000.0, 100.0, 1.12),
+        make_quote("BBRI", 4500.0, -50.0, -1.10),
+    ]);
+    app.start_compare();
+    assert_eq!(app.input_mode, InputMode::ComparePick);
+    assert_eq!(app.compare_symbols, vec!["BBCA"]);
+
+    app.input_buffer = "bbri".to_string();
+    app.confirm_compare().await;
+    assert_eq!(app.input_mode, InputMode::Compare);
+    assert_eq!(app.compare_symbols, vec!["BBCA", "BBRI"]);
+    // Fetched on demand since nothing was quoted yet, without joining the
+    // quotes the watchlist views read
+    assert!(app.compare_quote("BBRI").is_some());
+    assert!(!app.quotes.contains_key("BBRI"));
+    assert_eq!(app.compare_charts.len(), 2);
+
+    let text = rendered_text(&mut app);
+    assert!(text.contains("Compare BBCA vs BBRI"));
+    assert!(text.contains("BBCA Corp") && text.contains("BBRI Corp"));
+
+    app.close_compare();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.compare_symbols.is_empty() && app.compare_charts.is_empty());
+    assert!(app.compare_quote("BBRI").is_none());
+}
+
+#[tokio::test]
+async fn test_compare_with_unknown_symbol_shows_missing_quote() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.start_compare();
+    app.input_buffer = "ZZZZ".to_string();
+    app.confirm_compare().await;
+    assert!(app.compare_quote("ZZZZ").is_none());
+    assert!(!app.compare_charts.contains_key("ZZZZ"));
+
+    let text = rendered_text(&mut app);
+    assert!(text.contains("No quote for ZZZZ"));
+    assert!(text.contains("Chart data unavailable"));
+    assert!(text.contains("BBCA Corp"));
+}
+
+#[tokio::test]
+async fn test_compare_with_empty_input_cancels() {
+    let mut app = mock_app(vec![make_quote("BBCA", 9000.0, 100.0, 1.12)]);
+    app.start_compare();
+    app.confirm_compare().await;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.compare_symbols.is_empty());
+}
+
+// --- mouse ---
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+fn click(column: u16, row: u16) -> MouseEvent {
+    mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+}
+
+#[test]
+fn test_click_selects_table_row() {
+    let mut app = test_app();
+    rendered_text(&mut app);
+    let body = app.table_hit_area.as_ref().unwrap().body;
+
+    app.handle_mouse(click(body.x + 2, body.y + 2));
+    assert_eq!(app.selected_index, 2);
+
+    // Below the last row and outside the table: nothing happens
+    app.handle_mouse(click(body.x + 2, body.y + 10));
+    app.handle_mouse(click(body.x + 2, 0));
+    assert_eq!(app.selected_index, 2);
+}
+
+#[test]
+fn test_click_on_header_sorts_then_flips_direction() {
+    let (mut app, path) = temp_config_app("mouse-sort");
+    rendered_text(&mut app);
+    let hit = app.table_hit_area.clone().unwrap();
+    let &(x, _, col) = hit.columns.iter().find(|c| c.2 == 2).unwrap();
+
+    app.handle_mouse(click(x, hit.header.y));
+    assert_eq!(app.watchlist_sort_column, Some(col));
+    let direction = app.watchlist_sort_direction;
+    app.handle_mouse(click(x, hit.header.y));
+    assert_eq!(app.watchlist_sort_column, Some(col));
+    assert_ne!(app.watchlist_sort_direction, direction);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_scroll_wheel_moves_selection_but_not_in_modals() {
+    let mut app = test_app();
+    rendered_text(&mut app);
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0, 0));
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0, 0));
+    app.handle_mouse(mouse(MouseEventKind::ScrollUp, 0, 0));
+    assert_eq!(app.selected_index, 1);
+
+    app.show_help();
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0, 0));
+    assert_eq!(app.selected_index, 1);
+}
+
+#[test]
+fn test_scroll_wheel_stops_at_ends_with_wrap_navigation() {
+    let mut app = test_app();
+    app.config.wrap_navigation = true;
+    rendered_text(&mut app);
+    app.handle_mouse(mouse(MouseEventKind::ScrollUp, 0, 0));
+    assert_eq!(app.selected_index, 0);
+    app.go_bottom();
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 0, 0));
+    assert_eq!(app.selected_index, 3);
+}
+
+// --- event log ---
+
+#[test]
+fn test_notify_sets_status_and_logs() {
+    let mut app = test_app();
+    app.notify("Exported to /tmp/x.csv");
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Exported to /tmp/x.csv")
+    );
+    assert_eq!(app.event_log.len(), 1);
+    assert_eq!(app.event_log[0].1, "Exported to /tmp/x.csv");
+}
+
+#[test]
+fn test_event_log_is_capped() {
+    let mut app = test_app();
+    for i in 0..EVENT_LOG_CAPACITY + 5 {
+        app.log_event(format!("event {}", i));
+    }
+    assert_eq!(app.event_log.len(), EVENT_LOG_CAPACITY);
+    // Oldest entries are dropped first
+    assert_eq!(app.event_log.front().unwrap().1, "event 5");
+    assert_eq!(
+        app.event_log.back().unwrap().1,
+        format!("event {}", EVENT_LOG_CAPACITY + 4)
+    );
+}
+
+#[tokio::test]
+async fn test_refresh_error_lands_in_event_log() {
+    let mut provider = MockProvider::new(vec![]);
+    provider.offline = true;
+    let mut app = test_app().with_provider(Box::new(provider));
+    let symbols = app.refresh_symbols().unwrap();
+    app.execute_refresh(&symbols).await.unwrap();
+    assert!(app.event_log.iter().any(|(_, msg)| msg.contains("Offline")));
+}
+
+#[test]
+fn test_event_log_modal_lists_newest_first() {
+    let mut app = test_app();
+    app.log_event("Added BBCA");
+    app.log_event("Removed TLKM");
+    app.show_event_log();
+    assert_eq!(app.input_mode, InputMode::EventLog);
+    let text = rendered_text(&mut app);
+    let added = text.find("Added BBCA").unwrap();
+    let removed = text.find("Removed TLKM").unwrap();
+    assert!(removed < added);
+
+    app.event_log_scroll_down();
+    let text = rendered_text(&mut app);
+    assert!(!text.contains("Removed TLKM"));
+    app.close_event_log();
+    assert_eq!(app.input_mode, InputMode::Normal);
+}
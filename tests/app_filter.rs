@@ -1,6 +1,7 @@
 mod common;
 
 use common::{make_news_item, make_quote, test_app};
+use idx_cli::app::{InputMode, ViewMode};
 use idx_cli::config::Holding;
 
 // --- get_filtered_watchlist ---
@@ -26,6 +27,28 @@ fn test_filtered_watchlist_with_search() {
     assert!(symbols.contains(&"BBRI"));
 }
 
+#[test]
+fn test_filtered_watchlist_search_matches_company_name() {
+    let mut app = test_app();
+    let mut bbca = make_quote("BBCA", 9000.0, 0.0, 0.0);
+    bbca.short_name = "Bank Central Asia Tbk.".to_string();
+    let mut tlkm = make_quote("TLKM", 3500.0, 0.0, 0.0);
+    tlkm.long_name = Some("PT Telkom Indonesia (Persero) Tbk".to_string());
+    app.quotes.insert("BBCA".to_string(), bbca);
+    app.quotes.insert("TLKM".to_string(), tlkm);
+    app.search_active = true;
+
+    app.search_query = "BANK".to_string();
+    let filtered = app.get_filtered_watchlist();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].0, "BBCA");
+
+    app.search_query = "TELKOM".to_string();
+    let filtered = app.get_filtered_watchlist();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].0, "TLKM");
+}
+
 #[test]
 fn test_filtered_watchlist_with_sort() {
     let mut app = test_app();
@@ -60,11 +83,13 @@ fn test_filtered_portfolio_no_filter() {
         symbol: "BBCA".to_string(),
         lots: 10,
         avg_price: 8000.0,
+        note: None,
     });
     app.config.portfolios[0].holdings.push(Holding {
         symbol: "TLKM".to_string(),
         lots: 20,
         avg_price: 3000.0,
+        note: None,
     });
     let filtered = app.get_filtered_portfolio();
     assert_eq!(filtered.len(), 2);
@@ -77,11 +102,13 @@ fn test_filtered_portfolio_with_search() {
         symbol: "BBCA".to_string(),
         lots: 10,
         avg_price: 8000.0,
+        note: None,
     });
     app.config.portfolios[0].holdings.push(Holding {
         symbol: "TLKM".to_string(),
         lots: 20,
         avg_price: 3000.0,
+        note: None,
     });
     app.search_active = true;
     app.search_query = "BB".to_string();
@@ -90,6 +117,31 @@ fn test_filtered_portfolio_with_search() {
     assert_eq!(filtered[0].1.symbol, "BBCA");
 }
 
+#[test]
+fn test_filtered_portfolio_search_matches_company_name() {
+    let mut app = test_app();
+    app.config.portfolios[0].holdings.push(Holding {
+        symbol: "BBCA".to_string(),
+        lots: 10,
+        avg_price: 8000.0,
+        note: None,
+    });
+    app.config.portfolios[0].holdings.push(Holding {
+        symbol: "TLKM".to_string(),
+        lots: 20,
+        avg_price: 3000.0,
+        note: None,
+    });
+    let mut bbca = make_quote("BBCA", 9000.0, 0.0, 0.0);
+    bbca.short_name = "Bank Central Asia Tbk.".to_string();
+    app.quotes.insert("BBCA".to_string(), bbca);
+    app.search_active = true;
+    app.search_query = "CENTRAL".to_string();
+    let filtered = app.get_filtered_portfolio();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].1.symbol, "BBCA");
+}
+
 // --- get_filtered_news ---
 
 #[test]
@@ -120,6 +172,41 @@ fn test_filtered_news_search_by_publisher() {
     assert_eq!(filtered[0].publisher, "CNBC Indonesia");
 }
 
+#[test]
+fn test_news_ticker_filter_uses_last_selected_symbol() {
+    let mut app = test_app();
+    app.news_items
+        .push(make_news_item("BBCA naik tajam", "CNBC", 1000));
+    app.news_items
+        .push(make_news_item("TLKM melemah", "Tempo", 2000));
+    // Watchlist -> Portfolio -> News; portfolio is empty so nothing is recorded
+    app.selected_index = 2;
+    app.toggle_view();
+    app.toggle_view();
+    app.toggle_news_ticker_filter();
+    assert!(app.news_ticker_filter.is_none());
+    assert_eq!(app.get_filtered_news().len(), 2);
+
+    app.last_selected_symbol = Some("TLKM".to_string());
+    app.toggle_news_ticker_filter();
+    let filtered = app.get_filtered_news();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].title, "TLKM melemah");
+
+    app.toggle_news_ticker_filter();
+    assert!(app.news_ticker_filter.is_none());
+    assert_eq!(app.get_filtered_news().len(), 2);
+}
+
+#[test]
+fn test_toggle_view_records_selected_watchlist_symbol() {
+    let mut app = test_app();
+    app.selected_index = 1;
+    let expected = app.selected_watchlist_symbol();
+    app.toggle_view();
+    assert_eq!(app.last_selected_symbol, expected);
+}
+
 // --- selected_*_symbol ---
 
 #[test]
@@ -143,7 +230,169 @@ fn test_selected_portfolio_symbol() {
         symbol: "BBCA".to_string(),
         lots: 10,
         avg_price: 8000.0,
+        note: None,
     });
     app.portfolio_selected = 0;
     assert_eq!(app.selected_portfolio_symbol(), Some("BBCA".to_string()));
 }
+
+// --- all watchlists ---
+
+#[test]
+fn test_all_watchlists_unions_and_dedupes_symbols() {
+    let mut app = test_app();
+    app.config.add_watchlist("Mixed");
+    app.config.add_stock("TLKM");
+    app.config.add_stock("GOTO");
+    app.toggle_all_watchlists();
+
+    let symbols: Vec<&str> = app
+        .get_filtered_watchlist()
+        .iter()
+        .map(|(s, _)| s.as_str())
+        .collect();
+    assert_eq!(symbols, vec!["BBCA", "BBRI", "TLKM", "ASII", "GOTO"]);
+
+    let refresh = app.refresh_symbols().unwrap();
+    assert!(refresh.contains(&"GOTO".to_string()));
+    assert!(refresh.contains(&"BBCA".to_string()));
+    assert!(refresh.contains(&"^JKSE".to_string()));
+}
+
+#[test]
+fn test_all_watchlists_blocks_edits_and_exits_on_switch() {
+    let mut app = test_app();
+    app.toggle_all_watchlists();
+    app.remove_selected().unwrap();
+    assert_eq!(app.config.current_watchlist().symbols.len(), 4);
+    assert!(app.status_message.is_some());
+
+    app.next_watchlist();
+    assert!(!app.all_watchlists);
+}
+
+// --- quick jump ---
+
+#[test]
+fn test_quick_jump_selects_first_prefix_match_without_filtering() {
+    let mut app = test_app();
+    app.start_quick_jump();
+    assert_eq!(app.input_mode, InputMode::QuickJump);
+
+    app.input_buffer.push('t');
+    app.quick_jump_changed();
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.get_filtered_watchlist().len(), 4);
+
+    app.input_buffer = "BBR".to_string();
+    app.quick_jump_changed();
+    assert_eq!(app.selected_index, 1);
+
+    // No match keeps the current row
+    app.input_buffer = "BBRX".to_string();
+    app.quick_jump_changed();
+    assert_eq!(app.selected_index, 1);
+
+    app.end_quick_jump();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.input_buffer.is_empty());
+    assert_eq!(app.selected_index, 1);
+}
+
+#[test]
+fn test_quick_jump_in_portfolio_and_news() {
+    let mut app = test_app();
+    app.config.portfolios[0].holdings = vec![
+        Holding {
+            symbol: "ASII".to_string(),
+            lots: 1,
+            avg_price: 5000.0,
+            note: None,
+        },
+        Holding {
+            symbol: "UNVR".to_string(),
+            lots: 1,
+            avg_price: 3000.0,
+            note: None,
+        },
+    ];
+    app.view_mode = ViewMode::Portfolio;
+    app.input_buffer = "un".to_string();
+    app.quick_jump_changed();
+    assert_eq!(app.portfolio_selected, 1);
+
+    // Newest first: BBCA, then IHSG
+    app.news_items
+        .push(make_news_item("IHSG melemah", "Tempo", 1000));
+    app.news_items
+        .push(make_news_item("BBCA naik tajam", "CNBC", 2000));
+    app.view_mode = ViewMode::News;
+    app.input_buffer = "ihsg".to_string();
+    app.quick_jump_changed();
+    assert_eq!(app.news_selected, 1);
+}
+
+#[test]
+fn test_filtered_portfolio_matches_note() {
+    let mut app = test_app();
+    app.config.add_holding("BBCA", 1, 9000.0);
+    app.config.add_holding("TLKM", 1, 3000.0);
+    app.config.set_holding_note("TLKM", "dividend core");
+    app.search_active = true;
+    app.search_query = "DIVIDEND".to_string();
+    let filtered = app.get_filtered_portfolio();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].1.symbol, "TLKM");
+}
+
+// --- pinned symbols ---
+
+#[test]
+fn test_pinned_symbol_left_out_of_body() {
+    let mut app = test_app();
+    app.config.pinned = vec!["TLKM".to_string()];
+    let body: Vec<&str> = app
+        .get_filtered_watchlist()
+        .iter()
+        .map(|(s, _)| s.as_str())
+        .collect();
+    assert_eq!(body, vec!["BBCA", "BBRI", "ASII"]);
+
+    // Still excluded with a sort and a filter active
+    app.watchlist_sort_column = Some(0);
+    app.search_active = true;
+    app.search_query = "T".to_string();
+    assert!(
+        app.get_filtered_watchlist()
+            .iter()
+            .all(|(s, _)| s.as_str() != "TLKM")
+    );
+    assert_eq!(app.get_pinned_rows().len(), 1);
+}
+
+#[test]
+fn test_pinned_rows_use_display_symbol_quotes_once() {
+    let mut app = test_app();
+    app.config.pinned = vec!["^JKSE".to_string(), "^jkse".to_string()];
+    app.quotes
+        .insert("IHSG".to_string(), make_quote("IHSG", 7200.0, 20.0, 0.28));
+    let pinned = app.get_pinned_rows();
+    assert_eq!(pinned.len(), 1);
+    assert_eq!(pinned[0].1.unwrap().price, 7200.0);
+}
+
+#[test]
+fn test_refresh_includes_pinned_symbols() {
+    let mut app = test_app();
+    app.config.pinned = vec!["^JKSE".to_string(), "IDR=X".to_string()];
+    let symbols = app.refresh_symbols().unwrap();
+    assert_eq!(symbols.iter().filter(|s| *s == "^JKSE").count(), 1);
+    assert!(symbols.contains(&"IDR=X".to_string()));
+
+    app.view_mode = ViewMode::Portfolio;
+    assert!(
+        !app.refresh_symbols()
+            .unwrap()
+            .contains(&"IDR=X".to_string())
+    );
+}
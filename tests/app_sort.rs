@@ -1,9 +1,9 @@
 mod common;
 
-use common::{make_holding, make_news_item, make_quote};
+use common::{make_holding, make_news_item, make_quote, temp_config_app, test_app};
 use idx_cli::api::StockQuote;
-use idx_cli::app::SortDirection;
 use idx_cli::app::sort::*;
+use idx_cli::app::{SortDirection, ViewMode, relative_volume, vs_ihsg_pct};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -138,6 +138,38 @@ fn test_watchlist_sort_both_none_equal() {
     );
 }
 
+#[test]
+fn test_relative_volume_needs_average() {
+    let mut q = make_quote("BBCA", 9000.0, 0.0, 0.0);
+    assert_eq!(relative_volume(&q), None);
+    q.average_volume = Some(0);
+    assert_eq!(relative_volume(&q), None);
+    q.average_volume = Some(400_000);
+    assert_eq!(relative_volume(&q), Some(2.5));
+}
+
+#[test]
+fn test_watchlist_sort_by_relative_volume() {
+    let mut qa = make_quote("BBCA", 9000.0, 0.0, 0.0);
+    qa.average_volume = Some(250_000);
+    let mut qb = make_quote("BBRI", 5000.0, 0.0, 0.0);
+    qb.average_volume = Some(2_000_000);
+    let qc = make_quote("TLKM", 3500.0, 0.0, 0.0);
+    let (sa, sb, sc) = ("BBCA".to_string(), "BBRI".to_string(), "TLKM".to_string());
+    let a = (&sa, Some(&qa));
+    let b = (&sb, Some(&qb));
+    let c = (&sc, Some(&qc));
+    assert_eq!(
+        compare_watchlist_column(10, &a, &b, SortDirection::Descending),
+        Ordering::Less
+    );
+    // No average volume sorts below any ratio when descending
+    assert_eq!(
+        compare_watchlist_column(10, &b, &c, SortDirection::Descending),
+        Ordering::Less
+    );
+}
+
 #[test]
 fn test_watchlist_sort_invalid_column() {
     let qa = make_quote("BBCA", 8000.0, 50.0, 0.6);
@@ -201,6 +233,30 @@ fn test_portfolio_sort_by_pl_percent() {
     );
 }
 
+#[test]
+fn test_portfolio_sort_by_vs_ihsg() {
+    let a = make_holding("BBCA", 10, 8000.0);
+    let b = make_holding("BBRI", 10, 5000.0);
+    let mut quotes = HashMap::new();
+    quotes.insert("IHSG".to_string(), make_quote("IHSG", 7000.0, 70.0, 1.0));
+    quotes.insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 2.5));
+    quotes.insert("BBRI".to_string(), make_quote("BBRI", 5000.0, 0.0, 0.5));
+    assert_eq!(
+        compare_portfolio_column(9, &a, &b, &quotes),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn test_vs_ihsg_pct_needs_index_quote() {
+    let mut quotes = HashMap::new();
+    quotes.insert("BBCA".to_string(), make_quote("BBCA", 9000.0, 0.0, 2.5));
+    assert_eq!(vs_ihsg_pct("BBCA", &quotes), None);
+    quotes.insert("IHSG".to_string(), make_quote("IHSG", 7000.0, 70.0, 1.0));
+    assert_eq!(vs_ihsg_pct("BBCA", &quotes), Some(1.5));
+    assert_eq!(vs_ihsg_pct("TLKM", &quotes), None);
+}
+
 #[test]
 fn test_portfolio_sort_missing_quote_defaults_zero() {
     let a = make_holding("BBCA", 10, 8000.0);
@@ -252,3 +308,63 @@ fn test_news_sort_invalid_column() {
     let b = make_news_item("Title", "CNBC", 2000);
     assert_eq!(compare_news_column(99, &a, &b), Ordering::Equal);
 }
+
+#[test]
+fn test_news_defaults_to_newest_first() {
+    let mut app = test_app();
+    app.news_items.push(make_news_item("Old", "CNBC", 1000));
+    app.news_items.push(make_news_item("New", "CNBC", 3000));
+    app.news_items.push(make_news_item("Mid", "CNBC", 2000));
+    assert_eq!(app.news_sort_column, Some(0));
+    assert_eq!(app.news_sort_direction, SortDirection::Descending);
+    let titles: Vec<&str> = app
+        .get_filtered_news()
+        .iter()
+        .map(|n| n.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["New", "Mid", "Old"]);
+}
+
+#[test]
+fn test_news_sort_cycle_covers_age_source_headline() {
+    let mut app = test_app();
+    app.view_mode = ViewMode::News;
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        app.cycle_sort_column();
+        seen.push(app.news_sort_column);
+    }
+    assert_eq!(seen, vec![Some(1), Some(2), None, Some(0)]);
+}
+
+// --- per-watchlist sort ---
+
+#[test]
+fn test_switching_watchlist_applies_its_saved_sort() {
+    let mut app = test_app();
+    app.config.add_watchlist("Gainers");
+    let gainers = app.config.current_watchlist_mut();
+    gainers.sort_column = Some(4);
+    gainers.sort_direction = SortDirection::Descending;
+
+    app.next_watchlist();
+    assert_eq!(app.config.current_watchlist().name, "Default");
+    assert_eq!(app.watchlist_sort_column, None);
+    assert_eq!(app.watchlist_sort_direction, SortDirection::Ascending);
+
+    app.next_watchlist();
+    assert_eq!(app.config.current_watchlist().name, "Gainers");
+    assert_eq!(app.watchlist_sort_column, Some(4));
+    assert_eq!(app.watchlist_sort_direction, SortDirection::Descending);
+}
+
+#[test]
+fn test_sorting_updates_active_watchlist() {
+    let (mut app, path) = temp_config_app("sorting-updates-active-watchlist");
+    app.cycle_sort_column();
+    app.toggle_sort_direction();
+    let wl = app.config.current_watchlist();
+    assert_eq!(wl.sort_column, Some(0));
+    assert_eq!(wl.sort_direction, SortDirection::Descending);
+    let _ = std::fs::remove_file(path);
+}
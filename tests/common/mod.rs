@@ -1,8 +1,13 @@
 #![allow(dead_code)]
 
-use idx_cli::api::{NewsItem, StockQuote};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use idx_cli::api::yahoo::display_symbol;
+use idx_cli::api::{ChartData, ChartRange, NewsItem, QuoteProvider, StockQuote};
 use idx_cli::app::App;
 use idx_cli::config::{Config, Holding};
+use std::collections::HashMap;
+use std::time::Duration;
 
 pub fn make_quote(symbol: &str, price: f64, change: f64, change_pct: f64) -> StockQuote {
     StockQuote {
@@ -36,6 +41,7 @@ pub fn make_news_item(title: &str, publisher: &str, ts: i64) -> NewsItem {
         published_at: ts,
         url: None,
         summary: None,
+        category: None,
     }
 }
 
@@ -44,6 +50,7 @@ pub fn make_holding(symbol: &str, lots: u32, avg_price: f64) -> Holding {
         symbol: symbol.to_string(),
         lots,
         avg_price,
+        note: None,
     }
 }
 
@@ -51,3 +58,110 @@ pub fn make_holding(symbol: &str, lots: u32, avg_price: f64) -> Holding {
 pub fn test_app() -> App {
     App::test_new(Config::test_config())
 }
+
+/// App whose config saves to a scratch file instead of the user's config.
+/// Use it for anything that calls `config.save()`.
+pub fn temp_config_app(name: &str) -> (App, std::path::PathBuf) {
+    let path =
+        std::env::temp_dir().join(format!("idx-cli-test-{}-{}.json", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let config = Config::load_from(Some(path.clone())).unwrap();
+    (App::test_new(config), path)
+}
+
+/// Offline `QuoteProvider` that answers from a fixed set of quotes.
+/// Symbols it doesn't know are left out of the response, like Yahoo does.
+pub struct MockProvider {
+    pub quotes: HashMap<String, StockQuote>,
+    /// Fail quote requests with a real connection error, as when offline
+    pub offline: bool,
+    /// How long each intraday chart takes to arrive
+    pub chart_delay: Duration,
+}
+
+impl MockProvider {
+    pub fn new(quotes: Vec<StockQuote>) -> Self {
+        Self {
+            quotes: quotes.into_iter().map(|q| (q.symbol.clone(), q)).collect(),
+            offline: false,
+            chart_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl QuoteProvider for MockProvider {
+    fn get_quotes<'a>(
+        &'a mut self,
+        symbols: &'a [String],
+    ) -> BoxFuture<'a, Result<HashMap<String, StockQuote>>> {
+        if self.offline {
+            return Box::pin(async { Err(connection_error().await) });
+        }
+        let found = symbols
+            .iter()
+            .filter_map(|s| self.quotes.get(&display_symbol(s)))
+            .map(|q| (q.symbol.clone(), q.clone()))
+            .collect();
+        Box::pin(async move { Ok(found) })
+    }
+
+    fn get_chart<'a>(
+        &'a self,
+        symbol: &'a str,
+        _range: ChartRange,
+    ) -> BoxFuture<'a, Result<ChartData>> {
+        self.get_intraday_chart(symbol)
+    }
+
+    fn get_intraday_chart<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<ChartData>> {
+        let chart = self.quotes.get(&display_symbol(symbol)).map(|q| ChartData {
+            closes: vec![q.prev_close, q.price],
+            high: q.high,
+            low: q.low,
+        });
+        let delay = self.chart_delay;
+        Box::pin(async move {
+            tokio::time::sleep(delay).await;
+            chart.ok_or_else(|| anyhow!("no chart"))
+        })
+    }
+
+    fn search_symbols<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<(String, String)>>> {
+        let query = query.to_lowercase();
+        let mut found: Vec<(String, String)> = self
+            .quotes
+            .values()
+            .filter(|q| {
+                q.symbol.to_lowercase().starts_with(&query)
+                    || q.short_name.to_lowercase().contains(&query)
+            })
+            .map(|q| (q.symbol.clone(), q.short_name.clone()))
+            .collect();
+        found.sort();
+        Box::pin(async move { Ok(found) })
+    }
+
+    fn chart_handle(&self) -> Option<Box<dyn QuoteProvider>> {
+        Some(Box::new(MockProvider {
+            quotes: self.quotes.clone(),
+            offline: self.offline,
+            chart_delay: self.chart_delay,
+        }))
+    }
+}
+
+/// A genuine connect error: nothing listens on port 1 of localhost.
+pub async fn connection_error() -> anyhow::Error {
+    reqwest::get("http://127.0.0.1:1/")
+        .await
+        .unwrap_err()
+        .into()
+}
+
+// App backed by a MockProvider serving `quotes`.
+pub fn mock_app(quotes: Vec<StockQuote>) -> App {
+    test_app().with_provider(Box::new(MockProvider::new(quotes)))
+}
@@ -1,9 +1,21 @@
-use idx_cli::config::{Alert, AlertType, Bookmark, Config};
+mod common;
+
+use common::make_quote;
+use idx_cli::api::StockQuote;
+use idx_cli::config::{
+    Alert, AlertType, Bookmark, CONFIG_SCHEMA_VERSION, ChangeClass, Config, Density, Holding,
+    HoldingsColumns, NumberLocale, Theme, classify_change, heatmap_color, is_volume_spike,
+};
+use ratatui::style::Color;
 
 fn test_config() -> Config {
     Config::test_config()
 }
 
+fn quote(price: f64, change_pct: f64) -> StockQuote {
+    make_quote("BBCA", price, 0.0, change_pct)
+}
+
 #[test]
 fn add_holding_overflow_returns_false() {
     let mut config = test_config();
@@ -39,6 +51,80 @@ fn add_holding_normal_merge() {
     assert!((h.avg_price - 8500.0).abs() < 0.01);
 }
 
+#[test]
+fn lot_size_scales_shares_and_cost_basis() {
+    let holding = Holding {
+        symbol: "BBCA".to_string(),
+        lots: 3,
+        avg_price: 9000.0,
+        note: None,
+    };
+    assert_eq!(holding.shares(100), 300);
+    assert_eq!(holding.cost_basis(100), 2_700_000.0);
+    assert_eq!(holding.shares(1), 3);
+    assert_eq!(holding.cost_basis(1), 27_000.0);
+    let (value, cost, pl, _) = holding.pl_metrics(10_000.0, 1);
+    assert_eq!((value, cost, pl), (30_000.0, 27_000.0, 3_000.0));
+}
+
+#[test]
+fn add_holding_merge_with_lot_size_one() {
+    let mut config = test_config();
+    config.lot_size = 1;
+    config.add_holding("BTC", 1, 100.0);
+    config.add_holding("BTC", 3, 200.0);
+    let h = &config.current_portfolio().holdings[0];
+    assert_eq!(h.lots, 4);
+    // (1*100 + 3*200) / 4
+    assert!((h.avg_price - 175.0).abs() < 1e-9);
+    assert_eq!(h.cost_basis(config.lot_size), 700.0);
+
+    config.remove_holding("BTC", Some(250.0));
+    assert_eq!(config.realized_pl("BTC"), 4.0 * 250.0 - 700.0);
+}
+
+#[test]
+fn lot_size_defaults_to_100() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.lot_size, 100);
+}
+
+#[test]
+fn portfolio_from_watchlist_adds_zero_lot_holdings() {
+    let mut config = test_config();
+    config.add_watchlist("Banks");
+    config.add_stock("BBCA");
+    config.add_stock("BBRI");
+    let before = config.portfolios.len();
+
+    assert_eq!(config.portfolio_from_watchlist("Banks"), 2);
+    assert_eq!(config.portfolios.len(), before + 1);
+    let portfolio = config.current_portfolio();
+    assert_eq!(portfolio.name, "Banks");
+    assert!(portfolio.holdings.iter().all(|h| h.lots == 0));
+    assert!(portfolio.transactions.is_empty());
+
+    // Filling in a placeholder records the buy as usual
+    config.add_holding("BBCA", 5, 9000.0);
+    assert_eq!(config.current_portfolio().holdings[0].lots, 5);
+    assert_eq!(config.current_portfolio().holdings[0].avg_price, 9000.0);
+}
+
+#[test]
+fn watchlist_from_portfolio_copies_symbols() {
+    let mut config = test_config();
+    config.add_holding("TLKM", 2, 3000.0);
+    config.add_holding("ASII", 1, 5000.0);
+    assert_eq!(config.watchlist_from_portfolio("Mine"), 2);
+    assert_eq!(config.current_watchlist().name, "Mine");
+    assert_eq!(config.current_watchlist().symbols, vec!["TLKM", "ASII"]);
+
+    config.add_portfolio("Empty");
+    let before = config.watchlists.len();
+    assert_eq!(config.watchlist_from_portfolio("Empty"), 0);
+    assert_eq!(config.watchlists.len(), before);
+}
+
 #[test]
 fn add_holding_new_symbol() {
     let mut config = test_config();
@@ -113,40 +199,85 @@ fn portfolio_crud_operations() {
 #[test]
 fn alert_above_fires_when_price_meets_threshold() {
     let alert = Alert::new("BBCA", AlertType::Above, 8000.0);
-    assert!(alert.should_trigger(8000.0, 0.0));
-    assert!(alert.should_trigger(8001.0, 0.0));
-    assert!(!alert.should_trigger(7999.0, 0.0));
+    assert!(alert.should_trigger(&quote(8000.0, 0.0)));
+    assert!(alert.should_trigger(&quote(8001.0, 0.0)));
+    assert!(!alert.should_trigger(&quote(7999.0, 0.0)));
 }
 
 #[test]
 fn alert_below_fires_when_price_meets_threshold() {
     let alert = Alert::new("BBCA", AlertType::Below, 8000.0);
-    assert!(alert.should_trigger(8000.0, 0.0));
-    assert!(alert.should_trigger(7999.0, 0.0));
-    assert!(!alert.should_trigger(8001.0, 0.0));
+    assert!(alert.should_trigger(&quote(8000.0, 0.0)));
+    assert!(alert.should_trigger(&quote(7999.0, 0.0)));
+    assert!(!alert.should_trigger(&quote(8001.0, 0.0)));
 }
 
 #[test]
 fn alert_pct_gain_fires_when_change_meets_threshold() {
     let alert = Alert::new("BBCA", AlertType::PercentGain, 5.0);
-    assert!(alert.should_trigger(8000.0, 5.0));
-    assert!(alert.should_trigger(8000.0, 6.0));
-    assert!(!alert.should_trigger(8000.0, 4.0));
+    assert!(alert.should_trigger(&quote(8000.0, 5.0)));
+    assert!(alert.should_trigger(&quote(8000.0, 6.0)));
+    assert!(!alert.should_trigger(&quote(8000.0, 4.0)));
 }
 
 #[test]
 fn alert_pct_loss_fires_when_change_meets_threshold() {
     let alert = Alert::new("BBCA", AlertType::PercentLoss, 5.0);
-    assert!(alert.should_trigger(8000.0, -5.0));
-    assert!(alert.should_trigger(8000.0, -6.0));
-    assert!(!alert.should_trigger(8000.0, -4.0));
+    assert!(alert.should_trigger(&quote(8000.0, -5.0)));
+    assert!(alert.should_trigger(&quote(8000.0, -6.0)));
+    assert!(!alert.should_trigger(&quote(8000.0, -4.0)));
+}
+
+#[test]
+fn alert_cross_above_fires_only_on_transition() {
+    let alert = Alert::new("BBCA", AlertType::CrossAbove, 8000.0);
+    assert!(alert.should_trigger_from(Some(7950.0), &quote(8000.0, 0.0)));
+    // Already above: no cross
+    assert!(!alert.should_trigger_from(Some(8050.0), &quote(8100.0, 0.0)));
+    // Still below
+    assert!(!alert.should_trigger_from(Some(7900.0), &quote(7950.0, 0.0)));
+    // No previous price yet
+    assert!(!alert.should_trigger_from(None, &quote(8100.0, 0.0)));
+    assert!(!alert.should_trigger(&quote(8100.0, 0.0)));
+}
+
+#[test]
+fn alert_cross_below_fires_only_on_transition() {
+    let alert = Alert::new("BBCA", AlertType::CrossBelow, 8000.0);
+    assert!(alert.should_trigger_from(Some(8050.0), &quote(7990.0, 0.0)));
+    assert!(!alert.should_trigger_from(Some(7990.0), &quote(7900.0, 0.0)));
+    assert!(!alert.should_trigger_from(Some(8100.0), &quote(8050.0, 0.0)));
+}
+
+#[test]
+fn alert_new_day_high_fires_at_session_high() {
+    let alert = Alert::new("BBCA", AlertType::NewDayHigh, 0.0);
+    let mut q = quote(8000.0, 0.0);
+    assert!(!alert.should_trigger(&q));
+    q.high = 8000.0;
+    assert!(alert.should_trigger(&q));
+    q.high = 8000.0 + 1e-9;
+    assert!(alert.should_trigger(&q));
+}
+
+#[test]
+fn alert_new_day_low_fires_at_session_low() {
+    let alert = Alert::new("BBCA", AlertType::NewDayLow, 0.0);
+    let mut q = quote(8000.0, 0.0);
+    assert!(!alert.should_trigger(&q));
+    q.low = 8000.0;
+    assert!(alert.should_trigger(&q));
+    // No session data yet
+    let mut empty = quote(0.0, 0.0);
+    empty.low = 0.0;
+    assert!(!alert.should_trigger(&empty));
 }
 
 #[test]
 fn alert_disabled_does_not_fire() {
     let mut alert = Alert::new("BBCA", AlertType::Above, 8000.0);
     alert.enabled = false;
-    assert!(!alert.should_trigger(8001.0, 0.0));
+    assert!(!alert.should_trigger(&quote(8001.0, 0.0)));
 }
 
 #[test]
@@ -159,7 +290,7 @@ fn alert_respects_cooldown() {
         .unwrap()
         .as_secs();
     alert.last_triggered = Some(now);
-    assert!(!alert.should_trigger(8001.0, 0.0));
+    assert!(!alert.should_trigger(&quote(8001.0, 0.0)));
 }
 
 #[test]
@@ -204,7 +335,16 @@ fn alert_type_cycles() {
     at = at.next();
     assert_eq!(at, AlertType::PercentLoss);
     at = at.next();
+    assert_eq!(at, AlertType::CrossAbove);
+    at = at.next();
+    assert_eq!(at, AlertType::CrossBelow);
+    at = at.next();
+    assert_eq!(at, AlertType::NewDayHigh);
+    at = at.next();
+    assert_eq!(at, AlertType::NewDayLow);
+    at = at.next();
     assert_eq!(at, AlertType::Above);
+    assert_eq!(AlertType::Above.prev(), AlertType::NewDayLow);
 }
 
 fn make_test_bookmark(headline: &str, url: Option<&str>) -> Bookmark {
@@ -292,3 +432,793 @@ fn bookmark_deserialization_with_default() {
     let config: Config = serde_json::from_str(json).unwrap();
     assert!(config.bookmarks.is_empty());
 }
+
+fn temp_config_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("idx-cli-test-{}-{}", std::process::id(), name))
+        .join("nested")
+        .join("config.json")
+}
+
+#[test]
+fn load_from_missing_path_creates_defaults() {
+    let path = temp_config_path("load-missing");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+
+    let config = Config::load_from(Some(path.clone())).unwrap();
+    assert!(path.exists(), "load_from should create the file");
+    assert_eq!(config.watchlists.len(), Config::default().watchlists.len());
+
+    // save() should write back to the custom path, not the default location
+    let mut config = config;
+    config.add_watchlist("Custom");
+    config.save().unwrap();
+    let reloaded = Config::load_from(Some(path.clone())).unwrap();
+    assert!(reloaded.watchlists.iter().any(|w| w.name == "Custom"));
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+}
+
+#[test]
+fn load_from_directory_path_errors() {
+    let dir = std::env::temp_dir().join(format!("idx-cli-test-{}-dir", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let err = Config::load_from(Some(dir.clone())).unwrap_err();
+    assert!(err.to_string().contains("is a directory"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// --- atomic save ---
+
+#[test]
+fn save_replaces_file_without_leaving_temp_file() {
+    let path = temp_config_path("atomic-save");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    let mut config = Config::load_from(Some(path.clone())).unwrap();
+
+    config.add_watchlist("Atomic");
+    config.save().unwrap();
+
+    let tmp = path.with_file_name("config.json.tmp");
+    assert!(!tmp.exists());
+    let content = std::fs::read_to_string(&path).unwrap();
+    let saved: Config = serde_json::from_str(&content).unwrap();
+    assert!(saved.watchlists.iter().any(|w| w.name == "Atomic"));
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+}
+
+// --- out-of-range active indices ---
+
+#[test]
+fn load_clamps_far_out_of_range_active_indices() {
+    let path = temp_config_path("active-range");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    let mut config = Config::default();
+    config.active_watchlist = 999;
+    config.active_portfolio = usize::MAX;
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+
+    let config = Config::load_from(Some(path.clone())).unwrap();
+    assert_eq!(config.active_watchlist, 0);
+    assert_eq!(config.active_portfolio, 0);
+    assert_eq!(config.current_watchlist().name, config.watchlists[0].name);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+}
+
+#[test]
+fn current_lists_never_panic_on_bad_indices() {
+    let mut config = test_config();
+    config.active_watchlist = 999;
+    config.active_portfolio = 999;
+    assert_eq!(config.current_watchlist().name, "Default");
+    assert!(config.current_portfolio().holdings.is_empty());
+
+    // Mutable access repairs the index first
+    config.add_stock("GOTO");
+    assert_eq!(config.active_watchlist, 0);
+    assert!(config.watchlists[0].symbols.contains(&"GOTO".to_string()));
+
+    config.watchlists.clear();
+    config.portfolios.clear();
+    assert!(config.current_watchlist().symbols.is_empty());
+    assert!(config.current_portfolio().holdings.is_empty());
+    config.add_holding("BBCA", 1, 9000.0);
+    assert_eq!(config.portfolios.len(), 1);
+}
+
+// --- corrupt config recovery ---
+
+#[test]
+fn parse_or_default_returns_defaults_for_invalid_json() {
+    let (config, error) = Config::parse_or_default(r#"{"watchlists": [{"name": "Ba"#);
+    assert!(error.is_some());
+    assert_eq!(config.watchlists.len(), Config::default().watchlists.len());
+    assert_eq!(
+        config.refresh_interval_secs,
+        Config::default().refresh_interval_secs
+    );
+
+    let mut saved = Config::default();
+    saved.add_watchlist("Custom");
+    let (config, error) = Config::parse_or_default(&serde_json::to_string(&saved).unwrap());
+    assert!(error.is_none());
+    assert!(config.watchlists.iter().any(|w| w.name == "Custom"));
+}
+
+#[test]
+fn load_from_corrupt_file_backs_it_up_and_resets() {
+    let path = temp_config_path("load-corrupt");
+    let dir = path.parent().unwrap().to_path_buf();
+    let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&path, "{\"watchlists\": [").unwrap();
+
+    let config = Config::load_from(Some(path.clone())).unwrap();
+    assert!(
+        config
+            .load_warning
+            .as_deref()
+            .unwrap()
+            .contains("config.json.bak-")
+    );
+    assert_eq!(config.watchlists.len(), Config::default().watchlists.len());
+
+    // The defaults were saved and the bad file kept next to them
+    assert!(
+        Config::load_from(Some(path.clone()))
+            .unwrap()
+            .load_warning
+            .is_none()
+    );
+    let backups: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("config.json.bak-"))
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(dir.join(&backups[0])).unwrap(),
+        "{\"watchlists\": ["
+    );
+
+    let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+}
+
+// --- schema_version ---
+
+#[test]
+fn load_versionless_config_migrates_and_saves() {
+    let path = temp_config_path("schema-legacy");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &path,
+        r#"{
+            "watchlists": [{"name": "Mine", "symbols": ["BBCA", "TLKM"]}],
+            "portfolio": [{"symbol": "BBCA", "lots": 100, "avg_price": 8000.0}],
+            "news_sources": ["https://www.kontan.co.id/rss/investasi"]
+        }"#,
+    )
+    .unwrap();
+
+    let config = Config::load_from(Some(path.clone())).unwrap();
+    assert_eq!(config.schema_version, CONFIG_SCHEMA_VERSION);
+    assert_eq!(config.watchlists[0].name, "Mine");
+    assert_eq!(config.watchlists[0].symbols, vec!["BBCA", "TLKM"]);
+    assert_eq!(config.portfolios[0].holdings[0].symbol, "BBCA");
+    assert!(
+        !config
+            .news_sources
+            .iter()
+            .any(|u| u.contains("kontan.co.id/rss/investasi"))
+    );
+
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains(&format!("\"schema_version\": {}", CONFIG_SCHEMA_VERSION)));
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+}
+
+#[test]
+fn migrate_is_noop_at_current_version() {
+    let mut config = test_config();
+    config.news_sources = vec!["https://example.com/rss".to_string()];
+    assert!(!config.migrate());
+    // Removed default feeds stay removed once the config is current
+    assert_eq!(config.news_sources, vec!["https://example.com/rss"]);
+}
+
+#[test]
+fn migrate_shifts_watchlist_columns_past_rel_vol() {
+    let mut config: Config = serde_json::from_str(
+        r#"{"schema_version": 2, "watchlists": [], "watchlist_columns": [0, 4, 10, 11, 8]}"#,
+    )
+    .unwrap();
+    assert!(config.migrate());
+    assert_eq!(config.watchlist_columns, Some(vec![0, 4, 11, 12, 8]));
+}
+
+#[test]
+fn load_newer_schema_is_rejected() {
+    let path = temp_config_path("schema-newer");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let json = format!(
+        r#"{{"schema_version": {}, "watchlists": []}}"#,
+        CONFIG_SCHEMA_VERSION + 1
+    );
+    std::fs::write(&path, &json).unwrap();
+
+    let err = Config::load_from(Some(path.clone())).unwrap_err();
+    assert!(err.to_string().contains("please upgrade"));
+    // Left untouched for the newer build
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), json);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+}
+
+// --- transactions ---
+
+#[test]
+fn realized_pl_matches_sells_fifo() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 10, 8000.0);
+    config.add_holding("BBCA", 10, 9000.0);
+    // Sell 15 lots at 10000: 10 from the 8000 lot, 5 from the 9000 lot
+    config.update_holding("BBCA", 5, 9000.0, Some(10000.0));
+    let expected = 10.0 * 100.0 * 2000.0 + 5.0 * 100.0 * 1000.0;
+    assert_eq!(config.realized_pl("BBCA"), expected);
+}
+
+#[test]
+fn remove_holding_records_sell() {
+    let mut config = test_config();
+    config.add_holding("TLKM", 4, 3000.0);
+    config.remove_holding("TLKM", Some(2500.0));
+    assert!(config.current_portfolio().holdings.is_empty());
+    assert_eq!(config.realized_pl("TLKM"), -4.0 * 100.0 * 500.0);
+}
+
+#[test]
+fn legacy_holding_gets_opening_buy() {
+    let mut config = test_config();
+    config.current_portfolio_mut().holdings.push(Holding {
+        symbol: "ASII".to_string(),
+        lots: 2,
+        avg_price: 5000.0,
+        note: None,
+    });
+    config.remove_holding("ASII", Some(5500.0));
+    assert_eq!(config.realized_pl("ASII"), 2.0 * 100.0 * 500.0);
+}
+
+// --- theme ---
+
+#[test]
+fn theme_defaults_and_unknown_fall_back() {
+    let mut config = test_config();
+    assert_eq!(config.theme(), Theme::default());
+    config.theme = "nope".to_string();
+    assert_eq!(config.theme(), Theme::default());
+    config.theme = "mono".to_string();
+    assert_eq!(config.theme(), Theme::mono());
+}
+
+#[test]
+fn custom_theme_parses_partial_colors() {
+    let json = r##"{
+        "watchlists": [],
+        "theme": "custom",
+        "custom_theme": { "gain": "blue", "loss": "#ffa500" }
+    }"##;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let theme = config.theme();
+    assert_eq!(theme.gain, Color::Blue);
+    assert_eq!(theme.loss, Color::Rgb(255, 165, 0));
+    assert_eq!(theme.header, Theme::default().header);
+}
+
+// --- apply_split ---
+
+#[test]
+fn apply_split_preserves_cost_basis() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 10, 9000.0);
+    let cost = config.current_portfolio().holdings[0].cost_basis(100);
+
+    assert_eq!(config.apply_split("BBCA", 2, 1), Ok(0));
+    let h = &config.current_portfolio().holdings[0];
+    assert_eq!(h.lots, 20);
+    assert_eq!(h.avg_price, 4500.0);
+    assert_eq!(h.cost_basis(100), cost);
+
+    assert_eq!(config.apply_split("BBCA", 1, 4), Ok(0));
+    let h = &config.current_portfolio().holdings[0];
+    assert_eq!(h.lots, 5);
+    assert_eq!(h.avg_price, 18000.0);
+    assert!((h.cost_basis(100) - cost).abs() < 1e-6);
+}
+
+#[test]
+fn apply_split_rejects_invalid_ratio() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 1, 9000.0);
+    assert!(config.apply_split("BBCA", 0, 1).is_err());
+    // 20 shares: less than a lot
+    assert!(config.apply_split("BBCA", 1, 5).is_err());
+    assert!(config.apply_split("TLKM", 2, 1).is_err());
+    assert_eq!(config.current_portfolio().holdings[0].lots, 1);
+}
+
+#[test]
+fn apply_split_rejects_fractional_lots() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 10, 9000.0);
+    // 1,000 shares 1:3 is 333⅓ shares
+    let err = config.apply_split("BBCA", 1, 3).unwrap_err();
+    assert!(err.contains("3.33 lots"), "{}", err);
+    // 10 lots 3:2 is 1,500 shares, a whole 15 lots
+    assert_eq!(config.apply_split("BBCA", 3, 2), Ok(0));
+    // 15 lots 1:2 is 750 shares, 7.5 lots
+    assert!(config.apply_split("BBCA", 1, 2).is_err());
+    let h = &config.current_portfolio().holdings[0];
+    assert_eq!(h.lots, 15);
+    assert_eq!(h.avg_price, 6000.0);
+}
+
+#[test]
+fn apply_split_rounds_logged_trades_to_nearest_lot() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 3, 9000.0);
+    config.add_holding("BBCA", 1, 9000.0);
+    // 4 lots 1:4 leaves one lot; the 3-lot buy is 0.75 lots, the 1-lot 0.25
+    assert_eq!(config.apply_split("BBCA", 1, 4), Ok(2));
+    let lots: Vec<u32> = config
+        .current_portfolio()
+        .transactions
+        .iter()
+        .map(|t| t.lots)
+        .collect();
+    assert_eq!(lots, vec![1, 0]);
+    assert_eq!(config.current_portfolio().holdings[0].lots, 1);
+}
+
+// --- export_backup / import_backup ---
+
+#[test]
+fn backup_round_trips_into_another_config() {
+    let backup_path = temp_config_path("backup-src").with_file_name("backup.json");
+    let mut source = Config::load_from(Some(temp_config_path("backup-src"))).unwrap();
+    source.add_watchlist("Miners");
+    source.add_stock("ANTM");
+    source.add_alert(Alert::new("ANTM", AlertType::Above, 2000.0));
+    source.export_backup(&backup_path).unwrap();
+
+    let target_path = temp_config_path("backup-dst");
+    let mut target = Config::load_from(Some(target_path.clone())).unwrap();
+    target.import_backup(&backup_path).unwrap();
+    assert!(target.watchlists.iter().any(|w| w.name == "Miners"));
+    assert_eq!(target.alerts.len(), source.alerts.len());
+
+    // Saved to the target's own file, not the backup's
+    let reloaded = Config::load_from(Some(target_path)).unwrap();
+    assert!(reloaded.watchlists.iter().any(|w| w.name == "Miners"));
+}
+
+#[test]
+fn import_backup_rejects_invalid_json() {
+    let bad = temp_config_path("backup-bad").with_file_name("bad.json");
+    std::fs::create_dir_all(bad.parent().unwrap()).unwrap();
+    std::fs::write(&bad, r#"{"watchlists": "not a list"}"#).unwrap();
+
+    let mut config = Config::load_from(Some(temp_config_path("backup-bad"))).unwrap();
+    let before = config.watchlists.len();
+    let err = config.import_backup(&bad).unwrap_err();
+    assert!(err.to_string().contains("not a valid idx-cli backup"));
+    assert_eq!(config.watchlists.len(), before);
+}
+
+// --- import_watchlist_from_csv ---
+
+fn import_fixture(name: &str, content: &str) -> (Config, std::path::PathBuf) {
+    let config_path = temp_config_path(name);
+    let _ = std::fs::remove_dir_all(config_path.parent().unwrap().parent().unwrap());
+    let config = Config::load_from(Some(config_path.clone())).unwrap();
+    let csv = config_path.with_file_name("symbols.csv");
+    std::fs::write(&csv, content).unwrap();
+    (config, csv)
+}
+
+#[test]
+fn import_watchlist_reads_symbol_column_and_dedupes() {
+    let (mut config, csv) = import_fixture(
+        "import-column",
+        "Name,Symbol,Price\nBank Central Asia,bbca,9000\nTelkom,TLKM,3000\nBCA again,BBCA,9000\n",
+    );
+    let count = config.import_watchlist_from_csv(&csv, "Banks").unwrap();
+    assert_eq!(count, 2);
+    let wl = config.current_watchlist();
+    assert_eq!(wl.name, "Banks");
+    assert_eq!(wl.symbols, vec!["BBCA", "TLKM"]);
+}
+
+#[test]
+fn import_watchlist_reads_one_symbol_per_line() {
+    let (mut config, csv) = import_fixture("import-lines", "asii\n\n  goto \nASII\n");
+    assert_eq!(config.import_watchlist_from_csv(&csv, "Mine").unwrap(), 2);
+    assert_eq!(config.current_watchlist().symbols, vec!["ASII", "GOTO"]);
+}
+
+#[test]
+fn import_watchlist_rejects_empty_file() {
+    let (mut config, csv) = import_fixture("import-empty", "Symbol\n\n");
+    let before = config.watchlists.len();
+    assert!(config.import_watchlist_from_csv(&csv, "Empty").is_err());
+    assert_eq!(config.watchlists.len(), before);
+}
+
+// --- import_holdings_from_csv ---
+
+#[test]
+fn import_holdings_merges_duplicates_and_reports_bad_rows() {
+    let (mut config, csv) = import_fixture(
+        "holdings-merge",
+        "Stock,Lot,Avg Price\nbbca,10,9000\nTLKM,abc,3000\nBBCA,10,9500\nASII,0,5000\nGOTO,5,-1\n",
+    );
+    let result = config
+        .import_holdings_from_csv(&csv, &HoldingsColumns::default())
+        .unwrap();
+    assert_eq!(result.imported, 2);
+    assert_eq!(result.errors.len(), 3);
+    assert!(result.errors[0].starts_with("Row 3 (TLKM)"));
+    assert!(result.errors[2].contains("price '-1'"));
+
+    let holdings = &config.current_portfolio().holdings;
+    let bbca = holdings.iter().find(|h| h.symbol == "BBCA").unwrap();
+    assert_eq!(bbca.lots, 20);
+    assert!((bbca.avg_price - 9250.0).abs() < 1e-9);
+    assert!(!holdings.iter().any(|h| h.symbol == "TLKM"));
+}
+
+#[test]
+fn import_holdings_uses_custom_columns_and_semicolons() {
+    let (mut config, csv) =
+        import_fixture("holdings-custom", "Saham;Jumlah;Harga\nUNVR;3;4200.5\n");
+    let columns = HoldingsColumns {
+        symbol: Some("saham".to_string()),
+        lots: Some("Jumlah".to_string()),
+        avg_price: Some("Harga".to_string()),
+    };
+    let result = config.import_holdings_from_csv(&csv, &columns).unwrap();
+    assert_eq!(result.imported, 1);
+    assert!(result.errors.is_empty());
+    let unvr = config
+        .current_portfolio()
+        .holdings
+        .iter()
+        .find(|h| h.symbol == "UNVR")
+        .unwrap();
+    assert_eq!(unvr.lots, 3);
+    assert_eq!(unvr.avg_price, 4200.5);
+}
+
+#[test]
+fn import_holdings_reads_quoted_fields_and_decimal_commas() {
+    let (mut config, csv) = import_fixture(
+        "holdings-decimal-comma",
+        "Kode Saham;Lot;Harga Rata-rata\nBBCA;2;9.025,50\nTLKM;1;3025,5\n",
+    );
+    let result = config
+        .import_holdings_from_csv(&csv, &HoldingsColumns::default())
+        .unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let (mut quoted, quoted_csv) = import_fixture(
+        "holdings-quoted",
+        "Symbol,Name,Lots,Avg Price\nASII,\"Astra International, Tbk\",4,\"5,012.25\"\nUNVR,\"Unilever \"\"UNVR\"\"\",1,\"1.234.567\"\n",
+    );
+    let quoted_result = quoted
+        .import_holdings_from_csv(&quoted_csv, &HoldingsColumns::default())
+        .unwrap();
+    assert!(
+        quoted_result.errors.is_empty(),
+        "{:?}",
+        quoted_result.errors
+    );
+
+    let price = |config: &Config, symbol: &str| {
+        config
+            .current_portfolio()
+            .holdings
+            .iter()
+            .find(|h| h.symbol == symbol)
+            .unwrap()
+            .avg_price
+    };
+    assert_eq!(price(&config, "BBCA"), 9025.5);
+    assert_eq!(price(&config, "TLKM"), 3025.5);
+    assert_eq!(price(&quoted, "ASII"), 5012.25);
+    assert_eq!(price(&quoted, "UNVR"), 1234567.0);
+}
+
+#[test]
+fn import_holdings_reads_lone_separator_before_three_digits_as_grouping() {
+    let (mut config, csv) = import_fixture(
+        "holdings-grouping",
+        "Symbol;Lots;Avg Price\nBBCA;1;9,025\nBBRI;1;1.234\nTLKM;1;9.025,50\nASII;1;9,025.50\n",
+    );
+    let result = config
+        .import_holdings_from_csv(&csv, &HoldingsColumns::default())
+        .unwrap();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let holdings = &config.current_portfolio().holdings;
+    let price = |symbol: &str| {
+        holdings
+            .iter()
+            .find(|h| h.symbol == symbol)
+            .unwrap()
+            .avg_price
+    };
+    assert_eq!(price("BBCA"), 9025.0);
+    assert_eq!(price("BBRI"), 1234.0);
+    assert_eq!(price("TLKM"), 9025.5);
+    assert_eq!(price("ASII"), 9025.5);
+}
+
+#[test]
+fn import_holdings_rejects_missing_column() {
+    let (mut config, csv) = import_fixture("holdings-missing", "Symbol,Lots\nBBCA,1\n");
+    let err = config
+        .import_holdings_from_csv(&csv, &HoldingsColumns::default())
+        .unwrap_err();
+    assert!(err.to_string().contains("average price"));
+}
+
+// --- number_locale ---
+
+#[test]
+fn test_number_locale_defaults_to_en_and_round_trips() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.number_locale, NumberLocale::En);
+
+    let mut config = test_config();
+    config.number_locale = NumberLocale::Id;
+    let json = serde_json::to_string(&config).unwrap();
+    let loaded: Config = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.number_locale, NumberLocale::Id);
+}
+
+// --- price_decimals / percent_decimals ---
+
+#[test]
+fn test_decimals_default_to_two_and_round_trip() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.price_decimals, 2);
+    assert_eq!(config.percent_decimals, 2);
+
+    let mut config = test_config();
+    config.price_decimals = 3;
+    config.percent_decimals = 3;
+    let json = serde_json::to_string(&config).unwrap();
+    let loaded: Config = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.price_decimals, 3);
+    assert_eq!(loaded.percent_decimals, 3);
+
+    let fmt = loaded.number_format();
+    assert_eq!(fmt.price_decimals, 3);
+    assert_eq!(fmt.percent_decimals, 3);
+}
+
+// --- density ---
+
+#[test]
+fn test_density_defaults_to_normal_and_toggles() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.density, Density::Normal);
+    assert_eq!(Density::Normal.toggled(), Density::Compact);
+    assert_eq!(Density::Compact.toggled(), Density::Normal);
+    assert!(Density::Compact.bar_height() < Density::Normal.bar_height());
+}
+
+// --- classify_change ---
+
+#[test]
+fn classify_change_bands() {
+    assert_eq!(classify_change(0.05, 0.1, 3.0), ChangeClass::Neutral);
+    assert_eq!(classify_change(-0.05, 0.1, 3.0), ChangeClass::Neutral);
+    assert_eq!(classify_change(0.1, 0.1, 3.0), ChangeClass::Gain);
+    assert_eq!(classify_change(-1.5, 0.1, 3.0), ChangeClass::Loss);
+    assert_eq!(classify_change(3.0, 0.1, 3.0), ChangeClass::StrongGain);
+    assert_eq!(classify_change(-7.2, 0.1, 3.0), ChangeClass::StrongLoss);
+}
+
+#[test]
+fn classify_change_zero_thresholds_disable_classes() {
+    assert_eq!(classify_change(0.0, 0.0, 0.0), ChangeClass::Gain);
+    assert_eq!(classify_change(-25.0, 0.0, 0.0), ChangeClass::Loss);
+}
+
+#[test]
+fn highlights_use_configured_change_thresholds() {
+    let mut config = test_config();
+    config.change_neutral_band_pct = 0.5;
+    config.change_strong_pct = 2.0;
+    let theme = config.theme();
+    let hl = config.highlights();
+    assert_eq!(hl.classify_change(0.4), ChangeClass::Neutral);
+    assert_eq!(hl.change_color(&theme, 0.4, false), theme.neutral);
+    assert_eq!(hl.change_color(&theme, 1.0, false), theme.gain);
+    assert_eq!(hl.change_color(&theme, 2.5, false), theme.gain_selected);
+    assert_eq!(hl.change_color(&theme, -2.5, false), theme.loss_selected);
+}
+
+// --- volume spikes ---
+
+#[test]
+fn volume_spike_at_or_above_threshold() {
+    assert!(!is_volume_spike(1.9, 2.0));
+    assert!(is_volume_spike(2.0, 2.0));
+    assert!(is_volume_spike(6.5, 2.0));
+    // Zero disables the highlight
+    assert!(!is_volume_spike(50.0, 0.0));
+}
+
+#[test]
+fn highlights_use_configured_volume_spike_ratio() {
+    let mut config = test_config();
+    assert!(config.highlights().is_volume_spike(Some(2.5)));
+    assert!(!config.highlights().is_volume_spike(None));
+    config.volume_spike_ratio = 3.0;
+    assert!(!config.highlights().is_volume_spike(Some(2.5)));
+}
+
+// --- heatmap_color ---
+
+#[test]
+fn heatmap_color_interpolates_by_magnitude() {
+    assert_eq!(heatmap_color(0.0, 7.0), Color::Rgb(40, 40, 40));
+    assert_eq!(heatmap_color(3.5, 7.0), Color::Rgb(0, 130, 0));
+    assert_eq!(heatmap_color(7.0, 7.0), Color::Rgb(0, 220, 0));
+    assert_eq!(heatmap_color(-3.5, 7.0), Color::Rgb(130, 0, 0));
+    assert_eq!(heatmap_color(-7.0, 7.0), Color::Rgb(220, 0, 0));
+}
+
+#[test]
+fn heatmap_color_clamps_beyond_max() {
+    assert_eq!(heatmap_color(25.0, 7.0), heatmap_color(7.0, 7.0));
+    assert_eq!(heatmap_color(-10.0, 7.0), heatmap_color(-7.0, 7.0));
+}
+
+#[test]
+fn heatmap_defaults_off() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert!(!config.change_heatmap);
+    assert_eq!(config.highlights().heatmap_color(3.5), None);
+}
+
+#[test]
+fn highlights_heatmap_follows_config() {
+    let mut config = test_config();
+    config.change_heatmap = true;
+    assert_eq!(
+        config.highlights().heatmap_color(3.5),
+        Some(Color::Rgb(0, 130, 0))
+    );
+    config.heatmap_max_pct = 3.5;
+    assert_eq!(
+        config.highlights().heatmap_color(3.5),
+        Some(Color::Rgb(0, 220, 0))
+    );
+    assert_eq!(config.highlights().without_color().heatmap_color(3.5), None);
+    config.change_heatmap = false;
+    assert_eq!(config.highlights().heatmap_color(3.5), None);
+}
+
+#[test]
+fn mono_theme_turns_the_heatmap_off() {
+    let mut config = test_config();
+    config.change_heatmap = true;
+    config.theme = "mono".to_string();
+    assert_eq!(config.highlights().heatmap_color(3.5), None);
+}
+
+// --- last_view_mode ---
+
+#[test]
+fn session_fields_default_when_missing() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.last_view_mode, "watchlist");
+    assert_eq!(config.last_watchlist_selected, 0);
+    assert_eq!(config.last_portfolio_selected, 0);
+}
+
+// --- watchlist sort ---
+
+#[test]
+fn watchlist_sort_defaults_to_unsorted() {
+    let json = r#"{"watchlists": [{"name": "Old", "symbols": ["BBCA"]}]}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.watchlists[0].sort_column, None);
+    assert_eq!(
+        config.watchlists[0].sort_direction,
+        idx_cli::app::SortDirection::Ascending
+    );
+}
+
+// --- holding notes ---
+
+#[test]
+fn holding_without_note_deserializes() {
+    let json = r#"{"watchlists": [], "portfolios": [{"name": "P", "holdings": [{"symbol": "BBCA", "lots": 1, "avg_price": 9000.0}]}]}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.portfolios[0].holdings[0].note, None);
+}
+
+#[test]
+fn set_holding_note_trims_and_clears() {
+    let mut config = test_config();
+    config.add_holding("BBCA", 1, 9000.0);
+    assert!(config.set_holding_note("BBCA", "  core, long term "));
+    assert_eq!(
+        config.current_portfolio().holdings[0].note.as_deref(),
+        Some("core, long term")
+    );
+    assert!(config.set_holding_note("BBCA", "   "));
+    assert_eq!(config.current_portfolio().holdings[0].note, None);
+    assert!(!config.set_holding_note("TLKM", "x"));
+}
+
+// --- move_symbol_up / move_symbol_down ---
+
+#[test]
+fn move_symbol_swaps_adjacent_entries() {
+    let mut config = test_config();
+    assert!(config.move_symbol_down(0));
+    assert_eq!(
+        config.current_watchlist().symbols,
+        vec!["BBRI", "BBCA", "TLKM", "ASII"]
+    );
+    assert!(config.move_symbol_up(3));
+    assert_eq!(
+        config.current_watchlist().symbols,
+        vec!["BBRI", "BBCA", "ASII", "TLKM"]
+    );
+}
+
+#[test]
+fn move_symbol_stops_at_ends() {
+    let mut config = test_config();
+    assert!(!config.move_symbol_up(0));
+    assert!(!config.move_symbol_down(3));
+    assert!(!config.move_symbol_down(10));
+    assert_eq!(
+        config.current_watchlist().symbols,
+        vec!["BBCA", "BBRI", "TLKM", "ASII"]
+    );
+}
+
+// --- projected_annual_dividend ---
+
+#[test]
+fn holding_projected_annual_dividend() {
+    let holding = Holding {
+        symbol: "BBRI".to_string(),
+        lots: 2,
+        avg_price: 4000.0,
+        note: None,
+    };
+    let mut quote: idx_cli::api::StockQuote =
+        serde_json::from_str(r#"{"symbol":"BBRI","short_name":"BRI","price":5000.0,"change":0.0,"change_percent":0.0,"open":0.0,"high":0.0,"low":0.0,"volume":0,"prev_close":0.0}"#)
+            .unwrap();
+    assert_eq!(holding.projected_annual_dividend(&quote, 100), None);
+    quote.dividend_yield = Some(0.05);
+    // 200 shares * 5000 * 5%
+    assert_eq!(
+        holding.projected_annual_dividend(&quote, 100),
+        Some(50_000.0)
+    );
+}
@@ -0,0 +1,49 @@
+use idx_cli::app::{eval_expression, parse_price};
+
+// --- eval_expression ---
+
+#[test]
+fn eval_handles_single_operations() {
+    assert_eq!(eval_expression("8000+50"), Some(8050.0));
+    assert_eq!(eval_expression("8000 - 50"), Some(7950.0));
+    assert_eq!(eval_expression("2500*4"), Some(10000.0));
+    assert_eq!(eval_expression("9000/4"), Some(2250.0));
+    assert_eq!(eval_expression("42"), Some(42.0));
+}
+
+#[test]
+fn eval_respects_precedence() {
+    assert_eq!(eval_expression("1000+2*50"), Some(1100.0));
+    assert_eq!(eval_expression("1000-200/4"), Some(950.0));
+    // Left-associative at equal precedence
+    assert_eq!(eval_expression("100-20-30"), Some(50.0));
+    assert_eq!(eval_expression("800/4/2"), Some(100.0));
+}
+
+#[test]
+fn eval_respects_parentheses() {
+    assert_eq!(eval_expression("(7800+8200)/2"), Some(8000.0));
+    assert_eq!(eval_expression("((1+1)*(2+3))"), Some(10.0));
+}
+
+#[test]
+fn eval_rejects_invalid_input() {
+    for input in [
+        "", "+", "8000+", "*5", "80 00", "8..0", "(100", "100)", "()", "1/0", "abc", "-5",
+    ] {
+        assert_eq!(eval_expression(input), None, "{:?}", input);
+    }
+}
+
+// --- parse_price ---
+
+#[test]
+fn parse_price_prefers_plain_numbers() {
+    assert_eq!(parse_price(" 8125.5 "), Some(8125.5));
+}
+
+#[test]
+fn parse_price_rounds_expression_results() {
+    assert_eq!(parse_price("8000*1.01"), Some(8080.0));
+    assert_eq!(parse_price("10/3"), Some(3.33));
+}
@@ -1,3 +1,4 @@
+use idx_cli::config::NumberLocale;
 use idx_cli::ui::formatters::*;
 
 #[test]
@@ -15,6 +16,13 @@ fn test_format_price_normal_cases() {
     assert_eq!(format_price(50.0), "50.00");
 }
 
+#[test]
+fn test_format_price_keeps_fractional_part() {
+    assert_eq!(format_price(7234.56), "7,234.56");
+    assert_eq!(format_price(8000.0), "8,000");
+    assert_eq!(format_price(999.5), "999.50");
+}
+
 // --- format_change ---
 
 #[test]
@@ -79,6 +87,93 @@ fn test_format_compact_negative_uses_abs() {
     assert_eq!(format_compact(-5_000_000.0), "5.00M");
 }
 
+// --- number_locale ---
+
+#[test]
+fn test_format_compact_with_locale_suffixes() {
+    assert_eq!(format_compact_with(1_500_000.0, NumberLocale::En), "1.50M");
+    assert_eq!(format_compact_with(1_500_000.0, NumberLocale::Id), "1,50jt");
+    assert_eq!(format_compact_with(7_890.0, NumberLocale::Id), "7,89rb");
+    assert_eq!(
+        format_compact_with(1_230_000_000.0, NumberLocale::Id),
+        "1,23M"
+    );
+    assert_eq!(
+        format_compact_with(2_500_000_000_000.0, NumberLocale::Id),
+        "2,50T"
+    );
+}
+
+#[test]
+fn test_format_price_with_locale_grouping() {
+    assert_eq!(format_price_with(7234.56, NumberLocale::En), "7,234.56");
+    assert_eq!(format_price_with(7234.56, NumberLocale::Id), "7.234,56");
+    assert_eq!(
+        format_price_with(1_250_000.0, NumberLocale::Id),
+        "1.250.000"
+    );
+    assert_eq!(format_price_with(999.5, NumberLocale::Id), "999,50");
+}
+
+#[test]
+fn test_number_format_follows_its_locale() {
+    let id = NumberFormat {
+        locale: NumberLocale::Id,
+        ..Default::default()
+    };
+    assert_eq!(id.price(7234.56), "7.234,56");
+    assert_eq!(id.pl(-1_500_000.0), "-1,50jt");
+    assert_eq!(NumberFormat::default().volume(7_890), "7.89K");
+}
+
+#[test]
+fn test_number_format_carries_its_decimals() {
+    let fmt = NumberFormat {
+        price_decimals: 3,
+        percent_decimals: 1,
+        ..Default::default()
+    };
+    assert_eq!(fmt.price(50.0), "50.000");
+    assert_eq!(fmt.signed_percent(1.234), "+1.2%");
+    assert_eq!(fmt.percent(4.21), "4.2%");
+    // The defaults are untouched by another format's settings
+    assert_eq!(format_price(50.0), "50.00");
+    assert_eq!(format_signed_percent(1.234), "+1.23%");
+}
+
+// --- decimal places ---
+
+#[test]
+fn test_format_price_places_three_decimals() {
+    assert_eq!(format_price_places(52.1234, NumberLocale::En, 3), "52.123");
+    assert_eq!(
+        format_price_places(7234.5678, NumberLocale::En, 3),
+        "7,234.568"
+    );
+    assert_eq!(format_price_places(7234.0004, NumberLocale::En, 3), "7,234");
+    assert_eq!(
+        format_price_places(7234.05, NumberLocale::Id, 3),
+        "7.234,050"
+    );
+    assert_eq!(format_price_places(999.5, NumberLocale::Id, 3), "999,500");
+}
+
+#[test]
+fn test_format_price_places_zero_decimals() {
+    assert_eq!(format_price_places(52.6, NumberLocale::En, 0), "53");
+    assert_eq!(format_price_places(7234.56, NumberLocale::En, 0), "7,235");
+}
+
+#[test]
+fn test_format_percent_places() {
+    assert_eq!(format_percent_places(1.23456, 3, true), "+1.235%");
+    assert_eq!(format_percent_places(-0.5, 3, true), "-0.500%");
+    assert_eq!(format_percent_places(4.2, 3, false), "4.200%");
+    assert_eq!(format_percent_places(4.2, 0, false), "4%");
+    // Clamped to MAX_DECIMALS
+    assert_eq!(format_percent_places(1.0, 12, false), "1.000000%");
+}
+
 // --- format_pl ---
 
 #[test]
@@ -130,6 +225,49 @@ fn test_truncate_very_short_max() {
     assert_eq!(truncate_str("ABCDEFG", 4), "A...");
 }
 
+#[test]
+fn test_truncate_max_below_ellipsis() {
+    assert_eq!(truncate_str("ABCDEFG", 2), "AB");
+    assert_eq!(truncate_str("ABCDEFG", 0), "");
+}
+
+#[test]
+fn test_truncate_multibyte_no_panic() {
+    assert_eq!(
+        truncate_str("Perusahaan Pérseroan Ñusantara", 14),
+        "Perusahaan ..."
+    );
+    assert_eq!(truncate_str("Bánk Négara Índonesia", 8), "Bánk ...");
+}
+
+#[test]
+fn test_truncate_multibyte_exact_boundary() {
+    // 6 chars, 9 bytes: length is measured in chars, so no truncation
+    assert_eq!(truncate_str("ÉÉÉabc", 6), "ÉÉÉabc");
+    assert_eq!(truncate_str("ÉÉÉÉÉÉÉ", 6), "ÉÉÉ...");
+}
+
+// --- sparkline_str ---
+
+#[test]
+fn test_sparkline_str_scales_min_to_max() {
+    assert_eq!(sparkline_str(&[1.0, 2.0, 3.0], 10), "▁▅█");
+}
+
+#[test]
+fn test_sparkline_str_samples_to_width() {
+    let values: Vec<f64> = (0..100).map(|v| v as f64).collect();
+    let line = sparkline_str(&values, 8);
+    assert_eq!(line.chars().count(), 8);
+    assert!(line.starts_with('▁') && line.ends_with('█'));
+}
+
+#[test]
+fn test_sparkline_str_flat_and_empty() {
+    assert_eq!(sparkline_str(&[5.0, 5.0], 4), "▄▄");
+    assert_eq!(sparkline_str(&[], 4), "");
+}
+
 // --- format_relative_time ---
 
 #[test]
@@ -171,3 +309,21 @@ fn test_relative_time_days() {
     let ts = chrono::Utc::now().timestamp() - (2 * 86400);
     assert_eq!(format_relative_time(ts), "2d ago");
 }
+
+// --- format_age ---
+
+#[test]
+fn test_format_age_picks_largest_unit() {
+    assert_eq!(format_age(0), "0s");
+    assert_eq!(format_age(59), "59s");
+    assert_eq!(format_age(125), "2m");
+    assert_eq!(format_age(7200), "2h");
+}
+
+// --- spinner_glyph ---
+
+#[test]
+fn test_spinner_glyph_wraps() {
+    assert_eq!(spinner_glyph(0), spinner_glyph(10));
+    assert_ne!(spinner_glyph(0), spinner_glyph(1));
+}
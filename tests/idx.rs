@@ -0,0 +1,70 @@
+use idx_cli::idx::{PriceLimit, auto_reject_limits, auto_reject_pct, price_limit, tick_size};
+
+// --- auto_reject_pct ---
+
+#[test]
+fn auto_reject_pct_follows_price_tiers() {
+    assert_eq!(auto_reject_pct(50.0), 35.0);
+    assert_eq!(auto_reject_pct(199.0), 35.0);
+    assert_eq!(auto_reject_pct(200.0), 35.0);
+    assert_eq!(auto_reject_pct(201.0), 25.0);
+    assert_eq!(auto_reject_pct(5_000.0), 25.0);
+    assert_eq!(auto_reject_pct(5_025.0), 20.0);
+    assert_eq!(auto_reject_pct(9_800.0), 20.0);
+}
+
+#[test]
+fn tick_size_follows_price_tiers() {
+    assert_eq!(tick_size(150.0), 1.0);
+    assert_eq!(tick_size(200.0), 2.0);
+    assert_eq!(tick_size(300.0), 2.0);
+    assert_eq!(tick_size(1_500.0), 5.0);
+    assert_eq!(tick_size(3_000.0), 10.0);
+    assert_eq!(tick_size(9_000.0), 25.0);
+}
+
+// --- auto_reject_limits ---
+
+#[test]
+fn limits_for_low_priced_stock() {
+    assert_eq!(auto_reject_limits(100.0), (65.0, 135.0));
+}
+
+#[test]
+fn limits_for_mid_priced_stock_round_to_tick() {
+    // 1,234 * 1.25 = 1,542.5 -> 1,540 (tick 5); 1,234 * 0.75 = 925.5 -> 930
+    assert_eq!(auto_reject_limits(1_234.0), (930.0, 1_540.0));
+}
+
+#[test]
+fn limits_for_high_priced_stock() {
+    // 9,800 * 1.2 = 11,760 -> 11,750 (tick 25); 9,800 * 0.8 = 7,840 -> 7,850
+    assert_eq!(auto_reject_limits(9_800.0), (7_850.0, 11_750.0));
+}
+
+#[test]
+fn arb_never_below_minimum_price() {
+    assert_eq!(auto_reject_limits(60.0).0, 50.0);
+}
+
+// --- price_limit ---
+
+#[test]
+fn price_limit_flags_ara_and_arb() {
+    assert_eq!(price_limit(1_234.0, 1_540.0), Some(PriceLimit::Ara));
+    assert_eq!(price_limit(1_234.0, 930.0), Some(PriceLimit::Arb));
+    assert_eq!(price_limit(1_234.0, 1_535.0), None);
+    assert_eq!(price_limit(1_234.0, 1_234.0), None);
+}
+
+#[test]
+fn price_limit_ignores_unchanged_stock_at_floor() {
+    assert_eq!(price_limit(50.0, 50.0), None);
+    assert_eq!(price_limit(0.0, 100.0), None);
+}
+
+#[test]
+fn price_limit_markers() {
+    assert_eq!(PriceLimit::Ara.marker(), "⇈");
+    assert_eq!(PriceLimit::Arb.marker(), "⇊");
+}
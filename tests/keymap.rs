@@ -0,0 +1,127 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use idx_cli::keymap::{Action, KeyMap, parse_key};
+use std::collections::BTreeMap;
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn overrides(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(a, k)| (a.to_string(), k.to_string()))
+        .collect()
+}
+
+// --- parse_key ---
+
+#[test]
+fn parse_key_accepts_chars_and_names() {
+    assert_eq!(parse_key("v"), Some(KeyCode::Char('v')));
+    assert_eq!(parse_key("?"), Some(KeyCode::Char('?')));
+    assert_eq!(parse_key("Enter"), Some(KeyCode::Enter));
+    assert_eq!(parse_key("pgdn"), Some(KeyCode::PageDown));
+    assert_eq!(parse_key("space"), Some(KeyCode::Char(' ')));
+    assert_eq!(parse_key("F5"), Some(KeyCode::F(5)));
+}
+
+#[test]
+fn parse_key_rejects_unknown_names() {
+    assert_eq!(parse_key(""), None);
+    assert_eq!(parse_key("ctrl"), None);
+    assert_eq!(parse_key("F13"), None);
+}
+
+// --- KeyMap ---
+
+#[test]
+fn default_keymap_matches_built_in_keys() {
+    let (keymap, warnings) = KeyMap::from_config(&BTreeMap::new());
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(
+        keymap.action(press(KeyCode::Char('p'))),
+        Some(Action::ToggleView)
+    );
+    assert_eq!(
+        keymap.action(press(KeyCode::Char('='))),
+        Some(Action::RefreshFaster)
+    );
+    assert_eq!(keymap.action(press(KeyCode::Left)), Some(Action::PrevList));
+    assert_eq!(keymap.action(press(KeyCode::Char('Q'))), None);
+}
+
+#[test]
+fn override_replaces_default_keys() {
+    let (keymap, warnings) = KeyMap::from_config(&overrides(&[("toggle_view", "V")]));
+    assert!(warnings.is_empty());
+    assert_eq!(
+        keymap.action(press(KeyCode::Char('V'))),
+        Some(Action::ToggleView)
+    );
+    assert_eq!(keymap.action(press(KeyCode::Char('p'))), None);
+}
+
+#[test]
+fn swapping_two_keys_does_not_conflict() {
+    let (keymap, warnings) = KeyMap::from_config(&overrides(&[("toggle_view", "a"), ("add", "p")]));
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(
+        keymap.action(press(KeyCode::Char('a'))),
+        Some(Action::ToggleView)
+    );
+    assert_eq!(keymap.action(press(KeyCode::Char('p'))), Some(Action::Add));
+}
+
+#[test]
+fn duplicate_binding_warns_and_keeps_first_action() {
+    let (keymap, warnings) = KeyMap::from_config(&overrides(&[("refresh", "q")]));
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("quit") && warnings[0].contains("refresh"));
+    assert_eq!(keymap.action(press(KeyCode::Char('q'))), Some(Action::Quit));
+}
+
+#[test]
+fn unknown_action_and_bad_key_warn() {
+    let (keymap, warnings) =
+        KeyMap::from_config(&overrides(&[("launch_rockets", "L"), ("refresh", "hyper")]));
+    assert_eq!(warnings.len(), 2);
+    // The bad override is ignored, so refresh keeps its default key
+    assert_eq!(
+        keymap.action(press(KeyCode::Char('r'))),
+        Some(Action::Refresh)
+    );
+}
+
+#[test]
+fn shift_arrows_reorder_symbols() {
+    let keymap = KeyMap::default();
+    assert_eq!(
+        keymap.action(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT)),
+        Some(Action::MoveSymbolUp)
+    );
+    assert_eq!(keymap.action(press(KeyCode::Up)), Some(Action::Up));
+}
+
+#[test]
+fn every_action_name_round_trips() {
+    for &action in Action::ALL {
+        assert_eq!(Action::from_name(action.name()), Some(action));
+    }
+}
+
+#[test]
+fn hints_follow_remapped_keys() {
+    let (keymap, _) = KeyMap::from_config(&overrides(&[("help", "F1"), ("compare", "V")]));
+    assert_eq!(keymap.hint(Action::Help), "F1");
+    assert_eq!(keymap.hint(Action::PrevList), "←");
+    assert_eq!(keymap.hint_all(Action::PrevList), "←/h");
+    assert!(keymap.triggers(Action::Compare, KeyCode::Char('V')));
+    assert!(!keymap.triggers(Action::Compare, KeyCode::Char('v')));
+}
+
+#[test]
+fn action_that_lost_its_key_has_no_hint() {
+    let (keymap, _) = KeyMap::from_config(&overrides(&[("refresh", "q")]));
+    assert!(keymap.keys(Action::Refresh).is_empty());
+    assert_eq!(keymap.hint(Action::Refresh), "-");
+}
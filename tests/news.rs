@@ -0,0 +1,235 @@
+mod common;
+
+use common::{make_news_item, temp_config_app, test_app};
+use idx_cli::api::news::{cap_news, merge_feeds, paged_feed_url, tag_category, validate_feed_url};
+use idx_cli::app::{App, InputMode};
+use idx_cli::config::Config;
+
+#[test]
+fn merge_feeds_dedupes_by_title_and_url() {
+    let mut a = make_news_item("IHSG ditutup menguat", "CNBC Indonesia", 100);
+    a.url = Some("https://example.com/a".to_string());
+    let dup = a.clone();
+    let mut other_url = a.clone();
+    other_url.url = Some("https://example.com/b".to_string());
+
+    let merged = merge_feeds(vec![vec![a], vec![dup, other_url]]);
+    assert_eq!(merged.len(), 2);
+}
+
+#[test]
+fn merge_feeds_sorts_newest_first() {
+    let merged = merge_feeds(vec![
+        vec![make_news_item("Old", "Tempo", 10)],
+        vec![
+            make_news_item("New", "IDX Channel", 30),
+            make_news_item("Mid", "Tempo", 20),
+        ],
+    ]);
+    let titles: Vec<&str> = merged.iter().map(|n| n.title.as_str()).collect();
+    assert_eq!(titles, vec!["New", "Mid", "Old"]);
+}
+
+// --- news_max_items ---
+
+#[test]
+fn cap_news_after_merge_keeps_newest() {
+    let older: Vec<_> = (0..5)
+        .map(|i| make_news_item(&format!("old {}", i), "A", i))
+        .collect();
+    let newer: Vec<_> = (10..13)
+        .map(|i| make_news_item(&format!("new {}", i), "B", i))
+        .collect();
+    let mut merged = merge_feeds(vec![older, newer]);
+    assert_eq!(merged.len(), 8);
+
+    assert_eq!(cap_news(&mut merged, 4), 4);
+    let times: Vec<i64> = merged.iter().map(|n| n.published_at).collect();
+    assert_eq!(times, vec![12, 11, 10, 4]);
+
+    // 0 keeps everything
+    assert_eq!(cap_news(&mut merged, 0), 0);
+    assert_eq!(merged.len(), 4);
+}
+
+#[test]
+fn news_limits_have_defaults() {
+    let config: Config = serde_json::from_str(r#"{"watchlists": []}"#).unwrap();
+    assert_eq!(config.news_refresh_secs, 300);
+    assert_eq!(config.news_max_items, 500);
+}
+
+// --- load more ---
+
+#[test]
+fn paged_feed_url_adds_page_parameter() {
+    assert_eq!(
+        paged_feed_url("https://a.example/rss", 1),
+        "https://a.example/rss"
+    );
+    assert_eq!(
+        paged_feed_url("https://a.example/rss", 2),
+        "https://a.example/rss?paged=2"
+    );
+    assert_eq!(
+        paged_feed_url("https://a.example/feed?cat=5", 3),
+        "https://a.example/feed?cat=5&paged=3"
+    );
+}
+
+#[tokio::test]
+async fn load_more_without_new_items_keeps_page() {
+    let mut app = test_app();
+    app.config.news_sources.clear();
+    app.news_items = vec![make_news_item("IHSG menguat", "A", 2)];
+
+    app.load_more_news().await;
+    assert_eq!(app.news_page, 1);
+    assert_eq!(app.news_items.len(), 1);
+    assert!(!app.rss_loading);
+    assert!(app.status_message.as_deref().unwrap().contains("No older"));
+}
+
+// --- feed categories ---
+
+#[test]
+fn tag_category_marks_every_item() {
+    let mut items = vec![make_news_item("A", "CNBC Indonesia", 1)];
+    tag_category(&mut items, Some(&"Market".to_string()));
+    assert_eq!(items[0].category.as_deref(), Some("Market"));
+    tag_category(&mut items, None);
+    assert_eq!(items[0].category, None);
+}
+
+fn categorized_app() -> App {
+    let mut app = test_app();
+    app.config.news_sources = vec![
+        "https://a.example/rss".to_string(),
+        "https://b.example/rss".to_string(),
+        "https://c.example/rss".to_string(),
+    ];
+    for (url, category) in [
+        ("https://a.example/rss", "Market"),
+        ("https://b.example/rss", "General"),
+        ("https://c.example/rss", "Market"),
+    ] {
+        app.config
+            .news_categories
+            .insert(url.to_string(), category.to_string());
+    }
+    let mut market = make_news_item("IHSG menguat", "A", 2);
+    market.category = Some("Market".to_string());
+    let mut general = make_news_item("Cuaca cerah", "B", 1);
+    general.category = Some("General".to_string());
+    app.news_items = vec![market, general, make_news_item("Untagged", "D", 0)];
+    app
+}
+
+#[test]
+fn news_categories_are_distinct_and_sorted() {
+    let app = categorized_app();
+    assert_eq!(app.news_categories(), vec!["General", "Market"]);
+}
+
+#[test]
+fn category_filter_cycles_and_limits_headlines() {
+    let mut app = categorized_app();
+    assert_eq!(app.get_filtered_news().len(), 3);
+
+    app.cycle_news_category_filter();
+    assert_eq!(app.news_category_filter.as_deref(), Some("General"));
+    let titles: Vec<&str> = app
+        .get_filtered_news()
+        .iter()
+        .map(|n| n.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["Cuaca cerah"]);
+
+    app.cycle_news_category_filter();
+    assert_eq!(app.news_category_filter.as_deref(), Some("Market"));
+    app.cycle_news_category_filter();
+    assert_eq!(app.news_category_filter, None);
+}
+
+#[test]
+fn category_filter_without_categories_explains() {
+    let mut app = test_app();
+    app.config.news_categories.clear();
+    app.cycle_news_category_filter();
+    assert_eq!(app.news_category_filter, None);
+    assert!(app.status_message.is_some());
+}
+
+#[test]
+fn editing_a_feed_category_saves_it() {
+    let path = std::env::temp_dir().join(format!(
+        "idx-cli-test-{}-feed-category.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let mut app = App::test_new(Config::load_from(Some(path.clone())).unwrap());
+    app.config.news_sources = vec!["https://a.example/rss".to_string()];
+    app.open_news_sources();
+
+    app.start_news_source_category();
+    assert_eq!(app.input_mode, InputMode::NewsSourceCategory);
+    app.input_buffer = "Market".to_string();
+    app.confirm_news_source_category().unwrap();
+    assert_eq!(app.input_mode, InputMode::NewsSources);
+    assert_eq!(app.news_categories(), vec!["Market"]);
+
+    app.start_news_source_category();
+    assert_eq!(app.input_buffer, "Market");
+    app.input_buffer.clear();
+    app.confirm_news_source_category().unwrap();
+    assert!(app.news_categories().is_empty());
+    let _ = std::fs::remove_file(&path);
+}
+
+// --- news source management ---
+
+#[test]
+fn validate_feed_url_accepts_http_and_rejects_others() {
+    assert_eq!(
+        validate_feed_url("  https://example.com/rss  ").unwrap(),
+        "https://example.com/rss"
+    );
+    assert!(validate_feed_url("http://example.com/feed.xml").is_ok());
+    assert!(validate_feed_url("example.com/rss").is_err());
+    assert!(validate_feed_url("ftp://example.com/rss").is_err());
+    assert!(validate_feed_url("").is_err());
+}
+
+#[tokio::test]
+async fn confirm_news_source_add_rejects_invalid_and_duplicate_urls() {
+    let mut app = test_app();
+    app.config.news_sources = vec!["https://example.com/rss".to_string()];
+    app.open_news_sources();
+    app.start_news_source_add();
+
+    app.input_buffer = "not a url".to_string();
+    app.confirm_news_source_add().await.unwrap();
+    assert_eq!(app.input_mode, InputMode::NewsSourceAdd);
+    assert_eq!(app.config.news_sources.len(), 1);
+
+    app.input_buffer = "https://example.com/rss".to_string();
+    app.confirm_news_source_add().await.unwrap();
+    assert!(app.status_message.as_deref().unwrap().contains("already"));
+    assert_eq!(app.config.news_sources.len(), 1);
+}
+
+#[test]
+fn add_and_remove_news_sources() {
+    let (mut app, path) = temp_config_app("add-and-remove-news-sources");
+    app.config.news_sources = vec!["https://a.example/rss".to_string()];
+    app.open_news_sources();
+    app.add_news_source("https://b.example/rss").unwrap();
+    assert_eq!(app.input_mode, InputMode::NewsSources);
+    assert_eq!(app.news_source_selected, 1);
+    assert!(app.news_last_refresh.is_none());
+
+    app.remove_selected_news_source().unwrap();
+    assert_eq!(app.config.news_sources, vec!["https://a.example/rss"]);
+    assert_eq!(app.news_source_selected, 0);
+    let _ = std::fs::remove_file(path);
+}
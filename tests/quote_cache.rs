@@ -0,0 +1,47 @@
+mod common;
+
+use common::make_quote;
+use idx_cli::api::QuoteCache;
+use std::collections::HashMap;
+
+fn cache_with(symbols: &[&str], fetched_at: u64) -> QuoteCache {
+    let mut quotes = HashMap::new();
+    for s in symbols {
+        quotes.insert(s.to_string(), make_quote(s, 1000.0, 10.0, 1.0));
+    }
+    let mut cache = QuoteCache::default();
+    cache.insert_all(&quotes, fetched_at);
+    cache
+}
+
+#[test]
+fn fresh_entry_is_returned_within_ttl() {
+    let cache = cache_with(&["BBCA"], 1_000);
+    assert!(cache.get_fresh("BBCA", 60, 1_030).is_some());
+    assert!(cache.get_fresh("bbca", 60, 1_030).is_some());
+}
+
+#[test]
+fn expired_entry_is_ignored() {
+    let cache = cache_with(&["BBCA"], 1_000);
+    assert!(cache.get_fresh("BBCA", 60, 1_060).is_none());
+    assert!(cache.get_fresh("BBCA", 0, 1_000).is_none());
+}
+
+#[test]
+fn index_symbol_maps_to_display_key() {
+    let cache = cache_with(&["IHSG"], 1_000);
+    assert!(cache.get_fresh("^JKSE", 60, 1_010).is_some());
+}
+
+#[test]
+fn cache_round_trips_through_disk() {
+    let path = std::env::temp_dir().join(format!(
+        "idx-cli-test-{}-quote-cache.json",
+        std::process::id()
+    ));
+    cache_with(&["TLKM"], 5_000).save(&path).unwrap();
+    let loaded = QuoteCache::load(&path);
+    assert_eq!(loaded.get_fresh("TLKM", 60, 5_001).unwrap().price, 1000.0);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,311 @@
+use idx_cli::api::http::DEFAULT_HTTP_TIMEOUT_SECS;
+use idx_cli::api::yahoo::{
+    LITE_QUOTE_FIELDS, display_symbol, parse_quotes, parse_symbol_search, quote_page_url,
+    to_yahoo_symbol,
+};
+use idx_cli::api::{
+    ChartData, ChartRange, HttpOptions, NewsClient, RetryPolicy, YahooClient, is_offline_error,
+};
+use std::time::Duration;
+
+// --- ChartRange ---
+
+#[test]
+fn chart_range_defaults_to_three_months() {
+    assert_eq!(ChartRange::default(), ChartRange::ThreeMonths);
+    assert_eq!(ChartRange::default().params(), ("3mo", "1d"));
+}
+
+#[test]
+fn chart_range_cycles_forward_and_back() {
+    let mut range = ChartRange::OneMonth;
+    let mut labels = Vec::new();
+    for _ in 0..5 {
+        labels.push(range.label());
+        range = range.next();
+    }
+    assert_eq!(labels, ["1M", "3M", "6M", "1Y", "YTD"]);
+    assert_eq!(range, ChartRange::OneMonth);
+    assert_eq!(range.prev(), ChartRange::YearToDate);
+}
+
+#[test]
+fn chart_range_params() {
+    assert_eq!(ChartRange::OneMonth.params(), ("1mo", "1d"));
+    assert_eq!(ChartRange::OneYear.params(), ("1y", "1wk"));
+    assert_eq!(ChartRange::YearToDate.params(), ("ytd", "1d"));
+}
+
+// --- ChartData::from_raw_closes ---
+
+#[test]
+fn test_chart_carries_forward_null_closes() {
+    let raw = [Some(100.0), None, Some(120.0), None, None, Some(90.0), None];
+    let chart = ChartData::from_raw_closes(&raw).unwrap();
+    assert_eq!(chart.closes.len(), raw.len());
+    assert_eq!(
+        chart.closes,
+        vec![100.0, 100.0, 120.0, 120.0, 120.0, 90.0, 90.0]
+    );
+    assert_eq!(chart.high, 120.0);
+    assert_eq!(chart.low, 90.0);
+}
+
+#[test]
+fn test_chart_leading_nulls_take_first_close() {
+    let chart = ChartData::from_raw_closes(&[None, None, Some(50.0), Some(55.0)]).unwrap();
+    assert_eq!(chart.closes, vec![50.0, 50.0, 50.0, 55.0]);
+    assert_eq!((chart.low, chart.high), (50.0, 55.0));
+}
+
+#[test]
+fn test_chart_all_null_closes_is_none() {
+    assert!(ChartData::from_raw_closes(&[None, None]).is_none());
+    assert!(ChartData::from_raw_closes(&[]).is_none());
+}
+
+// --- RetryPolicy ---
+
+fn response(status: u16) -> reqwest::Result<reqwest::Response> {
+    Ok(http::Response::builder()
+        .status(status)
+        .body("")
+        .unwrap()
+        .into())
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 3,
+        base_delay: Duration::from_millis(1),
+    }
+}
+
+#[tokio::test]
+async fn retry_policy_retries_503_then_succeeds() {
+    let mut calls = 0;
+    let result = fast_policy()
+        .send(|| {
+            calls += 1;
+            let status = if calls == 1 { 503 } else { 200 };
+            async move { response(status) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.status(), 200);
+    assert_eq!(calls, 2);
+}
+
+#[tokio::test]
+async fn retry_policy_gives_up_after_max_retries() {
+    let mut calls = 0;
+    let result = fast_policy()
+        .send(|| {
+            calls += 1;
+            async { response(429) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.status(), 429);
+    assert_eq!(calls, 4);
+}
+
+#[tokio::test]
+async fn retry_policy_does_not_retry_client_errors() {
+    let mut calls = 0;
+    let result = fast_policy()
+        .send(|| {
+            calls += 1;
+            async { response(401) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.status(), 401);
+    assert_eq!(calls, 1);
+}
+
+#[tokio::test]
+async fn retry_policy_does_not_retry_connection_errors() {
+    let mut calls = 0;
+    let result = fast_policy()
+        .send(|| {
+            calls += 1;
+            reqwest::get("http://127.0.0.1:1/")
+        })
+        .await;
+    assert!(result.unwrap_err().is_connect());
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn retry_policy_default_backoff_doubles() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.delay(0), Duration::from_millis(500));
+    assert_eq!(policy.delay(1), Duration::from_secs(1));
+    assert_eq!(policy.delay(2), Duration::from_secs(2));
+}
+
+// --- HttpOptions ---
+
+#[test]
+fn http_options_zero_timeout_uses_default() {
+    let options = HttpOptions::new(0, None);
+    assert_eq!(
+        options.timeout,
+        Duration::from_secs(DEFAULT_HTTP_TIMEOUT_SECS)
+    );
+    assert_eq!(HttpOptions::new(30, None).timeout, Duration::from_secs(30));
+}
+
+#[test]
+fn http_options_explicit_proxy_wins() {
+    let options = HttpOptions::new(10, Some("http://proxy.example:8080".to_string()));
+    assert_eq!(options.proxy.as_deref(), Some("http://proxy.example:8080"));
+    assert!(YahooClient::with_http(&options).is_ok());
+    assert!(NewsClient::with_http(&options).is_ok());
+}
+
+#[test]
+fn http_options_blank_proxy_leaves_environment_to_reqwest() {
+    assert_eq!(HttpOptions::new(10, None).proxy, None);
+    assert_eq!(HttpOptions::new(10, Some("  ".to_string())).proxy, None);
+}
+
+#[test]
+fn http_options_rejects_malformed_proxy() {
+    let options = HttpOptions::new(10, Some("not a url".to_string()));
+    let err = YahooClient::with_http(&options).err().unwrap();
+    assert!(err.to_string().contains("Invalid proxy URL"));
+}
+
+// --- is_offline_error ---
+
+#[tokio::test]
+async fn connection_refused_is_offline() {
+    let err: anyhow::Error = reqwest::get("http://127.0.0.1:1/")
+        .await
+        .unwrap_err()
+        .into();
+    assert!(is_offline_error(&err));
+    assert!(is_offline_error(&err.context("fetching quotes")));
+}
+
+#[test]
+fn api_errors_are_not_offline() {
+    assert!(!is_offline_error(&anyhow::anyhow!(
+        "Yahoo API error: 500 Internal Server Error"
+    )));
+}
+
+// --- parse_symbol_search ---
+
+#[test]
+fn symbol_search_keeps_only_idx_listings() {
+    let body = r#"{"quotes": [
+        {"symbol": "BBCA.JK", "shortname": "Bank Central Asia", "longname": "PT Bank Central Asia Tbk"},
+        {"symbol": "BBCA", "shortname": "Some US listing"},
+        {"symbol": "BBRI.JK", "shortname": "Bank Rakyat"}
+    ], "news": []}"#;
+    let matches = parse_symbol_search(body).unwrap();
+    assert_eq!(
+        matches,
+        vec![
+            ("BBCA".to_string(), "PT Bank Central Asia Tbk".to_string()),
+            ("BBRI".to_string(), "Bank Rakyat".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn symbol_search_without_quotes_is_empty() {
+    assert!(parse_symbol_search(r#"{"news": []}"#).unwrap().is_empty());
+}
+
+// --- parse_quotes / lite payload ---
+
+const FULL_QUOTES: &str = include_str!("fixtures/quote_full.json");
+const LITE_QUOTES: &str = include_str!("fixtures/quote_lite.json");
+
+#[test]
+fn full_quote_fixture_parses_fundamentals() {
+    let quotes = parse_quotes(FULL_QUOTES).unwrap();
+    let bbca = &quotes["BBCA"];
+    assert_eq!(bbca.price, 9025.0);
+    assert_eq!(bbca.sector.as_deref(), Some("Financial Services"));
+    assert_eq!(bbca.average_volume, Some(98_731_400));
+    assert_eq!(quotes["TLKM"].change, -40.0);
+}
+
+#[test]
+fn lite_quotes_keep_prices_and_average_volume() {
+    let full = parse_quotes(FULL_QUOTES).unwrap();
+    let mut lite = parse_quotes(LITE_QUOTES).unwrap();
+    for quote in lite.values_mut() {
+        quote.strip_fundamentals();
+    }
+    for (symbol, quote) in &lite {
+        assert_eq!(quote.price, full[symbol].price);
+        assert_eq!(quote.volume, full[symbol].volume);
+        assert_eq!(quote.average_volume, full[symbol].average_volume);
+        assert_eq!(quote.sector, None);
+    }
+    assert!(LITE_QUOTE_FIELDS.split(',').any(|f| f == "averageVolume"));
+}
+
+#[test]
+fn lite_payload_is_well_under_half_the_full_one() {
+    // Sizes quoted on LITE_QUOTE_FIELDS: 1,849 vs 5,065 bytes
+    assert_eq!(FULL_QUOTES.trim_end().len(), 5065);
+    assert_eq!(LITE_QUOTES.trim_end().len(), 1849);
+    assert!(LITE_QUOTES.len() * 2 < FULL_QUOTES.len());
+}
+
+#[test]
+fn quote_response_error_is_reported() {
+    let body = r#"{"quoteResponse": {"result": [], "error": {"code": "Bad Request"}}}"#;
+    assert!(parse_quotes(body).is_err());
+}
+
+// --- to_yahoo_symbol / display_symbol ---
+
+#[test]
+fn to_yahoo_symbol_adds_jk_to_idx_codes() {
+    assert_eq!(to_yahoo_symbol("BBCA"), "BBCA.JK");
+    assert_eq!(to_yahoo_symbol("bbca"), "BBCA.JK");
+    assert_eq!(to_yahoo_symbol("BBCA.JK"), "BBCA.JK");
+}
+
+#[test]
+fn to_yahoo_symbol_keeps_qualified_symbols() {
+    assert_eq!(to_yahoo_symbol("^JKSE"), "^JKSE");
+    assert_eq!(to_yahoo_symbol("IDR=X"), "IDR=X");
+    assert_eq!(to_yahoo_symbol("BTC-USD"), "BTC-USD");
+    assert_eq!(to_yahoo_symbol("D05.SI"), "D05.SI");
+}
+
+#[test]
+fn to_yahoo_symbol_strips_raw_prefix() {
+    assert_eq!(to_yahoo_symbol("$AAPL"), "AAPL");
+    assert_eq!(to_yahoo_symbol("$^GSPC"), "^GSPC");
+}
+
+#[test]
+fn quote_page_url_uses_yahoo_symbol() {
+    assert_eq!(
+        quote_page_url("bbca"),
+        "https://finance.yahoo.com/quote/BBCA.JK"
+    );
+    assert_eq!(
+        quote_page_url("^JKSE"),
+        "https://finance.yahoo.com/quote/^JKSE"
+    );
+}
+
+#[test]
+fn display_symbol_round_trips_non_idx_symbols() {
+    assert_eq!(display_symbol("BBCA.JK"), "BBCA");
+    assert_eq!(display_symbol("^JKSE"), "IHSG");
+    assert_eq!(display_symbol("^N225"), "^N225");
+    assert_eq!(display_symbol("IDR=X"), "IDR=X");
+    assert_eq!(display_symbol("BTC-USD"), "BTC-USD");
+}